@@ -1,3 +1,60 @@
-fn main() {
-    println!("Order book benchmarks - placeholder");
-}
\ No newline at end of file
+//! Order book benchmarks: price level churn with and without level pooling
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use order_book::{LockFreeOrderBook, Order, OrderBook, OrderType, Price, Quantity, Side};
+use uuid::Uuid;
+
+const POOL_SIZE: usize = 64;
+
+fn limit(side: Side, price: f64) -> Order {
+    Order::new(
+        "BTCUSD".to_string(),
+        side,
+        OrderType::Limit,
+        Price::new(price),
+        Quantity::new(1.0),
+        Uuid::new_v4(),
+    )
+}
+
+/// Open a level just behind the touch and close it again, as quotes near the touch do
+fn bench_level_churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("level_churn");
+
+    for pooled in [false, true] {
+        let label = if pooled { "pooled" } else { "unpooled" };
+
+        let book = OrderBook::new("BTCUSD".to_string());
+        let book = if pooled { book.with_level_pool(POOL_SIZE) } else { book };
+        book.add_order(limit(Side::Buy, 50000.0));
+        // Archive as we go so the retired buffer never fills and starts dropping
+        let mut archived = Vec::new();
+        group.bench_function(BenchmarkId::new("rwlock_levels", label), |b| {
+            b.iter(|| {
+                let order = limit(Side::Buy, 49999.0);
+                let id = order.id;
+                black_box(book.add_order(order));
+                black_box(book.cancel_order(id));
+                archived.clear();
+                book.archive_terminal(&mut archived).unwrap();
+            })
+        });
+
+        let book = LockFreeOrderBook::new("BTCUSD".to_string());
+        let book = if pooled { book.with_level_pool(POOL_SIZE) } else { book };
+        book.add_order(limit(Side::Buy, 50000.0));
+        group.bench_function(BenchmarkId::new("atomic_levels", label), |b| {
+            b.iter(|| {
+                let order = limit(Side::Buy, 49999.0);
+                let id = order.id;
+                black_box(book.add_order(order));
+                black_box(book.cancel_order(id));
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_level_churn);
+criterion_main!(benches);
@@ -9,6 +9,85 @@ pub struct BatchConfig {
     pub max_batch_size: usize,
     pub max_batch_delay: Duration,
     pub max_memory_usage: usize,
+    /// Size batches from the observed arrival rate instead of always filling to `max_batch_size`
+    pub adaptive: Option<AdaptiveBatching>,
+}
+
+/// Batch sizing driven by recent inter-arrival gaps. A batch is sized so its first event waits
+/// about `latency_target` for the rest to arrive: large under bursts, one event (an immediate
+/// flush) once gaps exceed the target.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatching {
+    /// Longest an event may wait in a batch before the batch is due
+    pub latency_target: Duration,
+    /// Number of recent inter-arrival gaps kept for the estimate
+    pub window: usize,
+    /// Percentile (0-100) of the recent gaps taken as the expected gap. Higher values
+    /// shrink batches sooner when arrivals slow down.
+    pub gap_percentile: f64,
+}
+
+impl Default for AdaptiveBatching {
+    fn default() -> Self {
+        Self {
+            latency_target: Duration::from_millis(1),
+            window: 64,
+            gap_percentile: 90.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ArrivalTracker {
+    gaps: VecDeque<Duration>,
+    last_arrival: Option<Instant>,
+    /// Arrival of the oldest event in the current batch
+    oldest_pending: Option<Instant>,
+    effective_batch_size: usize,
+}
+
+impl ArrivalTracker {
+    fn new(initial_size: usize) -> Self {
+        Self {
+            gaps: VecDeque::new(),
+            last_arrival: None,
+            oldest_pending: None,
+            effective_batch_size: initial_size,
+        }
+    }
+
+    fn record_arrival(&mut self, now: Instant, adaptive: &AdaptiveBatching, max_batch_size: usize) {
+        if let Some(last) = self.last_arrival {
+            if self.gaps.len() >= adaptive.window.max(1) {
+                self.gaps.pop_front();
+            }
+            self.gaps.push_back(now.saturating_duration_since(last));
+        }
+        self.last_arrival = Some(now);
+        self.oldest_pending.get_or_insert(now);
+
+        let Some(expected_gap) = self.gap_percentile(adaptive.gap_percentile) else {
+            return;
+        };
+        let size = if expected_gap.is_zero() {
+            max_batch_size
+        } else {
+            // The first event waits (size - 1) gaps for the batch to fill
+            let gaps_within_target = adaptive.latency_target.as_nanos() / expected_gap.as_nanos();
+            usize::try_from(gaps_within_target).unwrap_or(usize::MAX).saturating_add(1)
+        };
+        self.effective_batch_size = size.clamp(1, max_batch_size.max(1));
+    }
+
+    fn gap_percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.gaps.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.gaps.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64).round() as usize;
+        Some(sorted[rank])
+    }
 }
 
 impl Default for BatchConfig {
@@ -17,6 +96,7 @@ impl Default for BatchConfig {
             max_batch_size: 1000,
             max_batch_delay: Duration::from_millis(10),
             max_memory_usage: 1024 * 1024, // 1MB
+            adaptive: None,
         }
     }
 }
@@ -103,6 +183,7 @@ pub struct BatchProcessor {
     config: BatchConfig,
     processed_batches: Arc<Mutex<u64>>,
     processed_events: Arc<Mutex<u64>>,
+    arrivals: Arc<Mutex<ArrivalTracker>>,
 }
 
 impl BatchProcessor {
@@ -110,20 +191,37 @@ impl BatchProcessor {
     pub fn new(config: BatchConfig) -> Self {
         Self {
             batch: Arc::new(Mutex::new(EventBatch::with_capacity(config.max_batch_size))),
-            config,
             processed_batches: Arc::new(Mutex::new(0)),
             processed_events: Arc::new(Mutex::new(0)),
+            arrivals: Arc::new(Mutex::new(ArrivalTracker::new(config.max_batch_size))),
+            config,
         }
     }
     
     #[inline]
     pub fn add_event(&self, event: Event) -> Option<EventBatch> {
+        self.add_event_at(event, Instant::now())
+    }
+    
+    /// Add an event that arrived at `now`. With adaptive batching the arrival updates the
+    /// effective batch size before the flush decision.
+    pub fn add_event_at(&self, event: Event, now: Instant) -> Option<EventBatch> {
         let mut batch = self.batch.lock();
         batch.add_event(event);
         
-        if batch.should_flush(&self.config) {
-            let old_batch = std::mem::replace(&mut *batch, EventBatch::with_capacity(self.config.max_batch_size));
-            Some(old_batch)
+        let Some(adaptive) = &self.config.adaptive else {
+            return batch.should_flush(&self.config).then(|| self.take_batch(&mut batch));
+        };
+        
+        let mut arrivals = self.arrivals.lock();
+        arrivals.record_arrival(now, adaptive, self.config.max_batch_size);
+        let overdue = arrivals
+            .oldest_pending
+            .is_some_and(|oldest| now.saturating_duration_since(oldest) >= adaptive.latency_target);
+        
+        if batch.len() >= arrivals.effective_batch_size || overdue || batch.should_flush(&self.config) {
+            arrivals.oldest_pending = None;
+            Some(self.take_batch(&mut batch))
         } else {
             None
         }
@@ -135,11 +233,43 @@ impl BatchProcessor {
         if batch.is_empty() {
             None
         } else {
-            let old_batch = std::mem::replace(&mut *batch, EventBatch::with_capacity(self.config.max_batch_size));
-            Some(old_batch)
+            self.arrivals.lock().oldest_pending = None;
+            Some(self.take_batch(&mut batch))
+        }
+    }
+    
+    /// When the pending batch reaches the adaptive latency target. `None` without adaptive
+    /// batching or with nothing pending.
+    pub fn flush_deadline(&self) -> Option<Instant> {
+        let adaptive = self.config.adaptive.as_ref()?;
+        self.arrivals.lock().oldest_pending.map(|oldest| oldest + adaptive.latency_target)
+    }
+    
+    /// Flush the pending batch if its latency target has passed by `now`. A timer calling
+    /// this at `flush_deadline` keeps every event within the target during a lull.
+    pub fn flush_due(&self, now: Instant) -> Option<EventBatch> {
+        match self.flush_deadline() {
+            Some(deadline) if deadline <= now => self.flush(),
+            _ => None,
+        }
+    }
+    
+    /// Batch size that currently triggers a flush: `max_batch_size` for fixed batching, the
+    /// rate-derived size with adaptive batching
+    #[inline]
+    pub fn effective_batch_size(&self) -> usize {
+        if self.config.adaptive.is_some() {
+            self.arrivals.lock().effective_batch_size
+        } else {
+            self.config.max_batch_size
         }
     }
     
+    #[inline]
+    fn take_batch(&self, batch: &mut EventBatch) -> EventBatch {
+        std::mem::replace(batch, EventBatch::with_capacity(self.config.max_batch_size))
+    }
+    
     #[inline]
     pub fn mark_batch_processed(&self, batch: &EventBatch) {
         let mut batches = self.processed_batches.lock();
@@ -158,6 +288,7 @@ impl BatchProcessor {
             pending_events: current_batch.len(),
             current_batch_age: current_batch.age(),
             current_batch_size: current_batch.size(),
+            effective_batch_size: self.effective_batch_size(),
         }
     }
     
@@ -180,6 +311,7 @@ pub struct BatchStats {
     pub pending_events: usize,
     pub current_batch_age: Duration,
     pub current_batch_size: usize,
+    pub effective_batch_size: usize,
 }
 
 #[derive(Debug)]
@@ -116,10 +116,18 @@ impl EventChannels {
         &self.priority_receiver
     }
     
+    #[inline]
+    pub fn pending_events(&self) -> usize {
+        self.order_receiver.len()
+            + self.trade_receiver.len()
+            + self.system_receiver.len()
+            + self.priority_receiver.len()
+    }
+    
     #[inline]
     pub fn send_event(&self, event: Event) -> anyhow::Result<()> {
         match &event {
-            Event::Order(_) => self.order_sender.send(event).map_err(anyhow::Error::from),
+            Event::Order(_) | Event::Batch(_) => self.order_sender.send(event).map_err(anyhow::Error::from),
             Event::Trade(_) => self.trade_sender.send(event).map_err(anyhow::Error::from),
             Event::System(_) => self.system_sender.send(event).map_err(anyhow::Error::from),
         }
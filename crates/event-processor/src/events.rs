@@ -1,4 +1,4 @@
-use order_book::{Order, OrderId, Trade, Price, Quantity};
+use order_book::{Order, OrderId, Trade, Price, Quantity, ExecutionReport};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -29,6 +29,7 @@ pub enum OrderEvent {
         reason: String,
         timestamp: DateTime<Utc>,
     },
+    Execution(ExecutionReport),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -84,11 +85,37 @@ pub enum HealthStatus {
     Down = 3,
 }
 
+/// Answer to a liveness ("restart me?") or readiness ("send me traffic?") question
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Probe {
+    Pass,
+    Fail(String),
+}
+
+impl Probe {
+    #[inline]
+    pub fn is_pass(&self) -> bool {
+        matches!(self, Probe::Pass)
+    }
+    
+    /// `Pass` when `ok`, otherwise `Fail` with the reason from `reason`
+    #[inline]
+    pub fn check(ok: bool, reason: impl FnOnce() -> String) -> Self {
+        if ok {
+            Probe::Pass
+        } else {
+            Probe::Fail(reason())
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event {
     Order(OrderEvent),
     Trade(TradeEvent),
     System(SystemEvent),
+    /// Events produced by one operation, dispatched with a single channel send
+    Batch(Vec<Event>),
 }
 
 impl Event {
@@ -101,6 +128,7 @@ impl Event {
                 OrderEvent::ModifyOrder { timestamp, .. } => *timestamp,
                 OrderEvent::OrderFilled { timestamp, .. } => *timestamp,
                 OrderEvent::OrderRejected { timestamp, .. } => *timestamp,
+                OrderEvent::Execution(report) => report.timestamp,
             },
             Event::Trade(trade_event) => match trade_event {
                 TradeEvent::TradeExecuted(trade) => trade.timestamp,
@@ -113,6 +141,7 @@ impl Event {
                 SystemEvent::TradingResume { timestamp, .. } => *timestamp,
                 SystemEvent::SystemHealthCheck { timestamp, .. } => *timestamp,
             },
+            Event::Batch(events) => events.first().map(Event::timestamp).unwrap_or_else(Utc::now),
         }
     }
     
@@ -126,6 +155,15 @@ impl Event {
             Event::System(SystemEvent::TradingHalt { .. }) => EventPriority::Critical,
             Event::System(SystemEvent::SystemHealthCheck { status: HealthStatus::Critical, .. }) => EventPriority::Critical,
             Event::System(_) => EventPriority::Low,
+            Event::Batch(events) => events.iter().map(Event::priority).max().unwrap_or_default(),
+        }
+    }
+    
+    /// Unpack batches (including nested ones) into their member events, in order
+    pub fn into_flat(self) -> Vec<Event> {
+        match self {
+            Event::Batch(events) => events.into_iter().flat_map(Event::into_flat).collect(),
+            event => vec![event],
         }
     }
 }
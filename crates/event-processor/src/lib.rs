@@ -3,9 +3,9 @@ pub mod events;
 pub mod channels;
 pub mod batch;
 
-pub use processor::EventProcessor;
+pub use processor::{EventProcessor, ProcessorConfig};
 pub use events::*;
 pub use channels::*;
-pub use batch::BatchProcessor;
+pub use batch::{AdaptiveBatching, BatchConfig, BatchProcessor};
 
 pub type Result<T> = anyhow::Result<T>;
\ No newline at end of file
@@ -1,9 +1,12 @@
 use crate::events::Event;
 use crate::channels::{EventChannels, PriorityQueue};
 use crate::batch::{BatchProcessor, BatchConfig, EventBatch};
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
-use std::time::Duration;
-use parking_lot::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use parking_lot::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::interval;
 use crossbeam_channel::select;
@@ -12,6 +15,9 @@ use anyhow::Result;
 pub type EventHandler = Arc<dyn Fn(&Event) -> Result<()> + Send + Sync>;
 pub type BatchHandler = Arc<dyn Fn(&EventBatch) -> Result<()> + Send + Sync>;
 
+/// Registered handlers taking `A`, `EventHandler`s or `BatchHandler`s
+type Handlers<A> = RwLock<Vec<Arc<dyn Fn(&A) -> Result<()> + Send + Sync>>>;
+
 #[derive(Debug)]
 pub struct ProcessorConfig {
     pub batch_config: BatchConfig,
@@ -19,6 +25,13 @@ pub struct ProcessorConfig {
     pub buffer_size: usize,
     pub flush_interval: Duration,
     pub enable_priority_queue: bool,
+    /// Unregister a handler the first time it panics instead of calling it again
+    pub remove_panicking_handlers: bool,
+    /// Longest one handler call may take before it is logged and counted as slow. Handlers
+    /// run on the dispatch loop and cannot be interrupted, so this is measured after the call.
+    pub handler_time_budget: Option<Duration>,
+    /// Unregister a handler once this many of its calls have been slow; 0 never removes it
+    pub max_slow_handler_calls: u32,
 }
 
 impl Default for ProcessorConfig {
@@ -29,8 +42,76 @@ impl Default for ProcessorConfig {
             buffer_size: 10000,
             flush_interval: Duration::from_millis(5),
             enable_priority_queue: false,  // Disable priority queue for now
+            remove_panicking_handlers: false,
+            handler_time_budget: None,
+            max_slow_handler_calls: 0,
+        }
+    }
+}
+
+/// Runs handlers with panics contained, so one faulty handler cannot stop a worker or
+/// starve the handlers registered after it, and times each call against the budget
+#[derive(Debug)]
+struct HandlerIsolation {
+    panics: AtomicU64,
+    remove_panicking: bool,
+    slow_calls: AtomicU64,
+    time_budget: Option<Duration>,
+    max_slow_calls: u32,
+    /// Slow calls so far by handler address
+    slow_by_handler: Mutex<HashMap<usize, u32>>,
+}
+
+impl HandlerIsolation {
+    fn run<A: ?Sized>(&self, handlers: &Handlers<A>, arg: &A, kind: &str) {
+        let mut panicked = Vec::new();
+        let mut disabled = Vec::new();
+        for (index, handler) in handlers.read().iter().enumerate() {
+            let started = Instant::now();
+            let outcome = std::panic::catch_unwind(AssertUnwindSafe(|| handler(arg)));
+            let elapsed = started.elapsed();
+            if self.time_budget.is_some_and(|budget| elapsed > budget) && self.record_slow(handler, index, elapsed, kind) {
+                disabled.push(Arc::clone(handler));
+            }
+            
+            match outcome {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::error!("{} handler error: {}", kind, e),
+                Err(payload) => {
+                    self.panics.fetch_add(1, Ordering::Relaxed);
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                        .unwrap_or("non-string panic payload");
+                    tracing::error!("{} handler panicked: {}", kind, message);
+                    panicked.push(Arc::clone(handler));
+                }
+            }
+        }
+        
+        if self.remove_panicking && !panicked.is_empty() {
+            handlers.write().retain(|handler| !panicked.iter().any(|faulty| Arc::ptr_eq(faulty, handler)));
+            tracing::warn!("Removed {} panicking {} handler(s)", panicked.len(), kind.to_lowercase());
+        }
+        if !disabled.is_empty() {
+            handlers.write().retain(|handler| !disabled.iter().any(|slow| Arc::ptr_eq(slow, handler)));
+            tracing::warn!("Removed {} slow {} handler(s)", disabled.len(), kind.to_lowercase());
         }
     }
+    
+    /// Count a call that ran over budget; true once the handler has used up its allowance
+    fn record_slow<H: ?Sized>(&self, handler: &Arc<H>, index: usize, elapsed: Duration, kind: &str) -> bool {
+        self.slow_calls.fetch_add(1, Ordering::Relaxed);
+        let mut slow_by_handler = self.slow_by_handler.lock();
+        let slow = slow_by_handler.entry(Arc::as_ptr(handler) as *const () as usize).or_insert(0);
+        *slow += 1;
+        tracing::warn!(
+            "{} handler #{} took {:?}, over its {:?} budget ({} slow calls)",
+            kind, index, elapsed, self.time_budget.unwrap_or_default(), slow
+        );
+        self.max_slow_calls > 0 && *slow >= self.max_slow_calls
+    }
 }
 
 pub struct EventProcessor {
@@ -39,7 +120,9 @@ pub struct EventProcessor {
     priority_queue: PriorityQueue,
     batch_processor: BatchProcessor,
     event_handlers: Arc<RwLock<Vec<EventHandler>>>,
+    coalesced_handlers: Arc<RwLock<Vec<EventHandler>>>,
     batch_handlers: Arc<RwLock<Vec<BatchHandler>>>,
+    isolation: Arc<HandlerIsolation>,
     worker_handles: Arc<RwLock<Vec<JoinHandle<()>>>>,
     running: Arc<RwLock<bool>>,
 }
@@ -54,6 +137,14 @@ impl EventProcessor {
     pub fn with_config(config: ProcessorConfig) -> Self {
         let channels = EventChannels::new(config.buffer_size);
         let batch_processor = BatchProcessor::new(config.batch_config.clone());
+        let isolation = Arc::new(HandlerIsolation {
+            panics: AtomicU64::new(0),
+            remove_panicking: config.remove_panicking_handlers,
+            slow_calls: AtomicU64::new(0),
+            time_budget: config.handler_time_budget,
+            max_slow_calls: config.max_slow_handler_calls,
+            slow_by_handler: Mutex::new(HashMap::new()),
+        });
         
         Self {
             config,
@@ -61,7 +152,9 @@ impl EventProcessor {
             priority_queue: PriorityQueue::new(),
             batch_processor,
             event_handlers: Arc::new(RwLock::new(Vec::new())),
+            coalesced_handlers: Arc::new(RwLock::new(Vec::new())),
             batch_handlers: Arc::new(RwLock::new(Vec::new())),
+            isolation,
             worker_handles: Arc::new(RwLock::new(Vec::new())),
             running: Arc::new(RwLock::new(false)),
         }
@@ -72,11 +165,34 @@ impl EventProcessor {
         self.event_handlers.write().push(handler);
     }
     
+    /// Register a handler that receives `Event::Batch` as a single event instead of its members
+    #[inline]
+    pub fn add_coalesced_event_handler(&self, handler: EventHandler) {
+        self.coalesced_handlers.write().push(handler);
+    }
+    
     #[inline]
     pub fn add_batch_handler(&self, handler: BatchHandler) {
         self.batch_handlers.write().push(handler);
     }
     
+    /// Handler invocations that panicked since the processor was created
+    #[inline]
+    pub fn handler_panics(&self) -> u64 {
+        self.isolation.panics.load(Ordering::Relaxed)
+    }
+    
+    /// Handler invocations that ran over `handler_time_budget` since the processor was created
+    #[inline]
+    pub fn handler_slow_calls(&self) -> u64 {
+        self.isolation.slow_calls.load(Ordering::Relaxed)
+    }
+    
+    /// Handlers currently registered: per-event, coalesced and batch
+    pub fn handler_count(&self) -> usize {
+        self.event_handlers.read().len() + self.coalesced_handlers.read().len() + self.batch_handlers.read().len()
+    }
+    
     #[inline]
     pub fn send_event(&self, event: Event) -> Result<()> {
         if self.config.enable_priority_queue {
@@ -131,6 +247,11 @@ impl EventProcessor {
         *self.running.read()
     }
     
+    #[inline]
+    pub fn queue_depth(&self) -> usize {
+        self.channels.pending_events() + self.priority_queue.len()
+    }
+    
     #[inline]
     pub fn channels(&self) -> &EventChannels {
         &self.channels
@@ -151,7 +272,9 @@ impl EventProcessor {
         let priority_queue = self.priority_queue.clone();
         let batch_processor = self.batch_processor.clone();
         let event_handlers = Arc::clone(&self.event_handlers);
+        let coalesced_handlers = Arc::clone(&self.coalesced_handlers);
         let batch_handlers = Arc::clone(&self.batch_handlers);
+        let isolation = Arc::clone(&self.isolation);
         let running = Arc::clone(&self.running);
         let enable_priority = self.config.enable_priority_queue;
         
@@ -177,21 +300,15 @@ impl EventProcessor {
                 };
                 
                 if let Some(event) = event {
-                    let handlers = event_handlers.read();
-                    for handler in handlers.iter() {
-                        if let Err(e) = handler(&event) {
-                            tracing::error!("Event handler error: {}", e);
-                        }
-                    }
+                    isolation.run(&coalesced_handlers, &event, "Event");
                     
-                    if let Some(batch) = batch_processor.add_event(event) {
-                        let batch_handlers = batch_handlers.read();
-                        for handler in batch_handlers.iter() {
-                            if let Err(e) = handler(&batch) {
-                                tracing::error!("Batch handler error: {}", e);
+                    match event {
+                        Event::Batch(_) => {
+                            for event in event.into_flat() {
+                                Self::dispatch(event, &event_handlers, &batch_processor, &batch_handlers, &isolation);
                             }
                         }
-                        batch_processor.mark_batch_processed(&batch);
+                        event => Self::dispatch(event, &event_handlers, &batch_processor, &batch_handlers, &isolation),
                     }
                 } else {
                     tokio::task::yield_now().await;
@@ -204,9 +321,25 @@ impl EventProcessor {
         Ok(handle)
     }
     
+    fn dispatch(
+        event: Event,
+        event_handlers: &RwLock<Vec<EventHandler>>,
+        batch_processor: &BatchProcessor,
+        batch_handlers: &RwLock<Vec<BatchHandler>>,
+        isolation: &HandlerIsolation,
+    ) {
+        isolation.run(event_handlers, &event, "Event");
+        
+        if let Some(batch) = batch_processor.add_event(event) {
+            isolation.run(batch_handlers, &batch, "Batch");
+            batch_processor.mark_batch_processed(&batch);
+        }
+    }
+    
     async fn spawn_flush_worker(&self) -> Result<JoinHandle<()>> {
         let batch_processor = self.batch_processor.clone();
         let batch_handlers = Arc::clone(&self.batch_handlers);
+        let isolation = Arc::clone(&self.isolation);
         let running = Arc::clone(&self.running);
         let flush_interval = self.config.flush_interval;
         
@@ -217,12 +350,7 @@ impl EventProcessor {
                 interval.tick().await;
                 
                 if let Some(batch) = batch_processor.flush() {
-                    let handlers = batch_handlers.read();
-                    for handler in handlers.iter() {
-                        if let Err(e) = handler(&batch) {
-                            tracing::error!("Batch flush handler error: {}", e);
-                        }
-                    }
+                    isolation.run(&batch_handlers, &batch, "Batch flush");
                     batch_processor.mark_batch_processed(&batch);
                 }
             }
@@ -0,0 +1,117 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CodecError {
+    #[error("JSON codec error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("MessagePack encode error: {0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    #[error("MessagePack decode error: {0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    #[error("Unknown payload codec: {0}")]
+    Unknown(String),
+}
+
+/// Wire format for integration request and response bodies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadCodec {
+    #[default]
+    Json,
+    /// Self-describing binary encoding; structs keep their field names so either side
+    /// can add optional fields, and `serde_json::Value` metadata round-trips
+    MessagePack,
+}
+
+impl PayloadCodec {
+    #[inline]
+    pub fn content_type(self) -> &'static str {
+        match self {
+            PayloadCodec::Json => "application/json",
+            PayloadCodec::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Codec matching a response `Content-Type`, ignoring parameters such as `charset`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next()?.trim() {
+            "application/json" => Some(PayloadCodec::Json),
+            "application/msgpack" | "application/x-msgpack" => Some(PayloadCodec::MessagePack),
+            _ => None,
+        }
+    }
+
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(match self {
+            PayloadCodec::Json => serde_json::to_vec(value)?,
+            PayloadCodec::MessagePack => rmp_serde::to_vec_named(value)?,
+        })
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, CodecError> {
+        Ok(match self {
+            PayloadCodec::Json => serde_json::from_slice(bytes)?,
+            PayloadCodec::MessagePack => rmp_serde::from_slice(bytes)?,
+        })
+    }
+}
+
+impl FromStr for PayloadCodec {
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(PayloadCodec::Json),
+            "msgpack" | "messagepack" => Ok(PayloadCodec::MessagePack),
+            other => Err(CodecError::Unknown(other.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{SignalSource, SignalType, TradingSignal};
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_trading_signal_round_trips_through_both_codecs() {
+        let signal = TradingSignal {
+            id: Uuid::new_v4(),
+            symbol: "BTC-USDT".to_string(),
+            signal_type: SignalType::StrongBuy,
+            strength: 0.82,
+            confidence: 0.67,
+            price_target: Some(Decimal::new(51_250, 0)),
+            stop_loss: Some(Decimal::new(49_500, 0)),
+            take_profit: None,
+            metadata: HashMap::from([
+                ("horizon".to_string(), serde_json::json!("short_term")),
+                ("features".to_string(), serde_json::json!({ "rsi": 71.5, "volume_z": 2.3 })),
+            ]),
+            timestamp: Utc::now(),
+            source: SignalSource::RAG,
+        };
+
+        let json = PayloadCodec::Json.encode(&signal).unwrap();
+        let msgpack = PayloadCodec::MessagePack.encode(&signal).unwrap();
+        assert!(msgpack.len() < json.len(), "msgpack {} bytes, json {} bytes", msgpack.len(), json.len());
+
+        // TradingSignal has no PartialEq; compare through its JSON value
+        let expected = serde_json::to_value(&signal).unwrap();
+        for (codec, bytes) in [(PayloadCodec::Json, json), (PayloadCodec::MessagePack, msgpack)] {
+            let decoded: TradingSignal = codec.decode(&bytes).unwrap();
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), expected, "{:?} round trip", codec);
+        }
+
+        assert_eq!("msgpack".parse::<PayloadCodec>().unwrap(), PayloadCodec::MessagePack);
+        assert_eq!(PayloadCodec::from_content_type("application/json; charset=utf-8"), Some(PayloadCodec::Json));
+        assert!("xml".parse::<PayloadCodec>().is_err());
+    }
+}
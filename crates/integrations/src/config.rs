@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use std::env;
 use anyhow::{Result, anyhow};
 
+use crate::codec::PayloadCodec;
+use crate::okx::SymbolMapping;
+use crate::types::PredictionHorizon;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntegrationConfig {
     pub okx: OkxConfig,
@@ -20,6 +24,37 @@ pub struct OkxConfig {
     pub base_url: Option<String>,
     pub timeout_ms: u64,
     pub rate_limit_requests_per_second: u32,
+    #[serde(default)]
+    pub websocket: OkxWebSocketConfig,
+    /// Internal symbol to OKX instrument table; every symbol traded on OKX must be listed
+    #[serde(default = "SymbolMapping::defaults")]
+    pub symbols: Vec<SymbolMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OkxWebSocketConfig {
+    pub url: Option<String>,
+    /// Offer permessage-deflate in the handshake and inflate the binary frames OKX deflates
+    /// itself
+    pub compression: bool,
+    pub max_frame_size: usize,
+    pub max_message_size: usize,
+    pub write_buffer_size: usize,
+    /// Send an OKX "ping" keepalive after this long without inbound traffic
+    pub ping_interval_ms: u64,
+}
+
+impl Default for OkxWebSocketConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            compression: true,
+            max_frame_size: 16 << 20,
+            max_message_size: 64 << 20,
+            write_buffer_size: 128 << 10,
+            ping_interval_ms: 25_000,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +64,70 @@ pub struct McpConfig {
     pub timeout_ms: u64,
     pub max_retries: u32,
     pub prediction_threshold: f64,
+    #[serde(default)]
+    pub horizon_routes: McpHorizonRoutes,
+    /// Reuse the prediction for an identical feature vector requested within this long;
+    /// 0 sends every request to the model
+    #[serde(default)]
+    pub prediction_cache_ttl_ms: u64,
+    /// Most predictions kept for reuse
+    #[serde(default = "default_prediction_cache_size")]
+    pub prediction_cache_size: usize,
+}
+
+fn default_prediction_cache_size() -> usize {
+    1_024
+}
+
+/// Model endpoint for one prediction horizon; unset fields fall back to the top-level `McpConfig`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpHorizonRoute {
+    pub server_url: Option<String>,
+    pub model_version: Option<String>,
+    pub prediction_threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpHorizonRoutes {
+    pub short_term: Option<McpHorizonRoute>,
+    pub medium_term: Option<McpHorizonRoute>,
+    pub long_term: Option<McpHorizonRoute>,
+}
+
+impl McpHorizonRoutes {
+    pub fn get(&self, horizon: &PredictionHorizon) -> Option<&McpHorizonRoute> {
+        match horizon {
+            PredictionHorizon::ShortTerm => self.short_term.as_ref(),
+            PredictionHorizon::MediumTerm => self.medium_term.as_ref(),
+            PredictionHorizon::LongTerm => self.long_term.as_ref(),
+        }
+    }
+
+    fn from_env() -> Self {
+        let route = |prefix: &str| {
+            let server_url = env::var(format!("MCP_{}_URL", prefix)).ok();
+            let model_version = env::var(format!("MCP_{}_MODEL", prefix)).ok();
+            let prediction_threshold = env::var(format!("MCP_{}_THRESHOLD", prefix))
+                .ok()
+                .and_then(|threshold| threshold.parse().ok());
+
+            if server_url.is_none() && model_version.is_none() && prediction_threshold.is_none() {
+                None
+            } else {
+                Some(McpHorizonRoute {
+                    server_url,
+                    model_version,
+                    prediction_threshold,
+                })
+            }
+        };
+
+        Self {
+            short_term: route("SHORT_TERM"),
+            medium_term: route("MEDIUM_TERM"),
+            long_term: route("LONG_TERM"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +138,36 @@ pub struct RagConfig {
     pub max_retries: u32,
     pub query_threshold: f32,
     pub top_k: usize,
+    /// Encoding of request and response bodies
+    #[serde(default)]
+    pub payload_codec: PayloadCodec,
+    #[serde(default)]
+    pub reranking: RagReranking,
+}
+
+/// How query results are re-ranked after the server scores them. Each result's rank score is
+/// the weighted mean of its server score, its recency and whether it is about the queried
+/// symbol, so with the default weights it is just the server score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RagReranking {
+    pub score_weight: f64,
+    /// Weight of recency, which halves every `recency_half_life_ms` of result age
+    pub recency_weight: f64,
+    pub recency_half_life_ms: u64,
+    /// Weight of the result's `symbol` metadata matching the queried symbol
+    pub symbol_weight: f64,
+}
+
+impl Default for RagReranking {
+    fn default() -> Self {
+        Self {
+            score_weight: 1.0,
+            recency_weight: 0.0,
+            recency_half_life_ms: 3_600_000,
+            symbol_weight: 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +177,69 @@ pub struct CoordinatorConfig {
     pub max_concurrent_requests: usize,
     pub decision_timeout_ms: u64,
     pub consensus_threshold: f64,
+    /// Refuse to generate signals from market data older than this
+    #[serde(default = "default_max_market_data_age_ms")]
+    pub max_market_data_age_ms: u64,
+    /// Report an exchange order as partially filled if it is not complete after this long
+    #[serde(default = "default_order_fill_timeout_ms")]
+    pub order_fill_timeout_ms: u64,
+    /// Caps on in-flight remote calls to each integration while generating signals
+    #[serde(default)]
+    pub concurrency: IntegrationConcurrency,
+    /// After trading a symbol, act on no further signals for it until this much time has passed
+    #[serde(default = "default_signal_cooldown_ms")]
+    pub signal_cooldown_ms: u64,
+    /// Reuse a symbol's market context for signals generated within this long of fetching it;
+    /// 0 fetches for every signal
+    #[serde(default)]
+    pub market_context_ttl_ms: u64,
+    /// What the coordinator feeds back into RAG about the signals it generates
+    #[serde(default)]
+    pub signal_ingestion: SignalIngestion,
+}
+
+/// How generated signals reach the knowledge base. Ingesting them raw lets the model learn
+/// from its own predictions rather than from what the market did with them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalIngestion {
+    /// Ingest every signal as it is generated
+    #[default]
+    Raw,
+    /// Never ingest signals
+    Suppressed,
+    /// Hold each signal until `IntegrationCoordinator::record_signal_outcome` reports how its
+    /// order ended, then ingest the two together. Signals that never trade are never ingested.
+    WithOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationConcurrency {
+    pub exchange: usize,
+    pub mcp: usize,
+    pub rag: usize,
+}
+
+impl Default for IntegrationConcurrency {
+    fn default() -> Self {
+        Self {
+            exchange: 16,
+            mcp: 8,
+            rag: 8,
+        }
+    }
+}
+
+fn default_max_market_data_age_ms() -> u64 {
+    2_000
+}
+
+fn default_order_fill_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_signal_cooldown_ms() -> u64 {
+    1_000
 }
 
 impl Default for CoordinatorConfig {
@@ -58,6 +250,12 @@ impl Default for CoordinatorConfig {
             max_concurrent_requests: 100,
             decision_timeout_ms: 50,
             consensus_threshold: 0.7,
+            max_market_data_age_ms: default_max_market_data_age_ms(),
+            order_fill_timeout_ms: default_order_fill_timeout_ms(),
+            concurrency: IntegrationConcurrency::default(),
+            signal_cooldown_ms: default_signal_cooldown_ms(),
+            market_context_ttl_ms: 0,
+            signal_ingestion: SignalIngestion::default(),
         }
     }
 }
@@ -81,6 +279,19 @@ impl IntegrationConfig {
                 .unwrap_or_default()
                 .parse()
                 .unwrap_or(20),
+            websocket: OkxWebSocketConfig {
+                url: env::var("OKX_WS_URL").ok(),
+                compression: env::var("OKX_WS_COMPRESSION").unwrap_or_default().parse().unwrap_or(true),
+                ..OkxWebSocketConfig::default()
+            },
+            symbols: match env::var("OKX_SYMBOL_MAP") {
+                Ok(map) => map
+                    .split(',')
+                    .filter(|entry| !entry.trim().is_empty())
+                    .map(str::parse)
+                    .collect::<std::result::Result<_, _>>()?,
+                Err(_) => SymbolMapping::defaults(),
+            },
         };
 
         let mcp = McpConfig {
@@ -98,6 +309,15 @@ impl IntegrationConfig {
                 .unwrap_or_default()
                 .parse()
                 .unwrap_or(0.7),
+            horizon_routes: McpHorizonRoutes::from_env(),
+            prediction_cache_ttl_ms: env::var("MCP_PREDICTION_CACHE_TTL_MS")
+                .unwrap_or_default()
+                .parse()
+                .unwrap_or(0),
+            prediction_cache_size: env::var("MCP_PREDICTION_CACHE_SIZE")
+                .unwrap_or_default()
+                .parse()
+                .unwrap_or_else(|_| default_prediction_cache_size()),
         };
 
         let rag = RagConfig {
@@ -119,6 +339,11 @@ impl IntegrationConfig {
                 .unwrap_or_default()
                 .parse()
                 .unwrap_or(10),
+            payload_codec: env::var("RAG_PAYLOAD_CODEC")
+                .unwrap_or_default()
+                .parse()
+                .unwrap_or_default(),
+            reranking: RagReranking::default(),
         };
 
         let coordinator = CoordinatorConfig::default();
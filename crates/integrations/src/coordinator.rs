@@ -1,25 +1,40 @@
 use anyhow::{Result, anyhow};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, RwLock, Mutex};
+use tokio::sync::{mpsc, RwLock, Mutex, Semaphore, SemaphorePermit};
 use tokio::time::interval;
 use tracing::{info, warn, error, debug};
 use std::collections::HashMap;
+use std::future::Future;
 use uuid::Uuid;
 use rust_decimal::prelude::ToPrimitive;
 
-use crate::config::IntegrationConfig;
+use crate::config::{IntegrationConcurrency, IntegrationConfig, SignalIngestion};
 #[cfg(test)]
 use crate::config::CoordinatorConfig;
 use crate::types::*;
+use crate::exchange::ExchangeAdapter;
+use crate::ids::{IdSource, RandomIds};
+use event_processor::Probe;
+use crate::order_tracker::{FillUpdate, OrderTracker, TrackedOrder};
 use crate::okx::OkxIntegration;
 use crate::mcp::McpIntegration;
 use crate::rag::RagIntegration;
 
+#[derive(Debug, thiserror::Error)]
+pub enum CoordinatorError {
+    #[error("Stale market data for {symbol}: {age_ms}ms old, limit is {max_age_ms}ms")]
+    StaleMarketData {
+        symbol: String,
+        age_ms: i64,
+        max_age_ms: u64,
+    },
+}
+
 #[derive(Debug)]
 pub struct IntegrationCoordinator {
     config: Arc<IntegrationConfig>,
-    okx: Arc<OkxIntegration>,
+    exchange: Arc<dyn ExchangeAdapter>,
     mcp: Arc<McpIntegration>,
     rag: Arc<RagIntegration>,
     signal_tx: mpsc::UnboundedSender<TradingSignal>,
@@ -27,6 +42,77 @@ pub struct IntegrationCoordinator {
     is_running: Arc<RwLock<bool>>,
     metrics: Arc<RwLock<IntegrationMetrics>>,
     active_requests: Arc<RwLock<HashMap<Uuid, ActiveRequest>>>,
+    order_tracker: Arc<OrderTracker>,
+    permits: IntegrationPermits,
+    /// When each symbol was last traded on a signal, shared between clones
+    last_traded: Arc<parking_lot::Mutex<HashMap<String, Instant>>>,
+    context_cache: MarketContextCache,
+    /// Signal events held back under `SignalIngestion::WithOutcome` until their order's outcome
+    /// is known, by signal id, shared between clones
+    pending_outcomes: Arc<parking_lot::Mutex<HashMap<Uuid, PendingOutcome>>>,
+    ids: Arc<dyn IdSource>,
+}
+
+/// A signal event waiting for its order's outcome
+#[derive(Debug)]
+struct PendingOutcome {
+    event: crate::rag::types::MarketEvent,
+    held_since: Instant,
+}
+
+/// Shared between clones so the caps hold across every task generating signals
+#[derive(Debug, Clone)]
+struct IntegrationPermits {
+    exchange: Arc<Semaphore>,
+    mcp: Arc<Semaphore>,
+    rag: Arc<Semaphore>,
+}
+
+impl IntegrationPermits {
+    fn new(config: &IntegrationConcurrency) -> Self {
+        Self {
+            exchange: Arc::new(Semaphore::new(config.exchange.max(1))),
+            mcp: Arc::new(Semaphore::new(config.mcp.max(1))),
+            rag: Arc::new(Semaphore::new(config.rag.max(1))),
+        }
+    }
+}
+
+/// One symbol's cached context and when it was fetched, locked across the fetch so
+/// concurrent callers wait for it
+type ContextSlot = Arc<Mutex<Option<(Instant, MarketContext)>>>;
+
+/// Recently fetched market contexts by symbol, shared between clones
+#[derive(Debug, Clone, Default)]
+struct MarketContextCache {
+    entries: Arc<parking_lot::Mutex<HashMap<String, ContextSlot>>>,
+}
+
+impl MarketContextCache {
+    /// The context for `symbol` if it was fetched within `ttl`, otherwise the result of `fetch`.
+    /// Concurrent callers for one symbol wait for a single fetch; failures are not cached.
+    async fn get_or_fetch<F, Fut>(&self, symbol: &str, ttl: Duration, fetch: F) -> Result<MarketContext>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<MarketContext>>,
+    {
+        let slot = self.entries.lock().entry(symbol.to_string()).or_default().clone();
+        let mut slot = slot.lock().await;
+        if let Some((fetched_at, context)) = slot.as_ref() {
+            if fetched_at.elapsed() < ttl {
+                return Ok(context.clone());
+            }
+        }
+        
+        let context = fetch().await?;
+        *slot = Some((Instant::now(), context.clone()));
+        Ok(context)
+    }
+}
+
+/// Wait for a slot on one integration; the permit is released when dropped
+async fn acquire(semaphore: &Semaphore) -> SemaphorePermit<'_> {
+    semaphore.acquire().await.expect("integration semaphores are never closed")
 }
 
 #[derive(Debug, Clone)]
@@ -47,14 +133,23 @@ enum RequestType {
 
 impl IntegrationCoordinator {
     pub async fn new(config: Arc<IntegrationConfig>) -> Result<Self> {
-        info!("Initializing Integration Coordinator");
+        let okx = Arc::new(OkxIntegration::new(config.okx.clone()).await?);
+        Self::with_exchange(config, okx).await
+    }
+    
+    /// Create a coordinator that trades through the given exchange adapter
+    pub async fn with_exchange(config: Arc<IntegrationConfig>, exchange: Arc<dyn ExchangeAdapter>) -> Result<Self> {
+        info!("Initializing Integration Coordinator with {} exchange", exchange.name());
         
         // Initialize all integrations
-        let okx = Arc::new(OkxIntegration::new(config.okx.clone()).await?);
         let mcp = Arc::new(McpIntegration::new(config.mcp.clone()).await?);
         let rag = Arc::new(RagIntegration::new(config.rag.clone()).await?);
         
         let (signal_tx, signal_rx) = mpsc::unbounded_channel();
+        let permits = IntegrationPermits::new(&config.coordinator.concurrency);
+        let order_tracker = Arc::new(OrderTracker::new(
+            Duration::from_millis(config.coordinator.order_fill_timeout_ms)
+        ));
         
         let metrics = Arc::new(RwLock::new(IntegrationMetrics {
             requests_per_second: 0.0,
@@ -69,7 +164,7 @@ impl IntegrationCoordinator {
         
         Ok(Self {
             config,
-            okx,
+            exchange,
             mcp,
             rag,
             signal_tx,
@@ -77,9 +172,21 @@ impl IntegrationCoordinator {
             is_running: Arc::new(RwLock::new(false)),
             metrics,
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            order_tracker,
+            permits,
+            last_traded: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            context_cache: MarketContextCache::default(),
+            pending_outcomes: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            ids: Arc::new(RandomIds),
         })
     }
     
+    /// Draw signal, request and query ids from `ids` instead of random v4 ids
+    pub fn with_id_source(mut self, ids: Arc<dyn IdSource>) -> Self {
+        self.ids = ids;
+        self
+    }
+    
     pub async fn start(&self) -> Result<()> {
         let mut is_running = self.is_running.write().await;
         if *is_running {
@@ -92,13 +199,14 @@ impl IntegrationCoordinator {
         info!("Starting Integration Coordinator");
         
         // Start all integrations
-        self.okx.start().await?;
+        self.exchange.start().await?;
         self.rag.ingestion.start().await?;
         
         // Start coordinator services
         self.start_signal_processor().await?;
         self.start_health_monitor().await?;
         self.start_metrics_collector().await?;
+        self.start_fill_monitor().await?;
         
         info!("Integration Coordinator started successfully");
         Ok(())
@@ -111,7 +219,7 @@ impl IntegrationCoordinator {
         info!("Stopping Integration Coordinator");
         
         // Stop all integrations
-        self.okx.stop().await?;
+        self.exchange.stop().await?;
         self.rag.ingestion.stop().await?;
         
         info!("Integration Coordinator stopped");
@@ -171,6 +279,28 @@ impl IntegrationCoordinator {
         Ok(())
     }
     
+    async fn start_fill_monitor(&self) -> Result<()> {
+        let coordinator = self.clone();
+        let is_running = self.is_running.clone();
+        let sweep_interval = self.order_tracker.fill_timeout().clamp(Duration::from_millis(100), Duration::from_secs(1));
+        
+        tokio::spawn(async move {
+            let mut interval = interval(sweep_interval);
+            
+            while *is_running.read().await {
+                interval.tick().await;
+                for order in coordinator.order_tracker.expire_stale() {
+                    if let Err(e) = coordinator.record_signal_outcome(&order).await {
+                        warn!("Failed to ingest outcome of signal {}: {}", order.signal_id, e);
+                    }
+                }
+                coordinator.expire_pending_outcomes();
+            }
+        });
+        
+        Ok(())
+    }
+    
     async fn start_metrics_collector(&self) -> Result<()> {
         let coordinator = self.clone();
         
@@ -198,8 +328,22 @@ impl IntegrationCoordinator {
         Ok(())
     }
     
+    /// Market context for `symbol`, served from the cache while it is younger than the configured TTL
+    async fn market_context(&self, symbol: &str) -> Result<MarketContext> {
+        let fetch = || async {
+            let _permit = acquire(&self.permits.exchange).await;
+            self.exchange.get_market_context(symbol).await
+        };
+        
+        let ttl = Duration::from_millis(self.config.coordinator.market_context_ttl_ms);
+        if ttl.is_zero() {
+            return fetch().await;
+        }
+        self.context_cache.get_or_fetch(symbol, ttl, fetch).await
+    }
+    
     pub async fn generate_trading_signal(&self, symbol: &str) -> Result<TradingSignal> {
-        let request_id = Uuid::new_v4();
+        let request_id = self.ids.next_id();
         let start_time = Instant::now();
         
         debug!("Generating trading signal for {}", symbol);
@@ -214,14 +358,14 @@ impl IntegrationCoordinator {
         
         // Track market data request
         self.track_request(ActiveRequest {
-            request_id: Uuid::new_v4(),
+            request_id: self.ids.next_id(),
             symbol: symbol.to_string(),
             start_time: Instant::now(),
             request_type: RequestType::MarketData,
         }).await;
         
-        // Get market context from OKX
-        let market_context = match self.okx.get_market_context(symbol).await {
+        // Get market context from the exchange
+        let market_context = match self.market_context(symbol).await {
             Ok(context) => context,
             Err(e) => {
                 self.untrack_request(request_id).await;
@@ -229,6 +373,39 @@ impl IntegrationCoordinator {
             }
         };
         
+        let data_age_ms = (chrono::Utc::now() - market_context.timestamp).num_milliseconds();
+        let max_age_ms = self.config.coordinator.max_market_data_age_ms;
+        if data_age_ms > max_age_ms as i64 {
+            self.untrack_request(request_id).await;
+            warn!("Refusing to generate signal for {}: market data is {}ms old", symbol, data_age_ms);
+            
+            let stale_event = crate::rag::types::MarketEvent {
+                id: request_id.to_string(),
+                timestamp: chrono::Utc::now(),
+                event_type: crate::rag::types::MarketEventType::Alert,
+                symbol: symbol.to_string(),
+                data: serde_json::to_value(&market_context)?,
+                metadata: {
+                    let mut meta = HashMap::new();
+                    meta.insert("stale_market_data".to_string(), "true".to_string());
+                    meta.insert("market_data_age_ms".to_string(), data_age_ms.to_string());
+                    meta.insert("source".to_string(), "coordinator".to_string());
+                    meta
+                },
+            };
+            let _permit = acquire(&self.permits.rag).await;
+            if let Err(e) = self.rag.ingest_market_event(stale_event).await {
+                warn!("Failed to record stale market data alert: {}", e);
+            }
+            
+            return Err(CoordinatorError::StaleMarketData {
+                symbol: symbol.to_string(),
+                age_ms: data_age_ms,
+                max_age_ms,
+            }
+            .into());
+        }
+        
         // Extract features for MCP  
         let features = {
             let mut extractor = crate::mcp::FeatureExtractor::new();
@@ -249,18 +426,21 @@ impl IntegrationCoordinator {
         
         // Track prediction request
         self.track_request(ActiveRequest {
-            request_id: Uuid::new_v4(),
+            request_id: self.ids.next_id(),
             symbol: symbol.to_string(),
             start_time: Instant::now(),
             request_type: RequestType::Prediction,
         }).await;
         
         // Get AI prediction from MCP
-        let prediction_response = self.mcp.get_prediction(prediction_request).await.ok();
+        let prediction_response = {
+            let _permit = acquire(&self.permits.mcp).await;
+            self.mcp.get_prediction(prediction_request).await.ok()
+        };
         
         // Query knowledge base from RAG
         let knowledge_query = KnowledgeQuery {
-            query_id: Uuid::new_v4(),
+            query_id: self.ids.next_id(),
             query_text: format!("trading patterns similar to current {} market conditions", symbol),
             symbol: Some(symbol.to_string()),
             context: {
@@ -277,17 +457,21 @@ impl IntegrationCoordinator {
         
         // Track knowledge query request
         self.track_request(ActiveRequest {
-            request_id: Uuid::new_v4(),
+            request_id: self.ids.next_id(),
             symbol: symbol.to_string(),
             start_time: Instant::now(),
             request_type: RequestType::KnowledgeQuery,
         }).await;
         
-        let knowledge_response = self.rag.query_knowledge(knowledge_query).await.ok();
+        let knowledge_query_id = knowledge_query.query_id;
+        let knowledge_response = {
+            let _permit = acquire(&self.permits.rag).await;
+            self.rag.query_knowledge(knowledge_query).await.ok()
+        };
         
         // Create decision context
         let decision_context = DecisionContext {
-            signal_id: Uuid::new_v4(),
+            signal_id: self.ids.next_id(),
             symbol: symbol.to_string(),
             market_context: market_context.clone(),
             prediction: prediction_response,
@@ -297,9 +481,12 @@ impl IntegrationCoordinator {
         };
         
         // Generate consensus-based signal
-        let signal = self.generate_consensus_signal(decision_context).await?;
+        let mut signal = self.generate_consensus_signal(decision_context).await?;
+        signal.metadata.insert("market_data_age_ms".to_string(), serde_json::Value::Number(data_age_ms.into()));
+        signal.metadata.insert("request_id".to_string(), serde_json::Value::String(request_id.to_string()));
+        signal.metadata.insert("knowledge_query_id".to_string(), serde_json::Value::String(knowledge_query_id.to_string()));
         
-        // Ingest the signal into RAG for future learning
+        // Feed the signal back into RAG for future learning
         let market_event = crate::rag::types::MarketEvent {
             id: signal.id.to_string(),
             timestamp: signal.timestamp,
@@ -311,12 +498,29 @@ impl IntegrationCoordinator {
                 meta.insert("signal_strength".to_string(), signal.strength.to_string());
                 meta.insert("confidence".to_string(), signal.confidence.to_string());
                 meta.insert("source".to_string(), "coordinator".to_string());
+                meta.insert("stale_market_data".to_string(), "false".to_string());
+                meta.insert("market_data_age_ms".to_string(), data_age_ms.to_string());
                 meta
             },
         };
         
-        if let Err(e) = self.rag.ingest_market_event(market_event).await {
-            warn!("Failed to ingest signal into RAG: {}", e);
+        match self.config.coordinator.signal_ingestion {
+            SignalIngestion::Raw => {
+                let ingested = {
+                    let _permit = acquire(&self.permits.rag).await;
+                    self.rag.ingest_market_event(market_event).await
+                };
+                if let Err(e) = ingested {
+                    warn!("Failed to ingest signal into RAG: {}", e);
+                }
+            }
+            SignalIngestion::Suppressed => {}
+            SignalIngestion::WithOutcome => {
+                self.pending_outcomes.lock().insert(signal.id, PendingOutcome {
+                    event: market_event,
+                    held_since: Instant::now(),
+                });
+            }
         }
         
         self.untrack_request(request_id).await;
@@ -356,7 +560,7 @@ impl IntegrationCoordinator {
             if !knowledge.results.is_empty() {
                 contributing_factors += 1;
                 
-                // Analyze historical patterns
+                // Analyze historical patterns by server score; re-ranking only reorders results
                 let avg_score = knowledge.results.iter().map(|r| r.score as f64).sum::<f64>() / knowledge.results.len() as f64;
                 
                 // Simple heuristic: higher scores suggest similar successful patterns
@@ -412,7 +616,7 @@ impl IntegrationCoordinator {
                 .map(|target| target * rust_decimal::Decimal::new(105, 2)), // 5% take profit
             metadata: {
                 let mut meta = HashMap::new();
-                meta.insert("okx_connected".to_string(), serde_json::Value::Bool(true));
+                meta.insert("exchange".to_string(), serde_json::Value::String(self.exchange.name().to_string()));
                 meta.insert("mcp_prediction".to_string(), 
                     serde_json::Value::Bool(context.prediction.is_some()));
                 meta.insert("rag_knowledge".to_string(), 
@@ -496,29 +700,108 @@ impl IntegrationCoordinator {
     async fn process_trading_signal(&self, signal: TradingSignal) -> Result<()> {
         info!("Processing trading signal: {:?} for {}", signal.signal_type, signal.symbol);
         
-        // Place order through OKX if not a HOLD signal
+        // Place order through the exchange if not a HOLD signal
         if !matches!(signal.signal_type, SignalType::Hold) {
-            match self.okx.place_order(&signal).await {
+            let Some(previous) = self.start_cooldown(&signal.symbol) else {
+                info!(
+                    "Not trading {:?} signal {} for {}: symbol is cooling down",
+                    signal.signal_type, signal.id, signal.symbol
+                );
+                self.pending_outcomes.lock().remove(&signal.id);
+                return Ok(());
+            };
+            
+            let placed = {
+                let _permit = acquire(&self.permits.exchange).await;
+                self.exchange.place_order(&signal).await
+            };
+            match placed {
                 Ok(order_response) => {
                     info!("Order placed successfully: {:?}", order_response);
+                    if order_response.accepted {
+                        self.order_tracker.track(&signal, &order_response);
+                    } else {
+                        self.pending_outcomes.lock().remove(&signal.id);
+                    }
                 }
                 Err(e) => {
                     error!("Failed to place order: {}", e);
+                    self.pending_outcomes.lock().remove(&signal.id);
+                    // Nothing was traded, so the next signal may act
+                    let mut last_traded = self.last_traded.lock();
+                    match previous {
+                        Some(at) => last_traded.insert(signal.symbol.clone(), at),
+                        None => last_traded.remove(&signal.symbol),
+                    };
                 }
             }
+        } else {
+            // A signal that never trades has no outcome to wait for
+            self.pending_outcomes.lock().remove(&signal.id);
         }
         
         Ok(())
     }
     
+    /// Ingest the signal behind `order` together with how the order ended. `handle_order_update`
+    /// calls this for filled orders and the fill monitor for timed out ones. Only signals held
+    /// back under `SignalIngestion::WithOutcome` are ingested; returns whether one was.
+    pub async fn record_signal_outcome(&self, order: &TrackedOrder) -> Result<bool> {
+        let Some(PendingOutcome { mut event, .. }) = self.pending_outcomes.lock().remove(&order.signal_id) else {
+            return Ok(false);
+        };
+        
+        if let Some(data) = event.data.as_object_mut() {
+            data.insert("outcome".to_string(), serde_json::to_value(order)?);
+        }
+        event.metadata.insert("outcome_status".to_string(), format!("{:?}", order.status));
+        event.metadata.insert("filled_quantity".to_string(), order.filled_quantity.to_string());
+        if let Some(average_price) = order.average_price {
+            event.metadata.insert("average_fill_price".to_string(), average_price.to_string());
+        }
+        
+        let _permit = acquire(&self.permits.rag).await;
+        self.rag.ingest_market_event(event).await?;
+        Ok(true)
+    }
+    
+    /// Drop signal events whose outcome will never arrive. A tracked order is reported by its
+    /// fill timeout at the latest, so anything held for twice that never reached the tracker.
+    /// Returns the number dropped.
+    fn expire_pending_outcomes(&self) -> usize {
+        let ttl = self.order_tracker.fill_timeout() * 2;
+        let mut pending = self.pending_outcomes.lock();
+        let before = pending.len();
+        pending.retain(|_, outcome| outcome.held_since.elapsed() < ttl);
+        let expired = before - pending.len();
+        if expired > 0 {
+            debug!("Dropped {} signal events whose order outcome never arrived", expired);
+        }
+        expired
+    }
+    
+    /// Claim `symbol` for trading unless it was traded within the cooldown.
+    /// Returns the claim it replaced, or `None` if the symbol is still cooling down.
+    fn start_cooldown(&self, symbol: &str) -> Option<Option<Instant>> {
+        let cooldown = Duration::from_millis(self.config.coordinator.signal_cooldown_ms);
+        let now = Instant::now();
+        let mut last_traded = self.last_traded.lock();
+        let previous = last_traded.get(symbol).copied();
+        if previous.is_some_and(|at| now.duration_since(at) < cooldown) {
+            return None;
+        }
+        last_traded.insert(symbol.to_string(), now);
+        Some(previous)
+    }
+    
     async fn perform_health_checks(&self) -> Result<()> {
         debug!("Performing health checks");
         
-        let okx_health = self.okx.health_check().await.unwrap_or(HealthStatus::Unknown);
+        let exchange_health = self.exchange.health_check().await.unwrap_or(HealthStatus::Unknown);
         let mcp_health = self.mcp.health_check().await.unwrap_or(HealthStatus::Unknown);
         let rag_health = self.rag.health_check().await.unwrap_or(HealthStatus::Unknown);
         
-        let overall_status = match (okx_health, mcp_health, rag_health) {
+        let overall_status = match (exchange_health, mcp_health, rag_health) {
             (HealthStatus::Healthy, HealthStatus::Healthy, HealthStatus::Healthy) => HealthStatus::Healthy,
             (HealthStatus::Unhealthy, _, _) | (_, HealthStatus::Unhealthy, _) | (_, _, HealthStatus::Unhealthy) => HealthStatus::Unhealthy,
             _ => HealthStatus::Degraded,
@@ -564,11 +847,11 @@ impl IntegrationCoordinator {
     }
     
     pub async fn health_check(&self) -> Result<IntegrationHealth> {
-        let okx_status = self.okx.health_check().await.unwrap_or(HealthStatus::Unknown);
+        let exchange_status = self.exchange.health_check().await.unwrap_or(HealthStatus::Unknown);
         let mcp_status = self.mcp.health_check().await.unwrap_or(HealthStatus::Unknown);
         let rag_status = self.rag.health_check().await.unwrap_or(HealthStatus::Unknown);
         
-        let overall_status = match (okx_status.clone(), mcp_status.clone(), rag_status.clone()) {
+        let overall_status = match (exchange_status.clone(), mcp_status.clone(), rag_status.clone()) {
             (HealthStatus::Healthy, HealthStatus::Healthy, HealthStatus::Healthy) => HealthStatus::Healthy,
             (HealthStatus::Unhealthy, _, _) | (_, HealthStatus::Unhealthy, _) | (_, _, HealthStatus::Unhealthy) => HealthStatus::Unhealthy,
             _ => HealthStatus::Degraded,
@@ -576,7 +859,7 @@ impl IntegrationCoordinator {
         
         Ok(IntegrationHealth {
             overall_status,
-            okx_status,
+            okx_status: exchange_status,
             mcp_status,
             rag_status,
             last_check: chrono::Utc::now(),
@@ -589,14 +872,54 @@ impl IntegrationCoordinator {
         })
     }
     
+    /// Live while its exchange adapter is; MCP and RAG clients have nothing to restart
+    pub fn liveness(&self) -> Probe {
+        self.exchange.liveness()
+    }
+    
+    /// Ready once started and its exchange is ready. MCP and RAG are optional inputs to a
+    /// signal, so they are reported by `health_check` but do not gate trading.
+    pub async fn readiness(&self) -> Probe {
+        if !*self.is_running.read().await {
+            return Probe::Fail("coordinator not started".to_string());
+        }
+        match self.exchange.readiness().await {
+            Probe::Pass => Probe::Pass,
+            Probe::Fail(reason) => Probe::Fail(format!("{} exchange not ready: {}", self.exchange.name(), reason)),
+        }
+    }
+    
     pub async fn get_metrics(&self) -> IntegrationMetrics {
         let metrics = self.metrics.read().await;
         metrics.clone()
     }
     
+    pub fn exchange(&self) -> &Arc<dyn ExchangeAdapter> {
+        &self.exchange
+    }
+    
     pub fn get_signal_sender(&self) -> mpsc::UnboundedSender<TradingSignal> {
         self.signal_tx.clone()
     }
+    
+    pub fn order_tracker(&self) -> &Arc<OrderTracker> {
+        &self.order_tracker
+    }
+    
+    /// Apply an OKX `orders` channel push to tracked orders and record the outcome of every
+    /// signal whose order is now fully filled, returning those orders
+    pub async fn handle_order_update(&self, data: &serde_json::Value) -> Vec<TrackedOrder> {
+        let completed: Vec<TrackedOrder> = FillUpdate::from_okx(data)
+            .iter()
+            .filter_map(|update| self.order_tracker.apply_fill(update))
+            .collect();
+        for order in &completed {
+            if let Err(e) = self.record_signal_outcome(order).await {
+                warn!("Failed to ingest outcome of signal {}: {}", order.signal_id, e);
+            }
+        }
+        completed
+    }
 }
 
 impl Clone for IntegrationCoordinator {
@@ -605,7 +928,7 @@ impl Clone for IntegrationCoordinator {
         
         Self {
             config: self.config.clone(),
-            okx: self.okx.clone(),
+            exchange: self.exchange.clone(),
             mcp: self.mcp.clone(),
             rag: self.rag.clone(),
             signal_tx,
@@ -613,6 +936,12 @@ impl Clone for IntegrationCoordinator {
             is_running: Arc::new(RwLock::new(false)),
             metrics: self.metrics.clone(),
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            order_tracker: self.order_tracker.clone(),
+            permits: self.permits.clone(),
+            last_traded: self.last_traded.clone(),
+            context_cache: self.context_cache.clone(),
+            pending_outcomes: self.pending_outcomes.clone(),
+            ids: self.ids.clone(),
         }
     }
 }
@@ -620,10 +949,12 @@ impl Clone for IntegrationCoordinator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{OkxConfig, McpConfig, RagConfig};
+    use crate::codec::PayloadCodec;
+    use crate::config::{OkxConfig, OkxWebSocketConfig, McpConfig, McpHorizonRoutes, RagConfig, RagReranking};
+    use crate::okx::SymbolMapping;
     
-    async fn create_test_coordinator() -> Result<IntegrationCoordinator> {
-        let config = IntegrationConfig {
+    fn create_test_config() -> IntegrationConfig {
+        IntegrationConfig {
             okx: OkxConfig {
                 api_key: "test_key".to_string(),
                 secret_key: "dGVzdF9zZWNyZXQ=".to_string(),
@@ -632,6 +963,8 @@ mod tests {
                 base_url: None,
                 timeout_ms: 5000,
                 rate_limit_requests_per_second: 10,
+                websocket: OkxWebSocketConfig::default(),
+                symbols: SymbolMapping::defaults(),
             },
             mcp: McpConfig {
                 server_url: "http://localhost:8000".to_string(),
@@ -639,6 +972,9 @@ mod tests {
                 timeout_ms: 1000,
                 max_retries: 3,
                 prediction_threshold: 0.7,
+                horizon_routes: McpHorizonRoutes::default(),
+                prediction_cache_ttl_ms: 0,
+                prediction_cache_size: 1_024,
             },
             rag: RagConfig {
                 server_url: "http://localhost:8001".to_string(),
@@ -647,11 +983,87 @@ mod tests {
                 max_retries: 2,
                 query_threshold: 0.6,
                 top_k: 10,
+                payload_codec: PayloadCodec::default(),
+                reranking: RagReranking::default(),
             },
             coordinator: CoordinatorConfig::default(),
-        };
+        }
+    }
+    
+    async fn create_test_coordinator() -> Result<IntegrationCoordinator> {
+        IntegrationCoordinator::new(Arc::new(create_test_config())).await
+    }
+    
+    #[derive(Debug, Default)]
+    struct MockExchange {
+        started: std::sync::atomic::AtomicBool,
+        context_requests: std::sync::atomic::AtomicUsize,
+        context_age_ms: i64,
+        context_delay_ms: u64,
+        contexts_in_flight: std::sync::atomic::AtomicUsize,
+        max_contexts_in_flight: std::sync::atomic::AtomicUsize,
+        orders: parking_lot::Mutex<Vec<TradingSignal>>,
+    }
+    
+    #[async_trait::async_trait]
+    impl ExchangeAdapter for MockExchange {
+        fn name(&self) -> &str {
+            "mock"
+        }
+        
+        async fn start(&self) -> Result<()> {
+            self.started.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+        
+        async fn stop(&self) -> Result<()> {
+            self.started.store(false, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+        
+        async fn place_order(&self, signal: &TradingSignal) -> Result<ExchangeOrderAck> {
+            self.orders.lock().push(signal.clone());
+            Ok(ExchangeOrderAck {
+                exchange: "mock".to_string(),
+                order_id: signal.id.to_string(),
+                client_order_id: signal.id.to_string(),
+                quantity: rust_decimal::Decimal::ONE,
+                accepted: true,
+                message: String::new(),
+                timestamp: chrono::Utc::now(),
+            })
+        }
+        
+        async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn get_market_context(&self, symbol: &str) -> Result<MarketContext> {
+            self.context_requests.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let in_flight = self.contexts_in_flight.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            self.max_contexts_in_flight.fetch_max(in_flight, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(self.context_delay_ms)).await;
+            self.contexts_in_flight.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(MarketContext {
+                symbol: symbol.to_string(),
+                current_price: rust_decimal::Decimal::new(45000, 0),
+                bid: rust_decimal::Decimal::new(44999, 0),
+                ask: rust_decimal::Decimal::new(45001, 0),
+                volume_24h: rust_decimal::Decimal::new(1000, 0),
+                change_24h: rust_decimal::Decimal::ZERO,
+                volatility: Some(0.05),
+                order_book_depth: None,
+                timestamp: chrono::Utc::now() - chrono::Duration::milliseconds(self.context_age_ms),
+            })
+        }
+        
+        async fn subscribe_market_data(&self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
         
-        IntegrationCoordinator::new(Arc::new(config)).await
+        async fn health_check(&self) -> Result<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
     }
     
     #[tokio::test]
@@ -666,4 +1078,273 @@ mod tests {
         let health = coordinator.health_check().await;
         assert!(health.is_ok());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_coordinator_drives_exchange_adapter() {
+        let exchange = Arc::new(MockExchange::default());
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(create_test_config()), exchange.clone())
+            .await
+            .unwrap();
+        assert_eq!(coordinator.exchange().name(), "mock");
+        
+        coordinator.start().await.unwrap();
+        assert!(exchange.started.load(std::sync::atomic::Ordering::SeqCst));
+        
+        let mut signal = coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        assert_eq!(exchange.context_requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(signal.symbol, "BTC-USDT");
+        assert_eq!(signal.metadata.get("exchange"), Some(&serde_json::Value::String("mock".to_string())));
+        
+        signal.signal_type = SignalType::Buy;
+        coordinator.get_signal_sender().send(signal.clone()).unwrap();
+        for _ in 0..100 {
+            if !exchange.orders.lock().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let orders = exchange.orders.lock().clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, signal.id);
+        
+        let health = coordinator.health_check().await.unwrap();
+        assert!(matches!(health.okx_status, HealthStatus::Healthy));
+        
+        coordinator.stop().await.unwrap();
+        assert!(!exchange.started.load(std::sync::atomic::Ordering::SeqCst));
+    }
+    
+    #[tokio::test]
+    async fn test_stale_market_data_rejected() {
+        let exchange = Arc::new(MockExchange {
+            context_age_ms: 60_000,
+            ..MockExchange::default()
+        });
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(create_test_config()), exchange.clone())
+            .await
+            .unwrap();
+        
+        let err = coordinator.generate_trading_signal("BTC-USDT").await.unwrap_err();
+        match err.downcast_ref::<CoordinatorError>() {
+            Some(CoordinatorError::StaleMarketData { symbol, age_ms, max_age_ms }) => {
+                assert_eq!(symbol, "BTC-USDT");
+                assert!(*age_ms >= 60_000);
+                assert_eq!(*max_age_ms, CoordinatorConfig::default().max_market_data_age_ms);
+            }
+            None => panic!("Expected stale market data error, got {}", err),
+        }
+        assert!(exchange.orders.lock().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_partial_fills_tracked_to_completion() {
+        let exchange = Arc::new(MockExchange::default());
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(create_test_config()), exchange.clone())
+            .await
+            .unwrap();
+        let mut events = coordinator.order_tracker().take_event_receiver().unwrap();
+        
+        let mut signal = coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        signal.signal_type = SignalType::Buy;
+        coordinator.process_trading_signal(signal.clone()).await.unwrap();
+        
+        let order_id = signal.id.to_string();
+        let fill = |fill_sz: &str, acc_fill_sz: &str| serde_json::json!([{
+            "ordId": order_id,
+            "fillSz": fill_sz,
+            "fillPx": "45000",
+            "accFillSz": acc_fill_sz
+        }]);
+        
+        assert!(coordinator.handle_order_update(&fill("0.3", "0.3")).await.is_empty());
+        let tracked = coordinator.order_tracker().get(&order_id).unwrap();
+        assert_eq!(tracked.signal_id, signal.id);
+        assert_eq!(tracked.status, crate::order_tracker::FillStatus::PartiallyFilled);
+        
+        let completed = coordinator.handle_order_update(&fill("0.7", "1")).await;
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].filled_quantity, rust_decimal::Decimal::ONE);
+        assert!(completed[0].is_filled());
+        assert!(matches!(events.try_recv(), Ok(crate::order_tracker::OrderTrackingEvent::Filled(_))));
+        assert_eq!(coordinator.order_tracker().open_orders(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_signals_respect_integration_concurrency_limit() {
+        let mut config = create_test_config();
+        config.coordinator.concurrency.exchange = 2;
+        let exchange = Arc::new(MockExchange {
+            context_delay_ms: 20,
+            ..MockExchange::default()
+        });
+        let coordinator = Arc::new(
+            IntegrationCoordinator::with_exchange(Arc::new(config), exchange.clone())
+                .await
+                .unwrap(),
+        );
+        
+        let symbols = ["BTC-USDT", "ETH-USDT", "SOL-USDT", "XRP-USDT", "ADA-USDT", "DOT-USDT", "LTC-USDT", "BCH-USDT"];
+        let handles: Vec<_> = symbols
+            .into_iter()
+            .map(|symbol| {
+                let coordinator = coordinator.clone();
+                tokio::spawn(async move { coordinator.generate_trading_signal(symbol).await })
+            })
+            .collect();
+        for result in futures::future::join_all(handles).await {
+            result.unwrap().unwrap();
+        }
+        
+        assert_eq!(exchange.context_requests.load(std::sync::atomic::Ordering::SeqCst), symbols.len());
+        assert_eq!(exchange.max_contexts_in_flight.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(exchange.contexts_in_flight.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_signal_cooldown_suppresses_repeat_trades() {
+        let mut config = create_test_config();
+        config.coordinator.signal_cooldown_ms = 60_000;
+        let exchange = Arc::new(MockExchange::default());
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(config), exchange.clone())
+            .await
+            .unwrap();
+        
+        let mut first = coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        first.signal_type = SignalType::Buy;
+        let mut second = coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        second.signal_type = SignalType::Sell;
+        assert_eq!(exchange.context_requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        
+        coordinator.process_trading_signal(first.clone()).await.unwrap();
+        coordinator.process_trading_signal(second).await.unwrap();
+        
+        let orders = exchange.orders.lock().clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, first.id);
+        
+        // Other symbols have their own cooldown
+        let mut other = coordinator.generate_trading_signal("ETH-USDT").await.unwrap();
+        other.signal_type = SignalType::Buy;
+        coordinator.process_trading_signal(other).await.unwrap();
+        assert_eq!(exchange.orders.lock().len(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_injected_id_source_makes_signal_ids_deterministic() {
+        let exchange = Arc::new(MockExchange::default());
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(create_test_config()), exchange)
+            .await
+            .unwrap()
+            .with_id_source(Arc::new(crate::ids::SequentialIds::default()));
+        let metadata_id = |signal: &TradingSignal, key: &str| signal.metadata[key].as_str().unwrap().parse::<Uuid>().unwrap();
+        
+        // Each signal draws, in order: its request, market data, prediction, knowledge query,
+        // knowledge tracking and signal ids
+        let first = coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        assert_eq!(metadata_id(&first, "request_id"), Uuid::from_u128(1));
+        assert_eq!(metadata_id(&first, "knowledge_query_id"), Uuid::from_u128(4));
+        assert_eq!(first.id, Uuid::from_u128(6));
+        
+        let second = coordinator.clone().generate_trading_signal("ETH-USDT").await.unwrap();
+        assert_eq!(metadata_id(&second, "request_id"), Uuid::from_u128(7));
+        assert_eq!(metadata_id(&second, "knowledge_query_id"), Uuid::from_u128(10));
+        assert_eq!(second.id, Uuid::from_u128(12));
+    }
+    
+    #[tokio::test]
+    async fn test_coordinator_is_live_but_not_ready_until_started() {
+        let exchange = Arc::new(MockExchange::default());
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(create_test_config()), exchange)
+            .await
+            .unwrap();
+        
+        assert!(coordinator.liveness().is_pass());
+        assert_eq!(coordinator.readiness().await, Probe::Fail("coordinator not started".to_string()));
+        
+        coordinator.start().await.unwrap();
+        assert!(coordinator.liveness().is_pass());
+        assert_eq!(coordinator.readiness().await, Probe::Pass);
+        
+        coordinator.stop().await.unwrap();
+        assert!(!coordinator.readiness().await.is_pass());
+    }
+    
+    #[tokio::test]
+    async fn test_market_context_cached_within_ttl() {
+        let mut config = create_test_config();
+        config.coordinator.market_context_ttl_ms = 60_000;
+        let exchange = Arc::new(MockExchange {
+            context_delay_ms: 20,
+            ..MockExchange::default()
+        });
+        let coordinator = IntegrationCoordinator::with_exchange(Arc::new(config), exchange.clone())
+            .await
+            .unwrap();
+        
+        coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        coordinator.generate_trading_signal("BTC-USDT").await.unwrap();
+        assert_eq!(exchange.context_requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        
+        // Concurrent signals for a symbol not yet cached share one fetch
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let coordinator = coordinator.clone();
+                tokio::spawn(async move { coordinator.generate_trading_signal("ETH-USDT").await })
+            })
+            .collect();
+        for result in futures::future::join_all(handles).await {
+            result.unwrap().unwrap();
+        }
+        assert_eq!(exchange.context_requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+    
+    async fn queued_rag_events(coordinator: &IntegrationCoordinator) -> usize {
+        coordinator.rag.ingestion.get_queue_stats().await.queue_size
+    }
+    
+    #[tokio::test]
+    async fn test_signal_ingestion_suppressed_or_gated_on_outcome() {
+        let coordinator_with = |signal_ingestion| async move {
+            let mut config = create_test_config();
+            config.coordinator.signal_ingestion = signal_ingestion;
+            config.coordinator.order_fill_timeout_ms = 50;
+            IntegrationCoordinator::with_exchange(Arc::new(config), Arc::new(MockExchange::default()))
+                .await
+                .unwrap()
+        };
+        
+        let raw = coordinator_with(SignalIngestion::Raw).await;
+        raw.generate_trading_signal("BTC-USDT").await.unwrap();
+        assert_eq!(queued_rag_events(&raw).await, 1);
+        
+        let suppressed = coordinator_with(SignalIngestion::Suppressed).await;
+        suppressed.generate_trading_signal("BTC-USDT").await.unwrap();
+        assert_eq!(queued_rag_events(&suppressed).await, 0);
+        
+        let gated = coordinator_with(SignalIngestion::WithOutcome).await;
+        let mut signal = gated.generate_trading_signal("BTC-USDT").await.unwrap();
+        signal.signal_type = SignalType::Buy;
+        gated.process_trading_signal(signal.clone()).await.unwrap();
+        assert_eq!(queued_rag_events(&gated).await, 0);
+        
+        let completed = gated.handle_order_update(&serde_json::json!([{
+            "ordId": signal.id.to_string(),
+            "fillSz": "1",
+            "fillPx": "45000",
+            "accFillSz": "1"
+        }])).await;
+        assert_eq!(completed.len(), 1);
+        assert_eq!(queued_rag_events(&gated).await, 1);
+        
+        // Each signal is ingested once, with its first recorded outcome
+        assert!(!gated.record_signal_outcome(&completed[0]).await.unwrap());
+        assert_eq!(queued_rag_events(&gated).await, 1);
+        
+        // A signal whose order never reaches the tracker is dropped after the outcome TTL
+        gated.generate_trading_signal("ETH-USDT").await.unwrap();
+        assert_eq!(gated.expire_pending_outcomes(), 0);
+        tokio::time::sleep(gated.order_tracker().fill_timeout() * 2).await;
+        assert_eq!(gated.expire_pending_outcomes(), 1);
+        assert!(gated.pending_outcomes.lock().is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use event_processor::Probe;
+use std::fmt::Debug;
+
+use crate::types::{ExchangeOrderAck, HealthStatus, MarketContext, TradingSignal};
+
+/// Common surface of a trading venue, so the coordinator does not depend on a specific exchange
+#[async_trait]
+pub trait ExchangeAdapter: Send + Sync + Debug {
+    fn name(&self) -> &str;
+    
+    async fn start(&self) -> Result<()>;
+    
+    async fn stop(&self) -> Result<()>;
+    
+    async fn place_order(&self, signal: &TradingSignal) -> Result<ExchangeOrderAck>;
+    
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<()>;
+    
+    async fn get_market_context(&self, symbol: &str) -> Result<MarketContext>;
+    
+    async fn subscribe_market_data(&self, symbol: &str) -> Result<()>;
+    
+    async fn health_check(&self) -> Result<HealthStatus>;
+    
+    /// Whether the adapter is wedged and needs a restart; remote outages are a readiness matter
+    fn liveness(&self) -> Probe {
+        Probe::Pass
+    }
+    
+    /// Whether orders can be routed through the adapter now
+    async fn readiness(&self) -> Probe {
+        readiness_from_health(self.name(), self.health_check().await)
+    }
+}
+
+/// Only a successful `Healthy` check is ready
+pub(crate) fn readiness_from_health(component: &str, health: Result<HealthStatus>) -> Probe {
+    match health {
+        Ok(HealthStatus::Healthy) => Probe::Pass,
+        Ok(status) => Probe::Fail(format!("{} reports {:?}", component, status)),
+        Err(e) => Probe::Fail(format!("{} health check failed: {}", component, e)),
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+/// Where signal, request and event ids come from. Production draws random v4 ids;
+/// tests and replays inject a deterministic source so every id a signal carries is reproducible.
+pub trait IdSource: Send + Sync + Debug {
+    fn next_id(&self) -> Uuid;
+}
+
+/// Random v4 ids
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomIds;
+
+impl IdSource for RandomIds {
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Ids counting up from a starting value, encoded as the low bits of the UUID
+#[derive(Debug)]
+pub struct SequentialIds {
+    next: AtomicU64,
+}
+
+impl SequentialIds {
+    pub fn starting_at(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first),
+        }
+    }
+}
+
+impl Default for SequentialIds {
+    /// Starts at 1 so the nil UUID is never issued
+    fn default() -> Self {
+        Self::starting_at(1)
+    }
+}
+
+impl IdSource for SequentialIds {
+    fn next_id(&self) -> Uuid {
+        Uuid::from_u128(self.next.fetch_add(1, Ordering::Relaxed) as u128)
+    }
+}
@@ -1,15 +1,23 @@
 use anyhow::Result;
 use std::sync::Arc;
 
+pub mod codec;
 pub mod config;
+pub mod exchange;
+pub mod ids;
 pub mod okx;
 pub mod mcp;
 pub mod rag;
 pub mod coordinator;
+pub mod order_tracker;
 pub mod types;
 
-pub use config::IntegrationConfig;
-pub use coordinator::IntegrationCoordinator;
+pub use codec::PayloadCodec;
+pub use config::{IntegrationConfig, RagReranking, SignalIngestion};
+pub use coordinator::{CoordinatorError, IntegrationCoordinator};
+pub use exchange::ExchangeAdapter;
+pub use ids::{IdSource, RandomIds, SequentialIds};
+pub use order_tracker::{FillStatus, FillUpdate, OrderTracker, OrderTrackingEvent, TrackedOrder};
 pub use types::*;
 
 #[derive(Debug, Clone)]
@@ -40,4 +48,12 @@ impl Integrations {
     pub async fn health_check(&self) -> Result<IntegrationHealth> {
         self.coordinator.health_check().await
     }
+    
+    pub fn liveness(&self) -> event_processor::Probe {
+        self.coordinator.liveness()
+    }
+    
+    pub async fn readiness(&self) -> event_processor::Probe {
+        self.coordinator.readiness().await
+    }
 }
\ No newline at end of file
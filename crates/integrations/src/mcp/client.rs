@@ -1,5 +1,8 @@
 use anyhow::{Result, anyhow};
 use reqwest::Client;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -7,17 +10,80 @@ use tracing::{info, warn, error, debug};
 use base64::{Engine as _, engine::general_purpose};
 
 use crate::config::McpConfig;
-use crate::types::{PredictionRequest, PredictionResponse, HealthStatus};
+use crate::types::{PredictionRequest, PredictionResponse, PredictionHorizon, HealthStatus};
 use super::types::{
     McpPredictionRequest, McpPredictionResponse, McpHealthResponse, 
     McpApiRequest, McpApiResponse, ModelInfo, McpErrorResponse
 };
 
+/// Where and against which model a prediction for one horizon is requested
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpRoute {
+    pub base_url: String,
+    pub model_version: Option<String>,
+    pub prediction_threshold: f64,
+}
+
+/// Predictions by `prediction_key`, shared between clones
+#[derive(Debug, Clone, Default)]
+struct PredictionCache {
+    entries: Arc<parking_lot::Mutex<HashMap<u64, (Instant, PredictionResponse)>>>,
+}
+
+impl PredictionCache {
+    fn get(&self, key: u64, ttl: Duration) -> Option<PredictionResponse> {
+        let entries = self.entries.lock();
+        let (cached_at, response) = entries.get(&key)?;
+        (cached_at.elapsed() < ttl).then(|| response.clone())
+    }
+    
+    /// Store `response`, first dropping expired entries and then the oldest if `capacity` is reached
+    fn insert(&self, key: u64, response: PredictionResponse, ttl: Duration, capacity: usize) {
+        let mut entries = self.entries.lock();
+        if entries.len() >= capacity && !entries.contains_key(&key) {
+            entries.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+            while entries.len() >= capacity.max(1) {
+                let Some(oldest) = entries.iter().min_by_key(|(_, (cached_at, _))| *cached_at).map(|(key, _)| *key) else {
+                    break;
+                };
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (Instant::now(), response));
+    }
+}
+
+/// Hash of everything a prediction depends on: the symbol, the horizon and model it is routed to,
+/// and the feature vector. Features are hashed in name order with zeros and NaNs canonicalised, so
+/// equal vectors hash equally however their maps were built.
+fn prediction_key(request: &PredictionRequest, route: &McpRoute) -> u64 {
+    let mut features: Vec<(&String, &f64)> = request.features.iter().collect();
+    features.sort_by(|a, b| a.0.cmp(b.0));
+    
+    let mut hasher = DefaultHasher::new();
+    request.symbol.hash(&mut hasher);
+    std::mem::discriminant(&request.prediction_horizon).hash(&mut hasher);
+    route.base_url.hash(&mut hasher);
+    route.model_version.hash(&mut hasher);
+    for (name, value) in features {
+        let value = match *value {
+            // Also matches -0.0
+            0.0 => 0.0,
+            value if value.is_nan() => f64::NAN,
+            value => value,
+        };
+        name.hash(&mut hasher);
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 #[derive(Debug, Clone)]
 pub struct McpClient {
     client: Client,
     base_url: String,
     config: Arc<McpConfig>,
+    predictions: PredictionCache,
 }
 
 impl McpClient {
@@ -35,15 +101,40 @@ impl McpClient {
             client,
             base_url,
             config,
+            predictions: PredictionCache::default(),
         })
     }
     
+    /// Resolve the endpoint, model and threshold configured for `horizon`
+    pub fn route(&self, horizon: &PredictionHorizon) -> McpRoute {
+        let route = self.config.horizon_routes.get(horizon);
+        
+        McpRoute {
+            base_url: route
+                .and_then(|r| r.server_url.as_deref())
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| self.base_url.clone()),
+            model_version: route.and_then(|r| r.model_version.clone()),
+            prediction_threshold: route
+                .and_then(|r| r.prediction_threshold)
+                .unwrap_or(self.config.prediction_threshold),
+        }
+    }
+    
     async fn make_request<T, R>(&self, endpoint: &str, request_data: T) -> Result<R>
     where
         T: serde::Serialize,
         R: for<'de> serde::Deserialize<'de>,
     {
-        let url = format!("{}/{}", self.base_url, endpoint.trim_start_matches('/'));
+        self.make_request_to(&self.base_url, endpoint, request_data).await
+    }
+    
+    async fn make_request_to<T, R>(&self, base_url: &str, endpoint: &str, request_data: T) -> Result<R>
+    where
+        T: serde::Serialize,
+        R: for<'de> serde::Deserialize<'de>,
+    {
+        let url = format!("{}/{}", base_url, endpoint.trim_start_matches('/'));
         
         let api_request = McpApiRequest {
             data: request_data,
@@ -126,15 +217,29 @@ impl McpClient {
     pub async fn get_prediction(&self, request: PredictionRequest) -> Result<PredictionResponse> {
         let start_time = Instant::now();
         
-        let mcp_request: McpPredictionRequest = request.into();
+        let route = self.route(&request.prediction_horizon);
+        let cache_ttl = Duration::from_millis(self.config.prediction_cache_ttl_ms);
+        let cache_key = (!cache_ttl.is_zero()).then(|| prediction_key(&request, &route));
+        if let Some(mut cached) = cache_key.and_then(|key| self.predictions.get(key, cache_ttl)) {
+            debug!("Reusing cached prediction for {}", request.symbol);
+            cached.request_id = request.request_id;
+            return Ok(cached);
+        }
+        
+        let mut mcp_request: McpPredictionRequest = request.into();
+        if let Some(model_config) = mcp_request.model_config.as_mut() {
+            model_config.model_version = route.model_version.clone();
+            model_config.confidence_threshold = route.prediction_threshold;
+        }
         
-        info!("Requesting prediction for symbol: {}", mcp_request.symbol);
+        info!("Requesting prediction for symbol: {} from {}", mcp_request.symbol, route.base_url);
         
         let mut attempts = 0;
         let max_retries = self.config.max_retries;
         
         loop {
-            match self.make_request::<McpPredictionRequest, McpPredictionResponse>(
+            match self.make_request_to::<McpPredictionRequest, McpPredictionResponse>(
+                &route.base_url,
                 "/api/predict", 
                 mcp_request.clone()
             ).await {
@@ -145,12 +250,15 @@ impl McpClient {
                         mcp_response.symbol, processing_time, mcp_response.confidence);
                     
                     // Check if prediction meets threshold
-                    if mcp_response.confidence < self.config.prediction_threshold {
+                    if mcp_response.confidence < route.prediction_threshold {
                         warn!("Prediction confidence {:.2} below threshold {:.2}", 
-                            mcp_response.confidence, self.config.prediction_threshold);
+                            mcp_response.confidence, route.prediction_threshold);
                     }
                     
                     let response: PredictionResponse = mcp_response.into();
+                    if let Some(key) = cache_key {
+                        self.predictions.insert(key, response.clone(), cache_ttl, self.config.prediction_cache_size);
+                    }
                     return Ok(response);
                 }
                 Err(e) => {
@@ -313,7 +421,12 @@ impl McpClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::McpConfig;
+    use crate::config::{McpConfig, McpHorizonRoute, McpHorizonRoutes};
+    use crate::types::MarketContext;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
     
     fn create_test_config() -> McpConfig {
         McpConfig {
@@ -322,9 +435,146 @@ mod tests {
             timeout_ms: 5000,
             max_retries: 3,
             prediction_threshold: 0.7,
+            horizon_routes: McpHorizonRoutes::default(),
+            prediction_cache_ttl_ms: 0,
+            prediction_cache_size: 1_024,
         }
     }
     
+    fn prediction_request(horizon: PredictionHorizon) -> PredictionRequest {
+        PredictionRequest {
+            request_id: Uuid::new_v4(),
+            symbol: "BTC-USDT".to_string(),
+            market_context: MarketContext {
+                symbol: "BTC-USDT".to_string(),
+                current_price: Decimal::new(50000, 0),
+                bid: Decimal::new(49995, 0),
+                ask: Decimal::new(50005, 0),
+                volume_24h: Decimal::new(1000, 0),
+                change_24h: Decimal::new(500, 0),
+                volatility: None,
+                order_book_depth: None,
+                timestamp: chrono::Utc::now(),
+            },
+            features: std::collections::HashMap::new(),
+            prediction_horizon: horizon,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+    
+    async fn mount_model(server: &MockServer, model_version: &str) {
+        let response = serde_json::json!({
+            "success": true,
+            "data": {
+                "request_id": Uuid::new_v4().to_string(),
+                "symbol": "BTC-USDT",
+                "prediction": {
+                    "direction": "up",
+                    "price_target": null,
+                    "probability": 0.8,
+                    "risk_score": 0.2,
+                    "strength": 0.6,
+                    "time_horizon": "1m",
+                    "factors": []
+                },
+                "confidence": 0.9,
+                "model_version": model_version,
+                "processing_time_ms": 3,
+                "features_used": [],
+                "timestamp": chrono::Utc::now()
+            },
+            "error": null,
+            "timestamp": chrono::Utc::now()
+        });
+        
+        Mock::given(method("POST"))
+            .and(path("/api/predict"))
+            .and(body_partial_json(serde_json::json!({
+                "data": { "model_config": { "model_version": model_version } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+    
+    #[tokio::test]
+    async fn test_prediction_routed_by_horizon() {
+        let short_term_server = MockServer::start().await;
+        let long_term_server = MockServer::start().await;
+        mount_model(&short_term_server, "scalper-v2").await;
+        mount_model(&long_term_server, "trend-v1").await;
+        
+        let config = McpConfig {
+            max_retries: 1,
+            horizon_routes: McpHorizonRoutes {
+                short_term: Some(McpHorizonRoute {
+                    server_url: Some(short_term_server.uri()),
+                    model_version: Some("scalper-v2".to_string()),
+                    prediction_threshold: Some(0.8),
+                }),
+                medium_term: None,
+                long_term: Some(McpHorizonRoute {
+                    server_url: Some(long_term_server.uri()),
+                    model_version: Some("trend-v1".to_string()),
+                    prediction_threshold: None,
+                }),
+            },
+            ..create_test_config()
+        };
+        let client = McpClient::new(Arc::new(config)).await.unwrap();
+        
+        assert_eq!(client.route(&PredictionHorizon::ShortTerm).prediction_threshold, 0.8);
+        assert_eq!(client.route(&PredictionHorizon::LongTerm).prediction_threshold, 0.7);
+        assert_eq!(client.route(&PredictionHorizon::MediumTerm).base_url, "http://localhost:8000");
+        
+        let short = client.get_prediction(prediction_request(PredictionHorizon::ShortTerm)).await.unwrap();
+        assert_eq!(short.model_version, "scalper-v2");
+        
+        let long = client.get_prediction(prediction_request(PredictionHorizon::LongTerm)).await.unwrap();
+        assert_eq!(long.model_version, "trend-v1");
+        
+        // Each server verifies on drop that it saw exactly its own request
+    }
+    
+    #[tokio::test]
+    async fn test_identical_features_within_ttl_served_from_cache() {
+        let server = MockServer::start().await;
+        mount_model(&server, "cached-v1").await;
+        
+        let config = McpConfig {
+            max_retries: 1,
+            prediction_cache_ttl_ms: 60_000,
+            horizon_routes: McpHorizonRoutes {
+                short_term: Some(McpHorizonRoute {
+                    server_url: Some(server.uri()),
+                    model_version: Some("cached-v1".to_string()),
+                    prediction_threshold: None,
+                }),
+                ..McpHorizonRoutes::default()
+            },
+            ..create_test_config()
+        };
+        let client = McpClient::new(Arc::new(config)).await.unwrap();
+        
+        let request = |features: &[(&str, f64)]| PredictionRequest {
+            features: features.iter().map(|(name, value)| (name.to_string(), *value)).collect(),
+            ..prediction_request(PredictionHorizon::ShortTerm)
+        };
+        let first = request(&[("rsi", 61.5), ("spread_bps", 2.0), ("imbalance", -0.0)]);
+        // The same vector built in a different order, with the other sign of zero
+        let second = request(&[("imbalance", 0.0), ("rsi", 61.5), ("spread_bps", 2.0)]);
+        let second_id = second.request_id;
+        
+        let fetched = client.get_prediction(first).await.unwrap();
+        let cached = client.get_prediction(second).await.unwrap();
+        assert_eq!(cached.model_version, fetched.model_version);
+        assert_eq!(cached.confidence, fetched.confidence);
+        assert_eq!(cached.request_id, second_id);
+        
+        // The server verifies on drop that it was called exactly once
+    }
+    
     #[tokio::test]
     async fn test_client_creation() {
         let config = Arc::new(create_test_config());
@@ -2,12 +2,14 @@ pub mod client;
 pub mod types;
 pub mod features;
 
-pub use client::McpClient;
+pub use client::{McpClient, McpRoute};
 pub use types::*;
 pub use features::FeatureExtractor;
 
 use anyhow::Result;
 use crate::config::McpConfig;
+use crate::exchange::readiness_from_health;
+use event_processor::Probe;
 use crate::types::{PredictionRequest, PredictionResponse, HealthStatus};
 use std::sync::Arc;
 
@@ -39,6 +41,16 @@ impl McpIntegration {
         self.client.health_check().await
     }
     
+    /// The client holds no connection that could wedge, so it is always live
+    pub fn liveness(&self) -> Probe {
+        Probe::Pass
+    }
+    
+    /// Ready once the model server answers its health check
+    pub async fn readiness(&self) -> Probe {
+        readiness_from_health("mcp", self.health_check().await)
+    }
+    
     pub async fn get_model_info(&self) -> Result<ModelInfo> {
         self.client.get_model_info().await
     }
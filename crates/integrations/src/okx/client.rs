@@ -9,13 +9,17 @@ use rust_decimal::prelude::ToPrimitive;
 use crate::config::OkxConfig;
 use crate::types::{MarketContext, TradingSignal, SignalType, HealthStatus};
 use super::auth::OkxAuth;
+use super::endpoint::OkxEndpoint;
 use super::types::*;
 
+/// Size sent with every order; the minimum lot while strategies are still being validated
+pub const ORDER_SIZE: &str = "0.01";
+
 #[derive(Debug, Clone)]
 pub struct OkxClient {
     client: Client,
     auth: OkxAuth,
-    base_url: String,
+    endpoint: OkxEndpoint,
     config: Arc<OkxConfig>,
 }
 
@@ -32,16 +36,12 @@ impl OkxClient {
             .user_agent("HFT-Rust/1.0")
             .build()?;
         
-        let base_url = if config.sandbox {
-            "https://www.okx.com".to_string()
-        } else {
-            config.base_url.clone().unwrap_or_else(|| "https://www.okx.com".to_string())
-        };
+        let endpoint = OkxEndpoint::resolve(&config)?;
         
         Ok(Self {
             client,
             auth,
-            base_url,
+            endpoint,
             config,
         })
     }
@@ -50,7 +50,7 @@ impl OkxClient {
     where
         T: for<'de> serde::Deserialize<'de>,
     {
-        let url = format!("{}{}", self.base_url, path);
+        let url = format!("{}{}", self.endpoint.rest_url, path);
         let headers = self.auth.get_headers(method, path, body)?;
         
         let mut request = match method {
@@ -64,6 +64,9 @@ impl OkxClient {
         for (key, value) in headers {
             request = request.header(key, value);
         }
+        for (key, value) in self.endpoint.headers() {
+            request = request.header(key, value);
+        }
         
         if !body.is_empty() {
             request = request.body(body.to_string());
@@ -141,7 +144,7 @@ impl OkxClient {
             } else {
                 "market".to_string()
             },
-            sz: "0.01".to_string(), // Minimum size for testing
+            sz: ORDER_SIZE.to_string(),
             px: signal.price_target.map(|p| p.to_string()),
             ccy: None,
             cl_ord_id: Some(signal.id.to_string()),
@@ -201,7 +204,11 @@ impl OkxClient {
             change_24h,
             volatility: Some(change_24h.abs().to_f64().unwrap_or(0.0) / 100.0), // Simple volatility approximation based on 24h change
             order_book_depth,
-            timestamp: chrono::Utc::now(),
+            // Exchange time of the quote, so staleness checks see feed delays
+            timestamp: ticker.ts.parse::<i64>()
+                .ok()
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .unwrap_or_else(chrono::Utc::now),
         })
     }
     
@@ -240,6 +247,11 @@ impl OkxClient {
             .ok_or_else(|| anyhow!("No funding rate data returned for symbol: {}", symbol))
     }
     
+    #[inline]
+    pub fn endpoint(&self) -> &OkxEndpoint {
+        &self.endpoint
+    }
+    
     async fn rate_limit(&self) -> Result<()> {
         let delay_ms = 1000 / self.config.rate_limit_requests_per_second as u64;
         sleep(Duration::from_millis(delay_ms)).await;
@@ -250,7 +262,8 @@ impl OkxClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::OkxConfig;
+    use crate::config::{OkxConfig, OkxWebSocketConfig};
+    use crate::okx::SymbolMapping;
     
     fn create_test_config() -> OkxConfig {
         OkxConfig {
@@ -261,6 +274,8 @@ mod tests {
             base_url: None,
             timeout_ms: 5000,
             rate_limit_requests_per_second: 10,
+            websocket: OkxWebSocketConfig::default(),
+            symbols: SymbolMapping::defaults(),
         }
     }
     
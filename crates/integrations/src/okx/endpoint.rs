@@ -0,0 +1,130 @@
+use anyhow::{Result, anyhow};
+
+use crate::config::OkxConfig;
+
+pub const PRODUCTION_REST_URL: &str = "https://www.okx.com";
+/// Demo trading shares the production REST host; only the simulated-trading header routes
+/// requests to it
+pub const SANDBOX_REST_URL: &str = "https://www.okx.com";
+pub const PRODUCTION_WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
+pub const SANDBOX_WS_URL: &str = "wss://wspap.okx.com:8443/ws/v5/public";
+pub const SIMULATED_TRADING_HEADER: &str = "x-simulated-trading";
+
+/// Where OKX requests go and whether they trade against the demo account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OkxEndpoint {
+    pub rest_url: String,
+    pub ws_url: String,
+    /// Sends `x-simulated-trading: 1` with every REST request
+    pub simulated_trading: bool,
+}
+
+impl OkxEndpoint {
+    /// Endpoint for `config`, checked with `ensure_sandbox`. `base_url` only overrides the
+    /// production REST host; `websocket.url` overrides the WebSocket host in either mode.
+    pub fn resolve(config: &OkxConfig) -> Result<Self> {
+        let endpoint = if config.sandbox {
+            Self {
+                rest_url: SANDBOX_REST_URL.to_string(),
+                ws_url: config.websocket.url.clone().unwrap_or_else(|| SANDBOX_WS_URL.to_string()),
+                simulated_trading: true,
+            }
+        } else {
+            Self {
+                rest_url: config.base_url.clone().unwrap_or_else(|| PRODUCTION_REST_URL.to_string()),
+                ws_url: config.websocket.url.clone().unwrap_or_else(|| PRODUCTION_WS_URL.to_string()),
+                simulated_trading: false,
+            }
+        };
+        endpoint.ensure_sandbox(config)?;
+        Ok(endpoint)
+    }
+
+    /// Refuse an endpoint that would reach the live account from a config that asked for the
+    /// sandbox: the simulated-trading header must be set and the WebSocket must not be the
+    /// production one
+    pub fn ensure_sandbox(&self, config: &OkxConfig) -> Result<()> {
+        if !config.sandbox {
+            return Ok(());
+        }
+        if !self.simulated_trading {
+            return Err(anyhow!("OKX sandbox requested but requests to {} would trade live", self.rest_url));
+        }
+        if self.ws_url.trim_end_matches('/') == PRODUCTION_WS_URL {
+            return Err(anyhow!("OKX sandbox requested but the WebSocket points at production: {}", self.ws_url));
+        }
+        Ok(())
+    }
+
+    /// Headers every REST request carries on top of authentication
+    pub fn headers(&self) -> Vec<(&'static str, &'static str)> {
+        if self.simulated_trading {
+            vec![(SIMULATED_TRADING_HEADER, "1")]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OkxWebSocketConfig;
+    use crate::okx::SymbolMapping;
+
+    fn create_test_config(sandbox: bool) -> OkxConfig {
+        OkxConfig {
+            api_key: "test_key".to_string(),
+            secret_key: "dGVzdF9zZWNyZXQ=".to_string(),
+            passphrase: "test_passphrase".to_string(),
+            sandbox,
+            base_url: None,
+            timeout_ms: 5000,
+            rate_limit_requests_per_second: 10,
+            websocket: OkxWebSocketConfig::default(),
+            symbols: SymbolMapping::defaults(),
+        }
+    }
+
+    #[test]
+    fn test_sandbox_and_production_resolve_to_different_endpoints() {
+        let sandbox = OkxEndpoint::resolve(&create_test_config(true)).unwrap();
+        assert_eq!(sandbox.rest_url, SANDBOX_REST_URL);
+        assert_eq!(sandbox.ws_url, SANDBOX_WS_URL);
+        assert_eq!(sandbox.headers(), vec![(SIMULATED_TRADING_HEADER, "1")]);
+
+        let production = OkxEndpoint::resolve(&create_test_config(false)).unwrap();
+        assert_eq!(production.rest_url, PRODUCTION_REST_URL);
+        assert_eq!(production.ws_url, PRODUCTION_WS_URL);
+        assert!(production.headers().is_empty());
+        assert_ne!(sandbox, production);
+
+        // A custom REST host is a production concern; the sandbox never leaves the demo host
+        let mut config = create_test_config(true);
+        config.base_url = Some("https://aws.okx.com".to_string());
+        assert_eq!(OkxEndpoint::resolve(&config).unwrap().rest_url, SANDBOX_REST_URL);
+        config.sandbox = false;
+        assert_eq!(OkxEndpoint::resolve(&config).unwrap().rest_url, "https://aws.okx.com");
+    }
+
+    #[test]
+    fn test_sandbox_config_refuses_production_endpoint() {
+        let config = create_test_config(true);
+        let production = OkxEndpoint::resolve(&create_test_config(false)).unwrap();
+        assert!(production.ensure_sandbox(&config).is_err());
+
+        // Without the header, even the demo WebSocket would leave REST orders live
+        let headerless = OkxEndpoint {
+            simulated_trading: false,
+            ..OkxEndpoint::resolve(&config).unwrap()
+        };
+        assert!(headerless.ensure_sandbox(&config).is_err());
+
+        let mut config = create_test_config(true);
+        config.websocket.url = Some(format!("{}/", PRODUCTION_WS_URL));
+        assert!(OkxEndpoint::resolve(&config).is_err());
+
+        // Production configs are not held to the sandbox check
+        assert!(production.ensure_sandbox(&create_test_config(false)).is_ok());
+    }
+}
@@ -1,27 +1,36 @@
 pub mod auth;
 pub mod client;
+pub mod endpoint;
 pub mod websocket;
 pub mod types;
+pub mod symbols;
 
 pub use auth::OkxAuth;
 pub use client::OkxClient;
+pub use endpoint::OkxEndpoint;
 pub use websocket::OkxWebSocket;
 pub use types::*;
+pub use symbols::{SymbolMapError, SymbolMapper, SymbolMapping};
 
 use anyhow::Result;
+use async_trait::async_trait;
 use crate::config::OkxConfig;
-use crate::types::{MarketContext, TradingSignal, HealthStatus};
+use crate::exchange::{readiness_from_health, ExchangeAdapter};
+use event_processor::Probe;
+use crate::types::{ExchangeOrderAck, MarketContext, TradingSignal, HealthStatus};
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct OkxIntegration {
     pub client: Arc<OkxClient>,
     pub websocket: Arc<OkxWebSocket>,
+    symbols: Arc<SymbolMapper>,
     config: Arc<OkxConfig>,
 }
 
 impl OkxIntegration {
     pub async fn new(config: OkxConfig) -> Result<Self> {
+        let symbols = Arc::new(SymbolMapper::new(&config.symbols)?);
         let config = Arc::new(config);
         let client = Arc::new(OkxClient::new(config.clone()).await?);
         let websocket = Arc::new(OkxWebSocket::new(config.clone()).await?);
@@ -29,6 +38,7 @@ impl OkxIntegration {
         Ok(Self {
             client,
             websocket,
+            symbols,
             config,
         })
     }
@@ -47,6 +57,11 @@ impl OkxIntegration {
         &self.config
     }
     
+    /// Translation between internal symbols and OKX instrument ids
+    pub fn symbols(&self) -> &SymbolMapper {
+        &self.symbols
+    }
+    
     pub async fn get_market_context(&self, symbol: &str) -> Result<MarketContext> {
         self.client.get_market_context(symbol).await
     }
@@ -58,4 +73,66 @@ impl OkxIntegration {
     pub async fn health_check(&self) -> Result<HealthStatus> {
         self.client.health_check().await
     }
-}
\ No newline at end of file
+    
+    /// Ready to trade once the WebSocket is connected, market data is subscribed and the
+    /// REST API answers. A connected socket with no subscriptions is still starting up.
+    pub async fn readiness(&self) -> Probe {
+        if !self.websocket.is_connected().await {
+            return Probe::Fail("okx websocket not connected".to_string());
+        }
+        if self.websocket.subscription_count().await == 0 {
+            return Probe::Fail("okx market data not subscribed".to_string());
+        }
+        readiness_from_health("okx", self.health_check().await)
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for OkxIntegration {
+    fn name(&self) -> &str {
+        "okx"
+    }
+    
+    async fn start(&self) -> Result<()> {
+        OkxIntegration::start(self).await
+    }
+    
+    async fn stop(&self) -> Result<()> {
+        OkxIntegration::stop(self).await
+    }
+    
+    async fn place_order(&self, signal: &TradingSignal) -> Result<ExchangeOrderAck> {
+        let response = self.client.place_order(signal).await?;
+        Ok(ExchangeOrderAck {
+            exchange: self.name().to_string(),
+            accepted: response.s_code == "0",
+            order_id: response.ord_id,
+            client_order_id: response.cl_ord_id,
+            quantity: client::ORDER_SIZE.parse().unwrap_or_default(),
+            message: response.s_msg,
+            timestamp: chrono::Utc::now(),
+        })
+    }
+    
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<()> {
+        self.client.cancel_order(order_id, symbol).await
+    }
+    
+    async fn get_market_context(&self, symbol: &str) -> Result<MarketContext> {
+        self.client.get_market_context(symbol).await
+    }
+    
+    async fn subscribe_market_data(&self, symbol: &str) -> Result<()> {
+        self.websocket.subscribe_ticker(symbol).await?;
+        self.websocket.subscribe_order_book(symbol).await?;
+        self.websocket.subscribe_trades(symbol).await
+    }
+    
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.client.health_check().await
+    }
+    
+    async fn readiness(&self) -> Probe {
+        OkxIntegration::readiness(self).await
+    }
+}
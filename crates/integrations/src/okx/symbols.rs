@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SymbolMapError {
+    #[error("No OKX instrument mapped for internal symbol {0}")]
+    UnknownInternal(String),
+    #[error("No internal symbol mapped for OKX instrument {0}")]
+    UnknownVenue(String),
+    #[error("Internal symbol {0} is mapped more than once")]
+    DuplicateInternal(String),
+    #[error("OKX instrument {0} is mapped more than once")]
+    DuplicateVenue(String),
+    #[error("Invalid internal symbol {0:?}; expected uppercase letters and digits")]
+    InvalidInternal(String),
+    #[error("Invalid OKX instrument {0:?}; expected dash-separated segments such as BTC-USDT")]
+    InvalidVenue(String),
+    #[error("Invalid symbol mapping {0:?}; expected INTERNAL=VENUE")]
+    InvalidEntry(String),
+}
+
+/// One internal symbol and the OKX instrument it trades as
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolMapping {
+    pub internal: String,
+    pub venue: String,
+}
+
+impl SymbolMapping {
+    pub fn new(internal: impl Into<String>, venue: impl Into<String>) -> Self {
+        Self {
+            internal: internal.into(),
+            venue: venue.into(),
+        }
+    }
+
+    /// The instruments the system trades by default. Internal books are USD-quoted while the
+    /// venue instruments settle in USDT, so this pairing is a deliberate choice, not a rename.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("BTCUSD", "BTC-USDT"),
+            Self::new("ETHUSD", "ETH-USDT"),
+            Self::new("SOLUSD", "SOL-USDT"),
+            Self::new("ADAUSD", "ADA-USDT"),
+        ]
+    }
+}
+
+impl FromStr for SymbolMapping {
+    type Err = SymbolMapError;
+
+    /// Parse `INTERNAL=VENUE`, e.g. `BTCUSD=BTC-USDT`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (internal, venue) = s.split_once('=').ok_or_else(|| SymbolMapError::InvalidEntry(s.to_string()))?;
+        Ok(Self::new(internal.trim(), venue.trim()))
+    }
+}
+
+/// Validated one-to-one mapping between internal symbols and OKX instrument ids.
+/// Lookups fail on anything outside the table rather than guessing a conversion.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolMapper {
+    to_venue: HashMap<String, String>,
+    to_internal: HashMap<String, String>,
+}
+
+impl SymbolMapper {
+    pub fn new(mappings: &[SymbolMapping]) -> Result<Self, SymbolMapError> {
+        let mut mapper = Self::default();
+        for mapping in mappings {
+            if !is_valid_internal(&mapping.internal) {
+                return Err(SymbolMapError::InvalidInternal(mapping.internal.clone()));
+            }
+            if !is_valid_venue(&mapping.venue) {
+                return Err(SymbolMapError::InvalidVenue(mapping.venue.clone()));
+            }
+            if mapper.to_venue.contains_key(&mapping.internal) {
+                return Err(SymbolMapError::DuplicateInternal(mapping.internal.clone()));
+            }
+            if mapper.to_internal.contains_key(&mapping.venue) {
+                return Err(SymbolMapError::DuplicateVenue(mapping.venue.clone()));
+            }
+
+            mapper.to_venue.insert(mapping.internal.clone(), mapping.venue.clone());
+            mapper.to_internal.insert(mapping.venue.clone(), mapping.internal.clone());
+        }
+        Ok(mapper)
+    }
+
+    pub fn to_venue(&self, internal: &str) -> Result<&str, SymbolMapError> {
+        self.to_venue
+            .get(internal)
+            .map(String::as_str)
+            .ok_or_else(|| SymbolMapError::UnknownInternal(internal.to_string()))
+    }
+
+    pub fn to_internal(&self, venue: &str) -> Result<&str, SymbolMapError> {
+        self.to_internal
+            .get(venue)
+            .map(String::as_str)
+            .ok_or_else(|| SymbolMapError::UnknownVenue(venue.to_string()))
+    }
+
+    /// Every mapped OKX instrument, in no particular order
+    pub fn venue_symbols(&self) -> impl Iterator<Item = &str> {
+        self.to_internal.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.to_venue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.to_venue.is_empty()
+    }
+}
+
+#[inline]
+fn is_valid_internal(symbol: &str) -> bool {
+    !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+#[inline]
+fn is_valid_venue(symbol: &str) -> bool {
+    let mut segments = symbol.split('-');
+    segments.clone().count() >= 2 && segments.all(is_valid_internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping_round_trips_both_ways() {
+        let mapper = SymbolMapper::new(&SymbolMapping::defaults()).unwrap();
+        assert_eq!(mapper.len(), 4);
+
+        for mapping in SymbolMapping::defaults() {
+            let venue = mapper.to_venue(&mapping.internal).unwrap();
+            assert_eq!(venue, mapping.venue);
+            assert_eq!(mapper.to_internal(venue).unwrap(), mapping.internal);
+        }
+        assert_eq!(mapper.to_venue("BTCUSD").unwrap(), "BTC-USDT");
+        assert_eq!(mapper.to_internal("BTC-USDT").unwrap(), "BTCUSD");
+    }
+
+    #[test]
+    fn test_unmapped_symbols_are_errors_not_rewritten() {
+        let mapper = SymbolMapper::new(&SymbolMapping::defaults()).unwrap();
+
+        assert_eq!(mapper.to_internal("BTC-USDC"), Err(SymbolMapError::UnknownVenue("BTC-USDC".to_string())));
+        assert_eq!(mapper.to_internal("BTC-USD"), Err(SymbolMapError::UnknownVenue("BTC-USD".to_string())));
+        assert_eq!(mapper.to_venue("BTCUSDT"), Err(SymbolMapError::UnknownInternal("BTCUSDT".to_string())));
+        assert_eq!(mapper.to_venue("XRPUSD"), Err(SymbolMapError::UnknownInternal("XRPUSD".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_tables_are_rejected() {
+        let duplicate_internal = [SymbolMapping::new("BTCUSD", "BTC-USDT"), SymbolMapping::new("BTCUSD", "BTC-USDC")];
+        assert_eq!(SymbolMapper::new(&duplicate_internal).unwrap_err(), SymbolMapError::DuplicateInternal("BTCUSD".to_string()));
+
+        let duplicate_venue = [SymbolMapping::new("BTCUSD", "BTC-USDT"), SymbolMapping::new("BTCUSDT", "BTC-USDT")];
+        assert_eq!(SymbolMapper::new(&duplicate_venue).unwrap_err(), SymbolMapError::DuplicateVenue("BTC-USDT".to_string()));
+
+        assert!(matches!(SymbolMapper::new(&[SymbolMapping::new("BTCUSD", "BTCUSDT")]), Err(SymbolMapError::InvalidVenue(_))));
+        assert!(matches!(SymbolMapper::new(&[SymbolMapping::new("BTC-USD", "BTC-USDT")]), Err(SymbolMapError::InvalidInternal(_))));
+        assert!(matches!("BTCUSD".parse::<SymbolMapping>(), Err(SymbolMapError::InvalidEntry(_))));
+        assert_eq!("ETHUSD = ETH-USDT".parse::<SymbolMapping>().unwrap(), SymbolMapping::new("ETHUSD", "ETH-USDT"));
+    }
+}
@@ -1,15 +1,29 @@
 use anyhow::Result;
-use futures::{SinkExt, StreamExt};
+use flate2::read::DeflateDecoder;
 use serde_json::Value;
+use soketto::connection::Error as WsError;
+use soketto::extension::deflate::Deflate;
+use soketto::handshake::{Client, ServerResponse};
+use soketto::{Data, Incoming, Mode};
+use std::io::Read;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncWrite, BufWriter, ReadBuf};
+use tokio::net::TcpStream;
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{interval, Duration};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+use tokio_util::compat::TokioAsyncReadCompatExt;
 use tracing::{info, warn, error, debug};
-use url::Url;
+use url::{Position, Url};
 
 use crate::config::OkxConfig;
 use super::auth::OkxAuth;
+use super::endpoint::OkxEndpoint;
 use super::types::{OkxWebSocketMessage, OkxWebSocketChannel, OkxWebSocketSubscription};
 
 #[derive(Debug, Clone)]
@@ -23,6 +37,48 @@ pub enum OkxWebSocketEvent {
     Error(String),
 }
 
+/// Messages for the writer task, which owns the sending half of the connection
+#[derive(Debug)]
+enum Outgoing {
+    Text(String),
+    Close,
+}
+
+#[derive(Debug, Default)]
+struct StreamCounters {
+    bytes_received: AtomicU64,
+    bytes_decompressed: AtomicU64,
+    compressed_frames: AtomicU64,
+    pings_sent: AtomicU64,
+    pongs_received: AtomicU64,
+    permessage_deflate: AtomicBool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OkxWebSocketStats {
+    /// Bytes read off the socket, before any inflating and including framing
+    pub bytes_received: u64,
+    /// Message payload bytes after inflating
+    pub bytes_decompressed: u64,
+    /// Binary messages OKX deflated itself, on top of any permessage-deflate
+    pub compressed_frames: u64,
+    pub pings_sent: u64,
+    pub pongs_received: u64,
+    /// The server accepted permessage-deflate on the current connection
+    pub permessage_deflate: bool,
+}
+
+impl OkxWebSocketStats {
+    /// Ratio of decoded payload to wire bytes; below 1.0 when nothing was compressed
+    pub fn decompression_ratio(&self) -> f64 {
+        if self.bytes_received == 0 {
+            1.0
+        } else {
+            self.bytes_decompressed as f64 / self.bytes_received as f64
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct OkxWebSocket {
     config: Arc<OkxConfig>,
@@ -31,6 +87,9 @@ pub struct OkxWebSocket {
     event_rx: Arc<RwLock<Option<mpsc::UnboundedReceiver<OkxWebSocketEvent>>>>,
     is_connected: Arc<RwLock<bool>>,
     subscriptions: Arc<RwLock<Vec<OkxWebSocketChannel>>>,
+    outgoing: Arc<RwLock<Option<mpsc::UnboundedSender<Outgoing>>>>,
+    last_received: Arc<parking_lot::Mutex<Instant>>,
+    counters: Arc<StreamCounters>,
 }
 
 impl OkxWebSocket {
@@ -50,17 +109,87 @@ impl OkxWebSocket {
             event_rx: Arc::new(RwLock::new(Some(event_rx))),
             is_connected: Arc::new(RwLock::new(false)),
             subscriptions: Arc::new(RwLock::new(Vec::new())),
+            outgoing: Arc::new(RwLock::new(None)),
+            last_received: Arc::new(parking_lot::Mutex::new(Instant::now())),
+            counters: Arc::new(StreamCounters::default()),
         })
     }
     
+    /// Open the stream, offering permessage-deflate when `compression` is on. Frames OKX
+    /// deflates itself arrive as binary messages and are inflated either way.
     pub async fn connect(&self) -> Result<()> {
-        let ws_url = "wss://ws.okx.com:8443/ws/v5/public";
+        let endpoint = OkxEndpoint::resolve(&self.config)?;
         
-        let url = Url::parse(ws_url)?;
+        let url = Url::parse(&endpoint.ws_url)?;
         info!("Connecting to OKX WebSocket: {}", url);
         
-        let (ws_stream, _) = connect_async(url).await?;
-        let (mut ws_sink, mut ws_stream) = ws_stream.split();
+        let host = url.host_str().ok_or_else(|| anyhow::anyhow!("WebSocket URL has no host: {}", url))?;
+        let port = url.port_or_known_default().ok_or_else(|| anyhow::anyhow!("WebSocket URL has no port: {}", url))?;
+        let tcp = TcpStream::connect((host, port)).await?;
+        tcp.set_nodelay(true)?;
+        
+        match url.scheme() {
+            "wss" => {
+                let tls = Self::tls_connector()?.connect(ServerName::try_from(host)?, tcp).await?;
+                self.start(tls, &url).await
+            }
+            "ws" => self.start(tcp, &url).await,
+            scheme => Err(anyhow::anyhow!("Unsupported WebSocket scheme: {}", scheme)),
+        }
+    }
+    
+    fn tls_connector() -> Result<TlsConnector> {
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(&rustls_native_certs::load_native_certs()?);
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(TlsConnector::from(Arc::new(config)))
+    }
+    
+    /// Run the handshake over `socket` and spawn the reader, writer and heartbeat tasks
+    async fn start<S>(&self, socket: S, url: &Url) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let options = &self.config.websocket;
+        let socket = WireCounter {
+            inner: socket,
+            counters: self.counters.clone(),
+        };
+        let socket = BufWriter::with_capacity(options.write_buffer_size, socket).compat();
+        
+        let host = url.host_str().unwrap_or_default();
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        let mut client = Client::new(socket, &host, &url[Position::BeforePath..]);
+        if options.compression {
+            client.add_extension(Box::new(Deflate::new(Mode::Client)));
+        }
+        match client.handshake().await? {
+            ServerResponse::Accepted { .. } => {}
+            ServerResponse::Redirect { status_code, location } => {
+                anyhow::bail!("OKX WebSocket handshake redirected ({}) to {}", status_code, location);
+            }
+            ServerResponse::Rejected { status_code } => {
+                anyhow::bail!("OKX WebSocket handshake rejected with status {}", status_code);
+            }
+        }
+        
+        // An offered extension the server declined stays disabled and the builder drops it
+        let extensions: Vec<_> = client.drain_extensions().collect();
+        let deflate = extensions.iter().any(|extension| extension.is_enabled());
+        self.counters.permessage_deflate.store(deflate, Ordering::Relaxed);
+        info!("OKX WebSocket connected, permessage-deflate {}", if deflate { "on" } else { "off" });
+        
+        let mut builder = client.into_builder();
+        builder.add_extensions(extensions);
+        builder.set_max_frame_size(options.max_frame_size);
+        builder.set_max_message_size(options.max_message_size);
+        let (mut ws_sink, mut ws_stream) = builder.finish();
         
         let event_tx = self.event_tx.clone();
         let is_connected = self.is_connected.clone();
@@ -72,88 +201,103 @@ impl OkxWebSocket {
             let mut connected = is_connected.write().await;
             *connected = true;
         }
+        *self.last_received.lock() = Instant::now();
         
         // Send connection event
         let _ = event_tx.send(OkxWebSocketEvent::Connected);
         
-        // Authentication for private channels
+        // Single writer task owns the sink; auth and keepalive messages go through the channel
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<Outgoing>();
+        *self.outgoing.write().await = Some(outgoing_tx.clone());
         tokio::spawn(async move {
-            if let Ok(auth_msg) = auth.get_websocket_auth() {
-                let login_message = auth_msg.to_login_message();
-                if let Ok(msg_text) = serde_json::to_string(&login_message) {
-                    if let Err(e) = ws_sink.send(Message::Text(msg_text)).await {
-                        error!("Failed to send authentication message: {}", e);
+            while let Some(msg) = outgoing_rx.recv().await {
+                let text = match msg {
+                    Outgoing::Text(text) => text,
+                    Outgoing::Close => {
+                        let _ = ws_sink.close().await;
+                        break;
                     }
+                };
+                let sent = match ws_sink.send_text_owned(text).await {
+                    Ok(()) => ws_sink.flush().await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = sent {
+                    error!("Failed to send WebSocket message: {}", e);
+                    break;
                 }
             }
         });
         
-        // Message handling loop
+        // Authentication for private channels
+        if let Ok(auth_msg) = auth.get_websocket_auth() {
+            let login_message = auth_msg.to_login_message();
+            if let Ok(msg_text) = serde_json::to_string(&login_message) {
+                let _ = outgoing_tx.send(Outgoing::Text(msg_text));
+            }
+        }
+        
+        // Message handling loop. Pings from the server are answered inside `receive`.
         let event_tx_clone = event_tx.clone();
         let is_connected_clone = is_connected.clone();
+        let last_received = self.last_received.clone();
+        let counters = self.counters.clone();
+        let compression = options.compression;
         tokio::spawn(async move {
-            while let Some(msg) = ws_stream.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        debug!("Received WebSocket message: {}", text);
+            let mut message = Vec::new();
+            loop {
+                message.clear();
+                let incoming = ws_stream.receive(&mut message).await;
+                if incoming.is_ok() {
+                    *last_received.lock() = Instant::now();
+                }
+                
+                match incoming {
+                    Ok(Incoming::Data(Data::Text(_))) => match std::str::from_utf8(&message) {
+                        Ok(text) => {
+                            counters.bytes_decompressed.fetch_add(text.len() as u64, Ordering::Relaxed);
+                            Self::handle_text(text, &event_tx_clone, &counters).await;
+                        }
+                        Err(e) => warn!("Dropping WebSocket text that is not UTF-8: {}", e),
+                    },
+                    Ok(Incoming::Data(Data::Binary(_))) => {
+                        if !compression {
+                            debug!("Received binary message (ignoring)");
+                            continue;
+                        }
                         
-                        if let Ok(ws_msg) = serde_json::from_str::<OkxWebSocketMessage>(&text) {
-                            Self::handle_message(ws_msg, &event_tx_clone).await;
-                        } else if let Ok(value) = serde_json::from_str::<Value>(&text) {
-                            // Handle other message types
-                            if let Some(event) = value.get("event").and_then(|v| v.as_str()) {
-                                match event {
-                                    "login" => {
-                                        if let Some(code) = value.get("code").and_then(|v| v.as_str()) {
-                                            if code == "0" {
-                                                info!("WebSocket authentication successful");
-                                            } else {
-                                                error!("WebSocket authentication failed: {:?}", value);
-                                            }
-                                        }
-                                    }
-                                    "subscribe" => {
-                                        info!("WebSocket subscription confirmed: {:?}", value);
-                                    }
-                                    "error" => {
-                                        error!("WebSocket error: {:?}", value);
-                                        let _ = event_tx_clone.send(OkxWebSocketEvent::Error(
-                                            format!("WebSocket error: {:?}", value)
-                                        ));
-                                    }
-                                    _ => {
-                                        debug!("Unknown WebSocket event: {}", event);
-                                    }
-                                }
+                        match Self::inflate(&message) {
+                            Ok(text) => {
+                                counters.compressed_frames.fetch_add(1, Ordering::Relaxed);
+                                counters.bytes_decompressed.fetch_add(text.len() as u64, Ordering::Relaxed);
+                                Self::handle_text(&text, &event_tx_clone, &counters).await;
+                            }
+                            Err(e) => {
+                                warn!("Failed to decompress WebSocket frame: {}", e);
                             }
                         }
                     }
-                    Ok(Message::Binary(_)) => {
-                        debug!("Received binary message (ignoring)");
+                    Ok(Incoming::Pong(_)) => {
+                        debug!("Received pong");
                     }
-                    Ok(Message::Close(_)) => {
-                        warn!("WebSocket connection closed");
-                        let mut connected = is_connected_clone.write().await;
-                        *connected = false;
-                        let _ = event_tx_clone.send(OkxWebSocketEvent::Disconnected);
+                    Ok(Incoming::Closed(reason)) => {
+                        warn!("WebSocket connection closed: {:?}", reason);
                         break;
                     }
-                    Ok(Message::Ping(_)) => {
-                        debug!("Received ping, sending pong");
-                        // WebSocket library handles pong automatically
-                    }
-                    Ok(Message::Pong(_)) => {
-                        debug!("Received pong");
-                    }
-                    Ok(Message::Frame(_)) => {
-                        debug!("Received frame message");
+                    // The oversized message was skipped; the stream is still usable
+                    Err(WsError::MessageTooLarge { current, maximum }) => {
+                        warn!("Dropped a {} byte WebSocket message over the {} byte limit", current, maximum);
                     }
                     Err(e) => {
                         error!("WebSocket error: {}", e);
                         let _ = event_tx_clone.send(OkxWebSocketEvent::Error(e.to_string()));
+                        break;
                     }
                 }
             }
+            
+            *is_connected_clone.write().await = false;
+            let _ = event_tx_clone.send(OkxWebSocketEvent::Disconnected);
         });
         
         // Start heartbeat
@@ -162,6 +306,53 @@ impl OkxWebSocket {
         Ok(())
     }
     
+    /// OKX compresses frames with raw deflate (no zlib header)
+    fn inflate(data: &[u8]) -> std::io::Result<String> {
+        let mut text = String::new();
+        DeflateDecoder::new(data).read_to_string(&mut text)?;
+        Ok(text)
+    }
+    
+    async fn handle_text(text: &str, event_tx: &mpsc::UnboundedSender<OkxWebSocketEvent>, counters: &StreamCounters) {
+        debug!("Received WebSocket message: {}", text);
+        
+        if text == "pong" {
+            counters.pongs_received.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        
+        if let Ok(ws_msg) = serde_json::from_str::<OkxWebSocketMessage>(text) {
+            Self::handle_message(ws_msg, event_tx).await;
+        } else if let Ok(value) = serde_json::from_str::<Value>(text) {
+            // Handle other message types
+            if let Some(event) = value.get("event").and_then(|v| v.as_str()) {
+                match event {
+                    "login" => {
+                        if let Some(code) = value.get("code").and_then(|v| v.as_str()) {
+                            if code == "0" {
+                                info!("WebSocket authentication successful");
+                            } else {
+                                error!("WebSocket authentication failed: {:?}", value);
+                            }
+                        }
+                    }
+                    "subscribe" => {
+                        info!("WebSocket subscription confirmed: {:?}", value);
+                    }
+                    "error" => {
+                        error!("WebSocket error: {:?}", value);
+                        let _ = event_tx.send(OkxWebSocketEvent::Error(
+                            format!("WebSocket error: {:?}", value)
+                        ));
+                    }
+                    _ => {
+                        debug!("Unknown WebSocket event: {}", event);
+                    }
+                }
+            }
+        }
+    }
+    
     async fn handle_message(ws_msg: OkxWebSocketMessage, event_tx: &mpsc::UnboundedSender<OkxWebSocketEvent>) {
         if let (Some(arg), Some(data)) = (ws_msg.arg, ws_msg.data) {
             match arg.channel.as_str() {
@@ -248,11 +439,14 @@ impl OkxWebSocket {
     }
     
     async fn start_heartbeat(&self) {
-        let _event_tx = self.event_tx.clone();
         let is_connected = self.is_connected.clone();
+        let outgoing = self.outgoing.clone();
+        let last_received = self.last_received.clone();
+        let counters = self.counters.clone();
+        let ping_interval = Duration::from_millis(self.config.websocket.ping_interval_ms.max(1));
         
         tokio::spawn(async move {
-            let mut heartbeat_interval = interval(Duration::from_secs(30));
+            let mut heartbeat_interval = interval(ping_interval);
             
             loop {
                 heartbeat_interval.tick().await;
@@ -266,17 +460,42 @@ impl OkxWebSocket {
                     break;
                 }
                 
-                // Send ping message
+                // OKX drops connections that stay idle for 30s
+                if last_received.lock().elapsed() < ping_interval {
+                    continue;
+                }
+                
                 debug!("Sending heartbeat ping");
-                // Note: In a real implementation, we would send a ping through the WebSocket
+                let sent = match outgoing.read().await.as_ref() {
+                    Some(tx) => tx.send(Outgoing::Text("ping".to_string())).is_ok(),
+                    None => false,
+                };
+                if sent {
+                    counters.pings_sent.fetch_add(1, Ordering::Relaxed);
+                }
             }
         });
     }
     
+    pub fn stats(&self) -> OkxWebSocketStats {
+        OkxWebSocketStats {
+            bytes_received: self.counters.bytes_received.load(Ordering::Relaxed),
+            bytes_decompressed: self.counters.bytes_decompressed.load(Ordering::Relaxed),
+            compressed_frames: self.counters.compressed_frames.load(Ordering::Relaxed),
+            pings_sent: self.counters.pings_sent.load(Ordering::Relaxed),
+            pongs_received: self.counters.pongs_received.load(Ordering::Relaxed),
+            permessage_deflate: self.counters.permessage_deflate.load(Ordering::Relaxed),
+        }
+    }
+    
     pub async fn disconnect(&self) -> Result<()> {
         let mut connected = self.is_connected.write().await;
         *connected = false;
         
+        if let Some(tx) = self.outgoing.write().await.take() {
+            let _ = tx.send(Outgoing::Close);
+        }
+        
         let _ = self.event_tx.send(OkxWebSocketEvent::Disconnected);
         info!("OKX WebSocket disconnected");
         
@@ -288,6 +507,11 @@ impl OkxWebSocket {
         *connected
     }
     
+    /// Channels subscribed so far, kept across reconnects
+    pub async fn subscription_count(&self) -> usize {
+        self.subscriptions.read().await.len()
+    }
+    
     pub async fn get_event_receiver(&self) -> Option<mpsc::UnboundedReceiver<OkxWebSocketEvent>> {
         let mut rx_option = self.event_rx.write().await;
         rx_option.take()
@@ -331,14 +555,56 @@ impl Clone for OkxWebSocket {
             event_rx: Arc::new(RwLock::new(Some(event_rx))),
             is_connected: Arc::new(RwLock::new(false)),
             subscriptions: Arc::new(RwLock::new(Vec::new())),
+            outgoing: Arc::new(RwLock::new(None)),
+            last_received: Arc::new(parking_lot::Mutex::new(Instant::now())),
+            counters: Arc::new(StreamCounters::default()),
         }
     }
 }
 
+/// Counts the bytes read off the socket into `bytes_received`
+struct WireCounter<S> {
+    inner: S,
+    counters: Arc<StreamCounters>,
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for WireCounter<S> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        let read = buf.filled().len() - before;
+        self.counters.bytes_received.fetch_add(read as u64, Ordering::Relaxed);
+        poll
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for WireCounter<S> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+    
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+    
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::OkxConfig;
+    use crate::config::{OkxConfig, OkxWebSocketConfig};
+    use crate::okx::SymbolMapping;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use soketto::data::ByteSlice125;
+    use soketto::handshake::server::Response;
+    use soketto::handshake::Server;
+    use std::io::Write;
+    use tokio::net::TcpListener;
+    use tokio_util::compat::Compat;
     
     fn create_test_config() -> OkxConfig {
         OkxConfig {
@@ -349,6 +615,8 @@ mod tests {
             base_url: None,
             timeout_ms: 5000,
             rate_limit_requests_per_second: 10,
+            websocket: OkxWebSocketConfig::default(),
+            symbols: SymbolMapping::defaults(),
         }
     }
     
@@ -367,4 +635,132 @@ mod tests {
         let result = ws.subscribe_ticker("BTC-USDT").await;
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+    
+    /// Accept one client, agreeing to permessage-deflate if it offers it
+    async fn accept(listener: &TcpListener) -> (soketto::Sender<Compat<TcpStream>>, soketto::Receiver<Compat<TcpStream>>, bool) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = Server::new(stream.compat());
+        server.add_extension(Box::new(Deflate::new(Mode::Server)));
+        let key = server.receive_request().await.unwrap().key();
+        server.send_response(&Response::Accept { key, protocol: None }).await.unwrap();
+        
+        let extensions: Vec<_> = server.drain_extensions().collect();
+        let deflate = extensions.iter().any(|extension| extension.is_enabled());
+        let mut builder = server.into_builder();
+        builder.add_extensions(extensions);
+        let (sender, receiver) = builder.finish();
+        (sender, receiver, deflate)
+    }
+    
+    #[tokio::test]
+    async fn test_compressed_frames_and_keepalive() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        let ticker = serde_json::json!({
+            "arg": { "channel": "tickers", "instId": "BTC-USDT" },
+            "data": vec![serde_json::json!({ "instId": "BTC-USDT", "last": "45000.1" }); 20],
+        })
+        .to_string();
+        let ticker_len = ticker.len() as u64;
+        
+        let server = tokio::spawn(async move {
+            let (mut sender, mut receiver, deflate) = accept(&listener).await;
+            
+            // Once compressed by the extension, once by OKX's own binary framing
+            sender.send_text(&ticker).await.unwrap();
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(ticker.as_bytes()).unwrap();
+            sender.send_binary(encoder.finish().unwrap()).await.unwrap();
+            sender.send_ping(ByteSlice125::try_from(&b"hb"[..]).unwrap()).await.unwrap();
+            sender.flush().await.unwrap();
+            
+            let (mut got_pong, mut got_ping) = (false, false);
+            let mut message = Vec::new();
+            while !(got_pong && got_ping) {
+                message.clear();
+                match receiver.receive(&mut message).await {
+                    Ok(Incoming::Pong(payload)) => got_pong = payload == b"hb",
+                    Ok(Incoming::Data(Data::Text(_))) if message == b"ping" => {
+                        got_ping = true;
+                        sender.send_text("pong").await.unwrap();
+                        sender.flush().await.unwrap();
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            }
+            (deflate, got_pong, got_ping)
+        });
+        
+        let mut config = create_test_config();
+        config.websocket = OkxWebSocketConfig {
+            url: Some(format!("ws://{}", addr)),
+            ping_interval_ms: 50,
+            ..OkxWebSocketConfig::default()
+        };
+        let ws = OkxWebSocket::new(Arc::new(config)).await.unwrap();
+        let mut events = ws.get_event_receiver().await.unwrap();
+        ws.connect().await.unwrap();
+        
+        assert!(matches!(events.recv().await, Some(OkxWebSocketEvent::Connected)));
+        for _ in 0..2 {
+            match tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap() {
+                Some(OkxWebSocketEvent::MarketData(data)) => {
+                    assert_eq!(data[0]["last"], "45000.1");
+                }
+                other => panic!("Expected market data, got {:?}", other),
+            }
+        }
+        
+        let (deflate, got_pong, got_ping) = tokio::time::timeout(Duration::from_secs(5), server).await.unwrap().unwrap();
+        assert!(deflate);
+        assert!(got_pong);
+        assert!(got_ping);
+        
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let stats = ws.stats();
+        assert!(stats.permessage_deflate);
+        assert_eq!(stats.compressed_frames, 1);
+        // Handshake and both messages together took less wire than one ticker uncompressed
+        assert!(stats.bytes_received < ticker_len);
+        assert!(stats.decompression_ratio() > 1.0);
+        assert!(stats.pings_sent >= 1);
+        assert_eq!(stats.pongs_received, 1);
+        
+        ws.disconnect().await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_permessage_deflate_is_only_offered_with_compression() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        let server = tokio::spawn(async move {
+            let (mut sender, receiver, deflate) = accept(&listener).await;
+            sender.send_text(r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"last":"1"}]}"#).await.unwrap();
+            sender.flush().await.unwrap();
+            (deflate, sender, receiver)
+        });
+        
+        let mut config = create_test_config();
+        config.websocket = OkxWebSocketConfig {
+            url: Some(format!("ws://{}", addr)),
+            compression: false,
+            ..OkxWebSocketConfig::default()
+        };
+        let ws = OkxWebSocket::new(Arc::new(config)).await.unwrap();
+        let mut events = ws.get_event_receiver().await.unwrap();
+        ws.connect().await.unwrap();
+        
+        assert!(matches!(events.recv().await, Some(OkxWebSocketEvent::Connected)));
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap();
+        assert!(matches!(event, Some(OkxWebSocketEvent::MarketData(_))));
+        
+        let (deflate, _sender, _receiver) = server.await.unwrap();
+        assert!(!deflate);
+        assert!(!ws.stats().permessage_deflate);
+        
+        ws.disconnect().await.unwrap();
+    }
+}
@@ -0,0 +1,345 @@
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::types::{ExchangeOrderAck, TradingSignal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    TimedOut,
+}
+
+/// One fill notification for an exchange order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillUpdate {
+    pub order_id: String,
+    pub fill_quantity: Decimal,
+    /// Total filled so far as reported by the exchange, preferred over summing `fill_quantity`
+    pub cumulative_quantity: Option<Decimal>,
+    pub fill_price: Option<Decimal>,
+    pub order_quantity: Option<Decimal>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl FillUpdate {
+    /// Parse the `data` array of an OKX `orders` channel push, skipping entries without a fill
+    pub fn from_okx(data: &serde_json::Value) -> Vec<Self> {
+        let decimal = |item: &serde_json::Value, key: &str| {
+            item.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .and_then(|v| Decimal::from_str(v).ok())
+        };
+
+        data.as_array()
+            .map(|items| {
+                items.iter()
+                    .filter_map(|item| {
+                        let order_id = item.get("ordId")?.as_str()?.to_string();
+                        let fill_quantity = decimal(item, "fillSz").unwrap_or(Decimal::ZERO);
+                        let cumulative_quantity = decimal(item, "accFillSz");
+                        if fill_quantity.is_zero() && cumulative_quantity.is_none_or(|q| q.is_zero()) {
+                            return None;
+                        }
+
+                        let timestamp = item.get("uTime")
+                            .and_then(|v| v.as_str())
+                            .and_then(|v| v.parse::<i64>().ok())
+                            .and_then(DateTime::from_timestamp_millis)
+                            .unwrap_or_else(Utc::now);
+
+                        Some(Self {
+                            order_id,
+                            fill_quantity,
+                            cumulative_quantity,
+                            fill_price: decimal(item, "fillPx"),
+                            order_quantity: decimal(item, "sz"),
+                            timestamp,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Exchange order placed for a signal, with fills accumulated so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedOrder {
+    pub signal_id: Uuid,
+    pub symbol: String,
+    pub exchange: String,
+    pub order_id: String,
+    pub quantity: Decimal,
+    pub filled_quantity: Decimal,
+    pub average_price: Option<Decimal>,
+    pub fill_count: u32,
+    pub status: FillStatus,
+    pub placed_at: DateTime<Utc>,
+    pub last_fill_at: Option<DateTime<Utc>>,
+    #[serde(skip, default = "Instant::now")]
+    placed_instant: Instant,
+}
+
+impl TrackedOrder {
+    #[inline]
+    pub fn remaining_quantity(&self) -> Decimal {
+        (self.quantity - self.filled_quantity).max(Decimal::ZERO)
+    }
+
+    #[inline]
+    pub fn is_filled(&self) -> bool {
+        self.status == FillStatus::Filled
+    }
+
+    fn apply(&mut self, update: &FillUpdate) {
+        if let Some(order_quantity) = update.order_quantity {
+            self.quantity = order_quantity;
+        }
+
+        let previous = self.filled_quantity;
+        self.filled_quantity = match update.cumulative_quantity {
+            Some(cumulative) => cumulative.max(previous),
+            None => previous + update.fill_quantity,
+        };
+
+        let delta = self.filled_quantity - previous;
+        if let Some(price) = update.fill_price.filter(|_| !delta.is_zero()) {
+            let notional = self.average_price.unwrap_or(Decimal::ZERO) * previous + price * delta;
+            self.average_price = Some(notional / self.filled_quantity);
+        }
+
+        self.fill_count += 1;
+        self.last_fill_at = Some(update.timestamp);
+        self.status = if self.filled_quantity >= self.quantity {
+            FillStatus::Filled
+        } else {
+            FillStatus::PartiallyFilled
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum OrderTrackingEvent {
+    Filled(TrackedOrder),
+    /// The order was still not fully filled when its fill timeout elapsed
+    PartialFillTimeout(TrackedOrder),
+}
+
+/// Maps exchange order ids back to the signals that produced them and follows their fills
+#[derive(Debug)]
+pub struct OrderTracker {
+    orders: RwLock<HashMap<String, TrackedOrder>>,
+    fill_timeout: Duration,
+    event_tx: mpsc::UnboundedSender<OrderTrackingEvent>,
+    event_rx: Mutex<Option<mpsc::UnboundedReceiver<OrderTrackingEvent>>>,
+}
+
+impl OrderTracker {
+    pub fn new(fill_timeout: Duration) -> Self {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        Self {
+            orders: RwLock::new(HashMap::new()),
+            fill_timeout,
+            event_tx,
+            event_rx: Mutex::new(Some(event_rx)),
+        }
+    }
+
+    /// Start following an acknowledged order placed for `signal`
+    pub fn track(&self, signal: &TradingSignal, ack: &ExchangeOrderAck) {
+        let order = TrackedOrder {
+            signal_id: signal.id,
+            symbol: signal.symbol.clone(),
+            exchange: ack.exchange.clone(),
+            order_id: ack.order_id.clone(),
+            quantity: ack.quantity,
+            filled_quantity: Decimal::ZERO,
+            average_price: None,
+            fill_count: 0,
+            status: FillStatus::Open,
+            placed_at: ack.timestamp,
+            last_fill_at: None,
+            placed_instant: Instant::now(),
+        };
+
+        debug!("Tracking {} order {} for signal {}", order.exchange, order.order_id, order.signal_id);
+        self.orders.write().insert(order.order_id.clone(), order);
+    }
+
+    /// Apply a fill; returns the order once it is fully filled and no longer tracked
+    pub fn apply_fill(&self, update: &FillUpdate) -> Option<TrackedOrder> {
+        let mut orders = self.orders.write();
+        let Some(order) = orders.get_mut(&update.order_id) else {
+            debug!("Ignoring fill for untracked order {}", update.order_id);
+            return None;
+        };
+
+        order.apply(update);
+        if !order.is_filled() {
+            debug!("Order {} filled {}/{}", order.order_id, order.filled_quantity, order.quantity);
+            return None;
+        }
+
+        let order = orders.remove(&update.order_id)?;
+        drop(orders);
+
+        info!("Order {} for signal {} fully filled: {} in {} fills",
+            order.order_id, order.signal_id, order.filled_quantity, order.fill_count);
+        let _ = self.event_tx.send(OrderTrackingEvent::Filled(order.clone()));
+        Some(order)
+    }
+
+    /// Stop tracking orders older than the fill timeout and report what they filled
+    pub fn expire_stale(&self) -> Vec<TrackedOrder> {
+        let mut orders = self.orders.write();
+        let expired: Vec<String> = orders.values()
+            .filter(|order| order.placed_instant.elapsed() >= self.fill_timeout)
+            .map(|order| order.order_id.clone())
+            .collect();
+
+        let expired: Vec<TrackedOrder> = expired.iter()
+            .filter_map(|order_id| orders.remove(order_id))
+            .map(|mut order| {
+                order.status = FillStatus::TimedOut;
+                order
+            })
+            .collect();
+        drop(orders);
+
+        for order in &expired {
+            warn!("Order {} for signal {} timed out with {}/{} filled",
+                order.order_id, order.signal_id, order.filled_quantity, order.quantity);
+            let _ = self.event_tx.send(OrderTrackingEvent::PartialFillTimeout(order.clone()));
+        }
+
+        expired
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<TrackedOrder> {
+        self.orders.read().get(order_id).cloned()
+    }
+
+    pub fn open_orders(&self) -> usize {
+        self.orders.read().len()
+    }
+
+    #[inline]
+    pub fn fill_timeout(&self) -> Duration {
+        self.fill_timeout
+    }
+
+    pub fn take_event_receiver(&self) -> Option<mpsc::UnboundedReceiver<OrderTrackingEvent>> {
+        self.event_rx.lock().take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{SignalSource, SignalType};
+
+    fn signal() -> TradingSignal {
+        TradingSignal {
+            id: Uuid::new_v4(),
+            symbol: "BTC-USDT".to_string(),
+            signal_type: SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.9,
+            price_target: None,
+            stop_loss: None,
+            take_profit: None,
+            metadata: HashMap::new(),
+            timestamp: Utc::now(),
+            source: SignalSource::Combined,
+        }
+    }
+
+    fn ack(order_id: &str, quantity: Decimal) -> ExchangeOrderAck {
+        ExchangeOrderAck {
+            exchange: "okx".to_string(),
+            order_id: order_id.to_string(),
+            client_order_id: String::new(),
+            quantity,
+            accepted: true,
+            message: String::new(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_partial_fills_accumulate_to_filled() {
+        let tracker = OrderTracker::new(Duration::from_secs(30));
+        let mut events = tracker.take_event_receiver().unwrap();
+        let signal = signal();
+        tracker.track(&signal, &ack("312269865356374016", Decimal::new(10, 2)));
+
+        let first = FillUpdate::from_okx(&serde_json::json!([{
+            "ordId": "312269865356374016",
+            "sz": "0.1",
+            "fillSz": "0.04",
+            "fillPx": "50000",
+            "accFillSz": "0.04",
+            "state": "partially_filled",
+            "uTime": "1700000000000"
+        }]));
+        assert_eq!(first.len(), 1);
+        assert!(tracker.apply_fill(&first[0]).is_none());
+
+        let partial = tracker.get("312269865356374016").unwrap();
+        assert_eq!(partial.status, FillStatus::PartiallyFilled);
+        assert_eq!(partial.filled_quantity, Decimal::new(4, 2));
+        assert_eq!(partial.remaining_quantity(), Decimal::new(6, 2));
+
+        let second = FillUpdate::from_okx(&serde_json::json!([{
+            "ordId": "312269865356374016",
+            "sz": "0.1",
+            "fillSz": "0.06",
+            "fillPx": "50100",
+            "accFillSz": "0.1",
+            "state": "filled",
+            "uTime": "1700000000500"
+        }]));
+        let filled = tracker.apply_fill(&second[0]).unwrap();
+
+        assert_eq!(filled.status, FillStatus::Filled);
+        assert_eq!(filled.signal_id, signal.id);
+        assert_eq!(filled.filled_quantity, Decimal::new(10, 2));
+        assert_eq!(filled.fill_count, 2);
+        assert_eq!(filled.average_price, Some(Decimal::new(50060, 0)));
+        assert_eq!(tracker.open_orders(), 0);
+        assert!(matches!(events.try_recv(), Ok(OrderTrackingEvent::Filled(order)) if order.order_id == filled.order_id));
+    }
+
+    #[test]
+    fn test_partial_fill_timeout_reported() {
+        let tracker = OrderTracker::new(Duration::ZERO);
+        let mut events = tracker.take_event_receiver().unwrap();
+        tracker.track(&signal(), &ack("1", Decimal::ONE));
+        tracker.apply_fill(&FillUpdate {
+            order_id: "1".to_string(),
+            fill_quantity: Decimal::new(25, 2),
+            cumulative_quantity: None,
+            fill_price: None,
+            order_quantity: None,
+            timestamp: Utc::now(),
+        });
+
+        let expired = tracker.expire_stale();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].status, FillStatus::TimedOut);
+        assert_eq!(expired[0].filled_quantity, Decimal::new(25, 2));
+        assert!(matches!(events.try_recv(), Ok(OrderTrackingEvent::PartialFillTimeout(_))));
+    }
+}
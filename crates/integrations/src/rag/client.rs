@@ -5,6 +5,7 @@ use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::{info, warn, error, debug};
 
+use crate::codec::PayloadCodec;
 use crate::config::RagConfig;
 use crate::types::{KnowledgeQuery, KnowledgeResponse, HealthStatus};
 use super::types::*;
@@ -41,16 +42,18 @@ impl RagClient {
     {
         let url = format!("{}/{}", self.base_url, endpoint.trim_start_matches('/'));
         
+        let codec = self.config.payload_codec;
         let mut request = self.client.post(&url)
-            .header("Content-Type", "application/json");
+            .header("Content-Type", codec.content_type())
+            .header("Accept", codec.content_type());
         
         // Add API key if configured
         if let Some(ref api_key) = self.config.api_key {
             request = request.header("Authorization", format!("Bearer {}", api_key));
         }
         
-        let body = serde_json::to_string(&request_data)?;
-        debug!("Sending RAG request to {}: {}", url, body);
+        let body = codec.encode(&request_data)?;
+        debug!("Sending {}-byte {:?} RAG request to {}", body.len(), codec, url);
         
         let response = request.body(body).send().await?;
         let status = response.status();
@@ -60,10 +63,16 @@ impl RagClient {
             return Err(anyhow!("RAG API error {}: {}", status, error_text));
         }
         
-        let response_text = response.text().await?;
-        debug!("Received RAG response: {}", response_text);
+        // Servers that ignore Accept still answer in JSON
+        let response_codec = response.headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(PayloadCodec::from_content_type)
+            .unwrap_or(codec);
+        let response_body = response.bytes().await?;
+        debug!("Received {}-byte {:?} RAG response", response_body.len(), response_codec);
         
-        let result: R = serde_json::from_str(&response_text)
+        let result: R = response_codec.decode(&response_body)
             .map_err(|e| anyhow!("Failed to parse RAG response: {}", e))?;
         
         Ok(result)
@@ -104,6 +113,8 @@ impl RagClient {
     pub async fn query_documents(&self, query: KnowledgeQuery) -> Result<KnowledgeResponse> {
         let start_time = Instant::now();
         
+        let query_id = query.query_id;
+        let symbol = query.symbol.clone();
         let rag_request: RagQueryRequest = query.into();
         
         info!("Querying RAG for: {}", rag_request.query);
@@ -122,7 +133,9 @@ impl RagClient {
                     info!("RAG query completed in {}ms, found {} documents", 
                         processing_time, rag_response.documents.len());
                     
-                    let response: KnowledgeResponse = rag_response.into();
+                    let mut response: KnowledgeResponse = rag_response.into();
+                    response.query_id = query_id;
+                    self.config.reranking.rerank(&mut response.results, symbol.as_deref(), chrono::Utc::now());
                     return Ok(response);
                 }
                 Err(e) => {
@@ -349,7 +362,7 @@ pub struct KnowledgeStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::RagConfig;
+    use crate::config::{RagConfig, RagReranking};
     
     fn create_test_config() -> RagConfig {
         RagConfig {
@@ -359,6 +372,8 @@ mod tests {
             max_retries: 3,
             query_threshold: 0.6,
             top_k: 10,
+            payload_codec: PayloadCodec::default(),
+            reranking: RagReranking::default(),
         }
     }
     
@@ -7,6 +7,7 @@ use std::collections::{HashMap, VecDeque};
 
 use super::client::RagClient;
 use super::types::{MarketEvent, MarketEventType};
+use crate::ids::{IdSource, RandomIds};
 use crate::types::MarketContext;
 
 #[derive(Debug)]
@@ -16,16 +17,23 @@ pub struct MarketEventIngestion {
     batch_size: usize,
     batch_timeout_ms: u64,
     is_running: Arc<RwLock<bool>>,
+    ids: Arc<dyn IdSource>,
 }
 
 impl MarketEventIngestion {
     pub fn new(client: Arc<RagClient>) -> Self {
+        Self::with_id_source(client, Arc::new(RandomIds))
+    }
+    
+    /// Ingestion that ids the events it builds from `ids`
+    pub fn with_id_source(client: Arc<RagClient>, ids: Arc<dyn IdSource>) -> Self {
         Self {
             client,
             event_queue: Arc::new(RwLock::new(VecDeque::new())),
             batch_size: 50,
             batch_timeout_ms: 5000, // 5 seconds
             is_running: Arc::new(RwLock::new(false)),
+            ids,
         }
     }
     
@@ -132,13 +140,14 @@ impl MarketEventIngestion {
     }
     
     pub async fn ingest_market_context(&self, context: MarketContext) -> Result<()> {
-        let event: MarketEvent = context.into();
+        let mut event: MarketEvent = context.into();
+        event.id = self.ids.next_id().to_string();
         self.ingest_event(event).await
     }
     
     pub async fn ingest_trade_execution(&self, trade: TradeExecution) -> Result<()> {
         let event = MarketEvent {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: self.ids.next_id().to_string(),
             timestamp: trade.timestamp,
             event_type: MarketEventType::Trade,
             symbol: trade.symbol.clone(),
@@ -157,7 +166,7 @@ impl MarketEventIngestion {
     
     pub async fn ingest_price_alert(&self, alert: PriceAlert) -> Result<()> {
         let event = MarketEvent {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: self.ids.next_id().to_string(),
             timestamp: alert.timestamp,
             event_type: MarketEventType::Alert,
             symbol: alert.symbol.clone(),
@@ -175,7 +184,7 @@ impl MarketEventIngestion {
     
     pub async fn ingest_volume_spike(&self, spike: VolumeSpike) -> Result<()> {
         let event = MarketEvent {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: self.ids.next_id().to_string(),
             timestamp: spike.timestamp,
             event_type: MarketEventType::VolumeSpike,
             symbol: spike.symbol.clone(),
@@ -193,7 +202,7 @@ impl MarketEventIngestion {
     
     pub async fn ingest_technical_signal(&self, signal: TechnicalSignal) -> Result<()> {
         let event = MarketEvent {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: self.ids.next_id().to_string(),
             timestamp: signal.timestamp,
             event_type: MarketEventType::TechnicalIndicator,
             symbol: signal.symbol.clone(),
@@ -320,6 +329,7 @@ impl Clone for MarketEventIngestion {
             batch_size: self.batch_size,
             batch_timeout_ms: self.batch_timeout_ms,
             is_running: Arc::new(RwLock::new(false)),
+            ids: self.ids.clone(),
         }
     }
 }
@@ -327,7 +337,8 @@ impl Clone for MarketEventIngestion {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::RagConfig;
+    use crate::codec::PayloadCodec;
+    use crate::config::{RagConfig, RagReranking};
     
     async fn create_test_ingestion() -> MarketEventIngestion {
         let config = Arc::new(RagConfig {
@@ -337,6 +348,8 @@ mod tests {
             max_retries: 3,
             query_threshold: 0.6,
             top_k: 10,
+            payload_codec: PayloadCodec::default(),
+            reranking: RagReranking::default(),
         });
         
         let client = Arc::new(RagClient::new(config).await.unwrap());
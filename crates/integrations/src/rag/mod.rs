@@ -1,6 +1,7 @@
 pub mod client;
 pub mod types;
 pub mod ingestion;
+pub mod rerank;
 
 pub use client::RagClient;
 pub use types::*;
@@ -8,6 +9,8 @@ pub use ingestion::MarketEventIngestion;
 
 use anyhow::Result;
 use crate::config::RagConfig;
+use crate::exchange::readiness_from_health;
+use event_processor::Probe;
 use crate::types::{KnowledgeQuery, KnowledgeResponse, HealthStatus};
 use std::sync::Arc;
 
@@ -43,6 +46,16 @@ impl RagIntegration {
         self.client.health_check().await
     }
     
+    /// The client holds no connection that could wedge, so it is always live
+    pub fn liveness(&self) -> Probe {
+        Probe::Pass
+    }
+    
+    /// Ready once the knowledge server answers its health check
+    pub async fn readiness(&self) -> Probe {
+        readiness_from_health("rag", self.health_check().await)
+    }
+    
     pub async fn search_patterns(&self, pattern_query: PatternSearchQuery) -> Result<PatternSearchResponse> {
         self.client.search_patterns(pattern_query).await
     }
@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::RagReranking;
+use crate::types::KnowledgeResult;
+
+/// Metadata key a result's symbol is read from
+pub const SYMBOL_METADATA_KEY: &str = "symbol";
+
+impl RagReranking {
+    /// Final score of `result` for a query about `symbol` at `now`
+    pub fn score(&self, result: &KnowledgeResult, symbol: Option<&str>, now: DateTime<Utc>) -> f64 {
+        let total_weight = self.score_weight + self.recency_weight + self.symbol_weight;
+        if total_weight <= 0.0 {
+            return result.score as f64;
+        }
+
+        let age_ms = (now - result.timestamp).num_milliseconds().max(0) as f64;
+        let recency = match self.recency_half_life_ms {
+            0 => 0.0,
+            half_life => 0.5f64.powf(age_ms / half_life as f64),
+        };
+        let symbol_match = match (symbol, result.metadata.get(SYMBOL_METADATA_KEY)) {
+            (Some(symbol), Some(result_symbol)) if result_symbol.eq_ignore_ascii_case(symbol) => 1.0,
+            _ => 0.0,
+        };
+
+        (self.score_weight * result.score as f64 + self.recency_weight * recency + self.symbol_weight * symbol_match)
+            / total_weight
+    }
+
+    /// Set each result's `rank_score` to its final score and sort best first, leaving the
+    /// server's `score` as it was. Ties keep the server's order.
+    pub fn rerank(&self, results: &mut [KnowledgeResult], symbol: Option<&str>, now: DateTime<Utc>) {
+        for result in results.iter_mut() {
+            result.rank_score = Some(self.score(result, symbol, now) as f32);
+        }
+        let rank = |result: &KnowledgeResult| result.rank_score.unwrap_or(result.score);
+        results.sort_by(|a, b| rank(b).total_cmp(&rank(a)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn result(id: &str, score: f32, age: chrono::Duration, symbol: Option<&str>, now: DateTime<Utc>) -> KnowledgeResult {
+        KnowledgeResult {
+            id: id.to_string(),
+            content: String::new(),
+            score,
+            rank_score: None,
+            metadata: symbol
+                .map(|symbol| HashMap::from([(SYMBOL_METADATA_KEY.to_string(), symbol.to_string())]))
+                .unwrap_or_default(),
+            timestamp: now - age,
+        }
+    }
+
+    #[test]
+    fn test_rerank_prefers_recent_same_symbol_results() {
+        let now = Utc::now();
+        let results = vec![
+            result("old-generic", 0.90, chrono::Duration::days(30), None, now),
+            result("recent-other-symbol", 0.88, chrono::Duration::minutes(1), Some("ETH-USDT"), now),
+            result("recent-same-symbol", 0.85, chrono::Duration::minutes(1), Some("btc-usdt"), now),
+        ];
+        let ids = |results: &[KnowledgeResult]| results.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+
+        // The defaults leave the server's ranking alone
+        let mut unweighted = results.clone();
+        RagReranking::default().rerank(&mut unweighted, Some("BTC-USDT"), now);
+        assert_eq!(ids(&unweighted), ["old-generic", "recent-other-symbol", "recent-same-symbol"]);
+        assert_eq!(unweighted[0].score, 0.90);
+
+        let reranking = RagReranking {
+            score_weight: 1.0,
+            recency_weight: 0.3,
+            recency_half_life_ms: 3_600_000,
+            symbol_weight: 0.3,
+        };
+        let mut reranked = results;
+        reranking.rerank(&mut reranked, Some("BTC-USDT"), now);
+        assert_eq!(ids(&reranked), ["recent-same-symbol", "recent-other-symbol", "old-generic"]);
+        assert_eq!(reranked[0].score, 0.85);
+        assert!(reranked.windows(2).all(|pair| pair[0].rank_score >= pair[1].rank_score));
+    }
+}
@@ -286,12 +286,13 @@ impl From<crate::types::KnowledgeQuery> for RagQueryRequest {
 impl From<RagQueryResponse> for crate::types::KnowledgeResponse {
     fn from(response: RagQueryResponse) -> Self {
         Self {
-            query_id: uuid::Uuid::new_v4(), // Generate new UUID
+            query_id: uuid::Uuid::new_v4(), // Replaced with the query's own id by the client
             results: response.documents.into_iter().map(|doc| {
                 crate::types::KnowledgeResult {
                     id: doc.id,
                     content: doc.content,
                     score: doc.score,
+                    rank_score: None,
                     metadata: doc.metadata,
                     timestamp: doc.timestamp,
                 }
@@ -136,7 +136,11 @@ pub struct KnowledgeResponse {
 pub struct KnowledgeResult {
     pub id: String,
     pub content: String,
+    /// Relevance as scored by the RAG server
     pub score: f32,
+    /// Score the results were re-ranked by, set by `RagReranking::rerank`
+    #[serde(default)]
+    pub rank_score: Option<f32>,
     pub metadata: HashMap<String, String>,
     pub timestamp: DateTime<Utc>,
 }
@@ -202,3 +206,14 @@ pub struct IntegrationMetrics {
     pub timestamp: DateTime<Utc>,
 }
 
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeOrderAck {
+    pub exchange: String,
+    pub order_id: String,
+    pub client_order_id: String,
+    pub quantity: Decimal,
+    pub accepted: bool,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
@@ -2,10 +2,17 @@ use hdrhistogram::Histogram as HdrHistogram;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
 
+/// Sample counts up to this many are answered exactly from retained samples. Beyond it the
+/// bucket interpolation error (well under 1% at 3 significant digits) is smaller than the
+/// sampling noise of the percentile itself, so the samples are dropped.
+pub const SMALL_SAMPLE_THRESHOLD: usize = 64;
+
 #[derive(Debug, Clone)]
 pub struct Histogram {
     inner: HdrHistogram<u64>,
     count: u64,
+    // Every recorded value while count <= SMALL_SAMPLE_THRESHOLD, emptied once it is exceeded
+    samples: Vec<u64>,
 }
 
 impl Histogram {
@@ -14,6 +21,7 @@ impl Histogram {
         Self {
             inner: HdrHistogram::<u64>::new(3).expect("Failed to create histogram"),
             count: 0,
+            samples: Vec::new(),
         }
     }
     
@@ -23,6 +31,7 @@ impl Histogram {
             inner: HdrHistogram::<u64>::new_with_bounds(min, max, precision as u8)
                 .expect("Failed to create histogram"),
             count: 0,
+            samples: Vec::new(),
         }
     }
     
@@ -30,6 +39,16 @@ impl Histogram {
     pub fn record(&mut self, value: u64) {
         if self.inner.record(value).is_ok() {
             self.count += 1;
+            self.retain_sample(value);
+        }
+    }
+    
+    #[inline]
+    fn retain_sample(&mut self, value: u64) {
+        if self.count as usize <= SMALL_SAMPLE_THRESHOLD {
+            self.samples.push(value);
+        } else if !self.samples.is_empty() {
+            self.samples = Vec::new();
         }
     }
     
@@ -38,9 +57,41 @@ impl Histogram {
         self.record(duration.as_nanos() as u64);
     }
     
-    #[inline]
+    /// Value at `percentile` (0-100). Up to `SMALL_SAMPLE_THRESHOLD` samples this is the exact
+    /// nearest-rank order statistic; above it, linear interpolation within the bucket holding that rank.
     pub fn percentile(&self, percentile: f64) -> u64 {
-        self.inner.value_at_percentile(percentile)
+        if self.count == 0 {
+            return 0;
+        }
+        
+        let percentile = percentile.clamp(0.0, 100.0);
+        if self.samples.len() as u64 == self.count {
+            let mut sorted = self.samples.clone();
+            sorted.sort_unstable();
+            let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+            return sorted[rank.clamp(1, sorted.len()) - 1];
+        }
+        
+        self.interpolated_percentile(percentile)
+    }
+    
+    fn interpolated_percentile(&self, percentile: f64) -> u64 {
+        let target = (percentile / 100.0) * self.inner.len() as f64;
+        let mut seen = 0u64;
+        
+        for bucket in self.inner.iter_recorded() {
+            let count = bucket.count_at_value();
+            if (seen + count) as f64 >= target {
+                let high = bucket.value_iterated_to();
+                let low = self.inner.lowest_equivalent(high);
+                let fraction = ((target - seen as f64) / count as f64).clamp(0.0, 1.0);
+                let value = low as f64 + (high - low) as f64 * fraction;
+                return (value.round() as u64).clamp(self.inner.min(), self.inner.max());
+            }
+            seen += count;
+        }
+        
+        self.inner.max()
     }
     
     #[inline]
@@ -77,12 +128,19 @@ impl Histogram {
     pub fn reset(&mut self) {
         self.inner.reset();
         self.count = 0;
+        self.samples.clear();
     }
     
     #[inline]
     pub fn merge(&mut self, other: &Histogram) {
         if self.inner.add(&other.inner).is_ok() {
+            let both_exact = self.samples.len() as u64 == self.count && other.samples.len() as u64 == other.count;
             self.count += other.count;
+            if both_exact && self.count as usize <= SMALL_SAMPLE_THRESHOLD {
+                self.samples.extend_from_slice(&other.samples);
+            } else {
+                self.samples = Vec::new();
+            }
         }
     }
     
@@ -114,6 +172,14 @@ impl Histogram {
             .collect()
     }
     
+    /// Recorded buckets as (upper bound in ns, count), in ascending bound order
+    pub fn buckets(&self) -> Vec<(u64, u64)> {
+        self.inner
+            .iter_recorded()
+            .map(|v| (v.value_iterated_to(), v.count_at_value()))
+            .collect()
+    }
+    
     pub fn export_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         use std::fs::File;
         use std::io::Write;
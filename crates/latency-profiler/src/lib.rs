@@ -2,10 +2,12 @@ pub mod profiler;
 pub mod metrics;
 pub mod histogram;
 pub mod rdtsc_timer;
+pub mod recorder;
 
-pub use profiler::LatencyProfiler;
+pub use profiler::{LatencyProfiler, Warmup};
 pub use metrics::*;
-pub use histogram::Histogram;
-pub use rdtsc_timer::{RdtscTimer, RdtscTimestamp, RdtscProfiler, AtomicLatencyMetrics, LatencySnapshot, RdtscScopedMeasurement, GLOBAL_RDTSC_PROFILER};
+pub use histogram::{Histogram, SMALL_SAMPLE_THRESHOLD};
+pub use rdtsc_timer::{RdtscTimer, RdtscTimestamp, RdtscProfiler, AtomicLatencyMetrics, LatencySnapshot, RdtscScopedMeasurement, GLOBAL_RDTSC_PROFILER, RDTSC_FREQUENCY_ENV};
+pub use recorder::{LatencyRecorder, LatencyFrame, read_latency_series};
 
 pub type Result<T> = anyhow::Result<T>;
\ No newline at end of file
@@ -18,6 +18,8 @@ pub enum MeasurementPoint {
     MarketDataProcessed,
     RiskChecked,
     EventProcessed,
+    /// From `submit_order` entry until its response is returned and its events sent
+    OrderAckLatency,
     Custom(&'static str),
 }
 
@@ -33,6 +35,7 @@ impl MeasurementPoint {
             MeasurementPoint::MarketDataProcessed => "market_data_processed",
             MeasurementPoint::RiskChecked => "risk_checked",
             MeasurementPoint::EventProcessed => "event_processed",
+            MeasurementPoint::OrderAckLatency => "order_ack_latency",
             MeasurementPoint::Custom(name) => name,
         }
     }
@@ -70,6 +73,31 @@ impl Measurement {
     }
 }
 
+/// How long a warmup lasts before samples count towards the steady-state metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warmup {
+    /// Wall-clock time from `start_warmup`
+    Duration(Duration),
+    /// Samples recorded across all measurement points
+    Samples(u64),
+}
+
+#[derive(Debug)]
+struct WarmupState {
+    until: Warmup,
+    started: Instant,
+    samples: u64,
+}
+
+impl WarmupState {
+    fn is_over(&self) -> bool {
+        match self.until {
+            Warmup::Duration(duration) => self.started.elapsed() >= duration,
+            Warmup::Samples(samples) => self.samples >= samples,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct LatencyProfiler {
     measurements: Arc<RwLock<HashMap<MeasurementPoint, LatencyMetrics>>>,
@@ -77,6 +105,10 @@ pub struct LatencyProfiler {
     active_measurements: Arc<RwLock<HashMap<u64, (MeasurementPoint, Instant)>>>,
     measurement_id_counter: Arc<parking_lot::Mutex<u64>>,
     enabled: Arc<AtomicBool>,
+    warmup: Arc<parking_lot::Mutex<Option<WarmupState>>>,
+    warmup_measurements: Arc<RwLock<HashMap<MeasurementPoint, LatencyMetrics>>>,
+    /// Mirrors `warmup.is_some()` so steady-state recording never takes the warmup lock
+    warming_up: Arc<AtomicBool>,
 }
 
 impl LatencyProfiler {
@@ -88,7 +120,51 @@ impl LatencyProfiler {
             active_measurements: Arc::new(RwLock::new(HashMap::new())),
             measurement_id_counter: Arc::new(parking_lot::Mutex::new(0)),
             enabled: Arc::new(AtomicBool::new(true)),
+            warmup: Arc::new(parking_lot::Mutex::new(None)),
+            warmup_measurements: Arc::new(RwLock::new(HashMap::new())),
+            warming_up: Arc::new(AtomicBool::new(false)),
+        }
+    }
+    
+    /// Keep samples out of the reported metrics until `warmup` has passed, so cold caches and
+    /// first-touch allocation do not skew steady-state percentiles. Warmup samples are still
+    /// kept, separately, in `warmup_stats`; starting a new warmup discards the previous ones.
+    pub fn start_warmup(&self, warmup: Warmup) {
+        let mut state = self.warmup.lock();
+        self.warmup_measurements.write().clear();
+        *state = Some(WarmupState {
+            until: warmup,
+            started: Instant::now(),
+            samples: 0,
+        });
+        self.warming_up.store(true, Ordering::Relaxed);
+    }
+    
+    #[inline]
+    pub fn is_warming_up(&self) -> bool {
+        self.warming_up.load(Ordering::Relaxed) && self.warmup.lock().as_ref().is_some_and(|state| !state.is_over())
+    }
+    
+    /// Metrics for the samples taken during the last warmup
+    pub fn warmup_stats(&self) -> HashMap<MeasurementPoint, LatencyMetrics> {
+        self.warmup_measurements.read().clone()
+    }
+    
+    /// Record `latency` as a warmup sample if a warmup is still running, ending it once over
+    fn record_warmup(&self, point: MeasurementPoint, latency: Duration) -> bool {
+        let mut warmup = self.warmup.lock();
+        let Some(state) = warmup.as_mut() else {
+            return false;
+        };
+        if state.is_over() {
+            *warmup = None;
+            self.warming_up.store(false, Ordering::Relaxed);
+            return false;
         }
+        
+        state.samples += 1;
+        self.warmup_measurements.write().entry(point).or_default().record(latency);
+        true
     }
     
     #[inline]
@@ -142,6 +218,10 @@ impl LatencyProfiler {
             return;
         }
         
+        if self.warming_up.load(Ordering::Relaxed) && self.record_warmup(point, latency) {
+            return;
+        }
+        
         // Try non-blocking approach first, fall back to blocking for reliability
         if let Some(mut measurements) = self.measurements.try_write() {
             let metrics = measurements.entry(point).or_default();
@@ -204,12 +284,42 @@ impl LatencyProfiler {
         }
     }
     
+    /// Fold `other`'s samples into this profiler, point by point, as if they had been recorded
+    /// here. Counts and sums add, min and max combine, histograms merge; warmup samples stay
+    /// separate. Used to aggregate per-worker profilers into one view.
+    pub fn merge(&self, other: &LatencyProfiler) {
+        // Copy first so merging a profiler into itself cannot deadlock
+        let measurements = other.measurements.read().clone();
+        let histograms = other.histograms.read().clone();
+        let warmup_measurements = other.warmup_measurements.read().clone();
+        
+        let mut own = self.measurements.write();
+        for (point, metrics) in &measurements {
+            own.entry(*point).or_default().merge(metrics);
+        }
+        drop(own);
+        
+        let mut own = self.histograms.write();
+        for (point, histogram) in &histograms {
+            own.entry(*point).or_default().merge(histogram);
+        }
+        drop(own);
+        
+        let mut own = self.warmup_measurements.write();
+        for (point, metrics) in &warmup_measurements {
+            own.entry(*point).or_default().merge(metrics);
+        }
+    }
+    
     #[inline]
     pub fn reset(&self) {
         self.measurements.write().clear();
         self.histograms.write().clear();
         self.active_measurements.write().clear();
         *self.measurement_id_counter.lock() = 0;
+        *self.warmup.lock() = None;
+        self.warmup_measurements.write().clear();
+        self.warming_up.store(false, Ordering::Relaxed);
     }
     
     #[inline]
@@ -261,6 +371,25 @@ impl LatencyProfiler {
         
         Ok(())
     }
+    
+    /// Export full bucket data, one row per (measurement point, recorded bucket)
+    pub fn export_histograms_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::{BufWriter, Write};
+        
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "measurement_point,bucket_upper_ns,count")?;
+        
+        let histograms = self.histograms.read();
+        for (point, histogram) in histograms.iter() {
+            for (upper_ns, count) in histogram.buckets() {
+                writeln!(file, "{},{},{}", point.as_str(), upper_ns, count)?;
+            }
+        }
+        
+        file.flush()?;
+        Ok(())
+    }
 }
 
 impl Default for LatencyProfiler {
@@ -582,6 +711,156 @@ mod tests {
         std::fs::remove_file(temp_path).ok();
     }
 
+    #[test]
+    fn test_histogram_csv_export() {
+        let profiler = LatencyProfiler::new();
+        let point = MeasurementPoint::OrderMatched;
+        
+        // 100 samples each at 1us, 10us and 100us, plus a 1ms tail
+        for latency_us in [1, 10, 100] {
+            for _ in 0..100 {
+                profiler.record_latency(point, Duration::from_micros(latency_us));
+            }
+        }
+        profiler.record_latency(point, Duration::from_millis(1));
+        
+        let temp_path = "/tmp/test_latency_histogram_export.csv";
+        profiler.export_histograms_csv(temp_path).unwrap();
+        let content = std::fs::read_to_string(temp_path).unwrap();
+        std::fs::remove_file(temp_path).ok();
+        
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("measurement_point,bucket_upper_ns,count"));
+        
+        let rows: Vec<(u64, u64)> = lines
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                assert_eq!(fields[0], "order_matched");
+                (fields[1].parse().unwrap(), fields[2].parse().unwrap())
+            })
+            .collect();
+        
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows.iter().map(|(_, count)| count).sum::<u64>(), 301);
+        assert!(rows.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_histogram_percentiles_small_and_large_counts() {
+        use crate::histogram::SMALL_SAMPLE_THRESHOLD;
+        
+        // 3 samples: order statistics, not bucket bounds
+        let mut three = Histogram::new();
+        for value in [1_000, 5_000, 1_000_003] {
+            three.record(value);
+        }
+        assert_eq!(three.percentile(50.0), 5_000);
+        assert_eq!(three.percentile(99.0), 1_000_003);
+        assert_eq!(three.percentile(0.0), 1_000);
+        
+        // 10 samples recorded out of order
+        let mut ten = Histogram::new();
+        for value in [7, 3, 10, 1, 9, 2, 8, 4, 6, 5] {
+            ten.record(value * 1_001);
+        }
+        assert_eq!(ten.percentile(50.0), 5 * 1_001);
+        assert_eq!(ten.percentile(90.0), 9 * 1_001);
+        assert_eq!(ten.percentile(99.0), 10 * 1_001);
+        
+        // Merging keeps exact answers while the total stays small
+        let mut merged = three.clone();
+        merged.merge(&ten);
+        assert_eq!(merged.count(), 13);
+        assert_eq!(merged.percentile(99.0), 1_000_003);
+        
+        let mut large = Histogram::new();
+        for value in 1..=10_000u64 {
+            large.record(value * 1_000);
+        }
+        assert!(large.count() as usize > SMALL_SAMPLE_THRESHOLD);
+        for (percentile, exact) in [(50.0, 5_000_000.0), (99.0, 9_900_000.0), (99.9, 9_990_000.0)] {
+            let value = large.percentile(percentile) as f64;
+            assert!((value - exact).abs() / exact < 0.001, "p{} = {} vs {}", percentile, value, exact);
+        }
+        
+        large.reset();
+        large.record(42);
+        assert_eq!(large.percentile(99.0), 42);
+    }
+
+    #[test]
+    fn test_warmup_samples_are_kept_out_of_steady_state_metrics() {
+        let profiler = LatencyProfiler::new();
+        let point = MeasurementPoint::OrderMatched;
+        
+        profiler.start_warmup(Warmup::Samples(3));
+        assert!(profiler.is_warming_up());
+        for _ in 0..3 {
+            profiler.record_latency(point, Duration::from_millis(5));
+        }
+        assert!(!profiler.is_warming_up());
+        assert!(profiler.get_metrics(point).is_none());
+        
+        for _ in 0..2 {
+            profiler.record_latency(point, Duration::from_micros(10));
+        }
+        
+        let steady = profiler.get_metrics(point).unwrap();
+        assert_eq!(steady.count(), 2);
+        assert_eq!(steady.max(), Duration::from_micros(10));
+        assert_eq!(profiler.get_histogram(point).unwrap().count(), 2);
+        
+        let warmup = &profiler.warmup_stats()[&point];
+        assert_eq!(warmup.count(), 3);
+        assert_eq!(warmup.min(), Duration::from_millis(5));
+        
+        // A timed warmup ends on its own once the duration has passed
+        profiler.start_warmup(Warmup::Duration(Duration::from_millis(5)));
+        profiler.record_latency(point, Duration::from_millis(1));
+        thread::sleep(Duration::from_millis(10));
+        profiler.record_latency(point, Duration::from_micros(10));
+        assert_eq!(profiler.warmup_stats()[&point].count(), 1);
+        assert_eq!(profiler.get_metrics(point).unwrap().count(), 3);
+    }
+    
+    #[test]
+    fn test_merge_combines_disjoint_distributions() {
+        let point = MeasurementPoint::OrderMatched;
+        let node0 = LatencyProfiler::new();
+        let node1 = LatencyProfiler::new();
+        for us in 1..=10 {
+            node0.record_latency(point, Duration::from_micros(us));
+        }
+        for us in 101..=110 {
+            node1.record_latency(point, Duration::from_micros(us));
+        }
+        node1.record_latency(MeasurementPoint::RiskChecked, Duration::from_micros(7));
+        
+        let merged = LatencyProfiler::new();
+        merged.merge(&node0);
+        merged.merge(&node1);
+        
+        let metrics = merged.get_metrics(point).unwrap();
+        assert_eq!(metrics.count(), 20);
+        assert_eq!(metrics.min(), Duration::from_micros(1));
+        assert_eq!(metrics.max(), Duration::from_micros(110));
+        // (55 + 1055) us over 20 samples
+        assert_eq!(metrics.mean(), Duration::from_nanos(55_500));
+        
+        let histogram = merged.get_histogram(point).unwrap();
+        assert_eq!(histogram.count(), 20);
+        assert_eq!(histogram.percentile(50.0), 10_000);
+        assert_eq!(merged.get_metrics(MeasurementPoint::RiskChecked).unwrap().count(), 1);
+        
+        // Merge order does not matter, and the sources are untouched
+        let reversed = LatencyProfiler::new();
+        reversed.merge(&node1);
+        reversed.merge(&node0);
+        let other = reversed.get_metrics(point).unwrap();
+        assert_eq!((other.count(), other.min(), other.max(), other.mean()), (20, metrics.min(), metrics.max(), metrics.mean()));
+        assert_eq!(node0.get_metrics(point).unwrap().count(), 10);
+    }
+    
     #[test]
     fn test_large_number_of_measurements() {
         let profiler = LatencyProfiler::new();
@@ -1,6 +1,10 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::sync::Arc;
+use std::path::Path;
+
+/// Environment variable holding a previously calibrated TSC frequency in Hz
+pub const RDTSC_FREQUENCY_ENV: &str = "HFT_RDTSC_FREQUENCY_HZ";
 
 /// RDTSC-based high-precision timer for sub-nanosecond latency measurement
 /// Uses CPU cycle counters for maximum precision and minimal overhead
@@ -17,7 +21,7 @@ pub struct RdtscTimer {
 impl RdtscTimer {
     /// Create a new RDTSC timer with automatic frequency calibration
     pub fn new() -> Self {
-        let (frequency, baseline_cycles, baseline_time_nanos) = Self::calibrate_frequency();
+        let (frequency, baseline_cycles, baseline_time_nanos) = Self::calibrate_frequency(5, Duration::from_millis(100));
         
         Self {
             frequency,
@@ -26,6 +30,64 @@ impl RdtscTimer {
         }
     }
     
+    /// Calibrate with a single short round; less accurate than `new()` but suited to tests
+    pub fn quick_calibrate() -> Self {
+        let (frequency, baseline_cycles, baseline_time_nanos) = Self::calibrate_frequency(1, Duration::from_millis(20));
+        
+        Self {
+            frequency,
+            baseline_cycles,
+            baseline_time_nanos,
+        }
+    }
+    
+    /// Use the frequency from `HFT_RDTSC_FREQUENCY_HZ` if set, otherwise calibrate
+    pub fn warm_start() -> Self {
+        Self::from_env().unwrap_or_default()
+    }
+    
+    /// Create a timer from the frequency in `HFT_RDTSC_FREQUENCY_HZ`, skipping calibration
+    pub fn from_env() -> Option<Self> {
+        std::env::var(RDTSC_FREQUENCY_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|frequency| Self::is_valid_frequency(*frequency))
+            .map(Self::with_frequency)
+    }
+    
+    /// Create a timer from a frequency saved with `save_calibration`, skipping calibration
+    pub fn from_calibration_file<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let frequency = content.trim().parse::<f64>()
+            .ok()
+            .filter(|frequency| Self::is_valid_frequency(*frequency))
+            .ok_or_else(|| std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid RDTSC frequency: {}", content.trim()),
+            ))?;
+        
+        Ok(Self::with_frequency(frequency))
+    }
+    
+    /// Load a saved calibration, or calibrate and save it for the next start
+    pub fn load_or_calibrate<P: AsRef<Path>>(path: P) -> Self {
+        Self::from_calibration_file(&path).unwrap_or_else(|_| {
+            let timer = Self::new();
+            let _ = timer.save_calibration(&path);
+            timer
+        })
+    }
+    
+    /// Persist the calibrated frequency for a later warm start
+    pub fn save_calibration<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, format!("{}\n", self.frequency))
+    }
+    
+    #[inline]
+    fn is_valid_frequency(frequency: f64) -> bool {
+        frequency.is_finite() && frequency > 0.0
+    }
+    
     /// Create a timer with a known CPU frequency (for better performance)
     pub fn with_frequency(frequency_hz: f64) -> Self {
         let baseline_cycles = unsafe { rdtsc() };
@@ -102,12 +164,11 @@ impl RdtscTimer {
     }
     
     /// Calibrate CPU frequency by measuring against system clock
-    fn calibrate_frequency() -> (f64, u64, u64) {
+    fn calibrate_frequency(rounds: usize, calibration_time: Duration) -> (f64, u64, u64) {
         // Multiple calibration rounds for better accuracy
-        let mut frequencies = Vec::new();
+        let mut frequencies = Vec::with_capacity(rounds);
         
-        for _ in 0..5 {
-            let calibration_time = Duration::from_millis(100);
+        for _ in 0..rounds.max(1) {
             
             let start_time = SystemTime::now();
             let start_cycles = unsafe { rdtsc() };
@@ -144,7 +205,7 @@ impl RdtscTimer {
     
     /// Re-calibrate the timer (useful for long-running processes)
     pub fn recalibrate(&mut self) {
-        let (frequency, baseline_cycles, baseline_time_nanos) = Self::calibrate_frequency();
+        let (frequency, baseline_cycles, baseline_time_nanos) = Self::calibrate_frequency(5, Duration::from_millis(100));
         self.frequency = frequency;
         self.baseline_cycles = baseline_cycles;
         self.baseline_time_nanos = baseline_time_nanos;
@@ -214,8 +275,13 @@ impl RdtscProfiler {
     
     /// Create profiler with known CPU frequency
     pub fn with_frequency(frequency_hz: f64) -> Self {
+        Self::with_timer(RdtscTimer::with_frequency(frequency_hz))
+    }
+    
+    /// Create profiler around an already constructed timer
+    pub fn with_timer(timer: RdtscTimer) -> Self {
         Self {
-            timer: RdtscTimer::with_frequency(frequency_hz),
+            timer,
             measurements: crossbeam_skiplist::SkipMap::new(),
         }
     }
@@ -427,7 +493,7 @@ unsafe impl Send for AtomicLatencyMetrics {}
 unsafe impl Sync for AtomicLatencyMetrics {}
 
 /// Snapshot of latency metrics at a point in time
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct LatencySnapshot {
     pub count: u64,
     pub total_nanos: u64,
@@ -507,7 +573,7 @@ impl<'a> Drop for RdtscScopedMeasurement<'a> {
 
 // Global RDTSC profiler instance for easy access
 lazy_static::lazy_static! {
-    pub static ref GLOBAL_RDTSC_PROFILER: RdtscProfiler = RdtscProfiler::new();
+    pub static ref GLOBAL_RDTSC_PROFILER: RdtscProfiler = RdtscProfiler::with_timer(RdtscTimer::warm_start());
 }
 
 /// CPU cycle counter intrinsic
@@ -724,6 +790,27 @@ mod tests {
         assert!(relative_diff < 0.01, "Frequency difference too large: {:.2}%", relative_diff * 100.0);
     }
 
+    #[test]
+    fn test_quick_calibrate_matches_full_calibration() {
+        let start = std::time::Instant::now();
+        let full = RdtscTimer::new();
+        let full_elapsed = start.elapsed();
+        
+        let start = std::time::Instant::now();
+        let quick = RdtscTimer::quick_calibrate();
+        let quick_elapsed = start.elapsed();
+        
+        let relative_diff = (quick.frequency() - full.frequency()).abs() / full.frequency();
+        assert!(relative_diff < 0.03, "Quick calibration off by {:.2}%", relative_diff * 100.0);
+        assert!(quick_elapsed * 4 < full_elapsed, "quick {:?} vs full {:?}", quick_elapsed, full_elapsed);
+        
+        let path = std::env::temp_dir().join(format!("rdtsc_calibration_{}", std::process::id()));
+        quick.save_calibration(&path).unwrap();
+        let warm = RdtscTimer::from_calibration_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(warm.frequency(), quick.frequency());
+    }
+
     #[test]
     fn test_csv_export() {
         let profiler = RdtscProfiler::new();
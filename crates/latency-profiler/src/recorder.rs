@@ -0,0 +1,156 @@
+use crate::rdtsc_timer::{LatencySnapshot, RdtscProfiler};
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// One timestamped sample of every measurement point
+pub type LatencyFrame = (DateTime<Utc>, HashMap<String, LatencySnapshot>);
+
+#[derive(Serialize, Deserialize)]
+struct Frame {
+    timestamp: DateTime<Utc>,
+    snapshots: Vec<(String, LatencySnapshot)>,
+}
+
+/// Appends timestamped `RdtscProfiler` snapshots to a binary time series file.
+/// Each frame is a little-endian `u32` length followed by its bincode encoding.
+pub struct LatencyRecorder {
+    writer: BufWriter<File>,
+    frames: u64,
+}
+
+impl LatencyRecorder {
+    /// Open `path` for appending, creating it if it does not exist
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            frames: 0,
+        })
+    }
+
+    /// Write one frame with the current metrics of every point in `profiler`
+    pub fn record(&mut self, profiler: &RdtscProfiler) -> Result<DateTime<Utc>> {
+        let frame = Frame {
+            timestamp: Utc::now(),
+            snapshots: profiler
+                .get_all_metrics()
+                .into_iter()
+                .map(|(point, snapshot)| (point.to_string(), snapshot))
+                .collect(),
+        };
+
+        let bytes = bincode::serialize(&frame)?;
+        self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        self.frames += 1;
+
+        Ok(frame.timestamp)
+    }
+
+    /// Frames written by this recorder since it was opened
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Record every `interval` on a background thread until `stop` is set.
+    /// A final frame is written on the way out so the tail of the run is kept.
+    pub fn spawn<P>(mut self, profiler: P, interval: Duration, stop: Arc<AtomicBool>) -> JoinHandle<Result<Self>>
+    where
+        P: Deref<Target = RdtscProfiler> + Send + 'static,
+    {
+        std::thread::Builder::new()
+            .name("latency-recorder".to_string())
+            .spawn(move || {
+                while !stop.load(Ordering::Acquire) {
+                    self.record(&profiler)?;
+                    std::thread::park_timeout(interval);
+                }
+                self.record(&profiler)?;
+                Ok(self)
+            })
+            .expect("failed to spawn latency recorder thread")
+    }
+}
+
+/// Load every frame written by `LatencyRecorder`, oldest first. A frame cut short
+/// by a crash mid-write ends the series instead of failing the whole read.
+pub fn read_latency_series<P: AsRef<Path>>(path: P) -> Result<Vec<LatencyFrame>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut series = Vec::new();
+
+    loop {
+        let mut len = [0u8; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        }
+
+        let frame: Frame = bincode::deserialize(&bytes)?;
+        series.push((frame.timestamp, frame.snapshots.into_iter().collect()));
+    }
+
+    Ok(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_snapshots_read_back_unchanged() {
+        let path = std::env::temp_dir().join(format!("latency_series_{}.bin", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let profiler = RdtscProfiler::with_frequency(1_000_000_000.0);
+        let mut recorder = LatencyRecorder::open(&path).unwrap();
+        let mut expected = Vec::new();
+
+        for round in 1..=3u64 {
+            profiler.record_latency("order_matched", 100 * round);
+            profiler.record_latency("risk_checked", 1_000 * round);
+            let timestamp = recorder.record(&profiler).unwrap();
+            expected.push((timestamp, profiler.get_metrics("order_matched").unwrap(), profiler.get_metrics("risk_checked").unwrap()));
+        }
+        assert_eq!(recorder.frames(), 3);
+        drop(recorder);
+
+        // A torn trailing frame is ignored
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&64u32.to_le_bytes()).unwrap();
+        file.write_all(&[0u8; 10]).unwrap();
+        drop(file);
+
+        let series = read_latency_series(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(series.len(), 3);
+        for ((timestamp, snapshots), (expected_timestamp, matched, risk)) in series.iter().zip(&expected) {
+            assert_eq!(timestamp, expected_timestamp);
+            assert_eq!(snapshots.len(), 2);
+            assert_eq!(snapshots["order_matched"], *matched);
+            assert_eq!(snapshots["risk_checked"], *risk);
+        }
+        assert_eq!(series[2].1["order_matched"].count, 3);
+        assert_eq!(series[2].1["order_matched"].total_nanos, 600);
+        assert_eq!(series[2].1["risk_checked"].max_nanos, 3_000);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::types::{OrderBookSnapshot, Tick};
+use chrono::{DateTime, Utc};
+use crossbeam_channel::Sender;
+use order_book::{clock, Price};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// What happens to data that fails a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnomalyAction {
+    /// Report the anomaly and drop the data
+    Suppress,
+    /// Report the anomaly but let the data through
+    Flag,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyConfig {
+    /// Largest accepted tick-to-tick price move, in percent of the last accepted price
+    pub max_move_pct: f64,
+    /// Oldest accepted data relative to the clock; 0 disables the staleness check
+    pub max_staleness_ms: u64,
+    pub on_spike: AnomalyAction,
+    pub on_crossed_quote: AnomalyAction,
+    pub on_stale: AnomalyAction,
+    pub on_invalid: AnomalyAction,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            max_move_pct: 10.0,
+            max_staleness_ms: 5_000,
+            on_spike: AnomalyAction::Suppress,
+            on_crossed_quote: AnomalyAction::Suppress,
+            on_stale: AnomalyAction::Suppress,
+            on_invalid: AnomalyAction::Suppress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnomalyKind {
+    /// Price moved more than `max_move_pct` from the last accepted price
+    PriceSpike { previous: Price, price: Price, move_pct: f64 },
+    /// Zero or negative price, or zero quantity
+    InvalidQuote,
+    /// Best bid at or above best ask
+    CrossedQuote { bid: Price, ask: Price },
+    /// Timestamp older than `max_staleness_ms`, or earlier than data already accepted
+    Stale { timestamp: DateTime<Utc>, last_accepted: Option<DateTime<Utc>> },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedAnomaly {
+    pub symbol: String,
+    pub kind: AnomalyKind,
+    pub action: AnomalyAction,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Outcome of screening one update
+#[derive(Debug, Clone, PartialEq)]
+pub enum Screening {
+    Pass,
+    Flagged(FeedAnomaly),
+    Suppressed(FeedAnomaly),
+}
+
+impl Screening {
+    /// Whether the update should continue on to consumers
+    #[inline]
+    pub fn forward(&self) -> bool {
+        !matches!(self, Screening::Suppressed(_))
+    }
+
+    #[inline]
+    pub fn anomaly(&self) -> Option<&FeedAnomaly> {
+        match self {
+            Screening::Pass => None,
+            Screening::Flagged(anomaly) | Screening::Suppressed(anomaly) => Some(anomaly),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SymbolState {
+    last_price: Option<Price>,
+    last_timestamp: Option<DateTime<Utc>>,
+}
+
+/// Screens feed data per symbol against configurable sanity bounds. Only accepted data moves
+/// the reference price and timestamp, so a suppressed spike cannot drag the baseline with it.
+#[derive(Debug)]
+pub struct FeedAnomalyDetector {
+    config: AnomalyConfig,
+    symbols: HashMap<String, SymbolState>,
+    anomaly_sender: Option<Sender<FeedAnomaly>>,
+    anomalies: u64,
+    suppressed: u64,
+}
+
+impl FeedAnomalyDetector {
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config,
+            symbols: HashMap::new(),
+            anomaly_sender: None,
+            anomalies: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Also send every anomaly to `sender`
+    pub fn with_anomaly_sender(mut self, sender: Sender<FeedAnomaly>) -> Self {
+        self.anomaly_sender = Some(sender);
+        self
+    }
+
+    #[inline]
+    pub fn config(&self) -> &AnomalyConfig {
+        &self.config
+    }
+
+    pub fn screen_tick(&mut self, tick: &Tick) -> Screening {
+        let state = self.symbols.get(&tick.symbol).copied().unwrap_or_default();
+
+        let kind = if tick.price <= Price::ZERO || tick.quantity == order_book::Quantity::ZERO {
+            Some(AnomalyKind::InvalidQuote)
+        } else if let Some(stale) = self.stale(tick.timestamp, state.last_timestamp) {
+            Some(stale)
+        } else {
+            state.last_price.and_then(|previous| {
+                let move_pct = ((tick.price.to_f64() - previous.to_f64()) / previous.to_f64()).abs() * 100.0;
+                (move_pct > self.config.max_move_pct).then_some(AnomalyKind::PriceSpike {
+                    previous,
+                    price: tick.price,
+                    move_pct,
+                })
+            })
+        };
+
+        let screening = self.report(&tick.symbol, kind);
+        if screening.forward() {
+            let state = self.symbols.entry(tick.symbol.clone()).or_default();
+            state.last_price = Some(tick.price);
+            state.last_timestamp = Some(tick.timestamp);
+        }
+        screening
+    }
+
+    /// Check a book snapshot's top of book for zero or crossed quotes and staleness
+    pub fn screen_snapshot(&mut self, snapshot: &OrderBookSnapshot) -> Screening {
+        let state = self.symbols.get(&snapshot.symbol).copied().unwrap_or_default();
+
+        let kind = match (snapshot.best_bid(), snapshot.best_ask()) {
+            (Some(bid), _) if bid <= Price::ZERO => Some(AnomalyKind::InvalidQuote),
+            (_, Some(ask)) if ask <= Price::ZERO => Some(AnomalyKind::InvalidQuote),
+            (Some(bid), Some(ask)) if bid >= ask => Some(AnomalyKind::CrossedQuote { bid, ask }),
+            _ => self.stale(snapshot.timestamp, state.last_timestamp),
+        };
+
+        let screening = self.report(&snapshot.symbol, kind);
+        if screening.forward() {
+            self.symbols.entry(snapshot.symbol.clone()).or_default().last_timestamp = Some(snapshot.timestamp);
+        }
+        screening
+    }
+
+    /// Anomalies detected so far, flagged or suppressed
+    #[inline]
+    pub fn anomalies(&self) -> u64 {
+        self.anomalies
+    }
+
+    #[inline]
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Forget a symbol's reference price and timestamp, e.g. after a trading halt
+    pub fn reset_symbol(&mut self, symbol: &str) {
+        self.symbols.remove(symbol);
+    }
+
+    fn stale(&self, timestamp: DateTime<Utc>, last_accepted: Option<DateTime<Utc>>) -> Option<AnomalyKind> {
+        let out_of_order = last_accepted.is_some_and(|last| timestamp < last);
+        let too_old = self.config.max_staleness_ms > 0
+            && clock::now() - timestamp > chrono::Duration::milliseconds(self.config.max_staleness_ms as i64);
+        (out_of_order || too_old).then_some(AnomalyKind::Stale { timestamp, last_accepted })
+    }
+
+    fn report(&mut self, symbol: &str, kind: Option<AnomalyKind>) -> Screening {
+        let Some(kind) = kind else {
+            return Screening::Pass;
+        };
+
+        let action = match kind {
+            AnomalyKind::PriceSpike { .. } => self.config.on_spike,
+            AnomalyKind::InvalidQuote => self.config.on_invalid,
+            AnomalyKind::CrossedQuote { .. } => self.config.on_crossed_quote,
+            AnomalyKind::Stale { .. } => self.config.on_stale,
+        };
+        let anomaly = FeedAnomaly {
+            symbol: symbol.to_string(),
+            kind,
+            action,
+            detected_at: clock::now(),
+        };
+        warn!("Feed anomaly on {}: {:?} ({:?})", symbol, anomaly.kind, action);
+
+        self.anomalies += 1;
+        if let Some(sender) = &self.anomaly_sender {
+            let _ = sender.send(anomaly.clone());
+        }
+
+        match action {
+            AnomalyAction::Flag => Screening::Flagged(anomaly),
+            AnomalyAction::Suppress => {
+                self.suppressed += 1;
+                Screening::Suppressed(anomaly)
+            }
+        }
+    }
+}
+
+impl Default for FeedAnomalyDetector {
+    fn default() -> Self {
+        Self::new(AnomalyConfig::default())
+    }
+}
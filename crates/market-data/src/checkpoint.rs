@@ -0,0 +1,260 @@
+use crate::server::{apply_level, BookDelta, BookDeltaServer, DeltaError, DeltaSubscription, SnapshotRequest, SnapshotResponse};
+use crossbeam_channel::TryRecvError;
+use order_book::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    /// Deltas logged before the next full snapshot is taken
+    pub max_deltas: u64,
+    /// Longest time between full snapshots while deltas keep arriving
+    pub max_interval_ms: u64,
+    /// Checkpoints kept on disk, each with the delta segment that follows it
+    pub retain: usize,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            max_deltas: 10_000,
+            max_interval_ms: 60_000,
+            retain: 2,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Checkpoint I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Checkpoint encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error(transparent)]
+    Delta(#[from] DeltaError),
+    #[error("No checkpoint for {0}")]
+    NoCheckpoint(String),
+}
+
+/// Persists one symbol of a `BookDeltaServer` as full snapshots plus the deltas between them.
+///
+/// Every checkpoint `<symbol>.<sequence>.snapshot` is paired with a segment
+/// `<symbol>.<sequence>.deltas` holding exactly the deltas after `sequence`, so recovery loads
+/// the newest checkpoint and replays one segment instead of the whole history.
+pub struct CheckpointManager {
+    dir: PathBuf,
+    symbol: String,
+    config: CheckpointConfig,
+    subscription: DeltaSubscription,
+    segment: BufWriter<File>,
+    sequence: u64,
+    deltas_since_checkpoint: u64,
+    last_checkpoint: Instant,
+}
+
+impl CheckpointManager {
+    /// Write an initial checkpoint for `symbol` into `dir` and start logging its deltas
+    pub fn start(
+        server: &BookDeltaServer,
+        symbol: &str,
+        dir: impl Into<PathBuf>,
+        config: CheckpointConfig,
+    ) -> Result<Self, CheckpointError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let (subscription, segment, sequence) = Self::write_checkpoint(server, symbol, &dir)?;
+
+        let manager = Self {
+            dir,
+            symbol: symbol.to_string(),
+            config,
+            subscription,
+            segment,
+            sequence,
+            deltas_since_checkpoint: 0,
+            last_checkpoint: Instant::now(),
+        };
+        manager.prune()?;
+        Ok(manager)
+    }
+
+    /// Log every delta received so far, taking a new checkpoint once either limit is reached
+    /// or the subscription fell behind. Returns the number of deltas logged.
+    pub fn poll(&mut self, server: &BookDeltaServer) -> Result<usize, CheckpointError> {
+        let mut logged = 0;
+        loop {
+            match self.subscription.try_recv() {
+                Ok(delta) => {
+                    if delta.sequence <= self.sequence {
+                        continue;
+                    }
+                    if delta.sequence != self.sequence + 1 {
+                        warn!(
+                            "{} checkpoint log missed deltas {}..{}; taking a fresh checkpoint",
+                            self.symbol,
+                            self.sequence + 1,
+                            delta.sequence
+                        );
+                        self.checkpoint(server)?;
+                        continue;
+                    }
+
+                    write_frame(&mut self.segment, &delta)?;
+                    self.sequence = delta.sequence;
+                    self.deltas_since_checkpoint += 1;
+                    logged += 1;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Err(DeltaError::Disconnected.into()),
+            }
+        }
+        self.segment.flush()?;
+
+        if self.deltas_since_checkpoint >= self.config.max_deltas
+            || (self.deltas_since_checkpoint > 0
+                && self.last_checkpoint.elapsed() >= Duration::from_millis(self.config.max_interval_ms))
+        {
+            self.checkpoint(server)?;
+        }
+        Ok(logged)
+    }
+
+    /// Take a full snapshot now and start a new delta segment after it
+    pub fn checkpoint(&mut self, server: &BookDeltaServer) -> Result<(), CheckpointError> {
+        self.segment.flush()?;
+        let (subscription, segment, sequence) = Self::write_checkpoint(server, &self.symbol, &self.dir)?;
+        self.subscription = subscription;
+        self.segment = segment;
+        self.sequence = sequence;
+        self.deltas_since_checkpoint = 0;
+        self.last_checkpoint = Instant::now();
+        self.prune()
+    }
+
+    /// Sequence of the last delta made durable
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Rebuild `symbol` from the newest checkpoint in `dir` and the deltas logged after it.
+    /// A delta cut short by a crash ends the replay.
+    pub fn recover(dir: impl AsRef<Path>, symbol: &str) -> Result<SnapshotResponse, CheckpointError> {
+        let dir = dir.as_ref();
+        let sequence = *checkpoints(dir, symbol)?
+            .last()
+            .ok_or_else(|| CheckpointError::NoCheckpoint(symbol.to_string()))?;
+
+        let mut response: SnapshotResponse = bincode::deserialize(&fs::read(snapshot_path(dir, symbol, sequence))?)?;
+        let mut bids: BTreeMap<Price, Quantity> = response.snapshot.bids.drain(..).collect();
+        let mut asks: BTreeMap<Price, Quantity> = response.snapshot.asks.drain(..).collect();
+
+        let mut reader = BufReader::new(File::open(segment_path(dir, symbol, sequence))?);
+        while let Some(delta) = read_frame::<BookDelta>(&mut reader)? {
+            if delta.sequence != response.sequence + 1 {
+                return Err(DeltaError::SequenceGap {
+                    expected: response.sequence + 1,
+                    received: delta.sequence,
+                }
+                .into());
+            }
+            apply_level(&mut bids, &mut asks, &delta.update);
+            response.sequence = delta.sequence;
+        }
+
+        response.snapshot.bids = bids.into_iter().rev().collect();
+        response.snapshot.asks = asks.into_iter().collect();
+        info!("Recovered {} at delta {} from checkpoint {}", symbol, response.sequence, sequence);
+        Ok(response)
+    }
+
+    fn write_checkpoint(
+        server: &BookDeltaServer,
+        symbol: &str,
+        dir: &Path,
+    ) -> Result<(DeltaSubscription, BufWriter<File>, u64), CheckpointError> {
+        let (response, subscription) = server.snapshot(&SnapshotRequest {
+            symbol: symbol.to_string(),
+        })?;
+        let sequence = response.sequence;
+
+        // The segment exists before the snapshot becomes visible, so recovery never finds
+        // a checkpoint without its deltas
+        let segment = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(segment_path(dir, symbol, sequence))?;
+        let path = snapshot_path(dir, symbol, sequence);
+        let staging = path.with_extension("snapshot.tmp");
+        fs::write(&staging, bincode::serialize(&response)?)?;
+        fs::rename(&staging, &path)?;
+
+        Ok((subscription, BufWriter::new(segment), sequence))
+    }
+
+    fn prune(&self) -> Result<(), CheckpointError> {
+        let sequences = checkpoints(&self.dir, &self.symbol)?;
+        let stale = sequences.len().saturating_sub(self.config.retain.max(1));
+        for &sequence in &sequences[..stale] {
+            fs::remove_file(snapshot_path(&self.dir, &self.symbol, sequence))?;
+            fs::remove_file(segment_path(&self.dir, &self.symbol, sequence)).ok();
+        }
+        Ok(())
+    }
+}
+
+fn snapshot_path(dir: &Path, symbol: &str, sequence: u64) -> PathBuf {
+    dir.join(format!("{}.{:020}.snapshot", symbol, sequence))
+}
+
+fn segment_path(dir: &Path, symbol: &str, sequence: u64) -> PathBuf {
+    dir.join(format!("{}.{:020}.deltas", symbol, sequence))
+}
+
+/// Sequences of the checkpoints for `symbol` in `dir`, oldest first
+fn checkpoints(dir: &Path, symbol: &str) -> Result<Vec<u64>, CheckpointError> {
+    let prefix = format!("{}.", symbol);
+    let mut sequences = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let sequence = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|rest| rest.strip_suffix(".snapshot"))
+            .and_then(|sequence| sequence.parse::<u64>().ok());
+        sequences.extend(sequence);
+    }
+    sequences.sort_unstable();
+    Ok(sequences)
+}
+
+/// Little-endian `u32` length followed by the bincode encoding
+fn write_frame<T: Serialize>(writer: &mut impl Write, value: &T) -> Result<(), CheckpointError> {
+    let bytes = bincode::serialize(value)?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn read_frame<T: for<'de> Deserialize<'de>>(reader: &mut impl Read) -> Result<Option<T>, CheckpointError> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(bincode::deserialize(&bytes)?)),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
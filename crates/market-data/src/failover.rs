@@ -0,0 +1,157 @@
+use crate::feed::MarketDataFeed;
+use crate::types::{MarketSummary, OrderBookSnapshot};
+use chrono::{DateTime, Utc};
+use order_book::clock;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Which of a `FailoverFeed`'s feeds a read is served from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeedSource {
+    Primary,
+    Backup,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailoverConfig {
+    /// The primary counts as stale for a symbol once its newest data is older than this
+    pub max_staleness_ms: u64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            max_staleness_ms: 2_000,
+        }
+    }
+}
+
+/// Serves reads from a primary feed while it is healthy and from a backup while it is not.
+///
+/// The primary is healthy for a symbol while it is connected and has published a snapshot or
+/// trade for that symbol within `max_staleness_ms`. The source is chosen on every read, so reads
+/// move to the backup as soon as the primary goes quiet and back once it publishes again.
+#[derive(Debug)]
+pub struct FailoverFeed {
+    primary: Arc<MarketDataFeed>,
+    backup: Arc<MarketDataFeed>,
+    config: FailoverConfig,
+    primary_connected: AtomicBool,
+    /// When a connected primary last dropped, cleared once it reconnects
+    primary_down_since: Mutex<Option<DateTime<Utc>>>,
+    /// Source each symbol was last read from, so switches are logged once
+    selected: Mutex<HashMap<String, FeedSource>>,
+    failovers: AtomicU64,
+}
+
+impl FailoverFeed {
+    pub fn new(primary: Arc<MarketDataFeed>, backup: Arc<MarketDataFeed>, config: FailoverConfig) -> Self {
+        Self {
+            primary,
+            backup,
+            config,
+            primary_connected: AtomicBool::new(true),
+            primary_down_since: Mutex::new(None),
+            selected: Mutex::new(HashMap::new()),
+            failovers: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub fn primary(&self) -> &Arc<MarketDataFeed> {
+        &self.primary
+    }
+
+    #[inline]
+    pub fn backup(&self) -> &Arc<MarketDataFeed> {
+        &self.backup
+    }
+
+    /// Start with the primary marked connected or not, without counting it as a drop
+    pub fn with_primary_connected(self, connected: bool) -> Self {
+        self.primary_connected.store(connected, Ordering::Relaxed);
+        self
+    }
+
+    /// Report the primary's connection state; every read uses the backup while it is down
+    pub fn set_primary_connected(&self, connected: bool) {
+        let was_connected = self.primary_connected.swap(connected, Ordering::Relaxed);
+        let mut down_since = self.primary_down_since.lock();
+        if connected {
+            *down_since = None;
+        } else if was_connected {
+            *down_since = Some(clock::now());
+        }
+    }
+
+    /// When the primary dropped, while it is down after having been connected
+    pub fn primary_down_since(&self) -> Option<DateTime<Utc>> {
+        *self.primary_down_since.lock()
+    }
+
+    pub fn is_primary_healthy(&self, symbol: &str) -> bool {
+        if !self.primary_connected.load(Ordering::Relaxed) {
+            return false;
+        }
+        let max_age = chrono::Duration::milliseconds(self.config.max_staleness_ms as i64);
+        last_update(&self.primary, symbol).is_some_and(|updated| clock::now() - updated <= max_age)
+    }
+
+    /// Feed that reads for `symbol` are served from right now
+    pub fn active_source(&self, symbol: &str) -> FeedSource {
+        let source = if self.is_primary_healthy(symbol) {
+            FeedSource::Primary
+        } else {
+            FeedSource::Backup
+        };
+
+        let previous = self
+            .selected
+            .lock()
+            .insert(symbol.to_string(), source)
+            .unwrap_or(FeedSource::Primary);
+        if previous != source {
+            match source {
+                FeedSource::Backup => {
+                    self.failovers.fetch_add(1, Ordering::Relaxed);
+                    warn!("Primary feed stale or disconnected for {}; reading from backup", symbol);
+                }
+                FeedSource::Primary => info!("Primary feed recovered for {}", symbol),
+            }
+        }
+        source
+    }
+
+    pub fn get_snapshot(&self, symbol: &str) -> Option<OrderBookSnapshot> {
+        self.feed(self.active_source(symbol)).get_snapshot(symbol)
+    }
+
+    pub fn get_summary(&self, symbol: &str) -> Option<MarketSummary> {
+        self.feed(self.active_source(symbol)).get_summary(symbol)
+    }
+
+    /// Times any symbol switched from the primary to the backup
+    #[inline]
+    pub fn failovers(&self) -> u64 {
+        self.failovers.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn feed(&self, source: FeedSource) -> &MarketDataFeed {
+        match source {
+            FeedSource::Primary => &self.primary,
+            FeedSource::Backup => &self.backup,
+        }
+    }
+}
+
+/// Newest snapshot or trade `feed` has published for `symbol`
+fn last_update(feed: &MarketDataFeed, symbol: &str) -> Option<DateTime<Utc>> {
+    let snapshot = feed.get_snapshot(symbol).map(|snapshot| snapshot.timestamp);
+    let summary = feed.get_summary(symbol).map(|summary| summary.timestamp);
+    snapshot.max(summary)
+}
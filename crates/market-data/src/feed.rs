@@ -1,11 +1,12 @@
+use crate::anomaly::FeedAnomalyDetector;
 use crate::types::{Tick, Level2Update, OrderBookSnapshot, MarketSummary};
 use crate::stream::{MarketDataStream, MarketEvent};
 use crate::snapshot::SnapshotManager;
 use crossbeam_channel::Sender;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use std::collections::HashMap;
-use chrono::Utc;
+use order_book::clock;
 use tokio::task;
 
 #[derive(Debug)]
@@ -13,6 +14,7 @@ pub struct MarketDataFeed {
     streams: HashMap<String, MarketDataStream>,
     snapshot_manager: Arc<RwLock<SnapshotManager>>,
     global_sender: Option<Sender<MarketEvent>>,
+    anomaly_detector: Option<Mutex<FeedAnomalyDetector>>,
 }
 
 impl MarketDataFeed {
@@ -22,6 +24,7 @@ impl MarketDataFeed {
             streams: HashMap::new(),
             snapshot_manager: Arc::new(RwLock::new(SnapshotManager::new())),
             global_sender: None,
+            anomaly_detector: None,
         }
     }
     
@@ -43,8 +46,20 @@ impl MarketDataFeed {
         self.global_sender = Some(sender);
     }
     
+    /// Screen ticks and snapshots before publishing; suppressed data never reaches streams
+    /// or the snapshot manager
+    pub fn set_anomaly_detector(&mut self, detector: FeedAnomalyDetector) {
+        self.anomaly_detector = Some(Mutex::new(detector));
+    }
+    
     #[inline]
     pub fn publish_tick(&self, tick: Tick) {
+        if let Some(detector) = &self.anomaly_detector {
+            if !detector.lock().screen_tick(&tick).forward() {
+                return;
+            }
+        }
+        
         let event = MarketEvent::Tick(tick.clone());
         
         if let Some(stream) = self.streams.get(&tick.symbol) {
@@ -77,6 +92,12 @@ impl MarketDataFeed {
     
     #[inline]
     pub fn publish_snapshot(&self, snapshot: OrderBookSnapshot) {
+        if let Some(detector) = &self.anomaly_detector {
+            if !detector.lock().screen_snapshot(&snapshot).forward() {
+                return;
+            }
+        }
+        
         let event = MarketEvent::Snapshot(snapshot.clone());
         
         if let Some(stream) = self.streams.get(&snapshot.symbol) {
@@ -122,7 +143,7 @@ impl MarketDataFeed {
     
     #[inline]
     pub fn cleanup_old_data(&self) {
-        let cutoff = Utc::now() - chrono::Duration::hours(24);
+        let cutoff = clock::now() - chrono::Duration::hours(24);
         self.snapshot_manager.write().clear_old_snapshots(cutoff);
     }
 }
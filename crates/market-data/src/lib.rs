@@ -1,9 +1,17 @@
+pub mod anomaly;
+pub mod checkpoint;
+pub mod failover;
 pub mod feed;
+pub mod server;
 pub mod snapshot;
 pub mod stream;
 pub mod types;
 
+pub use anomaly::{AnomalyAction, AnomalyConfig, AnomalyKind, FeedAnomaly, FeedAnomalyDetector, Screening};
+pub use checkpoint::{CheckpointConfig, CheckpointError, CheckpointManager};
+pub use failover::{FailoverConfig, FailoverFeed, FeedSource};
 pub use feed::MarketDataFeed;
+pub use server::{BookDelta, BookDeltaServer, BookMirror, DeltaError, DeltaServerConfig, DeltaSubscription, SnapshotRequest, SnapshotResponse};
 pub use snapshot::*;
 pub use stream::*;
 pub use types::*;
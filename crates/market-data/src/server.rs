@@ -0,0 +1,330 @@
+use crate::types::{Level2Update, UpdateType};
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TryRecvError, TrySendError};
+use order_book::{
+    LockFreeBookSnapshot, LockFreeMatchResult, LockFreeOrderBook, Order, OrderId, Price, Quantity, Side,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaServerConfig {
+    /// Deltas buffered per subscriber; a subscriber that falls further behind misses
+    /// deltas and must recover through a snapshot
+    pub subscriber_capacity: usize,
+}
+
+impl Default for DeltaServerConfig {
+    fn default() -> Self {
+        Self {
+            subscriber_capacity: 4096,
+        }
+    }
+}
+
+/// New absolute quantity of one price level; zero quantity deletes the level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookDelta {
+    pub sequence: u64,
+    pub update: Level2Update,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotRequest {
+    pub symbol: String,
+}
+
+/// Full book as of delta `sequence`; the paired subscription starts at `sequence + 1`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotResponse {
+    pub snapshot: LockFreeBookSnapshot,
+    pub sequence: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DeltaError {
+    #[error("Unknown symbol: {0}")]
+    UnknownSymbol(String),
+    #[error("Sequence gap: expected {expected}, received {received}")]
+    SequenceGap { expected: u64, received: u64 },
+    #[error("Delta stream disconnected")]
+    Disconnected,
+}
+
+/// Receiving end of a symbol's delta stream
+#[derive(Debug)]
+pub struct DeltaSubscription {
+    receiver: Receiver<BookDelta>,
+}
+
+impl DeltaSubscription {
+    #[inline]
+    pub fn try_recv(&self) -> Result<BookDelta, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    #[inline]
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<BookDelta, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+}
+
+struct PublisherState {
+    sequence: u64,
+    subscribers: Vec<Sender<BookDelta>>,
+}
+
+/// One symbol's book plus its delta stream. Mutations, sequencing and snapshots share one
+/// lock, so a snapshot always lines up exactly with a delta sequence number.
+struct SymbolPublisher {
+    book: LockFreeOrderBook,
+    state: Mutex<PublisherState>,
+}
+
+impl SymbolPublisher {
+    fn publish(&self, state: &mut PublisherState, touched: &[(Side, Price)]) {
+        for &(side, price) in touched {
+            let quantity = self.book.level_quantity(side, price);
+            let update = if quantity == Quantity::ZERO {
+                Level2Update::delete(self.book.symbol().to_string(), side, price)
+            } else {
+                Level2Update::update(self.book.symbol().to_string(), side, price, quantity)
+            };
+
+            state.sequence += 1;
+            let delta = BookDelta {
+                sequence: state.sequence,
+                update,
+            };
+            state.subscribers.retain(|subscriber| match subscriber.try_send(delta.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    debug!("Subscriber to {} is full, dropped delta {}", self.book.symbol(), delta.sequence);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            });
+        }
+    }
+}
+
+/// Sequenced level-delta server for a set of lock-free books. Consumers take a snapshot and
+/// subscription together through `snapshot`, then apply deltas strictly after its sequence.
+pub struct BookDeltaServer {
+    config: DeltaServerConfig,
+    publishers: HashMap<String, Arc<SymbolPublisher>>,
+}
+
+impl BookDeltaServer {
+    pub fn new(config: DeltaServerConfig) -> Self {
+        Self {
+            config,
+            publishers: HashMap::new(),
+        }
+    }
+
+    pub fn add_symbol(&mut self, symbol: String) {
+        self.publishers.entry(symbol.clone()).or_insert_with(|| {
+            Arc::new(SymbolPublisher {
+                book: LockFreeOrderBook::new(symbol),
+                state: Mutex::new(PublisherState {
+                    sequence: 0,
+                    subscribers: Vec::new(),
+                }),
+            })
+        });
+    }
+
+    #[inline]
+    pub fn config(&self) -> &DeltaServerConfig {
+        &self.config
+    }
+
+    /// Match and rest `order`, publishing one delta per price level it changed
+    pub fn add_order(&self, order: Order) -> Result<LockFreeMatchResult, DeltaError> {
+        let publisher = self.publisher(&order.symbol)?;
+        let (side, price) = (order.side, order.price);
+
+        let mut state = publisher.state.lock();
+        let result = publisher.book.add_order(order);
+
+        let mut touched: Vec<(Side, Price)> = match &result {
+            LockFreeMatchResult::NoMatch => Vec::new(),
+            LockFreeMatchResult::PartialMatch { trades, .. } | LockFreeMatchResult::FullMatch { trades } => {
+                trades.iter().map(|trade| (side.opposite(), trade.price)).collect()
+            }
+        };
+        touched.dedup();
+        if !matches!(result, LockFreeMatchResult::FullMatch { .. }) {
+            touched.push((side, price));
+        }
+
+        publisher.publish(&mut state, &touched);
+        Ok(result)
+    }
+
+    pub fn cancel_order(&self, symbol: &str, order_id: OrderId) -> Result<Option<Order>, DeltaError> {
+        let publisher = self.publisher(symbol)?;
+
+        let mut state = publisher.state.lock();
+        let cancelled = publisher.book.cancel_order(order_id);
+        if let Some(order) = &cancelled {
+            publisher.publish(&mut state, &[(order.side, order.price)]);
+        }
+        Ok(cancelled)
+    }
+
+    /// Snapshot the book and subscribe to every delta after it, atomically with respect to
+    /// mutations: no delta is both reflected in the snapshot and delivered, and none is lost
+    pub fn snapshot(&self, request: &SnapshotRequest) -> Result<(SnapshotResponse, DeltaSubscription), DeltaError> {
+        let publisher = self.publisher(&request.symbol)?;
+
+        let mut state = publisher.state.lock();
+        let (sender, receiver) = bounded(self.config.subscriber_capacity);
+        state.subscribers.push(sender);
+
+        let response = SnapshotResponse {
+            snapshot: publisher.book.depth(usize::MAX),
+            sequence: state.sequence,
+        };
+        Ok((response, DeltaSubscription { receiver }))
+    }
+
+    /// Sequence number of the last published delta for `symbol`
+    pub fn sequence(&self, symbol: &str) -> Result<u64, DeltaError> {
+        Ok(self.publisher(symbol)?.state.lock().sequence)
+    }
+
+    pub fn depth(&self, symbol: &str, levels: usize) -> Result<LockFreeBookSnapshot, DeltaError> {
+        let publisher = self.publisher(symbol)?;
+        let _state = publisher.state.lock();
+        Ok(publisher.book.depth(levels))
+    }
+
+    #[inline]
+    fn publisher(&self, symbol: &str) -> Result<&Arc<SymbolPublisher>, DeltaError> {
+        self.publishers
+            .get(symbol)
+            .ok_or_else(|| DeltaError::UnknownSymbol(symbol.to_string()))
+    }
+}
+
+impl Default for BookDeltaServer {
+    fn default() -> Self {
+        Self::new(DeltaServerConfig::default())
+    }
+}
+
+/// Consumer-side copy of a book kept current from a delta subscription. On a sequence gap
+/// it discards its state and resynchronises from a fresh snapshot.
+pub struct BookMirror {
+    symbol: String,
+    bids: BTreeMap<Price, Quantity>,
+    asks: BTreeMap<Price, Quantity>,
+    sequence: u64,
+    subscription: DeltaSubscription,
+    recoveries: u64,
+}
+
+impl BookMirror {
+    pub fn subscribe(server: &BookDeltaServer, symbol: &str) -> Result<Self, DeltaError> {
+        let (response, subscription) = server.snapshot(&SnapshotRequest {
+            symbol: symbol.to_string(),
+        })?;
+        Ok(Self {
+            symbol: symbol.to_string(),
+            bids: response.snapshot.bids.into_iter().collect(),
+            asks: response.snapshot.asks.into_iter().collect(),
+            sequence: response.sequence,
+            subscription,
+            recoveries: 0,
+        })
+    }
+
+    /// Apply the next delta in sequence. Deltas at or below the mirror's sequence are
+    /// ignored; anything past the next expected sequence is a gap.
+    pub fn apply(&mut self, delta: &BookDelta) -> Result<(), DeltaError> {
+        if delta.sequence <= self.sequence {
+            return Ok(());
+        }
+        if delta.sequence != self.sequence + 1 {
+            return Err(DeltaError::SequenceGap {
+                expected: self.sequence + 1,
+                received: delta.sequence,
+            });
+        }
+
+        apply_level(&mut self.bids, &mut self.asks, &delta.update);
+        self.sequence = delta.sequence;
+        Ok(())
+    }
+
+    /// Apply every delta already received, recovering from `server` on a gap. Returns the
+    /// number of deltas applied.
+    pub fn poll(&mut self, server: &BookDeltaServer) -> Result<usize, DeltaError> {
+        let mut applied = 0;
+        loop {
+            match self.subscription.try_recv() {
+                Ok(delta) => match self.apply(&delta) {
+                    Ok(()) => applied += 1,
+                    Err(DeltaError::SequenceGap { expected, received }) => {
+                        warn!("{} delta gap: expected {}, received {}; resyncing", self.symbol, expected, received);
+                        self.resync(server)?;
+                    }
+                    Err(error) => return Err(error),
+                },
+                Err(TryRecvError::Empty) => return Ok(applied),
+                Err(TryRecvError::Disconnected) => return Err(DeltaError::Disconnected),
+            }
+        }
+    }
+
+    /// Replace the mirror's state and subscription with a fresh snapshot
+    pub fn resync(&mut self, server: &BookDeltaServer) -> Result<(), DeltaError> {
+        let recoveries = self.recoveries + 1;
+        *self = Self::subscribe(server, &self.symbol)?;
+        self.recoveries = recoveries;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Snapshot recoveries performed after the initial subscribe
+    #[inline]
+    pub fn recoveries(&self) -> u64 {
+        self.recoveries
+    }
+
+    /// Bids best-first
+    pub fn bids(&self) -> Vec<(Price, Quantity)> {
+        self.bids.iter().rev().map(|(price, quantity)| (*price, *quantity)).collect()
+    }
+
+    /// Asks best-first
+    pub fn asks(&self) -> Vec<(Price, Quantity)> {
+        self.asks.iter().map(|(price, quantity)| (*price, *quantity)).collect()
+    }
+}
+
+/// Set or remove the level `update` describes in a price-keyed copy of a book
+pub(crate) fn apply_level(bids: &mut BTreeMap<Price, Quantity>, asks: &mut BTreeMap<Price, Quantity>, update: &Level2Update) {
+    let levels = match update.side {
+        Side::Buy => bids,
+        Side::Sell => asks,
+    };
+    match update.update_type {
+        UpdateType::Delete => {
+            levels.remove(&update.price);
+        }
+        UpdateType::Add | UpdateType::Update => {
+            levels.insert(update.price, update.quantity);
+        }
+    }
+}
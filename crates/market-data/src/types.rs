@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use order_book::{Price, Quantity, Side};
+use order_book::{clock, Price, Quantity, Side};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[repr(C, align(64))]
@@ -20,7 +20,7 @@ impl Tick {
             price,
             quantity,
             side,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 }
@@ -53,7 +53,7 @@ impl Level2Update {
             price,
             quantity,
             update_type: UpdateType::Add,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
     
@@ -65,7 +65,7 @@ impl Level2Update {
             price,
             quantity,
             update_type: UpdateType::Update,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
     
@@ -77,7 +77,7 @@ impl Level2Update {
             price,
             quantity: Quantity::ZERO,
             update_type: UpdateType::Delete,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 }
@@ -98,7 +98,7 @@ impl OrderBookSnapshot {
             symbol,
             bids: Vec::with_capacity(100),
             asks: Vec::with_capacity(100),
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
             sequence_number,
         }
     }
@@ -155,7 +155,7 @@ impl MarketSummary {
             volume: Quantity::ZERO,
             vwap: open,
             num_trades: 0,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
     
@@ -171,6 +171,6 @@ impl MarketSummary {
         self.volume += quantity;
         self.vwap = Price::new((old_notional + new_notional) / self.volume.to_f64());
         self.num_trades += 1;
-        self.timestamp = Utc::now();
+        self.timestamp = clock::now();
     }
 }
\ No newline at end of file
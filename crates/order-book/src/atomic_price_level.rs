@@ -119,6 +119,16 @@ impl AtomicPriceLevel {
         self.order_count.load(Ordering::Acquire) == 0
     }
     
+    /// Empty the level and move it to `price`, keeping its queue's allocation for reuse
+    #[inline]
+    pub fn reset(&mut self, price: Price) {
+        self.price = price;
+        *self.total_quantity.get_mut() = 0;
+        *self.order_count.get_mut() = 0;
+        while self.orders.pop().is_some() {}
+        *self.modification_flag.get_mut() = false;
+    }
+    
     /// Get the total quantity at this price level
     #[inline]
     pub fn total_quantity(&self) -> Quantity {
@@ -0,0 +1,214 @@
+//! Process-wide time source for domain timestamps (orders, trades, events)
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::Thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockSource {
+    /// `Utc::now()` on every call
+    #[default]
+    Real,
+    /// Cached time refreshed by a background thread every `granularity`; reads are a single atomic load
+    Coarse { granularity: Duration },
+    /// Time moves only through `set_simulated_time` and `advance`
+    Simulated,
+}
+
+impl ClockSource {
+    /// `HFT_CLOCK` = `real` | `coarse` | `simulated`, with `HFT_CLOCK_GRANULARITY_MS` for the coarse clock
+    pub fn from_env() -> Self {
+        match std::env::var("HFT_CLOCK").unwrap_or_default().to_lowercase().as_str() {
+            "coarse" => Self::Coarse {
+                granularity: Duration::from_millis(
+                    std::env::var("HFT_CLOCK_GRANULARITY_MS")
+                        .ok()
+                        .and_then(|ms| ms.parse().ok())
+                        .unwrap_or(1),
+                ),
+            },
+            "simulated" => Self::Simulated,
+            _ => Self::Real,
+        }
+    }
+}
+
+const REAL: u8 = 0;
+const COARSE: u8 = 1;
+const SIMULATED: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(REAL);
+static GRANULARITY_NANOS: AtomicU64 = AtomicU64::new(1_000_000);
+static COARSE_NANOS: AtomicI64 = AtomicI64::new(0);
+static SIMULATED_NANOS: AtomicI64 = AtomicI64::new(0);
+static REFRESHER: OnceLock<Thread> = OnceLock::new();
+
+/// Current time from the installed clock source
+#[inline]
+pub fn now() -> DateTime<Utc> {
+    match MODE.load(Ordering::Relaxed) {
+        COARSE => Utc.timestamp_nanos(COARSE_NANOS.load(Ordering::Relaxed)),
+        SIMULATED => Utc.timestamp_nanos(SIMULATED_NANOS.load(Ordering::Relaxed)),
+        _ => Utc::now(),
+    }
+}
+
+/// Switch every subsequent `now()` call to `source`
+pub fn install(source: ClockSource) {
+    match source {
+        ClockSource::Real => MODE.store(REAL, Ordering::Relaxed),
+        ClockSource::Coarse { granularity } => {
+            GRANULARITY_NANOS.store(granularity.as_nanos().clamp(1, u64::MAX as u128) as u64, Ordering::Relaxed);
+            refresh_coarse();
+            MODE.store(COARSE, Ordering::Relaxed);
+            refresher().unpark();
+        }
+        ClockSource::Simulated => {
+            // Start from real time unless a simulated time was already set
+            let _ = SIMULATED_NANOS.compare_exchange(0, real_nanos(), Ordering::Relaxed, Ordering::Relaxed);
+            MODE.store(SIMULATED, Ordering::Relaxed);
+        }
+    }
+}
+
+pub fn source() -> ClockSource {
+    match MODE.load(Ordering::Relaxed) {
+        COARSE => ClockSource::Coarse {
+            granularity: Duration::from_nanos(GRANULARITY_NANOS.load(Ordering::Relaxed)),
+        },
+        SIMULATED => ClockSource::Simulated,
+        _ => ClockSource::Real,
+    }
+}
+
+/// Time source held by one book, risk manager or engine. `Global` reads whatever `install`
+/// chose for the process; a simulated clock belongs to the handle and its clones, so engines
+/// stepping their own time, such as concurrent backtests, never see each other's.
+#[derive(Debug, Clone, Default)]
+pub enum Clock {
+    #[default]
+    Global,
+    Simulated(Arc<AtomicI64>),
+}
+
+impl Clock {
+    /// A private simulated clock starting at `start`
+    pub fn simulated(start: DateTime<Utc>) -> Self {
+        Self::Simulated(Arc::new(AtomicI64::new(start.timestamp_nanos_opt().unwrap_or(0))))
+    }
+
+    #[inline]
+    pub fn now(&self) -> DateTime<Utc> {
+        match self {
+            Self::Global => now(),
+            Self::Simulated(nanos) => Utc.timestamp_nanos(nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    #[inline]
+    pub fn now_nanos(&self) -> i64 {
+        self.now().timestamp_nanos_opt().unwrap_or(0)
+    }
+
+    /// Move a simulated clock to `time`; on `Global` this moves the process-wide simulated time
+    pub fn set(&self, time: DateTime<Utc>) {
+        match self {
+            Self::Global => set_simulated_time(time),
+            Self::Simulated(nanos) => nanos.store(time.timestamp_nanos_opt().unwrap_or(0), Ordering::Relaxed),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        match self {
+            Self::Global => advance(by),
+            Self::Simulated(nanos) => {
+                nanos.fetch_add(by.as_nanos().min(i64::MAX as u128) as i64, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+pub fn set_simulated_time(time: DateTime<Utc>) {
+    SIMULATED_NANOS.store(time.timestamp_nanos_opt().unwrap_or(0), Ordering::Relaxed);
+}
+
+pub fn advance(by: Duration) {
+    SIMULATED_NANOS.fetch_add(by.as_nanos().min(i64::MAX as u128) as i64, Ordering::Relaxed);
+}
+
+#[inline]
+fn real_nanos() -> i64 {
+    Utc::now().timestamp_nanos_opt().unwrap_or(0)
+}
+
+// fetch_max keeps the cached clock monotonic even if the wall clock steps back
+#[inline]
+fn refresh_coarse() {
+    COARSE_NANOS.fetch_max(real_nanos(), Ordering::Relaxed);
+}
+
+fn refresher() -> &'static Thread {
+    REFRESHER.get_or_init(|| {
+        std::thread::Builder::new()
+            .name("coarse-clock".to_string())
+            .spawn(|| loop {
+                if MODE.load(Ordering::Relaxed) == COARSE {
+                    refresh_coarse();
+                    std::thread::sleep(Duration::from_nanos(GRANULARITY_NANOS.load(Ordering::Relaxed)));
+                } else {
+                    std::thread::park();
+                }
+            })
+            .expect("failed to spawn coarse clock refresher")
+            .thread()
+            .clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coarse_clock_monotonic_and_close_to_real_time() {
+        let granularity = Duration::from_millis(2);
+        install(ClockSource::Coarse { granularity });
+        assert_eq!(source(), ClockSource::Coarse { granularity });
+
+        // Allow for the refresher being descheduled briefly on a loaded test machine
+        let tolerance = chrono::Duration::from_std(granularity).unwrap() + chrono::Duration::milliseconds(20);
+        let first = now();
+        let mut previous = first;
+        for _ in 0..50 {
+            let sample = now();
+            let real = Utc::now();
+            assert!(sample >= previous);
+            assert!(sample <= real);
+            assert!(real - sample <= tolerance, "coarse clock lagged by {}", real - sample);
+            previous = sample;
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(previous > first);
+
+        install(ClockSource::Real);
+        assert_eq!(source(), ClockSource::Real);
+    }
+
+    #[test]
+    fn test_simulated_clocks_are_independent_of_each_other() {
+        let start = Utc.timestamp_nanos(1_700_000_000_000_000_000);
+        let first = Clock::simulated(start);
+        let second = first.clone();
+        let other = Clock::simulated(start);
+
+        first.advance(Duration::from_secs(5));
+        assert_eq!(second.now(), start + chrono::Duration::seconds(5));
+        assert_eq!(other.now(), start);
+
+        other.set(start + chrono::Duration::days(1));
+        assert_eq!(first.now(), start + chrono::Duration::seconds(5));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::types::{Order, OrderId, Price, Quantity, Side};
+use chrono::{DateTime, Utc};
+use crossbeam::channel::Sender;
+use serde::{Deserialize, Serialize};
+
+/// One resting order as it sits in its price level's queue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L3Order {
+    pub order_id: OrderId,
+    pub side: Side,
+    pub price: Price,
+    /// Full remaining size, including any iceberg reserve
+    pub remaining_quantity: Quantity,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&Order> for L3Order {
+    fn from(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            side: order.side,
+            price: order.price,
+            remaining_quantity: order.remaining_quantity(),
+            timestamp: order.timestamp,
+        }
+    }
+}
+
+/// Every resting order, best price first and in time priority within a level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L3Snapshot {
+    pub symbol: String,
+    pub bids: Vec<L3Order>,
+    pub asks: Vec<L3Order>,
+    /// Sequence of the last delta reflected here; the stream continues at `sequence + 1`
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl L3Snapshot {
+    /// Apply the next delta of the book's L3 stream. Returns false, leaving the snapshot
+    /// unchanged, when `update` is not the next sequence number.
+    pub fn apply(&mut self, update: &L3Update) -> bool {
+        if update.sequence != self.sequence + 1 {
+            return false;
+        }
+
+        match &update.delta {
+            L3Delta::Add(order) => {
+                let side = self.side_mut(order.side);
+                // Behind every order at a price as good or better
+                let position = side
+                    .iter()
+                    .position(|resting| match order.side {
+                        Side::Buy => resting.price < order.price,
+                        Side::Sell => resting.price > order.price,
+                    })
+                    .unwrap_or(side.len());
+                side.insert(position, order.clone());
+            }
+            L3Delta::Modify { order_id, side, remaining_quantity, .. } => {
+                if let Some(order) = self.side_mut(*side).iter_mut().find(|order| order.order_id == *order_id) {
+                    order.remaining_quantity = *remaining_quantity;
+                }
+            }
+            L3Delta::Delete { order_id, side, .. } => {
+                self.side_mut(*side).retain(|order| order.order_id != *order_id);
+            }
+        }
+        self.sequence = update.sequence;
+        true
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut Vec<L3Order> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+}
+
+/// Change to one resting order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum L3Delta {
+    /// Order joined the back of its price level
+    Add(L3Order),
+    /// Partially filled; keeps its queue position
+    Modify {
+        order_id: OrderId,
+        side: Side,
+        price: Price,
+        remaining_quantity: Quantity,
+    },
+    /// Filled, cancelled or evicted
+    Delete { order_id: OrderId, side: Side, price: Price },
+}
+
+impl L3Delta {
+    /// Delta for a maker that just traded: deleted once filled or cancelled as sub-lot dust,
+    /// otherwise modified in place
+    pub(crate) fn after_fill(order: &Order) -> Self {
+        if order.status.is_terminal() {
+            Self::delete(order)
+        } else {
+            L3Delta::Modify {
+                order_id: order.id,
+                side: order.side,
+                price: order.price,
+                remaining_quantity: order.remaining_quantity(),
+            }
+        }
+    }
+
+    pub(crate) fn delete(order: &Order) -> Self {
+        L3Delta::Delete {
+            order_id: order.id,
+            side: order.side,
+            price: order.price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L3Update {
+    pub sequence: u64,
+    pub delta: L3Delta,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct L3Feed {
+    pub(crate) sequence: u64,
+    pub(crate) subscribers: Vec<Sender<L3Update>>,
+}
+
+impl L3Feed {
+    pub(crate) fn publish(&mut self, deltas: impl IntoIterator<Item = L3Delta>) {
+        for delta in deltas {
+            self.sequence += 1;
+            let update = L3Update {
+                sequence: self.sequence,
+                delta,
+            };
+            self.subscribers.retain(|subscriber| subscriber.send(update.clone()).is_ok());
+        }
+    }
+}
@@ -4,12 +4,22 @@ pub mod price_level;
 pub mod atomic_price_level;
 pub mod lockfree_order_book;
 pub mod memory_pools;
+pub mod replica;
+pub mod clock;
+pub mod l3;
+pub mod touch;
+pub mod rolling;
 
-pub use order_book::{OrderBook, OrderBookError, OrderBookStats, MatchResult, BookSnapshot};
-pub use lockfree_order_book::{LockFreeOrderBook, LockFreeOrderBookError, LockFreeMatchResult, LockFreeBookSnapshot, LockFreeOrderBookStats};
+pub use order_book::{OrderBook, OrderBookError, ArchiveSink, DEFAULT_RETIRED_LIMIT, OrderBookStats, MatchResult, BookSnapshot, ConsistencyMode, DepthMode, PreMatchHook, SelfTradePrevention, StpScope, FillEstimate, MemoryFootprint, LevelCap, LevelCapPolicy, LotModel, OrderSizeLimits};
+pub use lockfree_order_book::{LockFreeOrderBook, LockFreeOrderBookError, LockFreeMatchResult, LockFreeBookSnapshot, LockFreeOrderBookStats, CachedDepth, TopOfBook, MAX_CACHED_LEVELS};
 pub use types::*;
 pub use price_level::{PriceLevel, OrderInfo};
 pub use atomic_price_level::{AtomicPriceLevel, LockFreeOrderQueue};
-pub use memory_pools::{MemoryPool, VecPool, PooledObject, PooledVec, TradeArray, OrderArray, GlobalPools, allocators};
+pub use replica::{OrderBookReplica, ReplicaSnapshot};
+pub use clock::{Clock, ClockSource};
+pub use l3::{L3Delta, L3Order, L3Snapshot, L3Update};
+pub use rolling::{RollingStats, TradeWindow};
+pub use touch::{BestPriceChange, ImbalanceAlert, ImbalanceAlertConfig, ImbalanceSide, SpreadTracker};
+pub use memory_pools::{MemoryPool, VecPool, PooledObject, PooledVec, ArcPool, ArcPoolStats, TradeArray, OrderArray, GlobalPools, allocators};
 
 pub type Result<T> = std::result::Result<T, OrderBookError>;
\ No newline at end of file
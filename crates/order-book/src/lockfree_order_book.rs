@@ -1,9 +1,11 @@
 use crate::types::{Price, Quantity, Order, OrderId, Side, Trade};
 use crate::atomic_price_level::AtomicPriceLevel;
+use crate::memory_pools::{ArcPool, ArcPoolStats};
 use crossbeam_skiplist::SkipMap;
 use dashmap::DashMap;
-use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
+use std::sync::atomic::{fence, AtomicI64, AtomicU64, AtomicUsize, AtomicBool, Ordering};
 use std::sync::Arc;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use thiserror::Error;
@@ -36,6 +38,255 @@ pub enum LockFreeMatchResult {
     },
 }
 
+/// Upper bound on the number of levels per side `LockFreeOrderBook::with_cached_levels` can publish
+pub const MAX_CACHED_LEVELS: usize = 16;
+
+/// Copy of the top levels of both sides, read from the book's published cache without locking or allocating
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachedDepth {
+    bids: [(Price, Quantity); MAX_CACHED_LEVELS],
+    bid_count: usize,
+    asks: [(Price, Quantity); MAX_CACHED_LEVELS],
+    ask_count: usize,
+    /// Number of publishes before this one; increases with every book mutation
+    pub version: u64,
+}
+
+impl CachedDepth {
+    const EMPTY_LEVEL: (Price, Quantity) = (Price::ZERO, Quantity::ZERO);
+
+    /// Bid levels, highest price first
+    #[inline]
+    pub fn bids(&self) -> &[(Price, Quantity)] {
+        &self.bids[..self.bid_count]
+    }
+
+    /// Ask levels, lowest price first
+    #[inline]
+    pub fn asks(&self) -> &[(Price, Quantity)] {
+        &self.asks[..self.ask_count]
+    }
+}
+
+/// Seqlock-published top-N levels. Writers serialize on `writer` and bump `sequence` to odd while
+/// storing; readers retry until they see the same even sequence before and after copying.
+#[derive(Debug)]
+struct TopLevelsCache {
+    levels: usize,
+    writer: Mutex<()>,
+    sequence: AtomicU64,
+    bid_count: AtomicUsize,
+    ask_count: AtomicUsize,
+    bids: [(AtomicI64, AtomicU64); MAX_CACHED_LEVELS],
+    asks: [(AtomicI64, AtomicU64); MAX_CACHED_LEVELS],
+}
+
+impl TopLevelsCache {
+    fn new(levels: usize) -> Self {
+        Self {
+            levels: levels.clamp(1, MAX_CACHED_LEVELS),
+            writer: Mutex::new(()),
+            sequence: AtomicU64::new(0),
+            bid_count: AtomicUsize::new(0),
+            ask_count: AtomicUsize::new(0),
+            bids: std::array::from_fn(|_| (AtomicI64::new(0), AtomicU64::new(0))),
+            asks: std::array::from_fn(|_| (AtomicI64::new(0), AtomicU64::new(0))),
+        }
+    }
+
+    fn publish(&self, book: &LockFreeOrderBook) {
+        let _writer = self.writer.lock();
+
+        // Gather outside the seqlock window so readers only ever wait on the stores below
+        let mut bids = [CachedDepth::EMPTY_LEVEL; MAX_CACHED_LEVELS];
+        let mut asks = [CachedDepth::EMPTY_LEVEL; MAX_CACHED_LEVELS];
+        let bid_count = Self::collect(book.bids.iter().map(|entry| entry.value().clone()), &mut bids[..self.levels]);
+        let ask_count = Self::collect(book.asks.iter().map(|entry| entry.value().clone()), &mut asks[..self.levels]);
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(sequence + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        for (slot, (price, quantity)) in self.bids.iter().zip(&bids[..bid_count]) {
+            slot.0.store(price.to_raw(), Ordering::Relaxed);
+            slot.1.store(quantity.to_raw(), Ordering::Relaxed);
+        }
+        for (slot, (price, quantity)) in self.asks.iter().zip(&asks[..ask_count]) {
+            slot.0.store(price.to_raw(), Ordering::Relaxed);
+            slot.1.store(quantity.to_raw(), Ordering::Relaxed);
+        }
+        self.bid_count.store(bid_count, Ordering::Relaxed);
+        self.ask_count.store(ask_count, Ordering::Relaxed);
+
+        self.sequence.store(sequence + 2, Ordering::Release);
+    }
+
+    /// Copy non-empty levels in iteration order into `out`, returning how many were written
+    fn collect(levels: impl Iterator<Item = Arc<AtomicPriceLevel>>, out: &mut [(Price, Quantity)]) -> usize {
+        let mut count = 0;
+        for level in levels {
+            if count == out.len() {
+                break;
+            }
+            let quantity = level.total_quantity();
+            if quantity > Quantity::ZERO {
+                out[count] = (level.price, quantity);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn read(&self) -> CachedDepth {
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before & 1 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let mut depth = CachedDepth {
+                bids: [CachedDepth::EMPTY_LEVEL; MAX_CACHED_LEVELS],
+                bid_count: self.bid_count.load(Ordering::Relaxed).min(self.levels),
+                asks: [CachedDepth::EMPTY_LEVEL; MAX_CACHED_LEVELS],
+                ask_count: self.ask_count.load(Ordering::Relaxed).min(self.levels),
+                version: before / 2,
+            };
+            for (out, slot) in depth.bids.iter_mut().zip(&self.bids[..depth.bid_count]) {
+                *out = (Price::from_raw(slot.0.load(Ordering::Relaxed)), Quantity::from_raw(slot.1.load(Ordering::Relaxed)));
+            }
+            for (out, slot) in depth.asks.iter_mut().zip(&self.asks[..depth.ask_count]) {
+                *out = (Price::from_raw(slot.0.load(Ordering::Relaxed)), Quantity::from_raw(slot.1.load(Ordering::Relaxed)));
+            }
+
+            fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == before {
+                return depth;
+            }
+        }
+    }
+}
+
+/// Best bid and ask with their resting sizes, taken from a single published state of the book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TopOfBook {
+    pub bid: Option<(Price, Quantity)>,
+    pub ask: Option<(Price, Quantity)>,
+    /// Number of publishes before this one; increases with every book mutation
+    pub version: u64,
+}
+
+impl TopOfBook {
+    #[inline]
+    pub fn spread(&self) -> Option<Price> {
+        Some(self.ask?.0 - self.bid?.0)
+    }
+}
+
+/// Seqlock-published best level of each side, using the same protocol as `TopLevelsCache`.
+/// A zero size marks an empty side.
+#[derive(Debug)]
+struct TopOfBookCell {
+    writer: Mutex<()>,
+    sequence: AtomicU64,
+    bid: (AtomicI64, AtomicU64),
+    ask: (AtomicI64, AtomicU64),
+}
+
+impl TopOfBookCell {
+    fn new() -> Self {
+        Self {
+            writer: Mutex::new(()),
+            sequence: AtomicU64::new(0),
+            bid: (AtomicI64::new(0), AtomicU64::new(0)),
+            ask: (AtomicI64::new(0), AtomicU64::new(0)),
+        }
+    }
+
+    fn publish(&self, book: &LockFreeOrderBook) {
+        let _writer = self.writer.lock();
+
+        let mut bid = [CachedDepth::EMPTY_LEVEL];
+        let mut ask = [CachedDepth::EMPTY_LEVEL];
+        TopLevelsCache::collect(book.bids.iter().map(|entry| entry.value().clone()), &mut bid);
+        TopLevelsCache::collect(book.asks.iter().map(|entry| entry.value().clone()), &mut ask);
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(sequence + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.bid.0.store(bid[0].0.to_raw(), Ordering::Relaxed);
+        self.bid.1.store(bid[0].1.to_raw(), Ordering::Relaxed);
+        self.ask.0.store(ask[0].0.to_raw(), Ordering::Relaxed);
+        self.ask.1.store(ask[0].1.to_raw(), Ordering::Relaxed);
+
+        self.sequence.store(sequence + 2, Ordering::Release);
+    }
+
+    fn read(&self) -> TopOfBook {
+        let side = |slot: &(AtomicI64, AtomicU64)| {
+            let quantity = Quantity::from_raw(slot.1.load(Ordering::Relaxed));
+            (quantity > Quantity::ZERO).then(|| (Price::from_raw(slot.0.load(Ordering::Relaxed)), quantity))
+        };
+
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before & 1 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let top = TopOfBook {
+                bid: side(&self.bid),
+                ask: side(&self.ask),
+                version: before / 2,
+            };
+
+            fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == before {
+                return top;
+            }
+        }
+    }
+}
+
+/// Cached best price of one side. Presence is a separate flag rather than a sentinel price,
+/// since zero and negative prices are legitimate for spreads and power markets.
+#[derive(Debug)]
+struct BestPriceCache {
+    price: AtomicI64,
+    present: AtomicBool,
+}
+
+impl BestPriceCache {
+    fn new() -> Self {
+        Self {
+            price: AtomicI64::new(0),
+            present: AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    fn load(&self) -> Option<Price> {
+        self.present
+            .load(Ordering::Acquire)
+            .then(|| Price::from_raw(self.price.load(Ordering::Acquire)))
+    }
+
+    /// The price is written before the flag, so a reader that sees the flag set never sees
+    /// a price from before the side last became non-empty
+    #[inline]
+    fn store(&self, price: Option<Price>) {
+        match price {
+            Some(price) => {
+                self.price.store(price.to_raw(), Ordering::Release);
+                self.present.store(true, Ordering::Release);
+            }
+            None => self.present.store(false, Ordering::Release),
+        }
+    }
+}
+
 /// High-performance lock-free order book implementation
 /// Uses atomic operations and lock-free data structures for maximum throughput
 #[derive(Debug)]
@@ -50,8 +301,8 @@ pub struct LockFreeOrderBook {
     orders: DashMap<OrderId, Order>,
     
     // Atomic cache for best prices (avoids locks)
-    best_bid_cache: AtomicU64, // Store as raw bits for atomic access
-    best_ask_cache: AtomicU64,
+    best_bid_cache: BestPriceCache,
+    best_ask_cache: BestPriceCache,
     
     // Dirty flags to know when cache needs updating
     best_bid_dirty: AtomicBool,
@@ -61,6 +312,15 @@ pub struct LockFreeOrderBook {
     sequence_number: AtomicU64,
     total_trades: AtomicU64,
     last_update_nanos: AtomicU64,
+    
+    // Published top-N levels for allocation-free depth reads
+    top_levels: Option<TopLevelsCache>,
+    
+    // Published best level per side, always maintained
+    top_of_book: TopOfBookCell,
+    
+    // Emptied levels kept for reuse, when enabled
+    level_pool: Option<ArcPool<AtomicPriceLevel>>,
 }
 
 impl LockFreeOrderBook {
@@ -72,16 +332,34 @@ impl LockFreeOrderBook {
             bids: SkipMap::new(),
             asks: SkipMap::new(),
             orders: DashMap::new(),
-            best_bid_cache: AtomicU64::new(0), // 0 represents None
-            best_ask_cache: AtomicU64::new(u64::MAX), // MAX represents None
+            best_bid_cache: BestPriceCache::new(),
+            best_ask_cache: BestPriceCache::new(),
             best_bid_dirty: AtomicBool::new(true),
             best_ask_dirty: AtomicBool::new(true),
             sequence_number: AtomicU64::new(0),
             total_trades: AtomicU64::new(0),
             last_update_nanos: AtomicU64::new(0),
+            top_levels: None,
+            top_of_book: TopOfBookCell::new(),
+            level_pool: None,
         }
     }
     
+    /// Create a book that republishes its best `levels` per side (at most `MAX_CACHED_LEVELS`)
+    /// after every mutation, readable through `cached_depth`
+    pub fn with_cached_levels(symbol: String, levels: usize) -> Self {
+        let mut book = Self::new(symbol);
+        book.top_levels = Some(TopLevelsCache::new(levels));
+        book
+    }
+    
+    /// Recycle up to `max_idle` emptied price levels instead of allocating a new level each
+    /// time one opens
+    pub fn with_level_pool(mut self, max_idle: usize) -> Self {
+        self.level_pool = Some(ArcPool::new(max_idle));
+        self
+    }
+    
     /// Get the symbol for this order book
     #[inline]
     pub fn symbol(&self) -> &str {
@@ -107,16 +385,18 @@ impl LockFreeOrderBook {
             self.maybe_update_best_price_cache(order_side, order_price);
         }
         
+        self.publish_top_levels();
         match_result
     }
     
     /// Cancel an order by ID
     #[inline]
     pub fn cancel_order(&self, order_id: OrderId) -> Option<Order> {
-        if let Some((_, mut order)) = self.orders.remove(&order_id) {
-            order.cancel();
+        if let Some((_, mut order)) = self.orders.remove_if(&order_id, |_, order| !order.status.is_terminal()) {
+            order.cancel().ok()?;
             self.remove_order_from_book(&order);
             self.update_timestamp();
+            self.publish_top_levels();
             Some(order)
         } else {
             None
@@ -136,12 +416,7 @@ impl LockFreeOrderBook {
             self.update_best_bid_cache();
         }
         
-        let cached = self.best_bid_cache.load(Ordering::Acquire);
-        if cached == 0 {
-            None
-        } else {
-            Some(Price::from_raw(cached as i64))
-        }
+        self.best_bid_cache.load()
     }
     
     /// Get the best ask price
@@ -151,12 +426,7 @@ impl LockFreeOrderBook {
             self.update_best_ask_cache();
         }
         
-        let cached = self.best_ask_cache.load(Ordering::Acquire);
-        if cached == u64::MAX {
-            None
-        } else {
-            Some(Price::from_raw(cached as i64))
-        }
+        self.best_ask_cache.load()
     }
     
     /// Calculate the spread between best bid and ask
@@ -192,8 +462,8 @@ impl LockFreeOrderBook {
     
     /// Get market depth snapshot
     pub fn depth(&self, levels: usize) -> LockFreeBookSnapshot {
-        let mut bids = Vec::with_capacity(levels);
-        let mut asks = Vec::with_capacity(levels);
+        let mut bids = Vec::with_capacity(levels.min(self.bids.len()));
+        let mut asks = Vec::with_capacity(levels.min(self.asks.len()));
         
         // Collect bids (highest prices first)
         for entry in self.bids.iter().take(levels) {
@@ -220,6 +490,41 @@ impl LockFreeOrderBook {
         }
     }
     
+    /// Resting quantity at `price` on `side`; zero when no level exists there
+    #[inline]
+    pub fn level_quantity(&self, side: Side, price: Price) -> Quantity {
+        let level = match side {
+            Side::Buy => self.bids.get(&std::cmp::Reverse(price)).map(|entry| entry.value().total_quantity()),
+            Side::Sell => self.asks.get(&price).map(|entry| entry.value().total_quantity()),
+        };
+        level.unwrap_or(Quantity::ZERO)
+    }
+    
+    /// Consistent top-of-book levels from the published cache; `None` unless the book
+    /// was created with `with_cached_levels`
+    #[inline]
+    pub fn cached_depth(&self) -> Option<CachedDepth> {
+        self.top_levels.as_ref().map(TopLevelsCache::read)
+    }
+    
+    /// Best bid and ask with their sizes, read consistently without locking; `None` when both sides are empty
+    #[inline]
+    pub fn top_of_book(&self) -> Option<TopOfBook> {
+        let top = self.top_of_book.read();
+        (top.bid.is_some() || top.ask.is_some()).then_some(top)
+    }
+    
+    /// Number of levels per side the cache publishes
+    #[inline]
+    pub fn cached_levels(&self) -> Option<usize> {
+        self.top_levels.as_ref().map(|cache| cache.levels)
+    }
+    
+    /// Level allocations and reuses so far, when pooling is enabled
+    pub fn level_pool_stats(&self) -> Option<ArcPoolStats> {
+        self.level_pool.as_ref().map(ArcPool::stats)
+    }
+    
     /// Get statistics about the order book
     pub fn stats(&self) -> LockFreeOrderBookStats {
         LockFreeOrderBookStats {
@@ -238,6 +543,10 @@ impl LockFreeOrderBook {
     
     #[inline]
     fn match_order(&self, order: &mut Order) -> LockFreeMatchResult {
+        if order.status.is_terminal() {
+            return LockFreeMatchResult::NoMatch;
+        }
+        
         let mut trades = Vec::with_capacity(4); // Pre-allocate for common case
         let mut remaining_qty = order.remaining_quantity();
         
@@ -277,6 +586,12 @@ impl LockFreeOrderBook {
                                     continue;
                                 }
                                 
+                                // Never trade against a maker cancelled or filled while it was queued
+                                if matching_order.fill(trade_qty).is_err() {
+                                    price_level.pop_front_order();
+                                    continue;
+                                }
+                                
                                 // Create trade
                                 trades.push(Trade::new(
                                     &order.symbol,
@@ -286,11 +601,11 @@ impl LockFreeOrderBook {
                                     trade_qty,
                                     order.client_id,
                                     matching_order.client_id,
+                                    order.side,
                                 ));
                                 
                                 // Update orders
-                                order.fill(trade_qty);
-                                matching_order.fill(trade_qty);
+                                order.fill(trade_qty).expect("a live taker accepts fills up to its quantity");
                                 remaining_qty -= trade_qty;
                                 
                                 // Update price level
@@ -314,13 +629,15 @@ impl LockFreeOrderBook {
                 
                 // Remove empty price levels
                 for price in prices_to_remove {
-                    self.asks.remove(&price);
+                    if let Some(entry) = self.asks.remove(&price) {
+                        self.recycle_level(entry.value());
+                    }
                     self.best_ask_dirty.store(true, Ordering::Release);
                 }
             },
             Side::Sell => {
-                // Match against bids (buys) - iterate in reverse for highest prices first
-                for entry in self.bids.iter().rev() {
+                // Match against bids (buys) - keyed by Reverse<Price>, so highest prices come first
+                for entry in self.bids.iter() {
                     if remaining_qty == Quantity::ZERO {
                         break;
                     }
@@ -343,6 +660,12 @@ impl LockFreeOrderBook {
                                     continue;
                                 }
                                 
+                                // Never trade against a maker cancelled or filled while it was queued
+                                if matching_order.fill(trade_qty).is_err() {
+                                    price_level.pop_front_order();
+                                    continue;
+                                }
+                                
                                 let trade = Trade::new(
                                     &order.symbol,
                                     matching_order.id,
@@ -351,10 +674,10 @@ impl LockFreeOrderBook {
                                     trade_qty,
                                     matching_order.client_id,
                                     order.client_id,
+                                    order.side,
                                 );
                                 
-                                order.fill(trade_qty);
-                                matching_order.fill(trade_qty);
+                                order.fill(trade_qty).expect("a live taker accepts fills up to its quantity");
                                 remaining_qty -= trade_qty;
                                 price_level.reduce_quantity(trade_qty);
                                 trades.push(trade);
@@ -377,7 +700,9 @@ impl LockFreeOrderBook {
                 
                 // Remove empty price levels
                 for price in prices_to_remove {
-                    self.bids.remove(&std::cmp::Reverse(price));
+                    if let Some(entry) = self.bids.remove(&std::cmp::Reverse(price)) {
+                        self.recycle_level(entry.value());
+                    }
                     self.best_bid_dirty.store(true, Ordering::Release);
                 }
             }
@@ -406,9 +731,7 @@ impl LockFreeOrderBook {
         match order.side {
             Side::Buy => {
                 let price_level = self.bids
-                    .get_or_insert_with(std::cmp::Reverse(order.price), || {
-                        Arc::new(AtomicPriceLevel::new(order.price))
-                    })
+                    .get_or_insert_with(std::cmp::Reverse(order.price), || self.new_level(order.price))
                     .value()
                     .clone();
                 
@@ -416,9 +739,7 @@ impl LockFreeOrderBook {
             },
             Side::Sell => {
                 let price_level = self.asks
-                    .get_or_insert_with(order.price, || {
-                        Arc::new(AtomicPriceLevel::new(order.price))
-                    })
+                    .get_or_insert_with(order.price, || self.new_level(order.price))
                     .value()
                     .clone();
                 
@@ -429,6 +750,22 @@ impl LockFreeOrderBook {
         self.sequence_number.fetch_add(1, Ordering::Relaxed);
     }
     
+    fn new_level(&self, price: Price) -> Arc<AtomicPriceLevel> {
+        match &self.level_pool {
+            Some(pool) => pool.acquire(|level| level.reset(price), || AtomicPriceLevel::new(price)),
+            None => Arc::new(AtomicPriceLevel::new(price)),
+        }
+    }
+    
+    /// Hand a level just removed from the book back to the pool. Matching threads may still
+    /// hold it, so the pool only reuses it once this is the last handle.
+    #[inline]
+    fn recycle_level(&self, level: &Arc<AtomicPriceLevel>) {
+        if let Some(pool) = &self.level_pool {
+            pool.release(level.clone());
+        }
+    }
+    
     #[inline]
     fn remove_order_from_book(&self, order: &Order) {
         match order.side {
@@ -437,7 +774,9 @@ impl LockFreeOrderBook {
                     let price_level = entry.value();
                     if price_level.remove_order(order.id, order.remaining_quantity()) {
                         if price_level.is_empty() {
-                            self.bids.remove(&std::cmp::Reverse(order.price));
+                            if let Some(entry) = self.bids.remove(&std::cmp::Reverse(order.price)) {
+                                self.recycle_level(entry.value());
+                            }
                             self.best_bid_dirty.store(true, Ordering::Release);
                         }
                     }
@@ -448,7 +787,9 @@ impl LockFreeOrderBook {
                     let price_level = entry.value();
                     if price_level.remove_order(order.id, order.remaining_quantity()) {
                         if price_level.is_empty() {
-                            self.asks.remove(&order.price);
+                            if let Some(entry) = self.asks.remove(&order.price) {
+                                self.recycle_level(entry.value());
+                            }
                             self.best_ask_dirty.store(true, Ordering::Release);
                         }
                     }
@@ -461,28 +802,14 @@ impl LockFreeOrderBook {
     fn maybe_update_best_price_cache(&self, side: Side, price: Price) {
         match side {
             Side::Buy => {
-                let cached_raw = self.best_bid_cache.load(Ordering::Acquire);
-                let cached_price = if cached_raw == 0 {
-                    Price::MIN
-                } else {
-                    Price::from_raw(cached_raw as i64)
-                };
-                
-                if price > cached_price {
-                    self.best_bid_cache.store(price.to_raw() as u64, Ordering::Release);
+                if self.best_bid_cache.load().is_none_or(|cached| price > cached) {
+                    self.best_bid_cache.store(Some(price));
                     self.best_bid_dirty.store(false, Ordering::Release);
                 }
             },
             Side::Sell => {
-                let cached_raw = self.best_ask_cache.load(Ordering::Acquire);
-                let cached_price = if cached_raw == u64::MAX {
-                    Price::MAX
-                } else {
-                    Price::from_raw(cached_raw as i64)
-                };
-                
-                if price < cached_price {
-                    self.best_ask_cache.store(price.to_raw() as u64, Ordering::Release);
+                if self.best_ask_cache.load().is_none_or(|cached| price < cached) {
+                    self.best_ask_cache.store(Some(price));
                     self.best_ask_dirty.store(false, Ordering::Release);
                 }
             }
@@ -491,26 +818,24 @@ impl LockFreeOrderBook {
     
     #[inline]
     fn update_best_bid_cache(&self) {
-        if let Some(entry) = self.bids.front() {
-            let price = entry.key().0;
-            self.best_bid_cache.store(price.to_raw() as u64, Ordering::Release);
-        } else {
-            self.best_bid_cache.store(0, Ordering::Release);
-        }
+        self.best_bid_cache.store(self.bids.front().map(|entry| entry.key().0));
         self.best_bid_dirty.store(false, Ordering::Release);
     }
     
     #[inline]
     fn update_best_ask_cache(&self) {
-        if let Some(entry) = self.asks.front() {
-            let price = *entry.key();
-            self.best_ask_cache.store(price.to_raw() as u64, Ordering::Release);
-        } else {
-            self.best_ask_cache.store(u64::MAX, Ordering::Release);
-        }
+        self.best_ask_cache.store(self.asks.front().map(|entry| *entry.key()));
         self.best_ask_dirty.store(false, Ordering::Release);
     }
     
+    #[inline]
+    fn publish_top_levels(&self) {
+        self.top_of_book.publish(self);
+        if let Some(cache) = &self.top_levels {
+            cache.publish(self);
+        }
+    }
+    
     #[inline]
     fn update_timestamp(&self) {
         let now = std::time::SystemTime::now()
@@ -696,4 +1021,224 @@ mod tests {
         assert_eq!(book.best_bid(), Some(Price::new(49960.0)));
         assert_eq!(book.best_ask(), Some(Price::new(50040.0)));
     }
-}
\ No newline at end of file
+
+
+    #[test]
+    fn test_cached_top_levels_consistent_under_mutation() {
+        use std::sync::atomic::AtomicBool;
+        
+        let book = Arc::new(LockFreeOrderBook::with_cached_levels("BTCUSD".to_string(), 5));
+        assert_eq!(book.cached_levels(), Some(5));
+        assert!(LockFreeOrderBook::new("BTCUSD".to_string()).cached_depth().is_none());
+        
+        let done = Arc::new(AtomicBool::new(false));
+        let writers: Vec<_> = [Side::Buy, Side::Sell].into_iter().map(|side| {
+            let book = book.clone();
+            thread::spawn(move || {
+                let mut resting = Vec::new();
+                for i in 0..2_000u32 {
+                    // Bids stay below 50000 and asks above, so the book itself never crosses
+                    let offset = (i * 7 % 20) as f64;
+                    let price = match side {
+                        Side::Buy => 49_999.0 - offset,
+                        Side::Sell => 50_001.0 + offset,
+                    };
+                    let order = create_test_order("BTCUSD", side, price, 1.0 + (i % 3) as f64);
+                    resting.push(order.id);
+                    book.add_order(order);
+                    if i % 3 == 0 {
+                        let victim = resting.swap_remove((i as usize * 31) % resting.len());
+                        book.cancel_order(victim);
+                    }
+                }
+            })
+        }).collect();
+        
+        let readers: Vec<_> = (0..2).map(|_| {
+            let book = book.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let mut reads = 0u64;
+                let mut last_version = 0;
+                while !done.load(Ordering::Acquire) || reads == 0 {
+                    let depth = book.cached_depth().unwrap();
+                    assert!(depth.version >= last_version);
+                    last_version = depth.version;
+                    assert!(depth.bids().len() <= 5 && depth.asks().len() <= 5);
+                    assert!(depth.bids().windows(2).all(|pair| pair[0].0 > pair[1].0));
+                    assert!(depth.asks().windows(2).all(|pair| pair[0].0 < pair[1].0));
+                    assert!(depth.bids().iter().chain(depth.asks()).all(|(_, quantity)| *quantity > Quantity::ZERO));
+                    if let (Some(bid), Some(ask)) = (depth.bids().first(), depth.asks().first()) {
+                        assert!(bid.0 < ask.0);
+                    }
+                    reads += 1;
+                }
+                reads
+            })
+        }).collect();
+        
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Release);
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+        
+        let cached = book.cached_depth().unwrap();
+        let full = book.depth(5);
+        assert_eq!(cached.bids(), full.bids.as_slice());
+        assert_eq!(cached.asks(), full.asks.as_slice());
+        assert_eq!(cached.bids().len(), 5);
+    }
+    
+    #[test]
+    fn test_top_of_book_sizes_match_prices_under_mutation() {
+        use std::sync::atomic::AtomicBool;
+        
+        let book = Arc::new(LockFreeOrderBook::new("BTCUSD".to_string()));
+        assert!(book.top_of_book().is_none());
+        
+        // Every order at a price carries that price's unit, so a level's size is always a
+        // multiple of its own unit and a size read from another level almost never is
+        let unit = |offset: u32| Quantity::new((offset + 1) as f64);
+        let offset_of = |side: Side, price: Price| match side {
+            Side::Buy => (49_999.0 - price.to_f64()).round() as u32,
+            Side::Sell => (price.to_f64() - 50_001.0).round() as u32,
+        };
+        
+        let done = Arc::new(AtomicBool::new(false));
+        let writers: Vec<_> = [Side::Buy, Side::Sell].into_iter().map(|side| {
+            let book = book.clone();
+            thread::spawn(move || {
+                let mut resting = Vec::new();
+                for i in 0..3_000u32 {
+                    let offset = i * 7 % 20;
+                    let price = match side {
+                        Side::Buy => 49_999.0 - offset as f64,
+                        Side::Sell => 50_001.0 + offset as f64,
+                    };
+                    let order = create_test_order("BTCUSD", side, price, unit(offset).to_f64());
+                    resting.push(order.id);
+                    book.add_order(order);
+                    if i % 2 == 0 {
+                        let victim = resting.swap_remove((i as usize * 31) % resting.len());
+                        book.cancel_order(victim);
+                    }
+                }
+            })
+        }).collect();
+        
+        let readers: Vec<_> = (0..2).map(|_| {
+            let book = book.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let mut reads = 0u64;
+                let mut last_version = 0;
+                while !done.load(Ordering::Acquire) || reads == 0 {
+                    if let Some(top) = book.top_of_book() {
+                        assert!(top.version >= last_version);
+                        last_version = top.version;
+                        for (side, level) in [(Side::Buy, top.bid), (Side::Sell, top.ask)] {
+                            if let Some((price, size)) = level {
+                                let offset = offset_of(side, price);
+                                assert!(offset < 20, "{:?} price {} outside the written range", side, price);
+                                assert_eq!(size.to_raw() % unit(offset).to_raw(), 0, "size {} does not belong to {}", size, price);
+                            }
+                        }
+                        if let (Some(bid), Some(ask)) = (top.bid, top.ask) {
+                            assert!(bid.0 < ask.0);
+                        }
+                    }
+                    reads += 1;
+                }
+                reads
+            })
+        }).collect();
+        
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Release);
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+        
+        let top = book.top_of_book().unwrap();
+        assert_eq!(top.bid.map(|(price, _)| price), book.best_bid());
+        assert_eq!(top.ask.map(|(price, _)| price), book.best_ask());
+        assert_eq!(top.bid.unwrap().1, book.level_quantity(Side::Buy, top.bid.unwrap().0));
+    }
+
+    #[test]
+    fn test_trades_record_the_incoming_side_as_aggressor() {
+        let book = LockFreeOrderBook::new("BTCUSD".to_string());
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+
+        let LockFreeMatchResult::FullMatch { trades } = book.add_order(create_test_order("BTCUSD", Side::Sell, 49900.0, 1.0)) else {
+            panic!("Expected the sell to fill");
+        };
+        assert_eq!(trades[0].aggressor_side, Side::Sell);
+
+        let LockFreeMatchResult::FullMatch { trades } = book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)) else {
+            panic!("Expected the buy to fill");
+        };
+        assert_eq!(trades[0].aggressor_side, Side::Buy);
+    }
+    
+    #[test]
+    fn test_level_pool_recycles_atomic_levels_across_churn() {
+        let iterations = 2_000u64;
+        let book = LockFreeOrderBook::new("BTCUSD".to_string()).with_level_pool(8);
+        
+        for i in 0..iterations {
+            let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+            book.add_order(order.clone());
+            if i % 2 == 0 {
+                assert!(book.cancel_order(order.id).is_some());
+            } else {
+                book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+            }
+            assert_eq!(book.best_bid(), None);
+        }
+        
+        let stats = book.level_pool_stats().unwrap();
+        assert_eq!(stats.allocated + stats.reused, iterations);
+        assert!(stats.reused > 0);
+        assert!(stats.idle <= 8);
+        
+        // A recycled level starts empty at its new price
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49000.0, 2.0));
+        assert_eq!(book.level_quantity(Side::Buy, Price::new(49000.0)), Quantity::new(2.0));
+        assert!(LockFreeOrderBook::new("BTCUSD".to_string()).level_pool_stats().is_none());
+    }
+    
+    #[test]
+    fn test_zero_and_negative_prices_are_not_mistaken_for_an_empty_side() {
+        let book = LockFreeOrderBook::new("SPREAD".to_string());
+        
+        // Raw -1 and 0 were the old "no ask" and "no bid" sentinels
+        let mut ask = create_test_order("SPREAD", Side::Sell, 0.0, 1.0);
+        ask.price = Price::from_raw(-1);
+        book.add_order(ask);
+        book.add_order(create_test_order("SPREAD", Side::Buy, -5.0, 1.0));
+        assert_eq!(book.best_ask(), Some(Price::from_raw(-1)));
+        assert_eq!(book.best_bid(), Some(Price::new(-5.0)));
+        
+        let LockFreeMatchResult::FullMatch { trades } = book.add_order(create_test_order("SPREAD", Side::Buy, 0.0, 1.0)) else {
+            panic!("Expected the buy to lift the ask");
+        };
+        assert_eq!(trades[0].price, Price::from_raw(-1));
+        assert_eq!(book.best_ask(), None);
+        
+        book.add_order(create_test_order("SPREAD", Side::Buy, 0.0, 1.0));
+        assert_eq!(book.best_bid(), Some(Price::ZERO));
+        
+        let LockFreeMatchResult::FullMatch { trades } = book.add_order(create_test_order("SPREAD", Side::Sell, -6.0, 2.0)) else {
+            panic!("Expected the sell to hit both bids");
+        };
+        assert_eq!(trades.iter().map(|trade| trade.price).collect::<Vec<_>>(), vec![Price::ZERO, Price::new(-5.0)]);
+        assert_eq!((book.best_bid(), book.best_ask()), (None, None));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::types::{Order, Trade};
 use crossbeam_queue::SegQueue;
 use arrayvec::ArrayVec;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::ptr::NonNull;
 use std::alloc::{alloc, dealloc, Layout};
 
@@ -291,6 +292,81 @@ impl<T> std::ops::DerefMut for PooledVec<T> {
 
 unsafe impl<T: Send> Send for PooledVec<T> {}
 
+/// Allocation counts of an `ArcPool`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArcPoolStats {
+    /// Objects built because no idle one could be reused
+    pub allocated: u64,
+    /// Objects handed out again after a reset
+    pub reused: u64,
+    /// Objects currently idle in the pool
+    pub idle: usize,
+}
+
+/// Recycles `Arc`-shared objects such as price levels. A released object is reused only once
+/// no other handle to it remains, so a reader still holding a retired object never sees it
+/// change underneath it; until then the pool allocates.
+#[derive(Debug)]
+pub struct ArcPool<T> {
+    idle: SegQueue<Arc<T>>,
+    idle_count: AtomicUsize,
+    max_idle: usize,
+    allocated: AtomicU64,
+    reused: AtomicU64,
+}
+
+impl<T> ArcPool<T> {
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            idle: SegQueue::new(),
+            idle_count: AtomicUsize::new(0),
+            max_idle,
+            allocated: AtomicU64::new(0),
+            reused: AtomicU64::new(0),
+        }
+    }
+    
+    /// Reuse the oldest idle object after `reset`, or build a new one with `create`
+    pub fn acquire(&self, reset: impl FnOnce(&mut T), create: impl FnOnce() -> T) -> Arc<T> {
+        // Oldest first: if that one is still shared, the younger ones will be too
+        if let Some(mut object) = self.idle.pop() {
+            self.idle_count.fetch_sub(1, Ordering::Relaxed);
+            match Arc::get_mut(&mut object) {
+                Some(value) => {
+                    reset(value);
+                    self.reused.fetch_add(1, Ordering::Relaxed);
+                    return object;
+                }
+                None => self.release(object),
+            }
+        }
+        
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        Arc::new(create())
+    }
+    
+    /// Offer an object back; it is dropped instead when the pool is full
+    pub fn release(&self, object: Arc<T>) {
+        if self.idle_count.load(Ordering::Relaxed) < self.max_idle {
+            self.idle_count.fetch_add(1, Ordering::Relaxed);
+            self.idle.push(object);
+        }
+    }
+    
+    #[inline]
+    pub fn max_idle(&self) -> usize {
+        self.max_idle
+    }
+    
+    pub fn stats(&self) -> ArcPoolStats {
+        ArcPoolStats {
+            allocated: self.allocated.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            idle: self.idle_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// Global memory pools for common HFT objects
 pub struct GlobalPools {
     pub trade_pool: MemoryPool<Trade>,
@@ -415,6 +491,26 @@ mod tests {
         assert!(vec2.capacity() >= 16);
     }
     
+    #[test]
+    fn test_arc_pool_reuses_only_unshared_objects() {
+        let pool: ArcPool<Vec<u32>> = ArcPool::new(4);
+        
+        let first = pool.acquire(|v| v.clear(), || Vec::with_capacity(64));
+        let reader = Arc::clone(&first);
+        pool.release(first);
+        
+        // Still visible through `reader`, so a fresh object is built
+        let second = pool.acquire(|v| v.clear(), || Vec::with_capacity(64));
+        assert_eq!(pool.stats().allocated, 2);
+        drop(reader);
+        
+        let mut third = pool.acquire(|v| v.clear(), Vec::new);
+        assert_eq!(pool.stats(), ArcPoolStats { allocated: 2, reused: 1, idle: 0 });
+        // Reused storage keeps its capacity
+        assert!(Arc::get_mut(&mut third).unwrap().capacity() >= 64);
+        drop(second);
+    }
+    
     #[test]
     fn test_stack_arrays() {
         let mut trades = TradeArray::new();
@@ -428,6 +524,7 @@ mod tests {
             Quantity::new(1.0),
             Uuid::new_v4(),
             Uuid::new_v4(),
+            Side::Buy,
         );
         
         trades.push(trade);
@@ -449,6 +546,7 @@ mod tests {
                 Quantity::new(1.0),
                 Uuid::new_v4(),
                 Uuid::new_v4(),
+                Side::Buy,
             ));
         }
         
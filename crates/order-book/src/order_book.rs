@@ -1,12 +1,21 @@
-use crate::types::{Price, Quantity, Order, OrderId, Side, Trade};
+use crate::types::{Price, Quantity, Order, OrderId, OrderStatus, OrderType, Side, Trade, ExecutionReport, LiquidityFlag};
+use crate::clock::Clock;
 use crate::price_level::PriceLevel;
+use crate::l3::{L3Delta, L3Feed, L3Order, L3Snapshot, L3Update};
+use crate::memory_pools::{ArcPool, ArcPoolStats};
+use crate::rolling::{RollingStats, TradeWindow};
+use crate::touch::{BestPriceChange, BestPriceNotifier, ImbalanceAlert, ImbalanceAlertConfig, ImbalanceMonitor, SpreadTracker};
+use crossbeam::channel::{unbounded, Receiver, Sender};
 use crossbeam_skiplist::SkipMap;
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
-use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+use chrono::{DateTime, TimeZone, Utc};
+use uuid::Uuid;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,13 +28,171 @@ pub enum OrderBookError {
     InvalidQuantity { quantity: Quantity },
     #[error("Order already exists: {order_id}")]
     OrderAlreadyExists { order_id: OrderId },
-    #[error("Insufficient liquidity")]
-    InsufficientLiquidity,
+    #[error("Insufficient liquidity: {unfilled} of {requested} left unfilled")]
+    InsufficientLiquidity { requested: Quantity, unfilled: Quantity },
+    #[error("{side} side is at its {max_levels} price level cap, cannot add level {price}")]
+    LevelCapExceeded { side: Side, price: Price, max_levels: usize },
+    #[error("Order quantity {quantity} is below the minimum {min_quantity}")]
+    OrderBelowMinimumSize { quantity: Quantity, min_quantity: Quantity },
+    #[error("Order quantity {quantity} exceeds the maximum {max_quantity}")]
+    OrderAboveMaximumSize { quantity: Quantity, max_quantity: Quantity },
+    #[error("Order quantity {quantity} is not a whole multiple of the {lot_size}-unit lot")]
+    FractionalLot { quantity: Quantity, lot_size: u64 },
+    #[error("Order {order_id} cannot move from {from} to {to}")]
+    IllegalTransition { order_id: OrderId, from: OrderStatus, to: OrderStatus },
+    #[error("Order {order_id} rejected before matching: {reason}")]
+    PreMatchRejected { order_id: OrderId, reason: String },
+    #[error("Failed to archive terminal orders: {source}")]
+    ArchiveFailed {
+        #[from]
+        source: std::io::Error,
+    },
+}
+
+/// Check run on every order at the start of `OrderBook::add_order`, before the book's own checks
+/// and matching. It sees the book as it stands and may annotate the order in place.
+pub trait PreMatchHook: Send + Sync + std::fmt::Debug {
+    /// `Err` with a reason rejects the order, which is then neither matched nor rested
+    fn pre_match(&self, book: &OrderBook, order: &mut Order) -> Result<(), String>;
+}
+
+/// Store for filled and cancelled orders leaving a book through `OrderBook::archive_terminal`,
+/// kept for end-of-day reconciliation
+pub trait ArchiveSink {
+    /// Persist one batch. On error the book keeps the batch and offers it again on the next pass.
+    fn archive(&mut self, orders: &[Order]) -> std::io::Result<()>;
+}
+
+impl ArchiveSink for Vec<Order> {
+    fn archive(&mut self, orders: &[Order]) -> std::io::Result<()> {
+        self.extend_from_slice(orders);
+        Ok(())
+    }
+}
+
+/// What to do when a resting order would open a price level beyond the cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LevelCapPolicy {
+    /// Reject passive orders that would open a new level; only the remainder of an
+    /// order that crossed the spread can push out the worst level
+    Reject,
+    /// Cancel every order at the level farthest from the touch to make room
+    EvictWorst,
+}
+
+/// Bound on the number of distinct price levels per side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelCap {
+    /// Cap for a side without its own override below
+    pub max_levels_per_side: usize,
+    #[serde(default)]
+    pub max_bid_levels: Option<usize>,
+    #[serde(default)]
+    pub max_ask_levels: Option<usize>,
+    pub policy: LevelCapPolicy,
+}
+
+impl LevelCap {
+    pub fn new(max_levels_per_side: usize, policy: LevelCapPolicy) -> Self {
+        Self {
+            max_levels_per_side: max_levels_per_side.max(1),
+            max_bid_levels: None,
+            max_ask_levels: None,
+            policy,
+        }
+    }
+    
+    /// Separate caps per side, e.g. deep bids for support analysis but only the nearest asks
+    pub fn asymmetric(max_bid_levels: usize, max_ask_levels: usize, policy: LevelCapPolicy) -> Self {
+        Self {
+            max_bid_levels: Some(max_bid_levels.max(1)),
+            max_ask_levels: Some(max_ask_levels.max(1)),
+            ..Self::new(max_bid_levels.max(max_ask_levels), policy)
+        }
+    }
+    
+    /// Levels `side` may hold
+    #[inline]
+    pub fn max_levels(&self, side: Side) -> usize {
+        let max_levels = match side {
+            Side::Buy => self.max_bid_levels,
+            Side::Sell => self.max_ask_levels,
+        };
+        max_levels.unwrap_or(self.max_levels_per_side).max(1)
+    }
+}
+
+/// Bounds on a single order's quantity for one symbol. These are book rules (keeping dust and
+/// fat-finger orders out of the book), separate from the account-level `RiskLimits`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderSizeLimits {
+    pub min_quantity: Option<Quantity>,
+    pub max_quantity: Option<Quantity>,
+}
+
+impl OrderSizeLimits {
+    pub fn new(min_quantity: Quantity, max_quantity: Quantity) -> Self {
+        Self {
+            min_quantity: Some(min_quantity),
+            max_quantity: Some(max_quantity),
+        }
+    }
+    
+    pub fn check(&self, quantity: Quantity) -> crate::Result<()> {
+        if let Some(min_quantity) = self.min_quantity.filter(|min| quantity < *min) {
+            return Err(OrderBookError::OrderBelowMinimumSize { quantity, min_quantity });
+        }
+        if let Some(max_quantity) = self.max_quantity.filter(|max| quantity > *max) {
+            return Err(OrderBookError::OrderAboveMaximumSize { quantity, max_quantity });
+        }
+        Ok(())
+    }
+}
+
+/// The lot a symbol trades in. Fills are whole multiples of the lot, and whatever an order has
+/// left below one lot after matching is cancelled: a taker's dust is not rested and a maker's
+/// dust leaves the book.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LotModel {
+    /// Any quantity trades
+    #[default]
+    Fractional,
+    /// Fractional quantities, like crypto, matched in multiples of this lot, e.g. 0.001 BTC.
+    /// Orders of any size are accepted.
+    Lot(Quantity),
+    /// Whole lots of units, like equities: quantities must be multiples of this many units,
+    /// e.g. 1 share or a 100-share round lot, or the order is rejected
+    Integer(u64),
+}
+
+impl LotModel {
+    /// Smallest tradable quantity, if the model has one
+    pub fn lot(&self) -> Option<Quantity> {
+        match self {
+            LotModel::Fractional => None,
+            LotModel::Lot(lot) => (*lot > Quantity::ZERO).then_some(*lot),
+            LotModel::Integer(lot_size) => Some(Quantity::new((*lot_size).max(1) as f64)),
+        }
+    }
+    
+    pub fn check(&self, quantity: Quantity) -> crate::Result<()> {
+        match (self, self.lot()) {
+            (LotModel::Integer(lot_size), Some(lot)) if quantity.round_down_to(lot) != quantity => {
+                Err(OrderBookError::FractionalLot { quantity, lot_size: *lot_size })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookStats {
+    /// Orders in the book's order map: resting plus filled ones not yet archived
     pub total_orders: u64,
+    pub total_trades: u64,
+    /// Sequence of the last execution report
+    pub sequence_number: u64,
+    /// Resting quantity on both sides
     pub total_volume: Quantity,
     pub best_bid: Option<Price>,
     pub best_ask: Option<Price>,
@@ -34,17 +201,164 @@ pub struct OrderBookStats {
     pub last_update: DateTime<Utc>,
 }
 
+/// Approximate heap usage of an order book, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryFootprint {
+    pub order_count: usize,
+    pub bid_levels: usize,
+    pub ask_levels: usize,
+    pub orders_bytes: usize,
+    pub bid_map_bytes: usize,
+    pub ask_map_bytes: usize,
+    pub price_level_bytes: usize,
+}
+
+impl MemoryFootprint {
+    #[inline]
+    pub fn level_count(&self) -> usize {
+        self.bid_levels + self.ask_levels
+    }
+
+    #[inline]
+    pub fn total_bytes(&self) -> usize {
+        self.orders_bytes + self.bid_map_bytes + self.ask_map_bytes + self.price_level_bytes
+    }
+}
+
+impl std::ops::Add for MemoryFootprint {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            order_count: self.order_count + other.order_count,
+            bid_levels: self.bid_levels + other.bid_levels,
+            ask_levels: self.ask_levels + other.ask_levels,
+            orders_bytes: self.orders_bytes + other.orders_bytes,
+            bid_map_bytes: self.bid_map_bytes + other.bid_map_bytes,
+            ask_map_bytes: self.ask_map_bytes + other.ask_map_bytes,
+            price_level_bytes: self.price_level_bytes + other.price_level_bytes,
+        }
+    }
+}
+
+impl std::iter::Sum for MemoryFootprint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, fp| acc + fp)
+    }
+}
+
+/// Which orders count as having the same owner for self-trade prevention
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StpScope {
+    /// Any two orders for the same account
+    #[default]
+    Account,
+    /// Only orders for the same sub-account of the same account; orders without a
+    /// sub-account share the account's own
+    SubAccount,
+}
+
+/// Self-trade prevention for one symbol. An incoming order never trades with a resting order
+/// of the same owner under `scope`: the resting order is cancelled and matching continues
+/// behind it. Orders without an account are never treated as self-trades.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTradePrevention {
+    pub scope: StpScope,
+}
+
+impl SelfTradePrevention {
+    pub fn new(scope: StpScope) -> Self {
+        Self { scope }
+    }
+    
+    #[inline]
+    pub fn is_self_trade(&self, taker: &Order, maker: &Order) -> bool {
+        let same_account = taker.account_id.is_some() && taker.account_id == maker.account_id;
+        match self.scope {
+            StpScope::Account => same_account,
+            StpScope::SubAccount => same_account && taker.sub_account_id == maker.sub_account_id,
+        }
+    }
+}
+
+/// Whose view of the book a depth snapshot is taken for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepthMode {
+    /// Displayed quantity only, as an external market data feed would show it
+    #[default]
+    Public,
+    /// Full resting size including iceberg reserves, for risk and internal analytics
+    Internal,
+}
+
+/// How fresh a best price read has to be
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsistencyMode {
+    /// The touch cache, which lags a mutation in progress; the quoting fast path
+    #[default]
+    Cached,
+    /// The front of the price map itself, exact at the moment of the read; for risk checks
+    Authoritative,
+}
+
+// Skip list nodes carry a tower of next pointers plus a refcount; a few words covers the average height
+const SKIPLIST_NODE_OVERHEAD: usize = 4 * std::mem::size_of::<usize>();
+
+/// Cancelled and evicted orders a book holds for `archive_terminal` unless configured otherwise
+pub const DEFAULT_RETIRED_LIMIT: usize = 100_000;
+
 #[derive(Debug)]
 pub struct OrderBook {
     symbol: String,
     bids: SkipMap<std::cmp::Reverse<Price>, Arc<RwLock<PriceLevel>>>,
     asks: SkipMap<Price, Arc<RwLock<PriceLevel>>>,
     orders: DashMap<OrderId, Order>,
+    /// Ids in `orders` by owning client, kept in step with it
+    client_index: DashMap<Uuid, HashSet<OrderId>>,
     best_bid_cache: Arc<RwLock<Option<Price>>>,
     best_ask_cache: Arc<RwLock<Option<Price>>>,
-    #[allow(dead_code)]
     sequence_number: AtomicU64,
-    _last_update: DateTime<Utc>,
+    total_trades: AtomicU64,
+    level_cap: Option<LevelCap>,
+    evicted_levels: AtomicU64,
+    size_limits: OrderSizeLimits,
+    self_trade_prevention: Option<SelfTradePrevention>,
+    /// Trades execute in whole multiples of this; sub-lot residuals are cancelled, never rested
+    lot_model: LotModel,
+    has_icebergs: AtomicBool,
+    /// Cancelled and evicted orders awaiting `archive_terminal`, oldest first
+    retired: Mutex<Vec<Order>>,
+    /// Most orders `retired` holds; the oldest are dropped past it
+    retired_limit: usize,
+    dropped_retired: AtomicU64,
+    l3_feed: Mutex<L3Feed>,
+    /// Set while the L3 stream has subscribers, so unobserved books skip building deltas
+    l3_enabled: AtomicBool,
+    /// Nanoseconds since the epoch of the last add or cancel, by the configured clock
+    last_update_nanos: AtomicI64,
+    /// Time source for trade timestamps, activity and every time window
+    clock: Clock,
+    /// Emptied levels kept for reuse when pooling is enabled
+    level_pool: Option<ArcPool<RwLock<PriceLevel>>>,
+    best_price_notifier: Option<BestPriceNotifier>,
+    spread_tracker: Option<Mutex<SpreadTracker>>,
+    imbalance_monitor: Option<Mutex<ImbalanceMonitor>>,
+    trade_window: Option<Mutex<TradeWindow>>,
+    pre_match_hook: Option<Arc<dyn PreMatchHook>>,
+    /// Fills one `add_order` may make before matching stops and the taker is parked
+    match_limit: Option<usize>,
+    /// Takers whose matching hit `match_limit`, awaiting `resume_match`
+    interrupted: DashMap<OrderId, Order>,
+}
+
+/// What an order would take from the book right now, without touching it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillEstimate {
+    /// Opposite-side levels the order would consume, best price first, with the quantity taken at each
+    pub levels: Vec<(Price, Quantity)>,
+    pub filled_quantity: Quantity,
+    pub remaining_quantity: Quantity,
+    pub average_price: Option<Price>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -61,10 +375,54 @@ pub enum MatchResult {
     PartialMatch {
         trades: Vec<Trade>,
         remaining_quantity: Quantity,
+        price_improvement: f64,
     },
     FullMatch {
         trades: Vec<Trade>,
+        price_improvement: f64,
     },
+    /// Refused by the pre-match hook or `check_order`; neither matched nor rested
+    Rejected {
+        order_id: OrderId,
+        reason: String,
+    },
+}
+
+impl MatchResult {
+    fn rejected(order_id: OrderId, error: OrderBookError) -> Self {
+        MatchResult::Rejected { order_id, reason: error.to_string() }
+    }
+    
+    /// Notional saved against the order's limit by filling at better resting prices.
+    /// Always zero for market orders, which have no limit to improve on.
+    #[inline]
+    pub fn price_improvement(&self) -> f64 {
+        match self {
+            MatchResult::NoMatch | MatchResult::Rejected { .. } => 0.0,
+            MatchResult::PartialMatch { price_improvement, .. } | MatchResult::FullMatch { price_improvement, .. } => {
+                *price_improvement
+            }
+        }
+    }
+    
+    /// `InsufficientLiquidity` when `order` is a market order this result left partly or wholly
+    /// unfilled. Its remainder was discarded, so unlike a limit order's it is not resting.
+    /// A rejected order never reached the book, so liquidity has nothing to say about it.
+    pub fn check_liquidity(&self, order: &Order) -> crate::Result<()> {
+        if order.order_type != OrderType::Market {
+            return Ok(());
+        }
+        
+        let unfilled = match self {
+            MatchResult::NoMatch => order.remaining_quantity(),
+            MatchResult::PartialMatch { remaining_quantity, .. } => *remaining_quantity,
+            MatchResult::FullMatch { .. } | MatchResult::Rejected { .. } => return Ok(()),
+        };
+        Err(OrderBookError::InsufficientLiquidity {
+            requested: order.remaining_quantity(),
+            unfilled,
+        })
+    }
 }
 
 impl OrderBook {
@@ -75,26 +433,280 @@ impl OrderBook {
             bids: SkipMap::new(),
             asks: SkipMap::new(),
             orders: DashMap::new(),
+            client_index: DashMap::new(),
             best_bid_cache: Arc::new(RwLock::new(None)),
             best_ask_cache: Arc::new(RwLock::new(None)),
             sequence_number: AtomicU64::new(0),
-            _last_update: Utc::now(),
+            total_trades: AtomicU64::new(0),
+            level_cap: None,
+            evicted_levels: AtomicU64::new(0),
+            size_limits: OrderSizeLimits::default(),
+            self_trade_prevention: None,
+            lot_model: LotModel::default(),
+            has_icebergs: AtomicBool::new(false),
+            retired: Mutex::new(Vec::new()),
+            retired_limit: DEFAULT_RETIRED_LIMIT,
+            dropped_retired: AtomicU64::new(0),
+            l3_feed: Mutex::new(L3Feed::default()),
+            l3_enabled: AtomicBool::new(false),
+            last_update_nanos: AtomicI64::new(Clock::Global.now_nanos()),
+            clock: Clock::Global,
+            level_pool: None,
+            best_price_notifier: None,
+            spread_tracker: None,
+            imbalance_monitor: None,
+            trade_window: None,
+            pre_match_hook: None,
+            match_limit: None,
+            interrupted: DashMap::new(),
+        }
+    }
+    
+    /// Book that holds at most `level_cap.max_levels(side)` price levels on each side
+    pub fn with_level_cap(symbol: String, level_cap: LevelCap) -> Self {
+        Self {
+            level_cap: Some(level_cap),
+            ..Self::new(symbol)
         }
     }
     
+    /// Reject orders whose quantity falls outside `size_limits`
+    pub fn with_size_limits(mut self, size_limits: OrderSizeLimits) -> Self {
+        self.size_limits = size_limits;
+        self
+    }
+    
+    /// Cancel resting orders an incoming order of the same owner would otherwise trade with
+    pub fn with_self_trade_prevention(mut self, self_trade_prevention: SelfTradePrevention) -> Self {
+        self.self_trade_prevention = Some(self_trade_prevention);
+        self
+    }
+    
+    /// Trade in the lot `lot_model` sets, never matching part of a lot
+    pub fn with_lot_model(mut self, lot_model: LotModel) -> Self {
+        self.lot_model = lot_model;
+        self
+    }
+    
+    /// Read time from `clock` instead of the process-wide clock
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.last_update_nanos = AtomicI64::new(clock.now_nanos());
+        self.clock = clock;
+        self
+    }
+    
+    /// Hold at most `limit` cancelled and evicted orders between `archive_terminal` passes.
+    /// Past it the oldest are dropped unarchived and counted in `dropped_retired`.
+    pub fn with_retired_limit(mut self, limit: usize) -> Self {
+        self.retired_limit = limit;
+        self
+    }
+    
+    /// Recycle up to `max_idle` emptied price levels instead of allocating a new level each
+    /// time one opens, for books whose levels near the touch come and go constantly
+    pub fn with_level_pool(mut self, max_idle: usize) -> Self {
+        self.level_pool = Some(ArcPool::new(max_idle));
+        self
+    }
+    
+    /// Stop matching a taker after `max_fills` fills so no single call sweeps an unbounded number
+    /// of resting orders. A taker still holding marketable quantity at the limit is neither rested
+    /// nor discarded: it is parked, and `resume_match` continues it on a later call. This applies
+    /// to market orders too, so `check_liquidity` only holds once such an order has finished.
+    pub fn with_match_limit(mut self, max_fills: usize) -> Self {
+        self.match_limit = Some(max_fills.max(1));
+        self
+    }
+    
+    /// Call `callback` with the new touch whenever either best price changes, at most once per
+    /// `min_interval`. Changes in between are coalesced, and the latest touch is always delivered
+    /// once they stop. The callback runs on a dedicated thread, never the one matching orders.
+    pub fn on_best_price_change<F>(mut self, min_interval: Duration, callback: F) -> Self
+    where
+        F: Fn(BestPriceChange) + Send + 'static,
+    {
+        self.best_price_notifier = Some(BestPriceNotifier::spawn(&self.symbol, min_interval, callback));
+        self
+    }
+    
+    /// Record the spread on every touch change so `average_spread` can report it over windows
+    /// up to `max_window`
+    pub fn with_spread_tracking(mut self, max_window: Duration) -> Self {
+        self.spread_tracker = Some(Mutex::new(SpreadTracker::new(max_window)));
+        self
+    }
+    
+    /// Record every trade and incoming order so `rolling_stats` can summarize windows up to
+    /// `max_window`
+    pub fn with_rolling_stats(mut self, max_window: Duration) -> Self {
+        self.trade_window = Some(Mutex::new(TradeWindow::new(max_window)));
+        self
+    }
+    
+    /// Trade rate, volume, price volatility and fill ratio over the last `window`, by the
+    /// configured clock; `None` without rolling stats enabled
+    pub fn rolling_stats(&self, window: Duration) -> Option<RollingStats> {
+        let trade_window = self.trade_window.as_ref()?;
+        Some(trade_window.lock().summarize(window, self.clock_nanos()))
+    }
+    
+    /// Sample top-of-book imbalance on every touch change of a two-sided book and send an
+    /// `ImbalanceAlert` to `sender` when it crosses one of `config`'s bounds
+    pub fn with_imbalance_alerts(mut self, config: ImbalanceAlertConfig, sender: Sender<ImbalanceAlert>) -> Self {
+        self.imbalance_monitor = Some(Mutex::new(ImbalanceMonitor::new(config, sender)));
+        self
+    }
+    
+    /// Run `hook` on every order before it is checked and matched
+    pub fn with_pre_match_hook(mut self, hook: Arc<dyn PreMatchHook>) -> Self {
+        self.pre_match_hook = Some(hook);
+        self
+    }
+    
+    /// Time-weighted average spread over the last `window`, by the configured clock. `None`
+    /// without spread tracking or if the book was not two-sided at any point in the window.
+    pub fn average_spread(&self, window: Duration) -> Option<Price> {
+        let tracker = self.spread_tracker.as_ref()?;
+        tracker.lock().average(window, self.clock_nanos())
+    }
+    
+    /// Level allocations and reuses so far, when pooling is enabled
+    pub fn level_pool_stats(&self) -> Option<ArcPoolStats> {
+        self.level_pool.as_ref().map(ArcPool::stats)
+    }
+    
+    #[inline]
+    pub fn level_cap(&self) -> Option<LevelCap> {
+        self.level_cap
+    }
+    
+    #[inline]
+    pub fn size_limits(&self) -> OrderSizeLimits {
+        self.size_limits
+    }
+    
+    #[inline]
+    pub fn self_trade_prevention(&self) -> Option<SelfTradePrevention> {
+        self.self_trade_prevention
+    }
+    
+    #[inline]
+    pub fn lot_model(&self) -> LotModel {
+        self.lot_model
+    }
+    
+    /// Hook consulted before each incoming order is matched, if one is installed
+    #[inline]
+    pub fn pre_match_hook(&self) -> Option<&Arc<dyn PreMatchHook>> {
+        self.pre_match_hook.as_ref()
+    }
+    
+    /// Number of levels removed by `LevelCapPolicy::EvictWorst`
+    #[inline]
+    pub fn evicted_levels(&self) -> u64 {
+        self.evicted_levels.load(Ordering::Relaxed)
+    }
+    
+    /// Cancelled and evicted orders dropped unarchived because the retired buffer was full
+    #[inline]
+    pub fn dropped_retired(&self) -> u64 {
+        self.dropped_retired.load(Ordering::Relaxed)
+    }
+    
     #[inline]
     pub fn symbol(&self) -> &str {
         &self.symbol
     }
     
+    /// Add an order. An order refused by the pre-match hook or by `check_order` (size, duplicate id
+    /// or level cap) is neither matched nor rested and yields `Rejected` with the reason.
+    /// Market orders sweep opposite levels regardless of their price and never rest; any
+    /// unfilled remainder is reported in `PartialMatch` and discarded.
+    #[inline]
+    pub fn add_order(&self, order: Order) -> MatchResult {
+        let order_id = order.id;
+        self.add_order_inner(order, None).unwrap_or_else(|e| MatchResult::rejected(order_id, e))
+    }
+    
+    /// Add an order, failing with `PreMatchRejected` or the `check_order` error if it is refused
+    pub fn try_add_order(&self, order: Order) -> crate::Result<MatchResult> {
+        self.add_order_inner(order, None)
+    }
+    
+    /// Add an order, also returning maker and taker execution reports for every fill
+    pub fn add_order_with_reports(&self, order: Order) -> (MatchResult, Vec<ExecutionReport>) {
+        let mut reports = Vec::new();
+        let order_id = order.id;
+        let match_result = self.add_order_inner(order, Some(&mut reports))
+            .unwrap_or_else(|e| MatchResult::rejected(order_id, e));
+        (match_result, reports)
+    }
+    
+    #[inline]
+    fn add_order_inner(&self, mut order: Order, reports: Option<&mut Vec<ExecutionReport>>) -> crate::Result<MatchResult> {
+        if let Some(hook) = &self.pre_match_hook {
+            hook.pre_match(self, &mut order)
+                .map_err(|reason| OrderBookError::PreMatchRejected { order_id: order.id, reason })?;
+        }
+        self.check_order(&order)?;
+        let submitted = order.remaining_quantity();
+        let match_result = self.match_and_rest(order, reports);
+        self.record_rolling(Some(submitted), &match_result);
+        Ok(match_result)
+    }
+    
+    /// Continue matching a taker parked by the match limit, with a fresh allowance of fills.
+    /// It is parked again if it reaches the limit again, and otherwise finishes as `add_order`
+    /// would have: a limit order's remainder rests and a market order's is discarded.
+    pub fn resume_match(&self, order_id: OrderId) -> Option<MatchResult> {
+        let (_, order) = self.interrupted.remove(&order_id)?;
+        let match_result = self.match_and_rest(order, None);
+        self.record_rolling(None, &match_result);
+        Some(match_result)
+    }
+    
+    /// Sample an arrival, when `submitted` is known, and its trades into the rolling window
     #[inline]
-    pub fn add_order(&self, mut order: Order) -> MatchResult {
+    fn record_rolling(&self, submitted: Option<Quantity>, match_result: &MatchResult) {
+        let Some(trade_window) = &self.trade_window else {
+            return;
+        };
+        let trades: &[Trade] = match match_result {
+            MatchResult::NoMatch | MatchResult::Rejected { .. } => &[],
+            MatchResult::PartialMatch { trades, .. } | MatchResult::FullMatch { trades, .. } => trades,
+        };
+        trade_window.lock().record_match(self.clock_nanos(), submitted, trades);
+    }
+    
+    /// Takers whose matching stopped at the match limit, with their fills so far
+    pub fn interrupted_orders(&self) -> Vec<Order> {
+        self.interrupted.iter().map(|entry| entry.value().clone()).collect()
+    }
+    
+    fn match_and_rest(&self, mut order: Order, reports: Option<&mut Vec<ExecutionReport>>) -> MatchResult {
+        self.last_update_nanos.store(self.clock_nanos(), Ordering::Relaxed);
+        
         // Fast path for market orders that will likely match completely
-        let match_result = self.match_order(&mut order);
+        let match_result = self.match_order(&mut order, reports);
+        
+        if self.match_interrupted(&order, &match_result) {
+            self.interrupted.insert(order.id, order);
+            return match_result;
+        }
         
-        if order.remaining_quantity() > Quantity::ZERO {
+        // Whatever a market order could not take is dropped, never rested, as is sub-lot dust
+        if self.whole_lots(order.remaining_quantity()) > Quantity::ZERO && order.order_type != OrderType::Market {
+            let side = order.side;
+            if order.is_iceberg() {
+                self.has_icebergs.store(true, Ordering::Relaxed);
+            }
             self.insert_order_to_book(&order);
+            if self.l3_enabled.load(Ordering::Relaxed) {
+                self.publish_l3(vec![L3Delta::Add(L3Order::from(&order))]);
+            }
+            self.index_client(&order);
             self.orders.insert(order.id, order);
+            self.enforce_level_cap(side);
             // Only update cache if we added to book
             self.update_best_price_cache();
         }
@@ -102,15 +714,115 @@ impl OrderBook {
         match_result
     }
     
+    /// Whether matching stopped at the limit with quantity left that could still trade
+    fn match_interrupted(&self, order: &Order, match_result: &MatchResult) -> bool {
+        let MatchResult::PartialMatch { trades, .. } = match_result else {
+            return false;
+        };
+        if self.match_limit.is_none_or(|limit| trades.len() < limit) {
+            return false;
+        }
+        
+        order.order_type == OrderType::Market || match order.side {
+            Side::Buy => self.asks.front().is_some_and(|entry| order.price >= *entry.key()),
+            Side::Sell => self.bids.front().is_some_and(|entry| order.price <= entry.key().0),
+        }
+    }
+    
+    /// Whether `order` can be added: its quantity must be within the size limits and a whole lot, its id must not
+    /// belong to a resting order and the level cap must admit it
+    pub fn check_order(&self, order: &Order) -> crate::Result<()> {
+        if order.status.is_terminal() {
+            return Err(OrderBookError::IllegalTransition {
+                order_id: order.id,
+                from: order.status,
+                to: OrderStatus::PartiallyFilled,
+            });
+        }
+        self.size_limits.check(order.quantity)?;
+        self.lot_model.check(order.quantity)?;
+        
+        if self.orders.contains_key(&order.id) || self.interrupted.contains_key(&order.id) {
+            return Err(OrderBookError::OrderAlreadyExists { order_id: order.id });
+        }
+        
+        self.check_level_cap(order)
+    }
+    
+    /// Whether the level cap admits `order`. Orders that cross the spread are always admitted,
+    /// as are orders joining an existing level; only a new passive level on a full side is refused.
+    pub fn check_level_cap(&self, order: &Order) -> crate::Result<()> {
+        let Some(cap) = self.level_cap else {
+            return Ok(());
+        };
+        
+        let (level_count, worst, marketable, level_exists) = match order.side {
+            Side::Buy => (
+                self.bids.len(),
+                self.bids.back().map(|entry| entry.key().0),
+                self.asks.front().is_some_and(|entry| order.price >= *entry.key()),
+                self.bids.contains_key(&std::cmp::Reverse(order.price)),
+            ),
+            Side::Sell => (
+                self.asks.len(),
+                self.asks.back().map(|entry| *entry.key()),
+                self.bids.front().is_some_and(|entry| order.price <= entry.key().0),
+                self.asks.contains_key(&order.price),
+            ),
+        };
+        
+        if order.order_type == OrderType::Market || marketable || level_exists || level_count < cap.max_levels(order.side) {
+            return Ok(());
+        }
+        
+        let beyond_worst = worst.is_some_and(|worst| match order.side {
+            Side::Buy => order.price < worst,
+            Side::Sell => order.price > worst,
+        });
+        
+        if beyond_worst || cap.policy == LevelCapPolicy::Reject {
+            Err(OrderBookError::LevelCapExceeded {
+                side: order.side,
+                price: order.price,
+                max_levels: cap.max_levels(order.side),
+            })
+        } else {
+            Ok(())
+        }
+    }
+    
     #[inline]
     pub fn cancel_order(&self, order_id: OrderId) -> Option<Order> {
-        if let Some((_, mut order)) = self.orders.remove(&order_id) {
-            order.cancel();
-            self.remove_order_from_book(&order);
-            Some(order)
-        } else {
-            None
+        self.try_cancel_order(order_id).ok()
+    }
+    
+    /// Cancel a live order, failing with `IllegalTransition` if it already filled
+    pub fn try_cancel_order(&self, order_id: OrderId) -> crate::Result<Order> {
+        let Some((_, mut order)) = self.orders.remove_if(&order_id, |_, order| !order.status.is_terminal()) else {
+            // A parked taker never reached the book, so there is nothing to unlink
+            if let Some((_, mut order)) = self.interrupted.remove(&order_id) {
+                order.cancel()?;
+                self.retire(&mut self.retired.lock(), [order.clone()]);
+                return Ok(order);
+            }
+            return Err(match self.orders.get(&order_id) {
+                Some(order) => OrderBookError::IllegalTransition {
+                    order_id,
+                    from: order.status,
+                    to: OrderStatus::Cancelled,
+                },
+                None => OrderBookError::OrderNotFound { order_id },
+            });
+        };
+        self.unindex_client(&order);
+        order.cancel()?;
+        self.remove_order_from_book(&order);
+        self.last_update_nanos.store(self.clock_nanos(), Ordering::Relaxed);
+        if self.l3_enabled.load(Ordering::Relaxed) {
+            self.publish_l3(vec![L3Delta::delete(&order)]);
         }
+        self.retire(&mut self.retired.lock(), [order.clone()]);
+        Ok(order)
     }
     
     #[inline]
@@ -118,6 +830,45 @@ impl OrderBook {
         self.orders.get(&order_id).map(|entry| entry.clone())
     }
     
+    /// Live orders resting for `client_id`, oldest first
+    pub fn client_orders(&self, client_id: Uuid) -> Vec<Order> {
+        let Some(order_ids) = self.client_index.get(&client_id) else {
+            return Vec::new();
+        };
+        let mut orders: Vec<Order> = order_ids.iter()
+            .filter_map(|order_id| self.orders.get(order_id))
+            .filter(|order| !order.status.is_terminal())
+            .map(|order| order.clone())
+            .collect();
+        orders.sort_by_key(|order| (order.timestamp, order.id.to_raw()));
+        orders
+    }
+    
+    /// Every resting order, bids then asks, each side in price-time priority
+    pub fn resting_orders(&self) -> Vec<Order> {
+        let snapshot = self.l3_snapshot();
+        snapshot.bids.iter()
+            .chain(snapshot.asks.iter())
+            .filter_map(|resting| self.get_order(resting.order_id))
+            .collect()
+    }
+    
+    /// Put an order back on the book exactly as it rested, without matching it. Orders restored
+    /// in the sequence `resting_orders` returned them keep their queue positions.
+    pub fn restore_resting(&self, order: Order) -> crate::Result<()> {
+        if self.orders.contains_key(&order.id) {
+            return Err(OrderBookError::OrderAlreadyExists { order_id: order.id });
+        }
+        if order.is_iceberg() {
+            self.has_icebergs.store(true, Ordering::Relaxed);
+        }
+        self.insert_order_to_book(&order);
+        self.index_client(&order);
+        self.orders.insert(order.id, order);
+        self.update_best_price_cache();
+        Ok(())
+    }
+    
     #[inline]
     pub fn best_bid(&self) -> Option<Price> {
         if let Some(cached) = *self.best_bid_cache.read() {
@@ -136,6 +887,67 @@ impl OrderBook {
         }
     }
     
+    #[inline]
+    pub fn best_bid_with_mode(&self, mode: ConsistencyMode) -> Option<Price> {
+        match mode {
+            ConsistencyMode::Cached => self.best_bid(),
+            ConsistencyMode::Authoritative => self.bids.front().map(|entry| entry.key().0),
+        }
+    }
+    
+    #[inline]
+    pub fn best_ask_with_mode(&self, mode: ConsistencyMode) -> Option<Price> {
+        match mode {
+            ConsistencyMode::Cached => self.best_ask(),
+            ConsistencyMode::Authoritative => self.asks.front().map(|entry| *entry.key()),
+        }
+    }
+    
+    /// Touch price and full resting size on the side an order on `side` would trade against,
+    /// read from the one level without allocating. The size includes iceberg reserves.
+    #[inline]
+    pub fn best_opposite(&self, side: Side) -> Option<(Price, Quantity)> {
+        let level = match side {
+            Side::Buy => self.asks.front()?.value().clone(),
+            Side::Sell => self.bids.front()?.value().clone(),
+        };
+        let level = level.read();
+        Some((level.price, level.total_quantity))
+    }
+    
+    /// Notional an order for `quantity` on `side` would trade sweeping the opposite side level
+    /// by level, iceberg reserves included. Quantity beyond the resting depth is valued at the
+    /// worst level reached; against an empty side the notional is zero.
+    pub fn sweep_notional(&self, side: Side, quantity: Quantity) -> f64 {
+        let mut remaining = quantity.to_f64();
+        let mut notional = 0.0;
+        let mut worst = 0.0;
+        let mut take = |price_level: &PriceLevel| {
+            let traded = remaining.min(price_level.total_quantity.to_f64());
+            notional += traded * price_level.price.to_f64();
+            remaining -= traded;
+            worst = price_level.price.to_f64();
+            remaining > 0.0
+        };
+        match side {
+            Side::Buy => {
+                for entry in self.asks.iter() {
+                    if !take(&entry.value().read()) {
+                        break;
+                    }
+                }
+            }
+            Side::Sell => {
+                for entry in self.bids.iter() {
+                    if !take(&entry.value().read()) {
+                        break;
+                    }
+                }
+            }
+        }
+        notional + remaining * worst
+    }
+    
     #[inline]
     pub fn spread(&self) -> Option<Price> {
         match (self.best_ask(), self.best_bid()) {
@@ -144,6 +956,15 @@ impl OrderBook {
         }
     }
     
+    /// (bid - ask) / (bid + ask) resting volume over the top `levels`, in [-1, 1]; `None` on an
+    /// empty book
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_volume: f64 = self.bids.iter().take(levels).map(|level| level.value().read().total_quantity.to_f64()).sum();
+        let ask_volume: f64 = self.asks.iter().take(levels).map(|level| level.value().read().total_quantity.to_f64()).sum();
+        let total = bid_volume + ask_volume;
+        (total > 0.0).then(|| (bid_volume - ask_volume) / total)
+    }
+    
     #[inline]
     pub fn mid_price(&self) -> Option<Price> {
         match (self.best_ask(), self.best_bid()) {
@@ -152,28 +973,102 @@ impl OrderBook {
         }
     }
     
-    #[inline]
-    pub fn depth(&self, levels: usize) -> BookSnapshot {
-        let mut bids = Vec::with_capacity(levels);
-        let mut asks = Vec::with_capacity(levels);
+    /// Walk the opposite side as an order of `quantity` on `side` would, stopping at `limit_price`
+    /// if given. Uses full level quantities, including iceberg reserves.
+    pub fn estimate_fill(&self, side: Side, quantity: Quantity, limit_price: Option<Price>) -> FillEstimate {
+        let mut levels = Vec::new();
+        let mut remaining = quantity;
+        let mut notional = 0.0;
         
-        // For bids, we want highest prices first (bids are stored as Reverse(Price))
-        for entry in self.bids.iter().take(levels) {
-            let price_level = entry.value().read();
-            bids.push((price_level.price, price_level.total_quantity));
-        }
+        let mut take = |price_level: &PriceLevel| -> bool {
+            let taken = remaining.min(price_level.total_quantity);
+            if taken > Quantity::ZERO {
+                levels.push((price_level.price, taken));
+                notional += price_level.price.to_f64() * taken.to_f64();
+                remaining -= taken;
+            }
+            remaining > Quantity::ZERO
+        };
         
-        // For asks, we want lowest prices first
-        for entry in self.asks.iter().take(levels) {
-            let price_level = entry.value().read();
-            asks.push((price_level.price, price_level.total_quantity));
+        match side {
+            Side::Buy => {
+                for entry in self.asks.iter() {
+                    if limit_price.is_some_and(|limit| *entry.key() > limit) || !take(&entry.value().read()) {
+                        break;
+                    }
+                }
+            }
+            Side::Sell => {
+                for entry in self.bids.iter() {
+                    if limit_price.is_some_and(|limit| entry.key().0 < limit) || !take(&entry.value().read()) {
+                        break;
+                    }
+                }
+            }
+        }
+        
+        let filled_quantity = quantity - remaining;
+        FillEstimate {
+            levels,
+            filled_quantity,
+            remaining_quantity: remaining,
+            average_price: (filled_quantity > Quantity::ZERO)
+                .then(|| Price::new(notional / filled_quantity.to_f64())),
+        }
+    }
+    
+    /// Public depth: iceberg orders contribute only their displayed slice
+    #[inline]
+    pub fn depth(&self, levels: usize) -> BookSnapshot {
+        self.depth_with_mode(levels, DepthMode::Public)
+    }
+    
+    pub fn depth_with_mode(&self, levels: usize, mode: DepthMode) -> BookSnapshot {
+        let mut bids = Vec::with_capacity(levels.min(self.bids.len()));
+        let mut asks = Vec::with_capacity(levels.min(self.asks.len()));
+        
+        // Without resting icebergs both views are identical, so skip the per-order walk
+        let hide_reserves = mode == DepthMode::Public && self.has_icebergs.load(Ordering::Relaxed);
+        let level_quantity = |price_level: &PriceLevel| {
+            if hide_reserves {
+                price_level.orders()
+                    .iter()
+                    .filter_map(|order_id| self.orders.get(order_id).map(|order| order.visible_quantity()))
+                    .fold(Quantity::ZERO, |total, quantity| total + quantity)
+            } else {
+                price_level.total_quantity
+            }
+        };
+        
+        // For bids, we want highest prices first (bids are stored as Reverse(Price))
+        for entry in self.bids.iter() {
+            if bids.len() >= levels {
+                break;
+            }
+            let price_level = entry.value().read();
+            let quantity = level_quantity(&price_level);
+            if quantity > Quantity::ZERO {
+                bids.push((price_level.price, quantity));
+            }
+        }
+        
+        // For asks, we want lowest prices first
+        for entry in self.asks.iter() {
+            if asks.len() >= levels {
+                break;
+            }
+            let price_level = entry.value().read();
+            let quantity = level_quantity(&price_level);
+            if quantity > Quantity::ZERO {
+                asks.push((price_level.price, quantity));
+            }
         }
         
         BookSnapshot {
             symbol: self.symbol.clone(),
             bids,
             asks,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         }
     }
     
@@ -189,6 +1084,188 @@ impl OrderBook {
         }
     }
     
+    pub fn stats(&self) -> OrderBookStats {
+        OrderBookStats {
+            total_orders: self.orders.len() as u64,
+            total_trades: self.total_trades.load(Ordering::Relaxed),
+            sequence_number: self.sequence_number.load(Ordering::Relaxed),
+            total_volume: self.total_volume(Side::Buy) + self.total_volume(Side::Sell),
+            best_bid: self.best_bid(),
+            best_ask: self.best_ask(),
+            spread: self.spread(),
+            depth_levels: self.bids.len() + self.asks.len(),
+            last_update: Utc.timestamp_nanos(self.last_update_nanos.load(Ordering::Relaxed)),
+        }
+    }
+    
+    #[inline]
+    fn clock_nanos(&self) -> i64 {
+        self.clock.now_nanos()
+    }
+    
+    /// How long the longest-resting live order has been in the book, by the configured clock
+    pub fn oldest_order_age(&self) -> Option<std::time::Duration> {
+        self.orders.iter()
+            .filter(|entry| !entry.value().status.is_terminal())
+            .map(|entry| entry.value().timestamp)
+            .min()
+            .map(|timestamp| self.age_since(timestamp))
+    }
+    
+    /// Live orders that have rested longer than `max_age`, oldest first
+    pub fn orders_older_than(&self, max_age: std::time::Duration) -> Vec<(OrderId, std::time::Duration)> {
+        let mut stale: Vec<(OrderId, std::time::Duration)> = self.orders.iter()
+            .filter(|entry| !entry.value().status.is_terminal())
+            .map(|entry| (*entry.key(), self.age_since(entry.value().timestamp)))
+            .filter(|(_, age)| *age > max_age)
+            .collect();
+        stale.sort_by_key(|s| std::cmp::Reverse(s.1));
+        stale
+    }
+    
+    #[inline]
+    fn age_since(&self, timestamp: DateTime<Utc>) -> std::time::Duration {
+        // A timestamp ahead of the clock counts as brand new
+        (self.clock.now() - timestamp).to_std().unwrap_or_default()
+    }
+    
+    /// Every resting order in price-time priority, with full remaining size
+    pub fn l3_snapshot(&self) -> L3Snapshot {
+        let feed = self.l3_feed.lock();
+        self.l3_snapshot_at(feed.sequence)
+    }
+    
+    /// Snapshot plus a stream of every order-level change after it. The snapshot lines up
+    /// with the stream exactly when no order is being added or cancelled meanwhile, e.g.
+    /// when subscribing from the thread that feeds the book.
+    pub fn subscribe_l3(&self) -> (L3Snapshot, Receiver<L3Update>) {
+        let mut feed = self.l3_feed.lock();
+        let (sender, receiver) = unbounded();
+        feed.subscribers.push(sender);
+        self.l3_enabled.store(true, Ordering::Relaxed);
+        (self.l3_snapshot_at(feed.sequence), receiver)
+    }
+    
+    fn l3_snapshot_at(&self, sequence: u64) -> L3Snapshot {
+        let resting = |price_level: &PriceLevel| -> Vec<L3Order> {
+            price_level.orders()
+                .iter()
+                .filter_map(|order_id| self.orders.get(order_id))
+                .filter(|order| !order.status.is_terminal() && order.remaining_quantity() > Quantity::ZERO)
+                .map(|order| L3Order::from(order.value()))
+                .collect()
+        };
+        
+        L3Snapshot {
+            symbol: self.symbol.clone(),
+            bids: self.bids.iter().flat_map(|entry| resting(&entry.value().read())).collect(),
+            asks: self.asks.iter().flat_map(|entry| resting(&entry.value().read())).collect(),
+            sequence,
+            timestamp: self.clock.now(),
+        }
+    }
+    
+    fn publish_l3(&self, deltas: Vec<L3Delta>) {
+        let mut feed = self.l3_feed.lock();
+        feed.publish(deltas);
+        if feed.subscribers.is_empty() {
+            self.l3_enabled.store(false, Ordering::Relaxed);
+        }
+    }
+    
+    /// Hand every filled or cancelled order to `sink` and drop it from the book, then compact
+    /// the order map. Returns the number of orders archived. Filled makers otherwise stay in
+    /// the order map, so long-running books should call this periodically.
+    pub fn archive_terminal(&self, sink: &mut impl ArchiveSink) -> crate::Result<usize> {
+        let filled: Vec<OrderId> = self.orders.iter()
+            .filter(|entry| entry.value().is_fully_filled())
+            .map(|entry| *entry.key())
+            .collect();
+        let mut batch = std::mem::take(&mut *self.retired.lock());
+        batch.extend(
+            filled.iter()
+                .filter_map(|order_id| self.orders.remove_if(order_id, |_, order| order.is_fully_filled()))
+                .map(|(_, order)| {
+                    self.unindex_client(&order);
+                    order
+                }),
+        );
+        
+        if batch.is_empty() {
+            return Ok(0);
+        }
+        
+        // The sink may do I/O, so it runs without blocking cancels
+        if let Err(e) = sink.archive(&batch) {
+            let mut retired = self.retired.lock();
+            let arrived_since = std::mem::replace(&mut *retired, batch);
+            self.retire(&mut retired, arrived_since);
+            return Err(e.into());
+        }
+        
+        self.compact();
+        Ok(batch.len())
+    }
+    
+    /// Append to the retired buffer, dropping the oldest orders past `retired_limit`
+    fn retire(&self, retired: &mut Vec<Order>, orders: impl IntoIterator<Item = Order>) {
+        retired.extend(orders);
+        let excess = retired.len().saturating_sub(self.retired_limit);
+        if excess > 0 {
+            retired.drain(..excess);
+            self.dropped_retired.fetch_add(excess as u64, Ordering::Relaxed);
+            tracing::warn!("{}: retired buffer full, dropped {} orders unarchived", self.symbol, excess);
+        }
+    }
+    
+    /// Release capacity left behind by removed orders
+    pub fn compact(&self) {
+        self.orders.shrink_to_fit();
+        self.retired.lock().shrink_to_fit();
+    }
+    
+    /// Filled or cancelled orders that the next `archive_terminal` would hand over
+    pub fn pending_archive(&self) -> usize {
+        self.retired.lock().len() + self.orders.iter().filter(|entry| entry.value().is_fully_filled()).count()
+    }
+    
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let order_entry = std::mem::size_of::<OrderId>() + std::mem::size_of::<Order>();
+        let orders_bytes = self.orders.iter()
+            .map(|entry| order_entry + entry.value().symbol.capacity())
+            .sum();
+
+        // Each level is an Arc<RwLock<PriceLevel>> (two refcounts) plus its queue of order ids
+        let level_bytes = |level: &PriceLevel| {
+            2 * std::mem::size_of::<usize>()
+                + std::mem::size_of::<RwLock<PriceLevel>>()
+                + level.orders().capacity() * std::mem::size_of::<OrderId>()
+        };
+        let bid_node = SKIPLIST_NODE_OVERHEAD
+            + std::mem::size_of::<std::cmp::Reverse<Price>>()
+            + std::mem::size_of::<Arc<RwLock<PriceLevel>>>();
+        let ask_node = SKIPLIST_NODE_OVERHEAD
+            + std::mem::size_of::<Price>()
+            + std::mem::size_of::<Arc<RwLock<PriceLevel>>>();
+
+        let bid_levels = self.bids.len();
+        let ask_levels = self.asks.len();
+        let price_level_bytes = self.bids.iter()
+            .map(|entry| level_bytes(&entry.value().read()))
+            .chain(self.asks.iter().map(|entry| level_bytes(&entry.value().read())))
+            .sum();
+
+        MemoryFootprint {
+            order_count: self.orders.len(),
+            bid_levels,
+            ask_levels,
+            orders_bytes,
+            bid_map_bytes: bid_levels * bid_node,
+            ask_map_bytes: ask_levels * ask_node,
+            price_level_bytes,
+        }
+    }
+    
     #[inline]
     fn update_best_price_cache(&self) {
         // Batch cache updates to reduce lock contention
@@ -196,28 +1273,55 @@ impl OrderBook {
         let best_ask = self.asks.front().map(|entry| *entry.key());
         
         // Single write lock for both updates
-        *self.best_bid_cache.write() = best_bid;
-        *self.best_ask_cache.write() = best_ask;
+        let previous_bid = std::mem::replace(&mut *self.best_bid_cache.write(), best_bid);
+        let previous_ask = std::mem::replace(&mut *self.best_ask_cache.write(), best_ask);
+        
+        if previous_bid == best_bid && previous_ask == best_ask {
+            return;
+        }
+        if let Some(notifier) = &self.best_price_notifier {
+            notifier.publish(BestPriceChange { best_bid, best_ask });
+        }
+        if let Some(tracker) = &self.spread_tracker {
+            let spread = best_ask.zip(best_bid).map(|(ask, bid)| ask - bid);
+            tracker.lock().record(self.clock_nanos(), spread);
+        }
+        if let Some(monitor) = &self.imbalance_monitor {
+            // A one-sided book is empty, not imbalanced
+            if best_bid.is_some() && best_ask.is_some() {
+                let mut monitor = monitor.lock();
+                if let Some(imbalance) = self.imbalance(monitor.config().levels) {
+                    monitor.observe(&self.symbol, imbalance, self.clock.now());
+                }
+            }
+        }
     }
 
-    fn match_order(&self, order: &mut Order) -> MatchResult {
+    fn match_order(&self, order: &mut Order, mut reports: Option<&mut Vec<ExecutionReport>>) -> MatchResult {
         let mut trades = Vec::with_capacity(4); // Pre-allocate for common case
         let mut remaining_qty = order.remaining_quantity();
         
+        // A market order's price is meaningless: it takes any opposite liquidity
+        let is_market = order.order_type == OrderType::Market;
         let can_match = |order_price: Price, level_price: Price, side: Side| -> bool {
-            match side {
+            is_market || match side {
                 Side::Buy => order_price >= level_price,
                 Side::Sell => order_price <= level_price,
             }
         };
         
+        let max_fills = self.match_limit.unwrap_or(usize::MAX);
         let mut prices_to_remove = Vec::with_capacity(2); // Pre-allocate for common case
+        let track_l3 = self.l3_enabled.load(Ordering::Relaxed);
+        let mut l3_deltas = Vec::new();
+        let mut dust = Vec::new();
+        let mut self_trades = Vec::new();
         
         match order.side {
             Side::Buy => {
                 // For buy orders, match against asks (sells)
                 for entry in self.asks.iter() {
-                    if remaining_qty == Quantity::ZERO {
+                    if self.whole_lots(remaining_qty) == Quantity::ZERO || trades.len() >= max_fills {
                         break;
                     }
                     
@@ -229,14 +1333,39 @@ impl OrderBook {
                     let mut price_level = entry.value().write();
                     
                     // Optimized matching loop - minimize allocations and checks
-                    while remaining_qty > Quantity::ZERO && !price_level.is_empty() {
+                    while self.whole_lots(remaining_qty) > Quantity::ZERO && trades.len() < max_fills && !price_level.is_empty() {
                         if let Some(matching_order_id) = price_level.front_order() {
                             if let Some(mut matching_order_entry) = self.orders.get_mut(&matching_order_id) {
                                 let matching_order = matching_order_entry.value_mut();
-                                let trade_qty = remaining_qty.min(matching_order.remaining_quantity());
                                 
-                                // Skip zero-quantity trades
+                                // Cancel the taker's own resting order instead of trading with it
+                                if self.self_trade_prevention.is_some_and(|stp| stp.is_self_trade(order, matching_order)) {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        self_trades.push(matching_order.id);
+                                        if track_l3 {
+                                            l3_deltas.push(L3Delta::delete(matching_order));
+                                        }
+                                    }
+                                    continue;
+                                }
+                                
+                                let trade_qty = self.whole_lots(remaining_qty.min(matching_order.remaining_quantity()));
+                                
+                                // Skip zero-quantity trades, cancelling a maker left with only dust
                                 if trade_qty == Quantity::ZERO {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        dust.push(matching_order.id);
+                                        if track_l3 {
+                                            l3_deltas.push(L3Delta::delete(matching_order));
+                                        }
+                                    }
+                                    continue;
+                                }
+                                
+                                // Never trade against a maker cancelled or filled while it was queued
+                                if matching_order.fill(trade_qty).is_err() {
                                     price_level.pop_front_order();
                                     continue;
                                 }
@@ -250,16 +1379,28 @@ impl OrderBook {
                                     trade_qty,
                                     order.client_id,
                                     matching_order.client_id,
-                                ));
+                                    order.side,
+                                ).with_timestamp(self.clock.now()));
                                 
                                 // Batch updates
-                                order.fill(trade_qty);
-                                matching_order.fill(trade_qty);
+                                order.fill(trade_qty).expect("a live taker accepts fills up to its quantity");
                                 remaining_qty -= trade_qty;
                                 price_level.reduce_quantity(trade_qty);
                                 
+                                if let (Some(reports), Some(trade)) = (reports.as_deref_mut(), trades.last()) {
+                                    self.record_fill(reports, trade, order, matching_order);
+                                }
+                                
                                 if matching_order.is_fully_filled() {
                                     price_level.pop_front_order();
+                                } else if self.whole_lots(matching_order.remaining_quantity()) == Quantity::ZERO {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        dust.push(matching_order.id);
+                                    }
+                                }
+                                if track_l3 {
+                                    l3_deltas.push(L3Delta::after_fill(matching_order));
                                 }
                             } else {
                                 price_level.pop_front_order();
@@ -275,13 +1416,15 @@ impl OrderBook {
                 }
                 
                 for price in prices_to_remove {
-                    self.asks.remove(&price);
+                    if let Some(entry) = self.asks.remove(&price) {
+                        self.recycle_level(entry.value());
+                    }
                 }
             },
             Side::Sell => {
-                // For sell orders, match against bids (buys)
-                for entry in self.bids.iter().rev() {
-                    if remaining_qty == Quantity::ZERO {
+                // For sell orders, match against bids (buys), best (highest) first
+                for entry in self.bids.iter() {
+                    if self.whole_lots(remaining_qty) == Quantity::ZERO || trades.len() >= max_fills {
                         break;
                     }
                     
@@ -292,14 +1435,39 @@ impl OrderBook {
                     
                     let mut price_level = entry.value().write();
                     
-                    while remaining_qty > Quantity::ZERO && !price_level.is_empty() {
+                    while self.whole_lots(remaining_qty) > Quantity::ZERO && trades.len() < max_fills && !price_level.is_empty() {
                         if let Some(matching_order_id) = price_level.front_order() {
                             if let Some(mut matching_order_entry) = self.orders.get_mut(&matching_order_id) {
                                 let matching_order = matching_order_entry.value_mut();
-                                let trade_qty = remaining_qty.min(matching_order.remaining_quantity());
                                 
-                                // Skip zero-quantity trades
+                                // Cancel the taker's own resting order instead of trading with it
+                                if self.self_trade_prevention.is_some_and(|stp| stp.is_self_trade(order, matching_order)) {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        self_trades.push(matching_order.id);
+                                        if track_l3 {
+                                            l3_deltas.push(L3Delta::delete(matching_order));
+                                        }
+                                    }
+                                    continue;
+                                }
+                                
+                                let trade_qty = self.whole_lots(remaining_qty.min(matching_order.remaining_quantity()));
+                                
+                                // Skip zero-quantity trades, cancelling a maker left with only dust
                                 if trade_qty == Quantity::ZERO {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        dust.push(matching_order.id);
+                                        if track_l3 {
+                                            l3_deltas.push(L3Delta::delete(matching_order));
+                                        }
+                                    }
+                                    continue;
+                                }
+                                
+                                // Never trade against a maker cancelled or filled while it was queued
+                                if matching_order.fill(trade_qty).is_err() {
                                     price_level.pop_front_order();
                                     continue;
                                 }
@@ -312,19 +1480,31 @@ impl OrderBook {
                                     trade_qty,
                                     matching_order.client_id,
                                     order.client_id,
-                                );
+                                    order.side,
+                                ).with_timestamp(self.clock.now());
                                 
-                                order.fill(trade_qty);
-                                matching_order.fill(trade_qty);
+                                order.fill(trade_qty).expect("a live taker accepts fills up to its quantity");
                                 
                                 remaining_qty -= trade_qty;
                                 price_level.reduce_quantity(trade_qty);
                                 
-                                trades.push(trade);
+                                if let Some(reports) = reports.as_deref_mut() {
+                                    self.record_fill(reports, &trade, order, matching_order);
+                                }
                                 
                                 if matching_order.is_fully_filled() {
                                     price_level.pop_front_order();
+                                } else if self.whole_lots(matching_order.remaining_quantity()) == Quantity::ZERO {
+                                    price_level.pop_front_order();
+                                    if Self::cancel_resting(&mut price_level, matching_order) {
+                                        dust.push(matching_order.id);
+                                    }
+                                }
+                                if track_l3 {
+                                    l3_deltas.push(L3Delta::after_fill(matching_order));
                                 }
+                                
+                                trades.push(trade);
                             } else {
                                 price_level.pop_front_order();
                             }
@@ -339,23 +1519,113 @@ impl OrderBook {
                 }
                 
                 for price in prices_to_remove {
-                    self.bids.remove(&std::cmp::Reverse(price));
+                    if let Some(entry) = self.bids.remove(&std::cmp::Reverse(price)) {
+                        self.recycle_level(entry.value());
+                    }
                 }
             }
         }
         
         // Update cache after matching
         self.update_best_price_cache();
+        if !dust.is_empty() || !self_trades.is_empty() {
+            self.retire(
+                &mut self.retired.lock(),
+                dust.iter()
+                    .chain(&self_trades)
+                    .filter_map(|order_id| self.orders.remove(order_id))
+                    .map(|(_, order)| {
+                        self.unindex_client(&order);
+                        order
+                    }),
+            );
+        }
+        if !l3_deltas.is_empty() {
+            self.publish_l3(l3_deltas);
+        }
         
         if trades.is_empty() {
-            MatchResult::NoMatch
-        } else if remaining_qty > Quantity::ZERO {
+            return MatchResult::NoMatch;
+        }
+        self.total_trades.fetch_add(trades.len() as u64, Ordering::Relaxed);
+        
+        // Every fill is at the resting level's price, never beyond the limit
+        let price_improvement = if is_market {
+            0.0
+        } else {
+            trades
+                .iter()
+                .map(|trade| {
+                    let per_unit = match order.side {
+                        Side::Buy => order.price.to_f64() - trade.price.to_f64(),
+                        Side::Sell => trade.price.to_f64() - order.price.to_f64(),
+                    };
+                    per_unit * trade.quantity.to_f64()
+                })
+                .sum()
+        };
+        
+        if remaining_qty > Quantity::ZERO {
             MatchResult::PartialMatch {
                 trades,
                 remaining_quantity: remaining_qty,
+                price_improvement,
             }
         } else {
-            MatchResult::FullMatch { trades }
+            MatchResult::FullMatch { trades, price_improvement }
+        }
+    }
+    
+    #[inline]
+    fn whole_lots(&self, quantity: Quantity) -> Quantity {
+        match self.lot_model.lot() {
+            Some(lot) => quantity.round_down_to(lot),
+            None => quantity,
+        }
+    }
+    
+    /// Cancel a maker already popped from `price_level` whose remainder is below one lot.
+    /// Returns whether it was live, and so now needs retiring.
+    fn cancel_resting(price_level: &mut PriceLevel, maker: &mut Order) -> bool {
+        if maker.cancel().is_err() {
+            return false;
+        }
+        price_level.reduce_quantity(maker.remaining_quantity());
+        true
+    }
+    
+    fn record_fill(&self, reports: &mut Vec<ExecutionReport>, trade: &Trade, taker: &Order, maker: &Order) {
+        for (own, counterparty, liquidity) in [
+            (taker, maker, LiquidityFlag::Taker),
+            (maker, taker, LiquidityFlag::Maker),
+        ] {
+            reports.push(ExecutionReport {
+                sequence: self.sequence_number.fetch_add(1, Ordering::Relaxed) + 1,
+                trade_id: trade.id,
+                symbol: trade.symbol.clone(),
+                order_id: own.id,
+                counterparty_order_id: counterparty.id,
+                side: own.side,
+                fill_price: trade.price,
+                fill_quantity: trade.quantity,
+                cumulative_quantity: own.filled_quantity,
+                liquidity,
+                timestamp: trade.timestamp,
+            });
+        }
+    }
+    
+    fn index_client(&self, order: &Order) {
+        self.client_index.entry(order.client_id).or_default().insert(order.id);
+    }
+    
+    fn unindex_client(&self, order: &Order) {
+        if let Some(mut order_ids) = self.client_index.get_mut(&order.client_id) {
+            order_ids.remove(&order.id);
+            if order_ids.is_empty() {
+                drop(order_ids);
+                self.client_index.remove_if(&order.client_id, |_, order_ids| order_ids.is_empty());
+            }
         }
     }
     
@@ -363,7 +1633,7 @@ impl OrderBook {
         match order.side {
             Side::Buy => {
                 let price_level = self.bids
-                    .get_or_insert_with(std::cmp::Reverse(order.price), || Arc::new(RwLock::new(PriceLevel::new(order.price))))
+                    .get_or_insert_with(std::cmp::Reverse(order.price), || self.new_level(order.price))
                     .value()
                     .clone();
                 
@@ -371,7 +1641,7 @@ impl OrderBook {
             },
             Side::Sell => {
                 let price_level = self.asks
-                    .get_or_insert_with(order.price, || Arc::new(RwLock::new(PriceLevel::new(order.price))))
+                    .get_or_insert_with(order.price, || self.new_level(order.price))
                     .value()
                     .clone();
                 
@@ -380,6 +1650,69 @@ impl OrderBook {
         }
     }
     
+    fn new_level(&self, price: Price) -> Arc<RwLock<PriceLevel>> {
+        match &self.level_pool {
+            Some(pool) => pool.acquire(
+                |level| level.get_mut().reset(price),
+                || RwLock::new(PriceLevel::new(price)),
+            ),
+            None => Arc::new(RwLock::new(PriceLevel::new(price))),
+        }
+    }
+    
+    /// Hand a level just removed from the book back to the pool. The skip map may still hold
+    /// it until its node is reclaimed, so the pool only reuses it once this is the last handle.
+    #[inline]
+    fn recycle_level(&self, level: &Arc<RwLock<PriceLevel>>) {
+        if let Some(pool) = &self.level_pool {
+            pool.release(level.clone());
+        }
+    }
+    
+    /// Drop levels farthest from the touch until `side` is back within the cap
+    fn enforce_level_cap(&self, side: Side) {
+        let Some(cap) = self.level_cap else {
+            return;
+        };
+        
+        loop {
+            let evicted_orders: Vec<OrderId> = match side {
+                Side::Buy if self.bids.len() > cap.max_levels(Side::Buy) => self.bids
+                    .pop_back()
+                    .map(|entry| self.evict_level(entry.value()))
+                    .unwrap_or_default(),
+                Side::Sell if self.asks.len() > cap.max_levels(Side::Sell) => self.asks
+                    .pop_back()
+                    .map(|entry| self.evict_level(entry.value()))
+                    .unwrap_or_default(),
+                _ => break,
+            };
+            
+            let mut cancelled = Vec::new();
+            let mut l3_deltas = Vec::new();
+            for order_id in &evicted_orders {
+                if let Some((_, mut order)) = self.orders.remove(order_id) {
+                    self.unindex_client(&order);
+                    if order.cancel().is_ok() {
+                        l3_deltas.push(L3Delta::delete(&order));
+                        cancelled.push(order);
+                    }
+                }
+            }
+            self.retire(&mut self.retired.lock(), cancelled);
+            if self.l3_enabled.load(Ordering::Relaxed) {
+                self.publish_l3(l3_deltas);
+            }
+            self.evicted_levels.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    fn evict_level(&self, level: &Arc<RwLock<PriceLevel>>) -> Vec<OrderId> {
+        let orders = level.read().orders().iter().copied().collect();
+        self.recycle_level(level);
+        orders
+    }
+    
     fn remove_order_from_book(&self, order: &Order) {
         match order.side {
             Side::Buy => {
@@ -387,7 +1720,9 @@ impl OrderBook {
                     let mut price_level = entry.value().write();
                     if price_level.remove_order(order.id, order.remaining_quantity()) && price_level.is_empty() {
                         drop(price_level);
-                        self.bids.remove(&std::cmp::Reverse(order.price));
+                        if let Some(removed) = self.bids.remove(&std::cmp::Reverse(order.price)) {
+                            self.recycle_level(removed.value());
+                        }
                     }
                 }
             },
@@ -396,7 +1731,9 @@ impl OrderBook {
                     let mut price_level = entry.value().write();
                     if price_level.remove_order(order.id, order.remaining_quantity()) && price_level.is_empty() {
                         drop(price_level);
-                        self.asks.remove(&order.price);
+                        if let Some(removed) = self.asks.remove(&order.price) {
+                            self.recycle_level(removed.value());
+                        }
                     }
                 }
             }
@@ -409,10 +1746,26 @@ impl OrderBook {
 
 impl Clone for OrderBook {
     fn clone(&self) -> Self {
-        let new_book = Self::new(self.symbol.clone());
+        let mut new_book = Self::new(self.symbol.clone());
+        new_book.level_cap = self.level_cap;
+        new_book.clock = self.clock.clone();
+        new_book.retired_limit = self.retired_limit;
+        new_book.size_limits = self.size_limits;
+        new_book.lot_model = self.lot_model;
+        new_book.self_trade_prevention = self.self_trade_prevention;
+        new_book.level_pool = self.level_pool.as_ref().map(|pool| ArcPool::new(pool.max_idle()));
+        new_book.match_limit = self.match_limit;
+        new_book.pre_match_hook = self.pre_match_hook.clone();
+        new_book.spread_tracker = self.spread_tracker.as_ref().map(|tracker| Mutex::new(SpreadTracker::new(tracker.lock().max_window())));
+        new_book.trade_window = self.trade_window.as_ref().map(|window| Mutex::new(TradeWindow::new(window.lock().max_window())));
+        new_book.imbalance_monitor = self.imbalance_monitor.as_ref().map(|monitor| {
+            let monitor = monitor.lock();
+            Mutex::new(ImbalanceMonitor::new(monitor.config(), monitor.sender().clone()))
+        });
         
         for entry in self.orders.iter() {
             let order = entry.value().clone();
+            new_book.index_client(&order);
             new_book.orders.insert(*entry.key(), order.clone());
             new_book.insert_order_to_book(&order);
         }
@@ -428,6 +1781,7 @@ unsafe impl Sync for OrderBook {}
 mod tests {
     use super::*;
     use crate::types::{OrderType, OrderStatus};
+    use crate::touch::ImbalanceSide;
     use uuid::Uuid;
 
     fn create_test_order(
@@ -500,7 +1854,7 @@ mod tests {
         let result = book.add_order(buy_order);
         
         match result {
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 assert_eq!(trades.len(), 1);
                 assert_eq!(trades[0].price, Price::new(50000.0));
                 assert_eq!(trades[0].quantity, Quantity::new(1.0));
@@ -526,7 +1880,7 @@ mod tests {
         let result = book.add_order(buy_order);
         
         match result {
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 assert_eq!(trades.len(), 1);
                 assert_eq!(trades[0].quantity, Quantity::new(1.0));
             },
@@ -556,7 +1910,7 @@ mod tests {
         let result = book.add_order(buy_order);
         
         match result {
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 assert_eq!(trades.len(), 2); // Should match first two levels
                 assert_eq!(trades[0].price, Price::new(50000.0));
                 assert_eq!(trades[1].price, Price::new(50100.0));
@@ -656,6 +2010,17 @@ mod tests {
         assert_eq!(cloned_book.total_volume(Side::Buy), book.total_volume(Side::Buy));
         assert_eq!(cloned_book.total_volume(Side::Sell), book.total_volume(Side::Sell));
     }
+    
+    #[test]
+    fn test_cloned_book_keeps_its_lot_model() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_lot_model(LotModel::Integer(1));
+        let cloned_book = book.clone();
+        
+        assert_eq!(cloned_book.lot_model(), book.lot_model());
+        let odd_lot = create_test_order("BTCUSD", Side::Buy, 50000.0, 0.3);
+        assert!(book.check_order(&odd_lot).is_err());
+        assert!(cloned_book.check_order(&odd_lot).is_err());
+    }
 
     #[test]
     fn test_no_self_matching() {
@@ -711,7 +2076,7 @@ mod tests {
         let result = book.add_order(buy_order);
         
         match result {
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 assert_eq!(trades.len(), 1);
                 assert_eq!(trades[0].price, Price::new(50000.0)); // Should match best price
             },
@@ -735,4 +2100,1043 @@ mod tests {
         assert!(snapshot.bids.is_empty());
         assert!(snapshot.asks.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_memory_footprint() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        assert_eq!(book.memory_footprint().total_bytes(), 0);
+
+        for i in 0..10 {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, 49000.0 + i as f64, 1.0));
+            book.add_order(create_test_order("BTCUSD", Side::Sell, 51000.0 + i as f64, 1.0));
+        }
+        let small = book.memory_footprint();
+        assert_eq!(small.order_count, 20);
+        assert_eq!(small.bid_levels, 10);
+        assert_eq!(small.ask_levels, 10);
+
+        for i in 0..90 {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, 48000.0 + i as f64, 1.0));
+            book.add_order(create_test_order("BTCUSD", Side::Sell, 52000.0 + i as f64, 1.0));
+        }
+        let large = book.memory_footprint();
+        assert_eq!(large.order_count, 200);
+        assert_eq!(large.level_count(), 200);
+        assert!(large.orders_bytes >= 10 * small.orders_bytes);
+        assert!(large.total_bytes() > small.total_bytes());
+    }
+
+    #[test]
+    fn test_execution_reports_maker_taker() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let resting1 = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        let resting2 = create_test_order("BTCUSD", Side::Sell, 50100.0, 2.0);
+        let (resting1_id, resting2_id) = (resting1.id, resting2.id);
+        book.add_order(resting1);
+        book.add_order(resting2);
+
+        let aggressor = create_test_order("BTCUSD", Side::Buy, 50100.0, 2.5);
+        let aggressor_id = aggressor.id;
+        let (result, reports) = book.add_order_with_reports(aggressor);
+        assert!(matches!(result, MatchResult::FullMatch { .. }));
+
+        let taker: Vec<_> = reports.iter().filter(|r| r.order_id == aggressor_id).collect();
+        assert_eq!(taker.len(), 2);
+        assert!(taker.iter().all(|r| r.liquidity == LiquidityFlag::Taker));
+        assert_eq!(taker[0].counterparty_order_id, resting1_id);
+        assert_eq!(taker[0].fill_quantity, Quantity::new(1.0));
+        assert_eq!(taker[0].cumulative_quantity, Quantity::new(1.0));
+        assert_eq!(taker[1].counterparty_order_id, resting2_id);
+        assert_eq!(taker[1].fill_price, Price::new(50100.0));
+        assert_eq!(taker[1].fill_quantity, Quantity::new(1.5));
+        assert_eq!(taker[1].cumulative_quantity, Quantity::new(2.5));
+
+        let maker: Vec<_> = reports.iter().filter(|r| r.is_maker()).collect();
+        assert_eq!(maker.len(), 2);
+        assert_eq!(maker[0].order_id, resting1_id);
+        assert_eq!(maker[0].cumulative_quantity, Quantity::new(1.0));
+        assert_eq!(maker[1].order_id, resting2_id);
+        assert_eq!(maker[1].cumulative_quantity, Quantity::new(1.5));
+
+        assert!(reports.windows(2).all(|w| w[0].sequence < w[1].sequence));
+    }
+
+    #[test]
+    fn test_level_cap_reject_policy() {
+        let book = OrderBook::with_level_cap("BTCUSD".to_string(), LevelCap::new(3, LevelCapPolicy::Reject));
+        for price in [49900.0, 49800.0, 49700.0] {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, price, 1.0));
+        }
+
+        // Far from touch and inside the book are both refused once the side is full
+        let far = create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0);
+        assert!(matches!(
+            book.check_level_cap(&far),
+            Err(OrderBookError::LevelCapExceeded { max_levels: 3, .. })
+        ));
+        let far_id = far.id;
+        assert!(matches!(book.add_order(far), MatchResult::Rejected { .. }));
+        assert!(book.get_order(far_id).is_none());
+
+        let inside = create_test_order("BTCUSD", Side::Buy, 49850.0, 1.0);
+        assert!(book.check_level_cap(&inside).is_err());
+        book.add_order(inside);
+
+        // Joining an existing level is always allowed
+        let joining = create_test_order("BTCUSD", Side::Buy, 49700.0, 2.0);
+        assert!(book.check_level_cap(&joining).is_ok());
+        book.add_order(joining);
+
+        let snapshot = book.depth(usize::MAX);
+        assert_eq!(snapshot.bids.len(), 3);
+        assert_eq!(snapshot.bids[2], (Price::new(49700.0), Quantity::new(3.0)));
+        assert_eq!(book.evicted_levels(), 0);
+    }
+
+    #[test]
+    fn test_level_cap_evict_policy() {
+        let book = OrderBook::with_level_cap("BTCUSD".to_string(), LevelCap::new(3, LevelCapPolicy::EvictWorst));
+        let mut asks = Vec::new();
+        for price in [50100.0, 50200.0, 50300.0] {
+            let order = create_test_order("BTCUSD", Side::Sell, price, 1.0);
+            asks.push(order.id);
+            book.add_order(order);
+        }
+
+        // A level beyond the worst is still rejected rather than evicting a closer one
+        let far = create_test_order("BTCUSD", Side::Sell, 51000.0, 1.0);
+        assert!(book.check_level_cap(&far).is_err());
+        book.add_order(far);
+        assert_eq!(book.depth(usize::MAX).asks.len(), 3);
+
+        // A new level inside the book pushes out the worst one
+        for price in [50150.0, 50050.0] {
+            let order = create_test_order("BTCUSD", Side::Sell, price, 1.0);
+            assert!(book.check_level_cap(&order).is_ok());
+            book.add_order(order);
+        }
+
+        let prices: Vec<_> = book.depth(usize::MAX).asks.iter().map(|(price, _)| *price).collect();
+        assert_eq!(prices, vec![Price::new(50050.0), Price::new(50100.0), Price::new(50150.0)]);
+        assert_eq!(book.evicted_levels(), 2);
+        assert!(book.get_order(asks[1]).is_none());
+        assert!(book.get_order(asks[2]).is_none());
+        assert!(book.get_order(asks[0]).is_some());
+        assert_eq!(book.best_ask(), Some(Price::new(50050.0)));
+    }
+
+    #[test]
+    fn test_asymmetric_level_caps_bound_each_side_independently() {
+        let cap = LevelCap::asymmetric(5, 2, LevelCapPolicy::EvictWorst);
+        assert_eq!((cap.max_levels(Side::Buy), cap.max_levels(Side::Sell)), (5, 2));
+        let book = OrderBook::with_level_cap("BTCUSD".to_string(), cap);
+
+        // Each new level is closer to the touch than the last, so it pushes out the worst
+        for i in 0..10 {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, 49000.0 + 100.0 * i as f64, 1.0));
+            book.add_order(create_test_order("BTCUSD", Side::Sell, 51000.0 - 100.0 * i as f64, 1.0));
+        }
+
+        let depth = book.depth(usize::MAX);
+        let bids: Vec<_> = depth.bids.iter().map(|(price, _)| *price).collect();
+        let asks: Vec<_> = depth.asks.iter().map(|(price, _)| *price).collect();
+        assert_eq!(bids, [49900.0, 49800.0, 49700.0, 49600.0, 49500.0].map(Price::new));
+        assert_eq!(asks, [50100.0, 50200.0].map(Price::new));
+        assert_eq!(book.evicted_levels(), 5 + 8);
+
+        // A passive level beyond the worst ask is refused against the ask side's own cap
+        let far_ask = create_test_order("BTCUSD", Side::Sell, 50500.0, 1.0);
+        assert!(matches!(
+            book.check_level_cap(&far_ask),
+            Err(OrderBookError::LevelCapExceeded { max_levels: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_iceberg_depth_modes() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        book.add_order(
+            create_test_order("BTCUSD", Side::Sell, 50100.0, 10.0).with_display_quantity(Quantity::new(1.0))
+        );
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 0.5));
+        book.add_order(
+            create_test_order("BTCUSD", Side::Buy, 49900.0, 5.0).with_display_quantity(Quantity::new(2.0))
+        );
+
+        let public = book.depth(10);
+        assert_eq!(public.asks, book.depth_with_mode(10, DepthMode::Public).asks);
+        assert_eq!(public.asks, vec![(Price::new(50100.0), Quantity::new(1.5))]);
+        assert_eq!(public.bids, vec![(Price::new(49900.0), Quantity::new(2.0))]);
+
+        let internal = book.depth_with_mode(10, DepthMode::Internal);
+        assert_eq!(internal.asks, vec![(Price::new(50100.0), Quantity::new(10.5))]);
+        assert_eq!(internal.bids, vec![(Price::new(49900.0), Quantity::new(5.0))]);
+
+        // Hidden reserve still trades; the display slice never exceeds what is left
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50100.0, 9.8));
+        let public = book.depth(10);
+        let internal = book.depth_with_mode(10, DepthMode::Internal);
+        assert_eq!(public.asks[0].1, Quantity::new(0.7));
+        assert_eq!(internal.asks[0].1, Quantity::new(0.7));
+    }
+    
+    #[test]
+    fn test_duplicate_order_id_rejected() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let first = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let mut duplicate = create_test_order("BTCUSD", Side::Sell, 49000.0, 2.0);
+        duplicate.id = first.id;
+        
+        book.add_order(first.clone());
+        
+        assert!(matches!(
+            book.check_order(&duplicate),
+            Err(OrderBookError::OrderAlreadyExists { order_id }) if order_id == first.id
+        ));
+        assert!(matches!(book.add_order(duplicate), MatchResult::Rejected { .. }));
+        
+        let resting = book.get_order(first.id).unwrap();
+        assert_eq!(resting.side, Side::Buy);
+        assert_eq!(resting.remaining_quantity(), Quantity::new(1.0));
+        assert_eq!(book.best_bid(), Some(Price::new(50000.0)));
+        assert_eq!(book.best_ask(), None);
+    }
+    
+    #[test]
+    fn test_order_size_limits() {
+        let limits = OrderSizeLimits::new(Quantity::new(0.01), Quantity::new(10.0));
+        let book = OrderBook::new("BTCUSD".to_string()).with_size_limits(limits);
+        assert_eq!(book.size_limits(), limits);
+        
+        let dust = create_test_order("BTCUSD", Side::Buy, 50000.0, 0.001);
+        assert!(matches!(
+            book.check_order(&dust),
+            Err(OrderBookError::OrderBelowMinimumSize { min_quantity, .. }) if min_quantity == Quantity::new(0.01)
+        ));
+        assert!(matches!(book.add_order(dust.clone()), MatchResult::Rejected { .. }));
+        assert!(book.get_order(dust.id).is_none());
+        
+        let oversized = create_test_order("BTCUSD", Side::Sell, 50000.0, 25.0);
+        assert!(matches!(
+            book.check_order(&oversized),
+            Err(OrderBookError::OrderAboveMaximumSize { max_quantity, .. }) if max_quantity == Quantity::new(10.0)
+        ));
+        
+        let boundary = create_test_order("BTCUSD", Side::Buy, 50000.0, 10.0);
+        assert!(book.check_order(&boundary).is_ok());
+        assert_eq!(book.clone().size_limits(), limits);
+    }
+    
+    #[test]
+    fn test_estimate_fill_leaves_book_untouched() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 2.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50200.0, 4.0));
+        
+        let estimate = book.estimate_fill(Side::Buy, Quantity::new(2.0), None);
+        assert_eq!(estimate.levels, vec![
+            (Price::new(50000.0), Quantity::new(1.0)),
+            (Price::new(50100.0), Quantity::new(1.0)),
+        ]);
+        assert_eq!(estimate.remaining_quantity, Quantity::ZERO);
+        assert!((estimate.average_price.unwrap().to_f64() - 50050.0).abs() < 0.05);
+        
+        let limited = book.estimate_fill(Side::Buy, Quantity::new(10.0), Some(Price::new(50100.0)));
+        assert_eq!(limited.filled_quantity, Quantity::new(3.0));
+        assert_eq!(limited.remaining_quantity, Quantity::new(7.0));
+        
+        assert!(book.estimate_fill(Side::Sell, Quantity::new(1.0), None).levels.is_empty());
+        assert_eq!(book.depth(10).asks.len(), 3);
+    }
+    
+    #[test]
+    fn test_market_buy_sweeps_levels_and_never_rests() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50200.0, 1.0));
+        
+        // Nominal price of zero would cross nothing as a limit order
+        let market_buy = Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            Price::new(0.0),
+            Quantity::new(2.5),
+            Uuid::new_v4(),
+        );
+        match book.add_order(market_buy.clone()) {
+            MatchResult::FullMatch { trades, .. } => {
+                let prices: Vec<Price> = trades.iter().map(|trade| trade.price).collect();
+                assert_eq!(prices, vec![Price::new(50000.0), Price::new(50100.0), Price::new(50200.0)]);
+                assert_eq!(trades[2].quantity, Quantity::new(0.5));
+            }
+            other => panic!("Expected full match, got {:?}", other),
+        }
+        assert_eq!(book.best_ask(), Some(Price::new(50200.0)));
+        assert!(book.get_order(market_buy.id).is_none());
+        
+        // More than the book holds: fills what exists and drops the rest
+        let oversized = Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            Price::new(0.0),
+            Quantity::new(5.0),
+            Uuid::new_v4(),
+        );
+        assert!(matches!(
+            book.add_order(oversized.clone()),
+            MatchResult::PartialMatch { remaining_quantity, .. } if remaining_quantity == Quantity::new(4.5)
+        ));
+        assert!(book.get_order(oversized.id).is_none());
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), None);
+        assert!(book.depth(10).bids.is_empty());
+    }
+    
+    #[test]
+    fn test_aggressive_buy_fills_at_resting_prices_with_improvement() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0));
+        
+        let result = book.add_order(create_test_order("BTCUSD", Side::Buy, 50150.0, 2.0));
+        match &result {
+            MatchResult::FullMatch { trades, price_improvement } => {
+                let prices: Vec<Price> = trades.iter().map(|trade| trade.price).collect();
+                assert_eq!(prices, vec![Price::new(50000.0), Price::new(50100.0)]);
+                // (50150 - 50000) * 1 + (50150 - 50100) * 1
+                assert_eq!(*price_improvement, 200.0);
+            }
+            other => panic!("Expected full match, got {:?}", other),
+        }
+        assert_eq!(result.price_improvement(), 200.0);
+        
+        // Filling exactly at the limit is no improvement
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50200.0, 1.0));
+        let at_limit = book.add_order(create_test_order("BTCUSD", Side::Buy, 50200.0, 1.0));
+        assert!(matches!(at_limit, MatchResult::FullMatch { .. }));
+        assert_eq!(at_limit.price_improvement(), 0.0);
+    }
+    
+    #[test]
+    fn test_aggressive_sell_takes_best_bids_first_without_trading_through() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50100.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50050.0, 1.0));
+        
+        let result = book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 3.0));
+        match &result {
+            MatchResult::PartialMatch { trades, remaining_quantity, price_improvement } => {
+                let prices: Vec<Price> = trades.iter().map(|trade| trade.price).collect();
+                assert_eq!(prices, vec![Price::new(50100.0), Price::new(50050.0)]);
+                assert!(trades.iter().all(|trade| trade.price >= Price::new(50000.0)));
+                assert_eq!(*remaining_quantity, Quantity::new(1.0));
+                assert_eq!(*price_improvement, 150.0);
+            }
+            other => panic!("Expected partial match, got {:?}", other),
+        }
+        
+        // The remainder rests at its limit without crossing the 49900 bid
+        assert_eq!(book.best_bid(), Some(Price::new(49900.0)));
+        assert_eq!(book.best_ask(), Some(Price::new(50000.0)));
+        
+        let market = Order::new(
+            "BTCUSD".to_string(),
+            Side::Sell,
+            OrderType::Market,
+            Price::ZERO,
+            Quantity::new(1.0),
+            Uuid::new_v4(),
+        );
+        let result = book.add_order(market);
+        assert!(matches!(result, MatchResult::FullMatch { .. }));
+        assert_eq!(result.price_improvement(), 0.0);
+    }
+    
+    #[test]
+    fn test_archive_terminal_moves_filled_and_cancelled_orders_to_sink() {
+        struct FailingSink;
+        impl ArchiveSink for FailingSink {
+            fn archive(&mut self, _orders: &[Order]) -> std::io::Result<()> {
+                Err(std::io::Error::other("store unavailable"))
+            }
+        }
+        
+        let book = OrderBook::new("BTCUSD".to_string());
+        let maker = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        let partial_maker = create_test_order("BTCUSD", Side::Sell, 50100.0, 2.0);
+        let cancelled = create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0);
+        let resting = create_test_order("BTCUSD", Side::Buy, 49500.0, 1.0);
+        for order in [&maker, &partial_maker, &cancelled, &resting] {
+            book.add_order(order.clone());
+        }
+        
+        // Fills the first maker and half of the second
+        let taker = create_test_order("BTCUSD", Side::Buy, 50100.0, 2.0);
+        assert!(matches!(book.add_order(taker), MatchResult::FullMatch { .. }));
+        book.cancel_order(cancelled.id).unwrap();
+        assert_eq!(book.pending_archive(), 2);
+        
+        // A failed store keeps the batch for the next pass
+        assert!(matches!(book.archive_terminal(&mut FailingSink), Err(OrderBookError::ArchiveFailed { .. })));
+        assert_eq!(book.pending_archive(), 2);
+        
+        let mut archive: Vec<Order> = Vec::new();
+        assert_eq!(book.archive_terminal(&mut archive).unwrap(), 2);
+        archive.sort_by_key(|order| order.price);
+        assert_eq!(archive.iter().map(|order| order.id).collect::<Vec<_>>(), vec![cancelled.id, maker.id]);
+        assert_eq!(archive[0].status, OrderStatus::Cancelled);
+        assert_eq!(archive[1].status, OrderStatus::Filled);
+        assert_eq!(archive[1].filled_quantity, Quantity::new(1.0));
+        
+        // Only resting orders stay live
+        assert_eq!(book.memory_footprint().order_count, 2);
+        assert!(book.get_order(maker.id).is_none());
+        assert_eq!(book.get_order(partial_maker.id).unwrap().remaining_quantity(), Quantity::new(1.0));
+        assert!(book.get_order(resting.id).is_some());
+        assert_eq!(book.pending_archive(), 0);
+        assert_eq!(book.archive_terminal(&mut archive).unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_retired_buffer_drops_oldest_orders_past_its_limit() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_retired_limit(2);
+        let orders: Vec<_> = (0..3)
+            .map(|i| create_test_order("BTCUSD", Side::Buy, 49000.0 + i as f64, 1.0))
+            .collect();
+        for order in &orders {
+            book.add_order(order.clone());
+            book.cancel_order(order.id).unwrap();
+        }
+        
+        assert_eq!(book.pending_archive(), 2);
+        assert_eq!(book.dropped_retired(), 1);
+        let mut archive: Vec<Order> = Vec::new();
+        assert_eq!(book.archive_terminal(&mut archive).unwrap(), 2);
+        assert_eq!(archive.iter().map(|order| order.id).collect::<Vec<_>>(), vec![orders[1].id, orders[2].id]);
+    }
+    
+    #[test]
+    fn test_filled_and_cancelled_orders_cannot_change_state_in_book() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let maker = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        book.add_order(maker.clone());
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0));
+        
+        // The filled maker is still in the order map but can no longer be cancelled
+        assert!(matches!(
+            book.try_cancel_order(maker.id),
+            Err(OrderBookError::IllegalTransition { from: OrderStatus::Filled, to: OrderStatus::Cancelled, .. })
+        ));
+        assert_eq!(book.get_order(maker.id).unwrap().status, OrderStatus::Filled);
+        assert!(matches!(book.try_cancel_order(OrderId::new()), Err(OrderBookError::OrderNotFound { .. })));
+        
+        let resting = create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0);
+        book.add_order(resting.clone());
+        assert_eq!(book.try_cancel_order(resting.id).unwrap().status, OrderStatus::Cancelled);
+        
+        // A cancelled order handed back to the book is refused rather than matched
+        let mut cancelled = create_test_order("BTCUSD", Side::Buy, 50100.0, 1.0);
+        cancelled.cancel().unwrap();
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0));
+        assert!(book.check_order(&cancelled).is_err());
+        assert!(matches!(book.add_order(cancelled), MatchResult::Rejected { .. }));
+        assert_eq!(book.best_ask(), Some(Price::new(50100.0)));
+    }
+    
+    #[test]
+    fn test_trades_record_the_incoming_side_as_aggressor() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let resting_sell = create_test_order("BTCUSD", Side::Sell, 50000.0, 2.0);
+        let resting_buy = create_test_order("BTCUSD", Side::Buy, 49900.0, 2.0);
+        book.add_order(resting_sell.clone());
+        book.add_order(resting_buy.clone());
+        
+        let lift = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let MatchResult::FullMatch { trades, .. } = book.add_order(lift.clone()) else {
+            panic!("Expected the buy to fill");
+        };
+        assert_eq!(trades[0].aggressor_side, Side::Buy);
+        assert_eq!((trades[0].buyer_order_id, trades[0].seller_order_id), (lift.id, resting_sell.id));
+        
+        let hit = create_test_order("BTCUSD", Side::Sell, 49900.0, 1.0);
+        let MatchResult::FullMatch { trades, .. } = book.add_order(hit.clone()) else {
+            panic!("Expected the sell to fill");
+        };
+        assert_eq!(trades[0].aggressor_side, Side::Sell);
+        assert_eq!((trades[0].buyer_order_id, trades[0].seller_order_id), (resting_buy.id, hit.id));
+    }
+    
+    #[test]
+    fn test_l3_snapshot_lists_every_order_in_price_time_priority() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let bid_first = create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0);
+        let bid_second = create_test_order("BTCUSD", Side::Buy, 49900.0, 2.0);
+        let bid_better = create_test_order("BTCUSD", Side::Buy, 49950.0, 0.5);
+        let ask_first = create_test_order("BTCUSD", Side::Sell, 50000.0, 3.0);
+        let ask_second = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.5);
+        let ask_worse = create_test_order("BTCUSD", Side::Sell, 50100.0, 2.0);
+        for order in [&bid_first, &bid_second, &bid_better, &ask_first, &ask_second, &ask_worse] {
+            book.add_order(order.clone());
+        }
+        let (_, updates) = book.subscribe_l3();
+        
+        // Partially fills the first ask, which keeps its place in the queue
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0));
+        
+        let snapshot = book.l3_snapshot();
+        let ids_and_sizes = |orders: &[L3Order]| -> Vec<(OrderId, Quantity)> {
+            orders.iter().map(|order| (order.order_id, order.remaining_quantity)).collect()
+        };
+        assert_eq!(ids_and_sizes(&snapshot.bids), vec![
+            (bid_better.id, Quantity::new(0.5)),
+            (bid_first.id, Quantity::new(1.0)),
+            (bid_second.id, Quantity::new(2.0)),
+        ]);
+        assert_eq!(ids_and_sizes(&snapshot.asks), vec![
+            (ask_first.id, Quantity::new(2.0)),
+            (ask_second.id, Quantity::new(1.5)),
+            (ask_worse.id, Quantity::new(2.0)),
+        ]);
+        assert_eq!(snapshot.bids[1].timestamp, bid_first.timestamp);
+        
+        // A snapshot kept current from the stream matches the book after a cancel and a sweep
+        let (mut replica, updates_after) = book.subscribe_l3();
+        book.cancel_order(bid_second.id);
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 3.5));
+        for update in updates_after.try_iter() {
+            assert!(replica.apply(&update));
+        }
+        let live = book.l3_snapshot();
+        assert_eq!((replica.sequence, &replica.bids, &replica.asks), (live.sequence, &live.bids, &live.asks));
+        assert_eq!(ids_and_sizes(&live.asks), vec![(ask_worse.id, Quantity::new(2.0))]);
+        assert_eq!(updates.try_iter().count() as u64, live.sequence);
+    }
+    
+    #[test]
+    fn test_lot_size_cancels_sub_lot_residuals_instead_of_resting_them() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_lot_model(LotModel::Lot(Quantity::new(0.25)));
+        
+        // Without a lot size, a 1.0 fill would leave this maker with a sliver no order can clear
+        let maker = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.1);
+        book.add_order(maker.clone());
+        let MatchResult::FullMatch { trades, .. } = book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)) else {
+            panic!("Expected the buy to fill");
+        };
+        assert_eq!(trades[0].quantity, Quantity::new(1.0));
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.total_volume(Side::Sell), Quantity::ZERO);
+        assert!(book.get_order(maker.id).is_none());
+        
+        // A taker's own sub-lot remainder is not rested either
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        let MatchResult::PartialMatch { trades, remaining_quantity, .. } = book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.1)) else {
+            panic!("Expected the buy to leave dust");
+        };
+        assert_eq!(trades[0].quantity, Quantity::new(1.0));
+        assert!(remaining_quantity < Quantity::new(0.25));
+        assert_eq!((book.best_bid(), book.best_ask()), (None, None));
+        
+        let mut archived = Vec::new();
+        book.archive_terminal(&mut archived).unwrap();
+        let dust = archived.iter().find(|order| order.id == maker.id).expect("dust maker is archived");
+        assert_eq!(dust.status, OrderStatus::Cancelled);
+        assert_eq!(dust.filled_quantity, Quantity::new(1.0));
+    }
+    
+    #[test]
+    fn test_negative_prices_order_and_match_like_positive_ones() {
+        let book = OrderBook::new("SPREAD".to_string());
+        book.add_order(create_test_order("SPREAD", Side::Buy, -2.5, 1.0));
+        book.add_order(create_test_order("SPREAD", Side::Buy, -4.0, 1.0));
+        book.add_order(create_test_order("SPREAD", Side::Sell, 0.0, 1.0));
+        book.add_order(create_test_order("SPREAD", Side::Sell, -1.0, 1.0));
+        
+        assert_eq!(book.best_bid(), Some(Price::new(-2.5)));
+        assert_eq!(book.best_ask(), Some(Price::new(-1.0)));
+        assert_eq!(book.mid_price(), Some(Price::new(-1.75)));
+        
+        let MatchResult::FullMatch { trades, .. } = book.add_order(create_test_order("SPREAD", Side::Buy, 0.0, 2.0)) else {
+            panic!("Expected the buy to sweep both asks");
+        };
+        assert_eq!(trades.iter().map(|trade| trade.price).collect::<Vec<_>>(), vec![Price::new(-1.0), Price::ZERO]);
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), Some(Price::new(-2.5)));
+    }
+    
+    #[test]
+    fn test_oldest_order_age_and_orders_older_than_skip_filled_orders() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        assert_eq!(book.oldest_order_age(), None);
+        
+        let rested = |side, price, age_secs| {
+            let mut order = create_test_order("BTCUSD", side, price, 1.0);
+            order.timestamp = crate::clock::now() - chrono::Duration::seconds(age_secs);
+            book.add_order(order.clone());
+            order.id
+        };
+        let filled = rested(Side::Sell, 50100.0, 900);
+        let old_bid = rested(Side::Buy, 49900.0, 600);
+        let older_ask = rested(Side::Sell, 50200.0, 700);
+        rested(Side::Buy, 49800.0, 5);
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50100.0, 1.0));
+        assert!(book.get_order(filled).unwrap().is_fully_filled());
+        
+        let oldest = book.oldest_order_age().unwrap();
+        assert!(oldest >= std::time::Duration::from_secs(700) && oldest < std::time::Duration::from_secs(900));
+        let stale: Vec<OrderId> = book.orders_older_than(std::time::Duration::from_secs(60))
+            .into_iter()
+            .map(|(order_id, _)| order_id)
+            .collect();
+        assert_eq!(stale, vec![older_ask, old_bid]);
+    }
+    
+    #[test]
+    fn test_stats_count_orders_trades_and_levels() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let created = book.stats();
+        assert_eq!((created.total_orders, created.total_trades, created.depth_levels), (0, 0, 0));
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 2.0));
+        let (_, reports) = book.add_order_with_reports(create_test_order("BTCUSD", Side::Buy, 50100.0, 1.5));
+        
+        let stats = book.stats();
+        // The taker fully filled and never rested; the filled maker stays until archived
+        assert_eq!(stats.total_orders, 3);
+        assert_eq!(stats.total_trades, 2);
+        assert_eq!(stats.sequence_number, reports.last().unwrap().sequence);
+        assert_eq!(stats.total_volume, Quantity::new(2.5));
+        assert_eq!((stats.best_bid, stats.best_ask), (Some(Price::new(49900.0)), Some(Price::new(50100.0))));
+        assert_eq!(stats.spread, Some(Price::new(200.0)));
+        assert_eq!(stats.depth_levels, 2);
+        assert!(stats.last_update >= created.last_update);
+    }
+    
+    #[test]
+    fn test_integer_lot_model_rejects_fractional_orders_and_trades_whole_lots() {
+        let book = OrderBook::new("AAPL".to_string()).with_lot_model(LotModel::Integer(1));
+        
+        let fractional = create_test_order("AAPL", Side::Buy, 190.0, 2.5);
+        assert!(matches!(
+            book.check_order(&fractional),
+            Err(OrderBookError::FractionalLot { lot_size: 1, .. })
+        ));
+        assert!(matches!(book.add_order(fractional), MatchResult::Rejected { .. }));
+        assert_eq!(book.best_bid(), None);
+        
+        book.add_order(create_test_order("AAPL", Side::Sell, 190.0, 3.0));
+        book.add_order(create_test_order("AAPL", Side::Sell, 190.5, 4.0));
+        let MatchResult::FullMatch { trades, .. } = book.add_order(create_test_order("AAPL", Side::Buy, 191.0, 5.0)) else {
+            panic!("Expected the buy to fill");
+        };
+        let quantities: Vec<Quantity> = trades.iter().map(|trade| trade.quantity).collect();
+        assert_eq!(quantities, vec![Quantity::new(3.0), Quantity::new(2.0)]);
+        assert_eq!(book.total_volume(Side::Sell), Quantity::new(2.0));
+        
+        let round_lots = OrderBook::new("AAPL".to_string()).with_lot_model(LotModel::Integer(100));
+        assert!(round_lots.check_order(&create_test_order("AAPL", Side::Buy, 190.0, 150.0)).is_err());
+        assert!(round_lots.check_order(&create_test_order("AAPL", Side::Buy, 190.0, 300.0)).is_ok());
+        assert!(OrderBook::new("BTCUSD".to_string()).check_order(&create_test_order("BTCUSD", Side::Buy, 50000.0, 0.5)).is_ok());
+    }
+    
+    #[test]
+    fn test_best_opposite_returns_touch_price_and_level_size() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        assert_eq!(book.best_opposite(Side::Buy), None);
+        assert_eq!(book.best_opposite(Side::Sell), None);
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 0.5));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50200.0, 3.0));
+        assert_eq!(book.best_opposite(Side::Buy), Some((Price::new(50100.0), Quantity::new(1.5))));
+        assert_eq!(book.best_opposite(Side::Sell), None);
+        
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 2.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49950.0, 0.25));
+        assert_eq!(book.best_opposite(Side::Sell), Some((Price::new(49950.0), Quantity::new(0.25))));
+    }
+    
+    #[test]
+    fn test_unfilled_market_orders_report_insufficient_liquidity() {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let market_buy = |quantity: f64| {
+            Order::new(
+                "BTCUSD".to_string(),
+                Side::Buy,
+                OrderType::Market,
+                Price::ZERO,
+                Quantity::new(quantity),
+                Uuid::new_v4(),
+            )
+        };
+        
+        let empty = market_buy(1.0);
+        let result = book.add_order(empty.clone());
+        assert!(matches!(result, MatchResult::NoMatch));
+        assert!(matches!(
+            result.check_liquidity(&empty),
+            Err(OrderBookError::InsufficientLiquidity { requested, unfilled })
+                if requested == Quantity::new(1.0) && unfilled == Quantity::new(1.0)
+        ));
+        assert!(book.get_order(empty.id).is_none());
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 0.75));
+        let partial = market_buy(2.0);
+        let result = book.add_order(partial.clone());
+        assert!(matches!(&result, MatchResult::PartialMatch { trades, .. } if trades.len() == 1));
+        assert!(matches!(
+            result.check_liquidity(&partial),
+            Err(OrderBookError::InsufficientLiquidity { requested, unfilled })
+                if requested == Quantity::new(2.0) && unfilled == Quantity::new(1.25)
+        ));
+        assert_eq!(book.best_bid(), None);
+        
+        // A limit order's remainder rests, so it is never a liquidity shortfall
+        let limit = create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0);
+        let result = book.add_order(limit.clone());
+        assert!(result.check_liquidity(&limit).is_ok());
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        let filled = market_buy(1.0);
+        assert!(book.add_order(filled.clone()).check_liquidity(&filled).is_ok());
+    }
+    
+    #[test]
+    fn test_level_pool_recycles_levels_across_churn() {
+        let iterations = 2_000u64;
+        let book = OrderBook::new("BTCUSD".to_string()).with_level_pool(8);
+        
+        for i in 0..iterations {
+            let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+            book.add_order(order.clone());
+            if i % 2 == 0 {
+                assert!(book.cancel_order(order.id).is_some());
+            } else {
+                book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+            }
+            assert_eq!(book.best_bid(), None);
+        }
+        
+        let stats = book.level_pool_stats().unwrap();
+        assert_eq!(stats.allocated + stats.reused, iterations);
+        assert!(stats.reused > 0);
+        assert!(stats.idle <= 8);
+        
+        // A recycled level starts empty at its new price
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 49000.0, 2.0));
+        let snapshot = book.depth(5);
+        assert_eq!(snapshot.bids, vec![(Price::new(49000.0), Quantity::new(2.0))]);
+        assert!(OrderBook::new("BTCUSD".to_string()).level_pool_stats().is_none());
+    }
+    
+    #[test]
+    fn test_best_price_notifications_are_throttled_and_settle_on_final_touch() {
+        let interval = Duration::from_millis(50);
+        let (tx, rx) = unbounded();
+        let book = OrderBook::new("BTCUSD".to_string())
+            .on_best_price_change(interval, move |change| {
+                let _ = tx.send(change);
+            });
+        
+        let started = std::time::Instant::now();
+        for i in 0..1_000 {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, 40000.0 + i as f64, 1.0));
+        }
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        let elapsed = started.elapsed();
+        
+        let expected = BestPriceChange {
+            best_bid: Some(Price::new(40999.0)),
+            best_ask: Some(Price::new(50000.0)),
+        };
+        let mut received = Vec::new();
+        while received.last() != Some(&expected) {
+            received.push(rx.recv_timeout(Duration::from_secs(2)).expect("final touch was not delivered"));
+        }
+        
+        // One immediate delivery, then at most one per elapsed interval plus the trailing one
+        let bound = 2 + (elapsed.as_millis() / interval.as_millis()) as usize;
+        assert!(received.len() <= bound, "{} notifications in {:?}", received.len(), elapsed);
+        assert!(received.len() < 1_001);
+        assert!(rx.recv_timeout(interval * 3).is_err());
+    }
+    
+    #[test]
+    fn test_match_limit_parks_taker_and_resume_continues_it() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_match_limit(25);
+        for level in 0..10 {
+            for _ in 0..10 {
+                book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0 + level as f64, 1.0));
+            }
+        }
+        
+        let taker = create_test_order("BTCUSD", Side::Buy, 60000.0, 1000.0);
+        let result = book.add_order(taker.clone());
+        assert!(matches!(
+            &result,
+            MatchResult::PartialMatch { trades, remaining_quantity, .. }
+                if trades.len() == 25 && *remaining_quantity == Quantity::new(975.0)
+        ));
+        
+        // The remainder is parked, not rested across the spread
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.best_ask(), Some(Price::new(50002.0)));
+        assert!(book.get_order(taker.id).is_none());
+        let parked = book.interrupted_orders();
+        assert_eq!(parked.len(), 1);
+        assert_eq!(parked[0].filled_quantity, Quantity::new(25.0));
+        
+        let mut fills = 25;
+        let mut resumes = 0;
+        while let Some(result) = book.resume_match(taker.id) {
+            let MatchResult::PartialMatch { trades, .. } = result else {
+                panic!("unexpected {:?}", result);
+            };
+            assert!(trades.len() <= 25);
+            fills += trades.len();
+            resumes += 1;
+        }
+        assert_eq!(resumes, 3);
+        assert_eq!(fills, 100);
+        
+        // Once nothing is left to take, the remainder rests as usual
+        assert!(book.interrupted_orders().is_empty());
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), Some(Price::new(60000.0)));
+        assert_eq!(book.get_order(taker.id).unwrap().remaining_quantity(), Quantity::new(900.0));
+        
+        // A parked taker can be cancelled before it is resumed
+        for _ in 0..30 {
+            book.add_order(create_test_order("BTCUSD", Side::Buy, 59000.0, 1.0));
+        }
+        let seller = create_test_order("BTCUSD", Side::Sell, 1000.0, 2000.0);
+        book.add_order(seller.clone());
+        assert_eq!(book.interrupted_orders().len(), 1);
+        let cancelled = book.cancel_order(seller.id).unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert!(book.resume_match(seller.id).is_none());
+    }
+    
+    #[test]
+    fn test_authoritative_best_price_exact_while_cached_converges() {
+        let book = Arc::new(OrderBook::new("BTCUSD".to_string()));
+        let done = Arc::new(AtomicBool::new(false));
+        
+        let readers: Vec<_> = (0..2)
+            .map(|_| {
+                let book = book.clone();
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        for mode in [ConsistencyMode::Cached, ConsistencyMode::Authoritative] {
+                            if let Some(bid) = book.best_bid_with_mode(mode) {
+                                assert!((100.0..200.0).contains(&bid.to_f64()), "{:?} read {:?}", mode, bid);
+                            }
+                            assert_eq!(book.best_ask_with_mode(mode), None);
+                        }
+                    }
+                })
+            })
+            .collect();
+        
+        // The only writer, so a walk of the book right after each mutation is the true front
+        let mut resting = std::collections::VecDeque::new();
+        for i in 0..2_000 {
+            let order = create_test_order("BTCUSD", Side::Buy, 100.0 + (i * 7 % 100) as f64, 1.0);
+            resting.push_back(order.id);
+            book.add_order(order);
+            if resting.len() > 20 {
+                book.cancel_order(resting.pop_front().unwrap());
+            }
+            let front = book.depth_with_mode(1, DepthMode::Internal).bids.first().map(|&(price, _)| price);
+            assert_eq!(book.best_bid_with_mode(ConsistencyMode::Authoritative), front);
+        }
+        
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        
+        let front = book.depth_with_mode(1, DepthMode::Internal).bids.first().map(|&(price, _)| price);
+        assert_eq!(book.best_bid_with_mode(ConsistencyMode::Cached), front);
+        assert_eq!(book.best_bid_with_mode(ConsistencyMode::Authoritative), front);
+        assert_eq!(book.best_bid(), front);
+    }
+    
+    #[test]
+    fn test_self_trade_prevention_scope() {
+        let account = |side, price, sub_account| create_test_order("BTCUSD", side, price, 1.0).with_account(7, sub_account);
+        
+        for (scope, prevented) in [(StpScope::Account, true), (StpScope::SubAccount, false)] {
+            let book = OrderBook::new("BTCUSD".to_string()).with_self_trade_prevention(SelfTradePrevention::new(scope));
+            assert_eq!(book.self_trade_prevention().unwrap().scope, scope);
+            
+            let own_desk = account(Side::Sell, 50000.0, Some(1));
+            let other_firm = create_test_order("BTCUSD", Side::Sell, 50001.0, 1.0).with_account(8, Some(1));
+            book.add_order(own_desk.clone());
+            book.add_order(other_firm.clone());
+            
+            // Same account, different sub-account
+            let taker = account(Side::Buy, 50001.0, Some(2));
+            let MatchResult::FullMatch { trades, .. } = book.add_order(taker) else {
+                panic!("taker should fill");
+            };
+            assert_eq!(trades.len(), 1, "{:?}", scope);
+            if prevented {
+                assert_eq!(trades[0].seller_order_id, other_firm.id);
+                assert!(book.get_order(own_desk.id).is_none());
+                let mut archived = Vec::new();
+                book.archive_terminal(&mut archived).unwrap();
+                assert!(archived.iter().any(|order| order.id == own_desk.id && order.status == OrderStatus::Cancelled));
+            } else {
+                assert_eq!(trades[0].seller_order_id, own_desk.id);
+                assert!(book.get_order(other_firm.id).is_some());
+            }
+        }
+        
+        // Within one sub-account both scopes prevent the trade
+        let book = OrderBook::new("BTCUSD".to_string()).with_self_trade_prevention(SelfTradePrevention::new(StpScope::SubAccount));
+        let resting = account(Side::Sell, 50000.0, Some(1));
+        book.add_order(resting.clone());
+        assert_eq!(book.add_order(account(Side::Buy, 50000.0, Some(1))), MatchResult::NoMatch);
+        assert!(book.get_order(resting.id).is_none());
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), Some(Price::new(50000.0)));
+    }
+    
+    #[test]
+    fn test_average_spread_tracks_touch_changes() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_spread_tracking(Duration::from_secs(60));
+        assert_eq!(book.average_spread(Duration::from_secs(10)), None);
+        assert!(OrderBook::new("BTCUSD".to_string()).average_spread(Duration::from_secs(10)).is_none());
+        
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50010.0, 1.0));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(book.average_spread(Duration::from_secs(10)), Some(Price::new(10.0)));
+        
+        // Tightening pulls the average below the old spread but not down to the new one
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 50008.0, 1.0));
+        std::thread::sleep(Duration::from_millis(5));
+        let average = book.average_spread(Duration::from_secs(10)).unwrap();
+        assert!(average > Price::new(2.0) && average < Price::new(10.0), "{:?}", average);
+    }
+    
+    #[test]
+    fn test_pre_match_hook_rejects_orders_too_far_through_the_touch() {
+        /// Refuses orders priced more than `max_ticks` ticks through the opposite touch
+        #[derive(Debug)]
+        struct PriceBand {
+            tick: f64,
+            max_ticks: f64,
+        }
+        
+        impl PreMatchHook for PriceBand {
+            fn pre_match(&self, book: &OrderBook, order: &mut Order) -> Result<(), String> {
+                let Some((touch, _)) = book.best_opposite(order.side) else {
+                    return Ok(());
+                };
+                let through = match order.side {
+                    Side::Buy => order.price.to_f64() - touch.to_f64(),
+                    Side::Sell => touch.to_f64() - order.price.to_f64(),
+                };
+                if through > self.max_ticks * self.tick {
+                    return Err(format!("{} ticks through the touch", through / self.tick));
+                }
+                Ok(())
+            }
+        }
+        
+        let book = OrderBook::new("BTCUSD".to_string())
+            .with_pre_match_hook(Arc::new(PriceBand { tick: 1.0, max_ticks: 10.0 }));
+        assert!(book.pre_match_hook().is_some());
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 50020.0, 1.0));
+        
+        // 20 ticks through the ask: refused without touching the book
+        let wide = create_test_order("BTCUSD", Side::Buy, 50020.0, 2.0);
+        let wide_id = wide.id;
+        assert!(matches!(
+            book.try_add_order(wide.clone()),
+            Err(OrderBookError::PreMatchRejected { order_id, .. }) if order_id == wide_id
+        ));
+        assert!(matches!(book.add_order(wide), MatchResult::Rejected { .. }));
+        assert!(book.get_order(wide_id).is_none());
+        assert_eq!(book.best_ask(), Some(Price::new(50000.0)));
+        
+        // Within the band the order matches as usual
+        match book.try_add_order(create_test_order("BTCUSD", Side::Buy, 50005.0, 1.0)) {
+            Ok(MatchResult::FullMatch { trades, .. }) => assert_eq!(trades[0].price, Price::new(50000.0)),
+            other => panic!("Expected the in-band buy to fill, got {:?}", other),
+        }
+        
+        // With no opposite touch there is nothing to be through, so passive orders still rest
+        let bid = create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0);
+        let bid_id = bid.id;
+        assert!(matches!(book.add_order(bid), MatchResult::NoMatch));
+        assert!(book.get_order(bid_id).is_some());
+        
+        // The hook travels with a clone
+        assert!(book.clone().try_add_order(create_test_order("BTCUSD", Side::Sell, 48000.0, 1.0)).is_err());
+    }
+    
+    #[test]
+    fn test_imbalance_alert_fires_once_until_it_crosses_back_through_the_band() {
+        let (tx, rx) = unbounded();
+        let config = ImbalanceAlertConfig { levels: 10, upper: 0.6, lower: -0.6, hysteresis: 0.3 };
+        let book = OrderBook::new("BTCUSD".to_string()).with_imbalance_alerts(config, tx);
+        let alerts = || rx.try_iter().collect::<Vec<_>>();
+        
+        // A one-sided book is not sampled, and a balanced one is quiet
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 99.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 101.0, 1.0));
+        assert!(alerts().is_empty());
+        
+        // 9 bid against 1 ask
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 100.0, 8.0));
+        let fired = alerts();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].side, ImbalanceSide::BidHeavy);
+        assert_eq!(fired[0].symbol, "BTCUSD");
+        assert!((fired[0].imbalance - 0.8).abs() < 1e-9);
+        
+        // Staying bid-heavy, or easing back only into the band (11 against 4), does not re-fire
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 100.5, 2.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 100.8, 3.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 100.6, 10.0));
+        assert!(alerts().is_empty());
+        
+        // Balanced again (21 against 21) re-arms the bound; crossing it again (91 against 21) alerts
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 100.7, 17.0));
+        assert!(alerts().is_empty());
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 100.65, 70.0));
+        let fired = alerts();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].side, ImbalanceSide::BidHeavy);
+        assert_eq!(book.imbalance(10), Some(fired[0].imbalance));
+    }
+    
+    #[test]
+    fn test_rolling_stats_sample_trades_and_arrivals() {
+        let book = OrderBook::new("BTCUSD".to_string()).with_rolling_stats(Duration::from_secs(60));
+        assert!(OrderBook::new("BTCUSD".to_string()).rolling_stats(Duration::from_secs(10)).is_none());
+        
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 100.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Sell, 104.0, 1.0));
+        book.add_order(create_test_order("BTCUSD", Side::Buy, 104.0, 4.0));
+        
+        // The buy swept both asks, 2 of the 6 submitted filled on arrival
+        let stats = book.rolling_stats(Duration::from_secs(10)).unwrap();
+        assert_eq!(stats.trade_count, 2);
+        assert_eq!(stats.volume, Quantity::new(2.0));
+        assert!((stats.volatility - 2.0).abs() < 1e-9);
+        assert!((stats.fill_ratio.unwrap() - 2.0 / 6.0).abs() < 1e-12);
+    }
+}
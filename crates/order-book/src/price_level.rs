@@ -76,6 +76,15 @@ impl PriceLevel {
     pub fn orders(&self) -> &VecDeque<OrderId> {
         &self.orders
     }
+    
+    /// Empty the level and move it to `price`, keeping its queue's allocation for reuse
+    #[inline]
+    pub fn reset(&mut self, price: Price) {
+        self.price = price;
+        self.total_quantity = Quantity::ZERO;
+        self.order_count = 0;
+        self.orders.clear();
+    }
 }
 
 impl fmt::Display for PriceLevel {
@@ -113,7 +122,7 @@ impl AtomicPriceLevel {
         let order_info = OrderInfo {
             order_id,
             quantity,
-            timestamp: chrono::Utc::now(),
+            timestamp: crate::clock::now(),
         };
         
         self.orders.write().push_back(order_info);
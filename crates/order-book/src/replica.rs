@@ -0,0 +1,230 @@
+use crate::order_book::{BookSnapshot, DepthMode, OrderBook};
+use crate::types::{Price, Quantity, Side};
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Immutable copy of the book taken by the replica's refresher
+#[derive(Debug, Clone)]
+pub struct ReplicaSnapshot {
+    pub symbol: String,
+    pub bids: Vec<(Price, Quantity)>,
+    pub asks: Vec<(Price, Quantity)>,
+    pub sequence: u64,
+    pub taken_at: DateTime<Utc>,
+    taken_instant: Instant,
+}
+
+impl ReplicaSnapshot {
+    fn capture(source: &OrderBook, sequence: u64) -> Self {
+        let snapshot = source.depth_with_mode(usize::MAX, DepthMode::Internal);
+        Self {
+            symbol: snapshot.symbol,
+            bids: snapshot.bids,
+            asks: snapshot.asks,
+            sequence,
+            taken_at: snapshot.timestamp,
+            taken_instant: Instant::now(),
+        }
+    }
+
+    #[inline]
+    pub fn age(&self) -> Duration {
+        self.taken_instant.elapsed()
+    }
+
+    #[inline]
+    pub fn best_bid(&self) -> Option<Price> {
+        self.bids.first().map(|(price, _)| *price)
+    }
+
+    #[inline]
+    pub fn best_ask(&self) -> Option<Price> {
+        self.asks.first().map(|(price, _)| *price)
+    }
+
+    pub fn depth(&self, levels: usize) -> BookSnapshot {
+        BookSnapshot {
+            symbol: self.symbol.clone(),
+            bids: self.bids.iter().take(levels).copied().collect(),
+            asks: self.asks.iter().take(levels).copied().collect(),
+            timestamp: self.taken_at,
+        }
+    }
+
+    /// (bid - ask) / (bid + ask) volume over the top `levels`, in [-1, 1]
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let side_volume = |side: &[(Price, Quantity)]| -> f64 {
+            side.iter().take(levels).map(|(_, qty)| qty.to_f64()).sum()
+        };
+        let bid_volume = side_volume(&self.bids);
+        let ask_volume = side_volume(&self.asks);
+        let total = bid_volume + ask_volume;
+
+        if total > 0.0 {
+            Some((bid_volume - ask_volume) / total)
+        } else {
+            None
+        }
+    }
+
+    /// Average price to fill `quantity` by an order on `side`, walking the opposite side of the book
+    pub fn vwap(&self, side: Side, quantity: Quantity) -> Option<Price> {
+        let levels = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+
+        let target = quantity.to_f64();
+        let mut remaining = target;
+        let mut notional = 0.0;
+        for (price, level_qty) in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let fill = remaining.min(level_qty.to_f64());
+            notional += fill * price.to_f64();
+            remaining -= fill;
+        }
+
+        if target > 0.0 && remaining <= 0.0 {
+            Some(Price::new(notional / target))
+        } else {
+            None
+        }
+    }
+}
+
+/// Periodically refreshed snapshot of an `OrderBook` that analytics read without touching the book's locks
+pub struct OrderBookReplica {
+    snapshot: Arc<ArcSwap<ReplicaSnapshot>>,
+    refresh_interval: Duration,
+    refreshes: Arc<AtomicU64>,
+    shutdown: Arc<AtomicBool>,
+    refresher: Option<JoinHandle<()>>,
+}
+
+impl OrderBookReplica {
+    /// Take an initial snapshot and refresh it from `source` every `refresh_interval`
+    pub fn spawn(source: Arc<OrderBook>, refresh_interval: Duration) -> Self {
+        let snapshot = Arc::new(ArcSwap::from_pointee(ReplicaSnapshot::capture(&source, 0)));
+        let refreshes = Arc::new(AtomicU64::new(0));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let refresher = {
+            let snapshot = Arc::clone(&snapshot);
+            let refreshes = Arc::clone(&refreshes);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::Builder::new()
+                .name(format!("replica-{}", source.symbol()))
+                .spawn(move || {
+                    while !shutdown.load(Ordering::Acquire) {
+                        std::thread::park_timeout(refresh_interval);
+                        if shutdown.load(Ordering::Acquire) {
+                            break;
+                        }
+                        let sequence = refreshes.fetch_add(1, Ordering::AcqRel) + 1;
+                        snapshot.store(Arc::new(ReplicaSnapshot::capture(&source, sequence)));
+                    }
+                })
+                .expect("failed to spawn replica refresher")
+        };
+
+        Self {
+            snapshot,
+            refresh_interval,
+            refreshes,
+            shutdown,
+            refresher: Some(refresher),
+        }
+    }
+
+    /// Current snapshot; holding it never blocks the refresher or the book
+    #[inline]
+    pub fn snapshot(&self) -> Arc<ReplicaSnapshot> {
+        self.snapshot.load_full()
+    }
+
+    /// How far the replica may lag the source
+    #[inline]
+    pub fn staleness(&self) -> Duration {
+        self.snapshot.load().age()
+    }
+
+    #[inline]
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    #[inline]
+    pub fn refresh_count(&self) -> u64 {
+        self.refreshes.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for OrderBookReplica {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Release);
+        if let Some(handle) = self.refresher.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Order, OrderType};
+    use uuid::Uuid;
+
+    fn limit(side: Side, price: f64, quantity: f64) -> Order {
+        Order::new(
+            "BTCUSD".to_string(),
+            side,
+            OrderType::Limit,
+            Price::new(price),
+            Quantity::new(quantity),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_replica_reflects_source_after_refresh() {
+        let book = Arc::new(OrderBook::new("BTCUSD".to_string()));
+        book.add_order(limit(Side::Buy, 49900.0, 1.0));
+        book.add_order(limit(Side::Sell, 50100.0, 3.0));
+
+        let replica = OrderBookReplica::spawn(Arc::clone(&book), Duration::from_millis(10));
+        let held = replica.snapshot();
+        assert_eq!(held.bids.len(), 1);
+        assert_eq!(held.imbalance(10), Some(-0.5));
+
+        // Writers proceed while a reader holds the old snapshot
+        let start = Instant::now();
+        book.add_order(limit(Side::Buy, 49800.0, 2.0));
+        book.add_order(limit(Side::Sell, 50200.0, 1.0));
+        assert!(start.elapsed() < replica.refresh_interval());
+
+        let target = held.sequence + 2;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while replica.refresh_count() < target && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let fresh = replica.snapshot();
+        assert!(fresh.sequence > held.sequence);
+        assert_eq!(fresh.depth(10).bids.len(), 2);
+        assert_eq!(fresh.depth(10).asks.len(), 2);
+        assert_eq!(fresh.best_bid(), Some(Price::new(49900.0)));
+        assert_eq!(fresh.imbalance(10), Some(-1.0 / 7.0));
+        assert_eq!(fresh.vwap(Side::Buy, Quantity::new(4.0)), Some(Price::new(50125.0)));
+        assert!(replica.staleness() < Duration::from_secs(5));
+
+        // The held snapshot is unchanged
+        assert_eq!(held.bids.len(), 1);
+    }
+}
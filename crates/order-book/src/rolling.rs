@@ -0,0 +1,184 @@
+use crate::types::{Price, Quantity, Trade};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Trading activity in one symbol over a trailing window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RollingStats {
+    pub window: Duration,
+    pub trade_count: usize,
+    pub trades_per_sec: f64,
+    pub volume: Quantity,
+    /// Population standard deviation of trade prices; 0 with fewer than two trades
+    pub volatility: f64,
+    /// Share of the quantity submitted in the window that filled on arrival; `None` if nothing
+    /// was submitted
+    pub fill_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TradeSample {
+    at_nanos: i64,
+    price: f64,
+    quantity: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OrderSample {
+    at_nanos: i64,
+    submitted: f64,
+    filled: f64,
+}
+
+/// Ring buffers of recent trades and submissions, appended on the matching path and only
+/// summarized when queried
+#[derive(Debug, Clone)]
+pub struct TradeWindow {
+    max_window: Duration,
+    trades: VecDeque<TradeSample>,
+    orders: VecDeque<OrderSample>,
+}
+
+impl TradeWindow {
+    /// Keep enough history to summarize windows up to `max_window`
+    pub fn new(max_window: Duration) -> Self {
+        Self {
+            max_window,
+            trades: VecDeque::new(),
+            orders: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    pub fn max_window(&self) -> Duration {
+        self.max_window
+    }
+
+    pub fn record_trade(&mut self, at_nanos: i64, price: Price, quantity: Quantity) {
+        self.trades.push_back(TradeSample {
+            at_nanos,
+            price: price.to_f64(),
+            quantity: quantity.to_f64(),
+        });
+        let horizon = self.horizon(at_nanos);
+        while self.trades.front().is_some_and(|sample| sample.at_nanos <= horizon) {
+            self.trades.pop_front();
+        }
+    }
+
+    /// An order of `submitted` arrived and `filled` of it traded straight away
+    pub fn record_order(&mut self, at_nanos: i64, submitted: Quantity, filled: Quantity) {
+        self.orders.push_back(OrderSample {
+            at_nanos,
+            submitted: submitted.to_f64(),
+            filled: filled.to_f64(),
+        });
+        let horizon = self.horizon(at_nanos);
+        while self.orders.front().is_some_and(|sample| sample.at_nanos <= horizon) {
+            self.orders.pop_front();
+        }
+    }
+
+    /// Record an incoming order and the trades it made
+    pub fn record_match(&mut self, at_nanos: i64, submitted: Option<Quantity>, trades: &[Trade]) {
+        let mut filled = Quantity::ZERO;
+        for trade in trades {
+            self.record_trade(at_nanos, trade.price, trade.quantity);
+            filled += trade.quantity;
+        }
+        if let Some(submitted) = submitted {
+            self.record_order(at_nanos, submitted, filled);
+        }
+    }
+
+    /// Activity in the `window` ending at `now_nanos`, capped at the longest window kept
+    pub fn summarize(&self, window: Duration, now_nanos: i64) -> RollingStats {
+        let window = window.min(self.max_window);
+        let start = now_nanos - window.as_nanos() as i64;
+        let in_window = |at_nanos: i64| at_nanos > start && at_nanos <= now_nanos;
+
+        let trades: Vec<&TradeSample> = self.trades.iter().filter(|sample| in_window(sample.at_nanos)).collect();
+        let trade_count = trades.len();
+        let volume: f64 = trades.iter().map(|sample| sample.quantity).sum();
+        let volatility = if trade_count > 1 {
+            let mean = trades.iter().map(|sample| sample.price).sum::<f64>() / trade_count as f64;
+            let variance = trades.iter().map(|sample| (sample.price - mean).powi(2)).sum::<f64>() / trade_count as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        let (submitted, filled) = self
+            .orders
+            .iter()
+            .filter(|sample| in_window(sample.at_nanos))
+            .fold((0.0, 0.0), |(submitted, filled), sample| (submitted + sample.submitted, filled + sample.filled));
+
+        let seconds = window.as_secs_f64();
+        RollingStats {
+            window,
+            trade_count,
+            trades_per_sec: if seconds > 0.0 { trade_count as f64 / seconds } else { 0.0 },
+            volume: Quantity::new(volume),
+            volatility,
+            fill_ratio: (submitted > 0.0).then(|| filled / submitted),
+        }
+    }
+
+    #[inline]
+    fn horizon(&self, at_nanos: i64) -> i64 {
+        at_nanos - self.max_window.as_nanos() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    #[test]
+    fn test_rolling_volatility_and_trade_rate_over_window() {
+        let mut window = TradeWindow::new(Duration::from_secs(60));
+        let trade = |window: &mut TradeWindow, at: i64, price: f64, quantity: f64| {
+            window.record_trade(at * SECOND, Price::new(price), Quantity::new(quantity));
+        };
+        trade(&mut window, 1, 90.0, 5.0);
+        trade(&mut window, 12, 100.0, 1.0);
+        trade(&mut window, 14, 102.0, 2.0);
+        trade(&mut window, 16, 98.0, 1.0);
+        trade(&mut window, 18, 104.0, 3.0);
+        trade(&mut window, 19, 96.0, 1.0);
+        window.record_order(15 * SECOND, Quantity::new(4.0), Quantity::new(3.0));
+        window.record_order(17 * SECOND, Quantity::new(6.0), Quantity::new(0.0));
+
+        // The last 10s hold the five trades from 12s on: mean 100, squared deviations 0+4+4+16+16
+        let stats = window.summarize(Duration::from_secs(10), 20 * SECOND);
+        assert_eq!(stats.trade_count, 5);
+        assert!((stats.trades_per_sec - 0.5).abs() < 1e-12);
+        assert_eq!(stats.volume, Quantity::new(8.0));
+        assert!((stats.volatility - 8.0f64.sqrt()).abs() < 1e-9);
+        assert_eq!(stats.fill_ratio, Some(0.3));
+
+        // The whole history adds the trade at 90: six trades over 20s
+        let stats = window.summarize(Duration::from_secs(20), 20 * SECOND);
+        let prices = [90.0, 100.0, 102.0, 98.0, 104.0, 96.0];
+        let mean = prices.iter().sum::<f64>() / 6.0;
+        let variance = prices.iter().map(|price| (price - mean).powi(2)).sum::<f64>() / 6.0;
+        assert_eq!(stats.trade_count, 6);
+        assert!((stats.trades_per_sec - 0.3).abs() < 1e-12);
+        assert!((stats.volatility - variance.sqrt()).abs() < 1e-9);
+
+        // A single trade has no spread, and an empty window no fill ratio
+        let stats = window.summarize(Duration::from_secs(2), 20 * SECOND);
+        assert_eq!(stats.trade_count, 1);
+        assert_eq!(stats.volatility, 0.0);
+        assert_eq!(stats.fill_ratio, None);
+
+        // History older than the longest window is dropped
+        trade(&mut window, 100, 100.0, 1.0);
+        assert_eq!(window.trades.len(), 1);
+        assert_eq!(window.summarize(Duration::from_secs(600), 100 * SECOND).window, Duration::from_secs(60));
+    }
+}
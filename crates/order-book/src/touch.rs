@@ -0,0 +1,297 @@
+use crate::types::Price;
+use chrono::{DateTime, Utc};
+use crossbeam::channel::Sender;
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// The touch after a change to either side's best price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestPriceChange {
+    pub best_bid: Option<Price>,
+    pub best_ask: Option<Price>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Latest touch not yet handed to the callback; newer changes overwrite it
+    pending: Option<BestPriceChange>,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+/// Delivers best-price changes to a callback on its own thread, at most once per interval.
+///
+/// The first change after a quiet period is delivered at once. Changes arriving within the
+/// interval after a delivery are coalesced into the latest touch, which is delivered when the
+/// interval ends, so the settled value always reaches the callback. The callback never runs on
+/// the thread mutating the book.
+pub(crate) struct BestPriceNotifier {
+    shared: Arc<Shared>,
+    interval: Duration,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl BestPriceNotifier {
+    pub(crate) fn spawn<F>(symbol: &str, interval: Duration, callback: F) -> Self
+    where
+        F: Fn(BestPriceChange) + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            wake: Condvar::new(),
+        });
+
+        let worker = {
+            let shared = shared.clone();
+            std::thread::Builder::new()
+                .name(format!("{}-touch", symbol))
+                .spawn(move || Self::run(&shared, interval, callback))
+                .expect("failed to spawn best price notifier thread")
+        };
+
+        Self {
+            shared,
+            interval,
+            worker: Some(worker),
+        }
+    }
+
+    /// Record the new touch; cheap enough to call on every book mutation
+    pub(crate) fn publish(&self, change: BestPriceChange) {
+        let mut state = self.shared.state.lock();
+        if state.pending.replace(change).is_none() {
+            self.shared.wake.notify_one();
+        }
+    }
+
+    fn run<F: Fn(BestPriceChange)>(shared: &Shared, interval: Duration, callback: F) {
+        let mut delivered = None;
+        loop {
+            let change = {
+                let mut state = shared.state.lock();
+                while state.pending.is_none() && !state.shutdown {
+                    shared.wake.wait(&mut state);
+                }
+                match state.pending.take() {
+                    Some(change) => change,
+                    None => return,
+                }
+            };
+
+            // A touch that moved away and back within the interval is not news
+            if delivered != Some(change) {
+                callback(change);
+                delivered = Some(change);
+            }
+
+            let deadline = Instant::now() + interval;
+            let mut state = shared.state.lock();
+            while !state.shutdown && !shared.wake.wait_until(&mut state, deadline).timed_out() {}
+        }
+    }
+}
+
+impl Drop for BestPriceNotifier {
+    /// Deliver whatever is still pending, then stop the thread
+    fn drop(&mut self) {
+        self.shared.state.lock().shutdown = true;
+        self.shared.wake.notify_one();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl fmt::Debug for BestPriceNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BestPriceNotifier")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Time-weighted history of a book's spread, fed on every touch change
+#[derive(Debug, Clone)]
+pub struct SpreadTracker {
+    max_window: Duration,
+    /// When each spread took effect, in nanoseconds; `None` while either side was empty
+    samples: VecDeque<(i64, Option<Price>)>,
+}
+
+impl SpreadTracker {
+    /// Keep enough history to average over windows up to `max_window`
+    pub fn new(max_window: Duration) -> Self {
+        Self {
+            max_window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    pub fn max_window(&self) -> Duration {
+        self.max_window
+    }
+
+    /// The spread became `spread` at `at_nanos`
+    pub fn record(&mut self, at_nanos: i64, spread: Option<Price>) {
+        if self.samples.back().is_some_and(|&(_, last)| last == spread) {
+            return;
+        }
+        self.samples.push_back((at_nanos, spread));
+
+        // A sample is only needed while the one after it starts inside the longest window
+        let horizon = at_nanos - self.max_window.as_nanos() as i64;
+        while self.samples.get(1).is_some_and(|&(next, _)| next <= horizon) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Average spread over the `window` ending at `now_nanos`, each spread weighted by how long
+    /// it held. Time with either side empty is left out; `None` if no two-sided time remains.
+    pub fn average(&self, window: Duration, now_nanos: i64) -> Option<Price> {
+        let start = now_nanos - window.min(self.max_window).as_nanos() as i64;
+        let mut weighted = 0.0;
+        let mut covered = 0i64;
+        for (i, &(from, spread)) in self.samples.iter().enumerate() {
+            let until = self.samples.get(i + 1).map_or(now_nanos, |&(next, _)| next).min(now_nanos);
+            let held = until - from.max(start);
+            if let Some(spread) = spread.filter(|_| held > 0) {
+                weighted += spread.to_f64() * held as f64;
+                covered += held;
+            }
+        }
+        (covered > 0).then(|| Price::new(weighted / covered as f64))
+    }
+}
+
+/// When top-of-book imbalance alerts fire. Imbalance is (bid - ask) / (bid + ask) volume over
+/// the top `levels`, in [-1, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImbalanceAlertConfig {
+    pub levels: usize,
+    /// Bid-heavy alert at or above this
+    pub upper: f64,
+    /// Ask-heavy alert at or below this
+    pub lower: f64,
+    /// How far back inside a bound imbalance must come before that bound can alert again
+    pub hysteresis: f64,
+}
+
+impl Default for ImbalanceAlertConfig {
+    fn default() -> Self {
+        Self {
+            levels: 5,
+            upper: 0.6,
+            lower: -0.6,
+            hysteresis: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImbalanceSide {
+    BidHeavy,
+    AskHeavy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImbalanceAlert {
+    pub symbol: String,
+    pub side: ImbalanceSide,
+    pub imbalance: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Turns imbalance samples into alerts with hysteresis: crossing a bound alerts once, and that
+/// bound stays quiet until imbalance comes back through it by more than `hysteresis`.
+#[derive(Debug)]
+pub(crate) struct ImbalanceMonitor {
+    config: ImbalanceAlertConfig,
+    sender: Sender<ImbalanceAlert>,
+    /// Bound currently crossed, if any
+    latched: Option<ImbalanceSide>,
+}
+
+impl ImbalanceMonitor {
+    pub(crate) fn new(config: ImbalanceAlertConfig, sender: Sender<ImbalanceAlert>) -> Self {
+        Self {
+            config,
+            sender,
+            latched: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn config(&self) -> ImbalanceAlertConfig {
+        self.config
+    }
+
+    #[inline]
+    pub(crate) fn sender(&self) -> &Sender<ImbalanceAlert> {
+        &self.sender
+    }
+
+    /// Feed one sample; sends an alert when it crosses a bound that is not already latched
+    pub(crate) fn observe(&mut self, symbol: &str, imbalance: f64, timestamp: DateTime<Utc>) {
+        let config = &self.config;
+        self.latched = match self.latched {
+            Some(ImbalanceSide::BidHeavy) if imbalance > config.upper - config.hysteresis => return,
+            Some(ImbalanceSide::AskHeavy) if imbalance < config.lower + config.hysteresis => return,
+            _ => None,
+        };
+
+        let side = if imbalance >= config.upper {
+            ImbalanceSide::BidHeavy
+        } else if imbalance <= config.lower {
+            ImbalanceSide::AskHeavy
+        } else {
+            return;
+        };
+        self.latched = Some(side);
+        // Nobody listening is not an error for the book
+        let _ = self.sender.send(ImbalanceAlert {
+            symbol: symbol.to_string(),
+            side,
+            imbalance,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    #[test]
+    fn test_average_spread_weights_each_spread_by_time_held() {
+        let mut tracker = SpreadTracker::new(Duration::from_secs(60));
+        tracker.record(0, Some(Price::new(4.0)));
+        tracker.record(30 * SECOND, Some(Price::new(1.0)));
+
+        // 30s at 4 then 10s at 1
+        assert_eq!(tracker.average(Duration::from_secs(40), 40 * SECOND), Some(Price::new(3.25)));
+        // The window only reaches back 20s: 10s at 4 then 10s at 1
+        assert_eq!(tracker.average(Duration::from_secs(20), 40 * SECOND), Some(Price::new(2.5)));
+
+        // A one-sided book contributes no time
+        tracker.record(40 * SECOND, None);
+        assert_eq!(tracker.average(Duration::from_secs(20), 50 * SECOND), Some(Price::new(1.0)));
+        assert_eq!(tracker.average(Duration::from_secs(5), 50 * SECOND), None);
+
+        // History older than the longest window is dropped
+        tracker.record(200 * SECOND, Some(Price::new(2.0)));
+        assert_eq!(tracker.samples.len(), 2);
+        assert_eq!(tracker.average(Duration::from_secs(600), 210 * SECOND), Some(Price::new(2.0)));
+    }
+}
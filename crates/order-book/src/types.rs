@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign};
 use std::cmp::Ordering;
 use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::time::Duration;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use fixed::{FixedI64, FixedU64};
@@ -43,11 +45,27 @@ impl Price {
     pub fn abs(self) -> Self {
         Self(self.0.abs())
     }
+    
+    /// This price as a decimal string with `decimals` places, for display and serde
+    #[inline]
+    pub fn with_decimals(self, decimals: u8) -> DecimalString<Self> {
+        DecimalString::new(self, decimals)
+    }
 }
 
+/// Six decimal places unless the format asks for a precision, e.g. `{:.2}`
 impl fmt::Display for Price {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.6}", self.to_f64())
+        write!(f, "{:.*}", f.precision().unwrap_or(6), self.0)
+    }
+}
+
+/// Parses a decimal string exactly, rounding only past the fixed-point resolution
+impl FromStr for Price {
+    type Err = fixed::ParseFixedError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PriceFixed::from_str(s).map(Self)
     }
 }
 
@@ -143,11 +161,86 @@ impl Quantity {
     pub fn abs(self) -> Self {
         self // Quantity is always positive (unsigned)
     }
+    
+    /// Largest whole multiple of `lot` not above this quantity; unchanged for a zero lot
+    #[inline]
+    pub fn round_down_to(self, lot: Quantity) -> Self {
+        if lot == Self::ZERO {
+            return self;
+        }
+        Self::from_raw(self.to_raw() - self.to_raw() % lot.to_raw())
+    }
+    
+    /// This quantity as a decimal string with `decimals` places, for display and serde
+    #[inline]
+    pub fn with_decimals(self, decimals: u8) -> DecimalString<Self> {
+        DecimalString::new(self, decimals)
+    }
 }
 
+/// Six decimal places unless the format asks for a precision, e.g. `{:.2}`
 impl fmt::Display for Quantity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.6}", self.to_f64())
+        write!(f, "{:.*}", f.precision().unwrap_or(6), self.0)
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = fixed::ParseFixedError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        QuantityFixed::from_str(s).map(Self)
+    }
+}
+
+/// A `Price` or `Quantity` with a symbol's decimal convention. Displays and serializes as a
+/// decimal string with exactly `decimals` places (`"45000.00"`); deserializing takes the
+/// places from the string. `Price` and `Quantity` themselves keep their fixed-point serde form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalString<T> {
+    pub value: T,
+    pub decimals: u8,
+}
+
+impl<T> DecimalString<T> {
+    #[inline]
+    pub fn new(value: T, decimals: u8) -> Self {
+        Self { value, decimals }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DecimalString<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}", self.decimals as usize, self.value)
+    }
+}
+
+impl<T: FromStr> FromStr for DecimalString<T> {
+    type Err = T::Err;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decimals = s.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+        Ok(Self {
+            value: s.parse()?,
+            decimals: decimals.min(u8::MAX as usize) as u8,
+        })
+    }
+}
+
+impl<T: fmt::Display> Serialize for DecimalString<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for DecimalString<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -195,9 +288,10 @@ impl SubAssign for Quantity {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum Side {
+    #[default]
     Buy = 0,
     Sell = 1,
 }
@@ -260,6 +354,52 @@ impl Default for OrderId {
     }
 }
 
+/// Issues monotonically increasing order ids for one engine, independent of the process-wide
+/// counter behind `OrderId::new`. Seed it from the highest id already persisted so ids keep
+/// climbing across restarts.
+#[derive(Debug)]
+pub struct OrderIdGenerator {
+    last_issued: AtomicU64,
+}
+
+impl OrderIdGenerator {
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_high_water(0)
+    }
+    
+    /// Generator whose first id is `high_water + 1`
+    #[inline]
+    pub fn from_high_water(high_water: u64) -> Self {
+        Self {
+            last_issued: AtomicU64::new(high_water),
+        }
+    }
+    
+    #[inline]
+    pub fn next_id(&self) -> OrderId {
+        OrderId(self.last_issued.fetch_add(1, AtomicOrdering::Relaxed) + 1)
+    }
+    
+    /// Make sure future ids land above `id`, e.g. for ids seen while replaying
+    #[inline]
+    pub fn observe(&self, id: OrderId) {
+        self.last_issued.fetch_max(id.0, AtomicOrdering::Relaxed);
+    }
+    
+    /// Highest id issued or observed so far
+    #[inline]
+    pub fn high_water(&self) -> u64 {
+        self.last_issued.load(AtomicOrdering::Relaxed)
+    }
+}
+
+impl Default for OrderIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl fmt::Display for OrderId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -296,6 +436,27 @@ pub enum OrderStatus {
     Rejected = 4,
 }
 
+impl OrderStatus {
+    /// Filled, cancelled and rejected orders never change status again
+    #[inline]
+    pub fn is_terminal(self) -> bool {
+        matches!(self, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected)
+    }
+    
+    /// Legal lifecycle moves: `Pending` may fill, cancel or be rejected; `PartiallyFilled`
+    /// may keep filling or cancel; terminal states go nowhere
+    #[inline]
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        match self {
+            OrderStatus::Pending => next != OrderStatus::Pending,
+            OrderStatus::PartiallyFilled => {
+                matches!(next, OrderStatus::PartiallyFilled | OrderStatus::Filled | OrderStatus::Cancelled)
+            }
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected => false,
+        }
+    }
+}
+
 impl fmt::Display for OrderStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -321,6 +482,21 @@ pub struct Order {
     pub status: OrderStatus,
     pub timestamp: DateTime<Utc>,
     pub client_id: Uuid,
+    /// Slice shown in public depth for an iceberg order; `None` displays the full size
+    #[serde(default)]
+    pub display_quantity: Option<Quantity>,
+    /// May only shrink the client's existing position, never grow or flip it
+    #[serde(default)]
+    pub reduce_only: bool,
+    /// Owning account, compared by self-trade prevention
+    #[serde(default)]
+    pub account_id: Option<u64>,
+    /// Sub-account within `account_id`
+    #[serde(default)]
+    pub sub_account_id: Option<u64>,
+    /// Time the engine may spend on the order from submission to matching before it is rejected
+    #[serde(default)]
+    pub latency_budget: Option<Duration>,
 }
 
 impl Order {
@@ -342,39 +518,101 @@ impl Order {
             quantity,
             filled_quantity: Quantity::ZERO,
             status: OrderStatus::Pending,
-            timestamp: Utc::now(),
+            timestamp: crate::clock::now(),
             client_id,
+            display_quantity: None,
+            reduce_only: false,
+            account_id: None,
+            sub_account_id: None,
+            latency_budget: None,
         }
     }
     
+    /// Make this an iceberg order that only shows `display_quantity` at a time
+    #[inline]
+    pub fn with_display_quantity(mut self, display_quantity: Quantity) -> Self {
+        self.display_quantity = Some(display_quantity);
+        self
+    }
+    
+    #[inline]
+    pub fn with_reduce_only(mut self) -> Self {
+        self.reduce_only = true;
+        self
+    }
+    
+    #[inline]
+    pub fn with_account(mut self, account_id: u64, sub_account_id: Option<u64>) -> Self {
+        self.account_id = Some(account_id);
+        self.sub_account_id = sub_account_id;
+        self
+    }
+    
+    /// Reject the order rather than match it late once processing has taken longer than `budget`
+    #[inline]
+    pub fn with_latency_budget(mut self, budget: Duration) -> Self {
+        self.latency_budget = Some(budget);
+        self
+    }
+    
+    #[inline]
+    pub fn is_iceberg(&self) -> bool {
+        self.display_quantity.is_some_and(|display| display < self.quantity)
+    }
+    
     #[inline]
     pub fn remaining_quantity(&self) -> Quantity {
         self.quantity - self.filled_quantity
     }
     
+    /// Remaining quantity an outside observer can see
+    #[inline]
+    pub fn visible_quantity(&self) -> Quantity {
+        match self.display_quantity {
+            Some(display) => display.min(self.remaining_quantity()),
+            None => self.remaining_quantity(),
+        }
+    }
+    
     #[inline]
     pub fn is_fully_filled(&self) -> bool {
         self.filled_quantity >= self.quantity
     }
     
+    /// Record a fill. Fails without changing the order if it is already filled, cancelled or rejected.
     #[inline]
-    pub fn fill(&mut self, quantity: Quantity) {
-        self.filled_quantity += quantity;
-        if self.is_fully_filled() {
-            self.status = OrderStatus::Filled;
+    pub fn fill(&mut self, quantity: Quantity) -> crate::Result<()> {
+        let next = if self.filled_quantity + quantity >= self.quantity {
+            OrderStatus::Filled
         } else {
-            self.status = OrderStatus::PartiallyFilled;
-        }
+            OrderStatus::PartiallyFilled
+        };
+        self.transition(next)?;
+        self.filled_quantity += quantity;
+        Ok(())
     }
     
     #[inline]
-    pub fn cancel(&mut self) {
-        self.status = OrderStatus::Cancelled;
+    pub fn cancel(&mut self) -> crate::Result<()> {
+        self.transition(OrderStatus::Cancelled)
     }
     
     #[inline]
-    pub fn reject(&mut self) {
-        self.status = OrderStatus::Rejected;
+    pub fn reject(&mut self) -> crate::Result<()> {
+        self.transition(OrderStatus::Rejected)
+    }
+    
+    #[inline]
+    fn transition(&mut self, next: OrderStatus) -> crate::Result<()> {
+        if !self.status.can_transition_to(next) {
+            return Err(crate::OrderBookError::IllegalTransition {
+                order_id: self.id,
+                from: self.status,
+                to: next,
+            });
+        }
+        self.status = next;
+        Ok(())
     }
 }
 
@@ -390,12 +628,17 @@ pub struct Trade {
     pub timestamp: DateTime<Utc>,
     pub buyer_client_id: Uuid,
     pub seller_client_id: Uuid,
+    /// Side of the incoming order that took liquidity. Trades recorded before this field
+    /// existed read back as `Buy`.
+    #[serde(default)]
+    pub aggressor_side: Side,
 }
 
 static TRADE_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 impl Trade {
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         symbol: &str,
         buyer_order_id: OrderId,
@@ -404,6 +647,7 @@ impl Trade {
         quantity: Quantity,
         buyer_client_id: Uuid,
         seller_client_id: Uuid,
+        aggressor_side: Side,
     ) -> Self {
         Self {
             id: TRADE_ID_COUNTER.fetch_add(1, AtomicOrdering::Relaxed),
@@ -412,16 +656,92 @@ impl Trade {
             seller_order_id,
             price,
             quantity,
-            timestamp: Utc::now(),
+            timestamp: crate::clock::now(),
             buyer_client_id,
             seller_client_id,
+            aggressor_side,
         }
     }
     
+    /// Stamp the trade with `timestamp` instead of the process-wide clock's time
+    #[inline]
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+    
     #[inline]
     pub fn notional_value(&self) -> f64 {
         self.price.to_f64() * self.quantity.to_f64()
     }
+    
+    /// Order id of the side that took liquidity
+    #[inline]
+    pub fn aggressor_order_id(&self) -> OrderId {
+        match self.aggressor_side {
+            Side::Buy => self.buyer_order_id,
+            Side::Sell => self.seller_order_id,
+        }
+    }
+    
+    /// Tape prints for `trades`: runs of consecutive fills by one aggressor at one price become a
+    /// single print with their summed quantity. Each print keeps the id, timestamp and maker of
+    /// the first fill in its run, so settlement must use the granular trades, not the prints.
+    pub fn consolidate(trades: &[Trade]) -> Vec<Trade> {
+        let mut prints: Vec<Trade> = Vec::with_capacity(trades.len());
+        for trade in trades {
+            match prints.last_mut() {
+                Some(print)
+                    if print.price == trade.price
+                        && print.aggressor_side == trade.aggressor_side
+                        && print.aggressor_order_id() == trade.aggressor_order_id() =>
+                {
+                    print.quantity += trade.quantity;
+                }
+                _ => prints.push(trade.clone()),
+            }
+        }
+        prints
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum LiquidityFlag {
+    Maker = 0,
+    Taker = 1,
+}
+
+impl fmt::Display for LiquidityFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiquidityFlag::Maker => write!(f, "MAKER"),
+            LiquidityFlag::Taker => write!(f, "TAKER"),
+        }
+    }
+}
+
+/// Per-fill report for one side of a trade, emitted as the match happens
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub sequence: u64,
+    pub trade_id: u64,
+    pub symbol: String,
+    pub order_id: OrderId,
+    pub counterparty_order_id: OrderId,
+    pub side: Side,
+    pub fill_price: Price,
+    pub fill_quantity: Quantity,
+    pub cumulative_quantity: Quantity,
+    pub liquidity: LiquidityFlag,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ExecutionReport {
+    #[inline]
+    pub fn is_maker(&self) -> bool {
+        self.liquidity == LiquidityFlag::Maker
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -450,7 +770,7 @@ impl MarketData {
             last_trade_price: None,
             last_trade_quantity: None,
             volume: Quantity::ZERO,
-            timestamp: Utc::now(),
+            timestamp: crate::clock::now(),
         }
     }
     
@@ -470,12 +790,33 @@ impl MarketData {
         }
     }
     
+    /// Size-weighted mid (microprice): leans toward the ask when bids outweigh asks and toward
+    /// the bid when asks outweigh bids. Falls back to `mid_price` when neither side has size.
+    #[inline]
+    pub fn weighted_mid(&self) -> Option<Price> {
+        let (ask, bid) = (self.best_ask?, self.best_bid?);
+        let bid_size = self.bid_size.to_f64();
+        let total_size = bid_size + self.ask_size.to_f64();
+        if total_size <= 0.0 {
+            return self.mid_price();
+        }
+        
+        Some(bid + (ask - bid) * (bid_size / total_size))
+    }
+    
+    /// Whether this snapshot is older than `max_age` by the configured clock
+    #[inline]
+    pub fn is_stale(&self, max_age: std::time::Duration) -> bool {
+        let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        crate::clock::now() - self.timestamp > max_age
+    }
+    
     #[inline]
     pub fn update_trade(&mut self, price: Price, quantity: Quantity) {
         self.last_trade_price = Some(price);
         self.last_trade_quantity = Some(quantity);
         self.volume += quantity;
-        self.timestamp = Utc::now();
+        self.timestamp = crate::clock::now();
     }
 }
 
@@ -496,7 +837,7 @@ impl MarketSnapshot {
             bids: Vec::with_capacity(100),
             asks: Vec::with_capacity(100),
             trades: Vec::with_capacity(1000),
-            timestamp: Utc::now(),
+            timestamp: crate::clock::now(),
         }
     }
     
@@ -567,6 +908,9 @@ mod tests {
         let mut q3 = q1;
         q3 += q2;
         assert_eq!(q3.to_f64(), 150.0);
+        
+        assert_eq!(Quantity::new(2.75).round_down_to(Quantity::new(0.5)), Quantity::new(2.5));
+        assert_eq!(Quantity::new(2.75).round_down_to(Quantity::ZERO), Quantity::new(2.75));
     }
 
     #[test]
@@ -592,6 +936,25 @@ mod tests {
         assert_eq!(id3.to_raw(), 12345);
     }
 
+    #[test]
+    fn test_order_id_generator_resumes_above_high_water() {
+        let recovered = OrderIdGenerator::from_high_water(41_000);
+        let first = recovered.next_id();
+        let second = recovered.next_id();
+        assert_eq!(first.to_raw(), 41_001);
+        assert_eq!(second.to_raw(), 41_002);
+        
+        recovered.observe(OrderId::from_raw(50_000));
+        recovered.observe(OrderId::from_raw(10));
+        assert_eq!(recovered.high_water(), 50_000);
+        assert_eq!(recovered.next_id().to_raw(), 50_001);
+        
+        // Independent of the process-wide counter and of other generators
+        let other = OrderIdGenerator::new();
+        assert_eq!(other.next_id().to_raw(), 1);
+        assert_eq!(recovered.high_water(), 50_001);
+    }
+    
     #[test]
     fn test_order_creation_and_filling() {
         let client_id = Uuid::new_v4();
@@ -608,13 +971,13 @@ mod tests {
         assert!(!order.is_fully_filled());
         assert_eq!(order.status, OrderStatus::Pending);
         
-        order.fill(Quantity::new(0.5));
+        order.fill(Quantity::new(0.5)).unwrap();
         assert_eq!(order.filled_quantity, Quantity::new(0.5));
         assert_eq!(order.remaining_quantity(), Quantity::new(0.5));
         assert!(!order.is_fully_filled());
         assert_eq!(order.status, OrderStatus::PartiallyFilled);
         
-        order.fill(Quantity::new(0.5));
+        order.fill(Quantity::new(0.5)).unwrap();
         assert_eq!(order.filled_quantity, Quantity::new(1.0));
         assert_eq!(order.remaining_quantity(), Quantity::ZERO);
         assert!(order.is_fully_filled());
@@ -633,7 +996,7 @@ mod tests {
             client_id,
         );
         
-        order.cancel();
+        order.cancel().unwrap();
         assert_eq!(order.status, OrderStatus::Cancelled);
         
         let mut order2 = Order::new(
@@ -645,7 +1008,7 @@ mod tests {
             client_id,
         );
         
-        order2.reject();
+        order2.reject().unwrap();
         assert_eq!(order2.status, OrderStatus::Rejected);
     }
 
@@ -664,6 +1027,7 @@ mod tests {
             Quantity::new(1.0),
             buyer_id,
             seller_id,
+            Side::Buy,
         );
         
         assert_eq!(trade.notional_value(), 50000.0);
@@ -686,6 +1050,33 @@ mod tests {
         assert_eq!(md.volume, Quantity::new(1.0));
     }
 
+    #[test]
+    fn test_market_data_weighted_mid_and_staleness() {
+        let mut market_data = MarketData::new("BTCUSD".to_string());
+        assert_eq!(market_data.weighted_mid(), None);
+        
+        market_data.best_bid = Some(Price::new(100.0));
+        market_data.best_ask = Some(Price::new(101.0));
+        assert_eq!(market_data.weighted_mid(), market_data.mid_price());
+        
+        market_data.bid_size = Quantity::new(9.0);
+        market_data.ask_size = Quantity::new(1.0);
+        let bid_heavy = market_data.weighted_mid().unwrap();
+        assert!((bid_heavy.to_f64() - 100.9).abs() < 0.02);
+        assert!(bid_heavy > market_data.mid_price().unwrap());
+        
+        market_data.bid_size = Quantity::new(1.0);
+        market_data.ask_size = Quantity::new(3.0);
+        let ask_heavy = market_data.weighted_mid().unwrap();
+        assert!((ask_heavy.to_f64() - 100.25).abs() < 0.02);
+        assert!(ask_heavy < market_data.mid_price().unwrap());
+        
+        assert!(!market_data.is_stale(std::time::Duration::from_secs(60)));
+        market_data.timestamp = Utc::now() - chrono::Duration::seconds(5);
+        assert!(market_data.is_stale(std::time::Duration::from_secs(1)));
+        assert!(!market_data.is_stale(std::time::Duration::from_secs(60)));
+    }
+    
     #[test]
     fn test_market_snapshot() {
         let mut snapshot = MarketSnapshot::new("BTCUSD".to_string());
@@ -742,4 +1133,126 @@ mod tests {
         let deserialized: Order = serde_json::from_str(&serialized).unwrap();
         assert_eq!(order, deserialized);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_trade_without_aggressor_side_still_deserializes() {
+        let trade = Trade::new(
+            "BTCUSD",
+            OrderId::new(),
+            OrderId::new(),
+            Price::new(50000.0),
+            Quantity::new(1.0),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Side::Sell,
+        );
+        let mut value = serde_json::to_value(&trade).unwrap();
+        value.as_object_mut().unwrap().remove("aggressor_side");
+        
+        let decoded: Trade = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.aggressor_side, Side::Buy);
+        assert_eq!(decoded.id, trade.id);
+    }
+    
+    #[test]
+    fn test_decimal_string_formats_and_round_trips_with_symbol_precision() {
+        let price = Price::new(45000.0);
+        assert_eq!(price.to_string(), "45000.000000");
+        assert_eq!(format!("{:.2}", price), "45000.00");
+        assert_eq!(price.with_decimals(2).to_string(), "45000.00");
+        
+        let json = serde_json::to_string(&price.with_decimals(2)).unwrap();
+        assert_eq!(json, "\"45000.00\"");
+        let decoded: DecimalString<Price> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, price.with_decimals(2));
+        
+        // Two-decimal values on the fixed-point grid survive the string form exactly
+        let price = Price::new(45000.25);
+        let decoded: DecimalString<Price> = serde_json::from_str(&serde_json::to_string(&price.with_decimals(2)).unwrap()).unwrap();
+        assert_eq!(decoded.value, price);
+        assert_eq!("45000.25".parse::<Price>().unwrap(), price);
+        assert_eq!("-1.50".parse::<Price>().unwrap(), Price::new(-1.5));
+        
+        let quantity = Quantity::new(1.5);
+        assert_eq!(quantity.with_decimals(3).to_string(), "1.500");
+        assert_eq!(quantity.with_decimals(0).to_string(), "2");
+        assert_eq!(serde_json::from_str::<DecimalString<Quantity>>("\"1.500\"").unwrap(), quantity.with_decimals(3));
+        assert!(serde_json::from_str::<DecimalString<Quantity>>("\"-1\"").is_err());
+    }
+    
+    #[test]
+    fn test_order_lifecycle_rejects_illegal_transitions() {
+        let new_order = || Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            Price::new(50000.0),
+            Quantity::new(1.0),
+            Uuid::new_v4(),
+        );
+        
+        // Pending -> PartiallyFilled -> PartiallyFilled -> Filled
+        let mut order = new_order();
+        order.fill(Quantity::new(0.25)).unwrap();
+        order.fill(Quantity::new(0.25)).unwrap();
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        order.fill(Quantity::new(0.5)).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert!(order.cancel().is_err());
+        
+        // Pending -> Filled, Pending -> Rejected, PartiallyFilled -> Cancelled
+        let mut order = new_order();
+        order.fill(Quantity::new(1.0)).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+        let mut order = new_order();
+        order.reject().unwrap();
+        assert!(order.fill(Quantity::new(1.0)).is_err());
+        let mut order = new_order();
+        order.fill(Quantity::new(0.5)).unwrap();
+        assert!(order.reject().is_err());
+        order.cancel().unwrap();
+        
+        // A cancelled order cannot be filled, and the failed fill leaves it untouched
+        let result = order.fill(Quantity::new(0.5));
+        assert!(matches!(
+            result,
+            Err(crate::OrderBookError::IllegalTransition { from: OrderStatus::Cancelled, to: OrderStatus::Filled, .. })
+        ));
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(order.filled_quantity, Quantity::new(0.5));
+        
+        for terminal in [OrderStatus::Filled, OrderStatus::Cancelled, OrderStatus::Rejected] {
+            assert!(terminal.is_terminal());
+            assert!(!terminal.can_transition_to(OrderStatus::PartiallyFilled));
+        }
+        assert!(!OrderStatus::PartiallyFilled.can_transition_to(OrderStatus::Pending));
+    }
+    
+    #[test]
+    fn test_consolidate_merges_only_contiguous_same_price_fills_of_one_aggressor() {
+        let taker = OrderId::new();
+        let other_taker = OrderId::new();
+        let fill = |taker: OrderId, price: f64, quantity: f64| {
+            Trade::new("BTCUSD", taker, OrderId::new(), Price::new(price), Quantity::new(quantity), Uuid::new_v4(), Uuid::new_v4(), Side::Buy)
+        };
+        
+        let trades = vec![
+            fill(taker, 100.0, 1.0),
+            fill(taker, 100.0, 2.0),
+            fill(taker, 101.0, 1.0),
+            fill(taker, 100.0, 1.0),
+            fill(other_taker, 100.0, 4.0),
+        ];
+        let prints = Trade::consolidate(&trades);
+        
+        let summary: Vec<_> = prints.iter().map(|p| (p.aggressor_order_id(), p.price, p.quantity)).collect();
+        assert_eq!(summary, vec![
+            (taker, Price::new(100.0), Quantity::new(3.0)),
+            (taker, Price::new(101.0), Quantity::new(1.0)),
+            (taker, Price::new(100.0), Quantity::new(1.0)),
+            (other_taker, Price::new(100.0), Quantity::new(4.0)),
+        ]);
+        assert_eq!(prints[0].id, trades[0].id);
+        assert!(Trade::consolidate(&[]).is_empty());
+    }
+}
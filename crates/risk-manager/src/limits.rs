@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use order_book::clock;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(u8)]
@@ -25,7 +26,7 @@ pub struct RiskLimit {
 impl RiskLimit {
     #[inline]
     pub fn new(limit_type: RiskLimitType, max_value: f64, symbol: Option<String>) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             limit_type,
             symbol,
@@ -40,7 +41,7 @@ impl RiskLimit {
     #[inline]
     pub fn update_current_value(&mut self, value: f64) {
         self.current_value = value;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
     
     #[inline]
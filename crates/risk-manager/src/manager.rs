@@ -1,10 +1,13 @@
 use crate::limits::{RiskLimits, RiskLimitType};
 use crate::position::{Position, PositionTracker};
-use crate::validation::OrderValidator;
-use order_book::{Order, Trade, Quantity, Side};
-use std::collections::HashMap;
+use crate::validation::{OrderValidator, ValidationError};
+use order_book::{clock, Clock, Order, OrderType, Price, Trade, Quantity, Side};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use parking_lot::RwLock;
+use arc_swap::ArcSwap;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -22,6 +25,27 @@ pub struct RiskConfig {
     pub default_daily_loss_limit: f64,
     pub max_order_size: Quantity,
     pub price_tolerance_pct: f64,
+    /// Initial margin as a fraction of notional for symbols without their own rate;
+    /// 1.0 means positions are fully cash-funded
+    #[serde(default = "default_initial_margin")]
+    pub default_initial_margin: f64,
+    /// Realized loss on one closing fill that puts its symbol into a cooldown; 0 disables it
+    #[serde(default)]
+    pub loss_cooldown_threshold: f64,
+    /// How long only position-reducing orders pass in a symbol after such a loss
+    #[serde(default = "default_loss_cooldown_ms")]
+    pub loss_cooldown_ms: u64,
+    /// Longest a position may stay open in symbols without their own limit; 0 means no limit
+    #[serde(default)]
+    pub default_max_holding_ms: u64,
+}
+
+fn default_initial_margin() -> f64 {
+    1.0
+}
+
+fn default_loss_cooldown_ms() -> u64 {
+    300_000
 }
 
 impl Default for RiskConfig {
@@ -36,6 +60,10 @@ impl Default for RiskConfig {
             default_daily_loss_limit: 100_000.0,
             max_order_size: Quantity::new(100.0),
             price_tolerance_pct: 5.0,
+            default_initial_margin: default_initial_margin(),
+            loss_cooldown_threshold: 0.0,
+            loss_cooldown_ms: default_loss_cooldown_ms(),
+            default_max_holding_ms: 0,
         }
     }
 }
@@ -47,6 +75,11 @@ pub struct RiskMetrics {
     pub daily_pnl: f64,
     pub max_position_size: Quantity,
     pub violations_count: u64,
+    /// Orders run through `validate_order`; `dry_check` probes are not counted
+    #[serde(default)]
+    pub orders_checked: u64,
+    #[serde(default)]
+    pub orders_rejected: u64,
     pub last_update: DateTime<Utc>,
 }
 
@@ -58,18 +91,31 @@ impl Default for RiskMetrics {
             daily_pnl: 0.0,
             max_position_size: Quantity::ZERO,
             violations_count: 0,
-            last_update: Utc::now(),
+            orders_checked: 0,
+            orders_rejected: 0,
+            last_update: clock::now(),
         }
     }
 }
 
 pub struct RiskManager {
     config: RiskConfig,
-    limits: Arc<RwLock<HashMap<String, RiskLimits>>>,
+    /// Replaced wholesale on every change so a validation never sees a half-applied update
+    limits: ArcSwap<HashMap<String, RiskLimits>>,
     positions: Arc<RwLock<HashMap<String, PositionTracker>>>,
     validator: OrderValidator,
     metrics: Arc<RwLock<RiskMetrics>>,
     daily_pnl: Arc<RwLock<HashMap<Uuid, f64>>>,
+    /// Clients with an account here are checked for buying power; others are not
+    account_equity: Arc<RwLock<HashMap<Uuid, f64>>>,
+    initial_margin: Arc<RwLock<HashMap<String, f64>>>,
+    /// Symbols flattened for the day, where only position-reducing orders pass
+    opening_blocked: RwLock<HashSet<String>>,
+    /// Symbols that recently closed a position at a large loss, and when their cooldown ends
+    loss_cooldowns: RwLock<HashMap<String, DateTime<Utc>>>,
+    max_holding_times: RwLock<HashMap<String, Duration>>,
+    healthy: AtomicBool,
+    clock: Clock,
 }
 
 impl RiskManager {
@@ -82,27 +128,114 @@ impl RiskManager {
     pub fn with_config(config: RiskConfig) -> Self {
         Self {
             config,
-            limits: Arc::new(RwLock::new(HashMap::new())),
+            limits: ArcSwap::from_pointee(HashMap::new()),
             positions: Arc::new(RwLock::new(HashMap::new())),
             validator: OrderValidator::new(),
             metrics: Arc::new(RwLock::new(RiskMetrics::default())),
             daily_pnl: Arc::new(RwLock::new(HashMap::new())),
+            account_equity: Arc::new(RwLock::new(HashMap::new())),
+            initial_margin: Arc::new(RwLock::new(HashMap::new())),
+            opening_blocked: RwLock::new(HashSet::new()),
+            loss_cooldowns: RwLock::new(HashMap::new()),
+            max_holding_times: RwLock::new(HashMap::new()),
+            healthy: AtomicBool::new(true),
+            clock: Clock::Global,
         }
     }
     
+    /// Read time for cooldowns and holding limits from `clock` instead of the process-wide clock
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+    
+    #[inline]
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+    
     #[inline]
     pub fn validate_order(&self, order: &Order) -> Result<()> {
-        self.validator.validate_order(order)
-            .map_err(|e| anyhow::anyhow!("Risk validation failed: {}", e))?;
+        self.validate_order_with_reference_price(order, None)
+    }
+    
+    /// `validate_order` with the price a market order is expected to fill at. Market orders
+    /// carry no price of their own, so one that needs margin is rejected without a reference.
+    pub fn validate_order_with_reference_price(&self, order: &Order, reference_price: Option<Price>) -> Result<()> {
+        let verdict = self.check(order, &self.limits.load(), reference_price);
+        
+        let mut metrics = self.metrics.write();
+        metrics.orders_checked += 1;
+        if verdict.is_err() {
+            metrics.orders_rejected += 1;
+        }
+        drop(metrics);
+        
+        verdict.map_err(|e| anyhow::anyhow!("Risk validation failed: {}", e))
+    }
+    
+    /// Mark the risk subsystem degraded or recovered. Callers decide from their own policy
+    /// whether orders proceed while it is unhealthy.
+    pub fn set_healthy(&self, healthy: bool) {
+        if self.healthy.swap(healthy, Ordering::AcqRel) != healthy {
+            info!("Risk manager marked {}", if healthy { "healthy" } else { "unhealthy" });
+        }
+    }
+    
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Acquire)
+    }
+    
+    /// Would `order` pass `validate_order` right now? Runs the same checks without touching any state,
+    /// so strategies can probe order sizes freely.
+    pub fn dry_check(&self, order: &Order) -> std::result::Result<(), ValidationError> {
+        self.dry_check_with_limits(order, &self.limits.load())
+    }
+    
+    /// `dry_check` against a specific limits snapshot from `limits_snapshot`
+    pub fn dry_check_with_limits(
+        &self,
+        order: &Order,
+        limits: &HashMap<String, RiskLimits>,
+    ) -> std::result::Result<(), ValidationError> {
+        self.check(order, limits, None)
+    }
+    
+    fn check(
+        &self,
+        order: &Order,
+        limits: &HashMap<String, RiskLimits>,
+        reference_price: Option<Price>,
+    ) -> std::result::Result<(), ValidationError> {
+        self.validator.validate_order(order)?;
+        
+        let opening_blocked = if self.is_opening_blocked(&order.symbol) {
+            Some(ValidationError::OpeningBlocked { symbol: order.symbol.clone() })
+        } else {
+            self.loss_cooldown_until(&order.symbol)
+                .map(|until| ValidationError::LossCooldown { symbol: order.symbol.clone(), until })
+        };
+        if let Some(error) = opening_blocked {
+            if order.quantity.to_f64() > OrderValidator::reduce_only_capacity(order, self.current_position(order)) {
+                return Err(error);
+            }
+        }
+        
+        if order.reduce_only {
+            self.validator.validate_reduce_only(order, self.current_position(order))?;
+        }
         
         if self.config.enable_position_limits {
-            self.validate_position_limits(order)?;
+            self.validate_position_limits(order, limits)?;
         }
         
         if self.config.enable_pnl_limits {
             self.validate_pnl_limits(order.client_id)?;
         }
         
+        self.validate_margin(order, reference_price)?;
+        
         Ok(())
     }
     
@@ -117,12 +250,27 @@ impl RiskManager {
     
     #[inline]
     pub fn add_symbol_limits(&self, symbol: String, limits: RiskLimits) {
-        self.limits.write().insert(symbol, limits);
+        self.update_limits(|all| {
+            all.insert(symbol.clone(), limits.clone());
+        });
     }
     
     #[inline]
     pub fn get_symbol_limits(&self, symbol: &str) -> Option<RiskLimits> {
-        self.limits.read().get(symbol).cloned()
+        self.limits.load().get(symbol).cloned()
+    }
+    
+    /// Replace every symbol's limits at once; symbols missing from `limits` fall back to the defaults.
+    /// Validations already running finish against the set they started with.
+    pub fn reload_limits(&self, limits: HashMap<String, RiskLimits>) {
+        info!("Reloading risk limits for {} symbols", limits.len());
+        self.limits.store(Arc::new(limits));
+    }
+    
+    /// The limits currently in force, unaffected by later reloads
+    #[inline]
+    pub fn limits_snapshot(&self) -> Arc<HashMap<String, RiskLimits>> {
+        self.limits.load_full()
     }
     
     #[inline]
@@ -146,6 +294,11 @@ impl RiskManager {
         self.daily_pnl.read().get(&client_id).copied().unwrap_or(0.0)
     }
     
+    /// Daily realized P&L of the client that has lost the most today; zero if none has
+    pub fn worst_daily_pnl(&self) -> f64 {
+        self.daily_pnl.read().values().copied().fold(0.0, f64::min)
+    }
+    
     #[inline]
     pub fn reset_daily_pnl(&self) {
         self.daily_pnl.write().clear();
@@ -154,21 +307,25 @@ impl RiskManager {
     
     #[inline]
     pub fn set_position_limit(&self, symbol: &str, limit: f64) {
-        if let Some(limits) = self.limits.write().get_mut(symbol) {
-            limits.get_limit_mut(RiskLimitType::PositionSize).max_value = limit;
-        }
+        self.update_limits(|all| {
+            if let Some(limits) = all.get_mut(symbol) {
+                limits.get_limit_mut(RiskLimitType::PositionSize).max_value = limit;
+            }
+        });
     }
     
     #[inline]
     pub fn set_daily_pnl_limit(&self, symbol: &str, limit: f64) {
-        if let Some(limits) = self.limits.write().get_mut(symbol) {
-            limits.get_limit_mut(RiskLimitType::DailyPnL).max_value = limit;
-        }
+        self.update_limits(|all| {
+            if let Some(limits) = all.get_mut(symbol) {
+                limits.get_limit_mut(RiskLimitType::DailyPnL).max_value = limit;
+            }
+        });
     }
     
     #[inline]
     pub fn check_risk_violations(&self) -> Vec<(String, Vec<RiskLimitType>)> {
-        let limits = self.limits.read();
+        let limits = self.limits.load();
         let mut violations = Vec::new();
         
         for (symbol, symbol_limits) in limits.iter() {
@@ -181,37 +338,193 @@ impl RiskManager {
         violations
     }
     
-    fn validate_position_limits(&self, order: &Order) -> Result<()> {
-        let limits = self.limits.read();
-        let positions = self.positions.read();
+    /// Give `client_id` an account with `equity`, after which its orders must fit its buying power
+    pub fn set_account_equity(&self, client_id: Uuid, equity: f64) {
+        self.account_equity.write().insert(client_id, equity);
+    }
+    
+    #[inline]
+    pub fn account_equity(&self, client_id: Uuid) -> Option<f64> {
+        self.account_equity.read().get(&client_id).copied()
+    }
+    
+    /// Initial margin for `symbol` as a fraction of notional, e.g. 0.1 for 10x leverage
+    pub fn set_initial_margin(&self, symbol: &str, fraction: f64) {
+        self.initial_margin.write().insert(symbol.to_string(), fraction);
+    }
+    
+    #[inline]
+    pub fn initial_margin(&self, symbol: &str) -> f64 {
+        self.initial_margin.read().get(symbol).copied().unwrap_or(self.config.default_initial_margin)
+    }
+    
+    /// Margin held against the client's open positions, at their average entry prices.
+    /// Grows as fills add to a position and is released as fills reduce it.
+    pub fn committed_margin(&self, client_id: Uuid) -> f64 {
+        self.positions
+            .read()
+            .iter()
+            .filter_map(|(symbol, tracker)| {
+                let position = tracker.get_position(client_id)?;
+                Some(position.notional_value().abs() * self.initial_margin(symbol))
+            })
+            .sum()
+    }
+    
+    /// Equity less committed margin, or `None` for clients without an account
+    pub fn buying_power(&self, client_id: Uuid) -> Option<f64> {
+        Some(self.account_equity(client_id)? - self.committed_margin(client_id))
+    }
+    
+    /// Quantity a reduce-only `order` may trade against the client's live position; a larger
+    /// order is rejected, so callers wanting to auto-resize should clamp to this first
+    pub fn reduce_only_capacity(&self, order: &Order) -> Quantity {
+        Quantity::new(OrderValidator::reduce_only_capacity(order, self.current_position(order)))
+    }
+    
+    /// Non-flat positions held in `symbol`, one per client
+    pub fn open_positions(&self, symbol: &str) -> Vec<Position> {
+        self.positions
+            .read()
+            .get(symbol)
+            .map(|tracker| tracker.positions.values().filter(|p| !p.is_flat()).cloned().collect())
+            .unwrap_or_default()
+    }
+    
+    pub fn set_max_holding_time(&self, symbol: &str, max_holding: Duration) {
+        self.max_holding_times.write().insert(symbol.to_string(), max_holding);
+    }
+    
+    /// Longest a position in `symbol` may stay open, if it is limited at all
+    pub fn max_holding_time(&self, symbol: &str) -> Option<Duration> {
+        let default = self.config.default_max_holding_ms;
+        self.max_holding_times
+            .read()
+            .get(symbol)
+            .copied()
+            .or_else(|| (default > 0).then(|| Duration::from_millis(default)))
+    }
+    
+    /// Open positions in every symbol that have been held longer than the symbol allows
+    pub fn positions_over_holding_time(&self) -> Vec<Position> {
+        let now = self.clock.now();
+        let mut expired = Vec::new();
+        for (symbol, tracker) in self.positions.read().iter() {
+            let Some(max_holding) = self.max_holding_time(symbol).and_then(|max| chrono::Duration::from_std(max).ok())
+            else {
+                continue;
+            };
+            expired.extend(
+                tracker
+                    .positions
+                    .values()
+                    .filter(|p| !p.is_flat() && p.holding_time(now).is_some_and(|held| held > max_holding))
+                    .cloned(),
+            );
+        }
+        expired.sort_by(|a, b| a.symbol.cmp(&b.symbol).then(a.opened_at.cmp(&b.opened_at)));
+        expired
+    }
+    
+    /// Accept only orders that shrink an existing position in `symbol` until `allow_opening`
+    pub fn block_opening(&self, symbol: &str) {
+        if self.opening_blocked.write().insert(symbol.to_string()) {
+            info!("Opening orders blocked for {}", symbol);
+        }
+    }
+    
+    pub fn allow_opening(&self, symbol: &str) {
+        if self.opening_blocked.write().remove(symbol) {
+            info!("Opening orders allowed again for {}", symbol);
+        }
+    }
+    
+    pub fn is_opening_blocked(&self, symbol: &str) -> bool {
+        self.opening_blocked.read().contains(symbol)
+    }
+    
+    /// When opening orders in `symbol` are allowed again, if it is cooling down after a loss
+    pub fn loss_cooldown_until(&self, symbol: &str) -> Option<DateTime<Utc>> {
+        self.loss_cooldowns.read().get(symbol).copied().filter(|until| *until > self.clock.now())
+    }
+    
+    /// End a symbol's post-loss cooldown early
+    pub fn clear_loss_cooldown(&self, symbol: &str) {
+        self.loss_cooldowns.write().remove(symbol);
+    }
+    
+    fn start_loss_cooldown(&self, symbol: &str, loss: f64) {
+        let until = self.clock.now() + chrono::Duration::milliseconds(self.config.loss_cooldown_ms as i64);
+        self.loss_cooldowns.write().insert(symbol.to_string(), until);
+        info!("{} closed a position at a loss of {:.2}; opening orders blocked until {}", symbol, loss, until);
+    }
+    
+    fn current_position(&self, order: &Order) -> f64 {
+        self.positions
+            .read()
+            .get(&order.symbol)
+            .and_then(|tracker| tracker.get_position(order.client_id))
+            .map(|p| p.quantity)
+            .unwrap_or(0.0)
+    }
+    
+    fn validate_position_limits(
+        &self,
+        order: &Order,
+        limits: &HashMap<String, RiskLimits>,
+    ) -> std::result::Result<(), ValidationError> {
+        let max_position = match limits.get(&order.symbol) {
+            Some(symbol_limits) => symbol_limits.position_limit.max_value,
+            None => RiskLimits::new(order.symbol.clone()).position_limit.max_value,
+        };
         
-        let symbol_limits = limits.get(&order.symbol).cloned()
-            .unwrap_or_else(|| RiskLimits::new(order.symbol.clone()));
+        self.validator.validate_position_impact(order, self.current_position(order), max_position)
+    }
+    
+    /// Only the part of `order` that grows the absolute position needs margin; reducing or
+    /// closing a position is always allowed
+    fn validate_margin(&self, order: &Order, reference_price: Option<Price>) -> std::result::Result<(), ValidationError> {
+        let Some(available) = self.buying_power(order.client_id) else {
+            return Ok(());
+        };
         
-        let current_position = if let Some(tracker) = positions.get(&order.symbol) {
-            tracker.get_position(order.client_id)
-                .map(|p| p.quantity)
-                .unwrap_or(0.0)
-        } else {
-            0.0
+        let current = self.current_position(order);
+        let signed_quantity = match order.side {
+            Side::Buy => order.quantity.to_f64(),
+            Side::Sell => -order.quantity.to_f64(),
         };
+        let added = ((current + signed_quantity).abs() - current.abs()).max(0.0);
+        if added == 0.0 {
+            return Ok(());
+        }
         
-        self.validator.validate_position_impact(
-            order,
-            current_position,
-            symbol_limits.position_limit.max_value,
-        ).map_err(|e| anyhow::anyhow!("Position limit validation failed: {}", e))?;
+        let price = match order.order_type {
+            OrderType::Market => reference_price.ok_or_else(|| ValidationError::NoReferencePrice {
+                symbol: order.symbol.clone(),
+            })?,
+            _ => order.price,
+        };
+        let required = added * price.to_f64() * self.initial_margin(&order.symbol);
         
+        if required > available {
+            return Err(ValidationError::InsufficientMargin { required, available });
+        }
         Ok(())
     }
     
-    fn validate_pnl_limits(&self, client_id: Uuid) -> Result<()> {
+    /// Copy-on-write edit of the limit set; concurrent edits retry rather than lose updates
+    fn update_limits(&self, mut edit: impl FnMut(&mut HashMap<String, RiskLimits>)) {
+        self.limits.rcu(|current| {
+            let mut next = HashMap::clone(current);
+            edit(&mut next);
+            next
+        });
+    }
+    
+    fn validate_pnl_limits(&self, client_id: Uuid) -> std::result::Result<(), ValidationError> {
         let daily_pnl = self.get_daily_pnl(client_id);
         
         self.validator.validate_pnl_impact(daily_pnl, self.config.default_daily_loss_limit)
-            .map_err(|e| anyhow::anyhow!("P&L limit validation failed: {}", e))?;
-        
-        Ok(())
     }
     
     fn update_positions(&self, trade: &Trade) -> Result<()> {
@@ -221,8 +534,21 @@ impl RiskManager {
             .entry(trade.symbol.clone())
             .or_insert_with(|| PositionTracker::new(trade.symbol.clone()));
         
-        tracker.update_position_with_trade(trade, trade.buyer_client_id, Side::Buy);
-        tracker.update_position_with_trade(trade, trade.seller_client_id, Side::Sell);
+        let realized = |tracker: &PositionTracker, client_id: Uuid| {
+            tracker.get_position(client_id).map_or(0.0, |p| p.realized_pnl)
+        };
+        let mut worst_loss: f64 = 0.0;
+        for (client_id, side) in [(trade.buyer_client_id, Side::Buy), (trade.seller_client_id, Side::Sell)] {
+            let before = realized(tracker, client_id);
+            tracker.update_position_with_trade(trade, client_id, side);
+            worst_loss = worst_loss.max(before - realized(tracker, client_id));
+        }
+        drop(positions);
+        
+        let threshold = self.config.loss_cooldown_threshold;
+        if threshold > 0.0 && worst_loss >= threshold {
+            self.start_loss_cooldown(&trade.symbol, worst_loss);
+        }
         
         Ok(())
     }
@@ -257,7 +583,7 @@ impl RiskManager {
         
         let violations = self.check_risk_violations();
         metrics.violations_count = violations.len() as u64;
-        metrics.last_update = Utc::now();
+        metrics.last_update = self.clock.now();
     }
 }
 
@@ -265,4 +591,304 @@ impl Default for RiskManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use order_book::{OrderType, Price};
+
+    fn order(quantity: f64) -> Order {
+        Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            Price::new(500.0),
+            Quantity::new(quantity),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_dry_check_matches_validate_without_side_effects() {
+        let risk_manager = RiskManager::new();
+        let orders = [order(1.0), order(5000.0), order(0.0)];
+
+        for order in &orders {
+            let before = risk_manager.get_metrics();
+            let dry = risk_manager.dry_check(order);
+            let after = risk_manager.get_metrics();
+            assert_eq!(before.orders_checked, after.orders_checked);
+            assert_eq!(before.orders_rejected, after.orders_rejected);
+
+            let real = risk_manager.validate_order(order);
+            assert_eq!(dry.is_ok(), real.is_ok());
+            if let (Err(dry), Err(real)) = (&dry, &real) {
+                assert!(real.to_string().contains(&dry.to_string()));
+            }
+        }
+
+        assert!(risk_manager.dry_check(&orders[0]).is_ok());
+        assert!(matches!(
+            risk_manager.dry_check(&orders[1]),
+            Err(ValidationError::OrderSizeExceedsLimit { .. })
+        ));
+
+        let metrics = risk_manager.get_metrics();
+        assert_eq!(metrics.orders_checked, 3);
+        assert_eq!(metrics.orders_rejected, 2);
+    }
+
+    #[test]
+    fn test_reduce_only_against_long_position() {
+        let risk_manager = RiskManager::new();
+        let client_id = Uuid::new_v4();
+        let trade = Trade::new(
+            "BTCUSD",
+            order_book::OrderId::new(),
+            order_book::OrderId::new(),
+            Price::new(500.0),
+            Quantity::new(3.0),
+            client_id,
+            Uuid::new_v4(),
+            Side::Buy,
+        );
+        risk_manager.process_trade(&trade).unwrap();
+
+        let reduce_only = |side: Side, quantity: f64| {
+            Order::new(
+                "BTCUSD".to_string(),
+                side,
+                OrderType::Limit,
+                Price::new(500.0),
+                Quantity::new(quantity),
+                client_id,
+            )
+            .with_reduce_only()
+        };
+
+        assert!(matches!(
+            risk_manager.validate_order(&reduce_only(Side::Buy, 1.0)).map_err(|e| e.to_string()),
+            Err(reason) if reason.contains("Reduce-only")
+        ));
+        assert!(risk_manager.validate_order(&reduce_only(Side::Sell, 3.0)).is_ok());
+        assert!(risk_manager.validate_order(&reduce_only(Side::Sell, 1.5)).is_ok());
+        assert!(matches!(
+            risk_manager.dry_check(&reduce_only(Side::Sell, 4.0)),
+            Err(ValidationError::ReduceOnlyWouldIncrease { allowed, .. }) if allowed == 3.0
+        ));
+        assert_eq!(risk_manager.reduce_only_capacity(&reduce_only(Side::Sell, 4.0)), Quantity::new(3.0));
+        assert_eq!(risk_manager.reduce_only_capacity(&reduce_only(Side::Buy, 1.0)), Quantity::ZERO);
+
+        // Without a position there is nothing to reduce
+        let flat_client = reduce_only(Side::Sell, 1.0);
+        let flat_client = Order { client_id: Uuid::new_v4(), ..flat_client };
+        assert!(risk_manager.dry_check(&flat_client).is_err());
+    }
+    
+    #[test]
+    fn test_hot_reload_swaps_limits_without_affecting_in_flight_checks() {
+        let risk_manager = RiskManager::new();
+        risk_manager.add_symbol_limits(
+            "BTCUSD".to_string(),
+            RiskLimits::with_custom_limits("BTCUSD".to_string(), 10.0, 50_000.0, 5.0, 2.0, 500_000.0),
+        );
+        assert!(risk_manager.validate_order(&order(4.0)).is_ok());
+        
+        // A validation that has already loaded its limits keeps them across the reload
+        let in_flight = risk_manager.limits_snapshot();
+        
+        let tighter = HashMap::from([(
+            "BTCUSD".to_string(),
+            RiskLimits::with_custom_limits("BTCUSD".to_string(), 2.0, 50_000.0, 5.0, 2.0, 500_000.0),
+        )]);
+        risk_manager.reload_limits(tighter);
+        
+        assert!(risk_manager.dry_check_with_limits(&order(4.0), &in_flight).is_ok());
+        assert!(matches!(
+            risk_manager.dry_check(&order(4.0)),
+            Err(ValidationError::PositionLimitExceeded { .. })
+        ));
+        assert!(risk_manager.validate_order(&order(1.5)).is_ok());
+        assert_eq!(risk_manager.get_symbol_limits("BTCUSD").unwrap().position_limit.max_value, 2.0);
+        assert_eq!(in_flight["BTCUSD"].position_limit.max_value, 10.0);
+        
+        risk_manager.set_position_limit("BTCUSD", 3.0);
+        assert_eq!(risk_manager.get_symbol_limits("BTCUSD").unwrap().position_limit.max_value, 3.0);
+    }
+    
+    #[test]
+    fn test_order_beyond_buying_power_is_rejected_for_margin() {
+        let risk_manager = RiskManager::new();
+        let client_id = Uuid::new_v4();
+        let buy = |quantity: f64| Order { client_id, ..order(quantity) };
+        
+        // 10% initial margin on 10,000 of equity supports 200 lots at 500
+        risk_manager.set_account_equity(client_id, 10_000.0);
+        risk_manager.set_initial_margin("BTCUSD", 0.1);
+        assert_eq!(risk_manager.buying_power(client_id), Some(10_000.0));
+        
+        let fill = |side: Side, quantity: f64| {
+            let (buyer, seller) = match side {
+                Side::Buy => (client_id, Uuid::new_v4()),
+                Side::Sell => (Uuid::new_v4(), client_id),
+            };
+            let trade = Trade::new(
+                "BTCUSD",
+                order_book::OrderId::new(),
+                order_book::OrderId::new(),
+                Price::new(500.0),
+                Quantity::new(quantity),
+                buyer,
+                seller,
+                side,
+            );
+            risk_manager.process_trade(&trade).unwrap();
+        };
+        
+        // Two fills of 99 lots commit 9,900 and leave 100 of buying power
+        assert!(risk_manager.validate_order(&buy(99.0)).is_ok());
+        fill(Side::Buy, 99.0);
+        assert!(risk_manager.validate_order(&buy(99.0)).is_ok());
+        fill(Side::Buy, 99.0);
+        assert_eq!(risk_manager.committed_margin(client_id), 9_900.0);
+        assert_eq!(risk_manager.buying_power(client_id), Some(100.0));
+        
+        assert!(risk_manager.validate_order(&buy(2.0)).is_ok());
+        assert!(matches!(
+            risk_manager.dry_check(&buy(3.0)),
+            Err(ValidationError::InsufficientMargin { required, available }) if required == 150.0 && available == 100.0
+        ));
+        
+        // Selling down needs no margin and releases what the position held
+        let sell = |quantity: f64| Order { side: Side::Sell, ..buy(quantity) };
+        assert!(risk_manager.validate_order(&sell(50.0)).is_ok());
+        fill(Side::Sell, 50.0);
+        assert_eq!(risk_manager.buying_power(client_id), Some(2_600.0));
+        assert!(risk_manager.validate_order(&buy(3.0)).is_ok());
+        
+        // Clients without an account are not margin checked
+        assert!(risk_manager.dry_check(&order(50.0)).is_ok());
+    }
+    
+    #[test]
+    fn test_market_order_is_margined_at_its_reference_price() {
+        let risk_manager = RiskManager::new();
+        let client_id = Uuid::new_v4();
+        risk_manager.set_account_equity(client_id, 10_000.0);
+        risk_manager.set_initial_margin("BTCUSD", 0.1);
+        let market = |side: Side, quantity: f64| Order {
+            client_id,
+            side,
+            order_type: OrderType::Market,
+            price: Price::ZERO,
+            ..order(quantity)
+        };
+        
+        // 201 lots at 500 need 10,050 against 10,000 of buying power
+        let reference = Some(Price::new(500.0));
+        assert!(risk_manager.validate_order_with_reference_price(&market(Side::Buy, 200.0), reference).is_ok());
+        assert!(risk_manager.validate_order_with_reference_price(&market(Side::Buy, 201.0), reference).is_err());
+        assert!(matches!(
+            risk_manager.dry_check(&market(Side::Buy, 1.0)),
+            Err(ValidationError::NoReferencePrice { .. })
+        ));
+        
+        // Closing out needs no margin, so no reference either
+        let trade = Trade::new(
+            "BTCUSD",
+            order_book::OrderId::new(),
+            order_book::OrderId::new(),
+            Price::new(500.0),
+            Quantity::new(10.0),
+            client_id,
+            Uuid::new_v4(),
+            Side::Buy,
+        );
+        risk_manager.process_trade(&trade).unwrap();
+        assert!(risk_manager.validate_order(&market(Side::Sell, 10.0)).is_ok());
+    }
+    
+    #[test]
+    fn test_large_realized_loss_blocks_opening_orders_until_cooldown_ends() {
+        let risk_manager = RiskManager::with_config(RiskConfig {
+            loss_cooldown_threshold: 1_000.0,
+            loss_cooldown_ms: 100,
+            ..RiskConfig::default()
+        });
+        let client_id = Uuid::new_v4();
+        let fill = |side: Side, price: f64, quantity: f64| {
+            let (buyer, seller) = match side {
+                Side::Buy => (client_id, Uuid::new_v4()),
+                Side::Sell => (Uuid::new_v4(), client_id),
+            };
+            let trade = Trade::new(
+                "BTCUSD",
+                order_book::OrderId::new(),
+                order_book::OrderId::new(),
+                Price::new(price),
+                Quantity::new(quantity),
+                buyer,
+                seller,
+                side,
+            );
+            risk_manager.process_trade(&trade).unwrap();
+        };
+        let buy = |quantity: f64| Order { client_id, ..order(quantity) };
+        let sell = |quantity: f64| Order { side: Side::Sell, ..buy(quantity) };
+        
+        // A small loss is below the threshold
+        fill(Side::Buy, 500.0, 10.0);
+        fill(Side::Sell, 490.0, 5.0);
+        assert!(risk_manager.loss_cooldown_until("BTCUSD").is_none());
+        
+        // Stopping out the rest 300 lower realizes 1,500
+        fill(Side::Sell, 200.0, 5.0);
+        assert!(risk_manager.loss_cooldown_until("BTCUSD").is_some());
+        assert!(matches!(
+            risk_manager.dry_check(&buy(1.0)),
+            Err(ValidationError::LossCooldown { symbol, .. }) if symbol == "BTCUSD"
+        ));
+        assert!(matches!(risk_manager.dry_check(&sell(1.0)), Err(ValidationError::LossCooldown { .. })));
+        
+        // Other symbols are unaffected
+        assert!(risk_manager.dry_check(&Order { symbol: "ETHUSD".to_string(), ..buy(1.0) }).is_ok());
+        
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        assert!(risk_manager.loss_cooldown_until("BTCUSD").is_none());
+        assert!(risk_manager.validate_order(&buy(1.0)).is_ok());
+    }
+    
+    #[test]
+    fn test_loss_cooldown_still_allows_reducing_orders() {
+        let risk_manager = RiskManager::with_config(RiskConfig {
+            loss_cooldown_threshold: 100.0,
+            ..RiskConfig::default()
+        });
+        let client_id = Uuid::new_v4();
+        let other_client = Uuid::new_v4();
+        let trade = |buyer: Uuid, seller: Uuid, price: f64, quantity: f64| {
+            Trade::new(
+                "BTCUSD",
+                order_book::OrderId::new(),
+                order_book::OrderId::new(),
+                Price::new(price),
+                Quantity::new(quantity),
+                buyer,
+                seller,
+                Side::Buy,
+            )
+        };
+        
+        // The client is long 2; the counterparty closes a short at a loss of 200
+        risk_manager.process_trade(&trade(client_id, other_client, 500.0, 2.0)).unwrap();
+        risk_manager.process_trade(&trade(other_client, Uuid::new_v4(), 600.0, 2.0)).unwrap();
+        assert!(risk_manager.loss_cooldown_until("BTCUSD").is_some());
+        
+        let sell = |quantity: f64| Order { client_id, side: Side::Sell, ..order(quantity) };
+        assert!(risk_manager.validate_order(&sell(2.0)).is_ok());
+        assert!(risk_manager.dry_check(&sell(3.0)).is_err());
+        
+        risk_manager.clear_loss_cooldown("BTCUSD");
+        assert!(risk_manager.dry_check(&sell(3.0)).is_ok());
+    }
+}
@@ -1,4 +1,4 @@
-use order_book::{Trade, Price, Side};
+use order_book::{clock, Trade, Price, Side};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -16,12 +16,17 @@ pub struct Position {
     pub mark_price: Option<Price>,
     pub last_update: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    /// When the position last opened from flat; `None` while flat. Adding to the position or
+    /// partly reducing it keeps the clock running, so a position trimmed and then built back
+    /// up is still as old as when it opened. Closing clears it and a flip through flat restarts it.
+    #[serde(default)]
+    pub opened_at: Option<DateTime<Utc>>,
 }
 
 impl Position {
     #[inline]
     pub fn new(symbol: String, client_id: Uuid) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             symbol,
             client_id,
@@ -33,6 +38,7 @@ impl Position {
             mark_price: None,
             last_update: now,
             created_at: now,
+            opened_at: None,
         }
     }
     
@@ -51,6 +57,12 @@ impl Position {
         self.quantity == 0.0
     }
     
+    /// How long the position has been open at `now`, or `None` while flat
+    #[inline]
+    pub fn holding_time(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.opened_at.map(|opened_at| now - opened_at)
+    }
+    
     #[inline]
     pub fn notional_value(&self) -> f64 {
         self.quantity * self.average_price.to_f64()
@@ -61,7 +73,7 @@ impl Position {
         self.mark_price = Some(mark_price);
         self.calculate_unrealized_pnl();
         self.update_total_pnl();
-        self.last_update = Utc::now();
+        self.last_update = clock::now();
     }
     
     #[inline]
@@ -70,6 +82,7 @@ impl Position {
             Side::Buy => trade.quantity.to_f64(),
             Side::Sell => -trade.quantity.to_f64(),
         };
+        let previous_quantity = self.quantity;
         
         if self.is_flat() {
             self.quantity = trade_quantity;
@@ -109,9 +122,17 @@ impl Position {
             }
         }
         
+        // Aged by when the trade happened, on whichever clock the book stamped it with
+        let now = trade.timestamp;
+        if self.is_flat() {
+            self.opened_at = None;
+        } else if previous_quantity == 0.0 || previous_quantity.signum() != self.quantity.signum() {
+            self.opened_at = Some(now);
+        }
+        
         self.calculate_unrealized_pnl();
         self.update_total_pnl();
-        self.last_update = Utc::now();
+        self.last_update = now;
     }
     
     fn calculate_unrealized_pnl(&mut self) {
@@ -158,7 +179,7 @@ impl PositionTracker {
             total_realized_pnl: 0.0,
             total_unrealized_pnl: 0.0,
             total_pnl: 0.0,
-            last_update: Utc::now(),
+            last_update: clock::now(),
         }
     }
     
@@ -226,6 +247,6 @@ impl PositionTracker {
         
         self.net_quantity = self.total_long_quantity + self.total_short_quantity;
         self.total_pnl = self.total_realized_pnl + self.total_unrealized_pnl;
-        self.last_update = Utc::now();
+        self.last_update = clock::now();
     }
 }
\ No newline at end of file
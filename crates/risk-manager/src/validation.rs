@@ -1,4 +1,5 @@
 use order_book::{Order, Price, Quantity, Side, OrderType};
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +42,21 @@ pub enum ValidationError {
     
     #[error("Order size is below minimum: {size} < {min_size}")]
     OrderSizeBelowMinimum { size: f64, min_size: f64 },
+    
+    #[error("Reduce-only order of {quantity} would increase position {current}; at most {allowed} can be reduced")]
+    ReduceOnlyWouldIncrease { current: f64, quantity: f64, allowed: f64 },
+    
+    #[error("Insufficient margin: order requires {required}, buying power is {available}")]
+    InsufficientMargin { required: f64, available: f64 },
+    
+    #[error("No reference price to margin a market order on {symbol}")]
+    NoReferencePrice { symbol: String },
+    
+    #[error("Orders opening new positions in {symbol} are blocked until the next session")]
+    OpeningBlocked { symbol: String },
+    
+    #[error("Orders opening new positions in {symbol} are blocked until {until} after a loss")]
+    LossCooldown { symbol: String, until: DateTime<Utc> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,6 +170,34 @@ impl OrderValidator {
         Ok(())
     }
     
+    /// Largest quantity of a reduce-only order that closes toward flat without crossing it
+    #[inline]
+    pub fn reduce_only_capacity(order: &Order, current_position: f64) -> f64 {
+        match order.side {
+            Side::Buy if current_position < 0.0 => -current_position,
+            Side::Sell if current_position > 0.0 => current_position,
+            _ => 0.0,
+        }
+    }
+    
+    pub fn validate_reduce_only(&self, order: &Order, current_position: f64) -> Result<(), ValidationError> {
+        if !order.reduce_only {
+            return Ok(());
+        }
+        
+        let allowed = Self::reduce_only_capacity(order, current_position);
+        let quantity = order.quantity.to_f64();
+        if quantity > allowed {
+            return Err(ValidationError::ReduceOnlyWouldIncrease {
+                current: current_position,
+                quantity,
+                allowed,
+            });
+        }
+        
+        Ok(())
+    }
+    
     #[inline]
     pub fn validate_pnl_impact(&self, current_pnl: f64, pnl_limit: f64) -> Result<(), ValidationError> {
         if !self.config.enable_pnl_validation {
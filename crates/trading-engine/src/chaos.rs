@@ -0,0 +1,58 @@
+//! Artificial matching latency for resilience testing. Only built with the `chaos` feature,
+//! so production builds carry no trace of it in the matching path.
+
+use parking_lot::RwLock;
+use rand::Rng;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How long each injected delay lasts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatencyDistribution {
+    Fixed(Duration),
+    /// Uniform between `min` and `max` inclusive
+    Uniform { min: Duration, max: Duration },
+    /// Exponential with the given mean, capped at `max` to keep tests bounded
+    Exponential { mean: Duration, max: Duration },
+}
+
+impl LatencyDistribution {
+    pub fn sample(&self, rng: &mut impl Rng) -> Duration {
+        match *self {
+            LatencyDistribution::Fixed(delay) => delay,
+            LatencyDistribution::Uniform { min, max } if min < max => rng.gen_range(min..=max),
+            LatencyDistribution::Uniform { min, .. } => min,
+            LatencyDistribution::Exponential { mean, max } => {
+                let uniform: f64 = rng.gen();
+                mean.mul_f64(-(1.0 - uniform).ln()).min(max)
+            }
+        }
+    }
+}
+
+/// Per-symbol delays applied before an order is matched
+#[derive(Debug, Default)]
+pub(crate) struct LatencyInjector {
+    symbols: RwLock<HashMap<String, LatencyDistribution>>,
+}
+
+impl LatencyInjector {
+    pub(crate) fn set(&self, symbol: &str, distribution: LatencyDistribution) {
+        self.symbols.write().insert(symbol.to_string(), distribution);
+    }
+
+    pub(crate) fn clear(&self, symbol: &str) -> bool {
+        self.symbols.write().remove(symbol).is_some()
+    }
+
+    /// Block the calling thread for a sampled delay if `symbol` has injection enabled
+    pub(crate) fn delay(&self, symbol: &str) {
+        let Some(distribution) = self.symbols.read().get(symbol).copied() else {
+            return;
+        };
+        let delay = distribution.sample(&mut rand::thread_rng());
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
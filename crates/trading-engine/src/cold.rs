@@ -0,0 +1,69 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use order_book::Order;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What an evicted book needs to come back: its resting orders in price-time priority.
+/// Everything else is rebuilt from the engine config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColdBook {
+    pub symbol: String,
+    pub orders: Vec<Order>,
+    pub evicted_at: DateTime<Utc>,
+}
+
+/// Where evicted books are kept until an order for the symbol arrives
+pub trait ColdStore: Send + Sync {
+    fn put(&self, book: ColdBook) -> Result<()>;
+
+    /// The book for `symbol`, left in the store until `remove` is called
+    fn get(&self, symbol: &str) -> Result<Option<ColdBook>>;
+    
+    fn remove(&self, symbol: &str) -> Result<()>;
+
+    fn contains(&self, symbol: &str) -> bool;
+}
+
+/// Keeps evicted books serialized in process memory, a fraction of the size of the live
+/// structures they replace
+#[derive(Debug, Default)]
+pub struct InMemoryColdStore {
+    books: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryColdStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialized bytes held across all evicted books
+    pub fn stored_bytes(&self) -> usize {
+        self.books.lock().values().map(Vec::len).sum()
+    }
+}
+
+impl ColdStore for InMemoryColdStore {
+    fn put(&self, book: ColdBook) -> Result<()> {
+        let bytes = bincode::serialize(&book)?;
+        self.books.lock().insert(book.symbol, bytes);
+        Ok(())
+    }
+
+    fn get(&self, symbol: &str) -> Result<Option<ColdBook>> {
+        match self.books.lock().get(symbol) {
+            Some(bytes) => Ok(Some(bincode::deserialize(bytes)?)),
+            None => Ok(None),
+        }
+    }
+    
+    fn remove(&self, symbol: &str) -> Result<()> {
+        self.books.lock().remove(symbol);
+        Ok(())
+    }
+
+    fn contains(&self, symbol: &str) -> bool {
+        self.books.lock().contains_key(symbol)
+    }
+}
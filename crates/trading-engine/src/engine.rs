@@ -1,11 +1,28 @@
-use order_book::{OrderBook, MatchResult, Order, OrderId, Trade, Quantity, Side};
-use event_processor::{EventProcessor, Event, OrderEvent, TradeEvent};
-use risk_manager::RiskManager;
-use std::collections::HashMap;
+use order_book::{ArchiveSink, Clock, MarketData, OrderBook, OrderBookError, MatchResult, MemoryFootprint, LevelCap, LotModel, Order, OrderSizeLimits, OrderId, SelfTradePrevention, OrderIdGenerator, OrderType, Price, OrderStatus, Trade, Quantity, Side};
+#[cfg(feature = "chaos")]
+use crate::chaos::{LatencyDistribution, LatencyInjector};
+use crate::cold::{ColdBook, ColdStore, InMemoryColdStore};
+use crate::gateway::RejectReason;
+use crate::matching_loop::{MatchingLoop, PendingOrder};
+use crate::progress::{progress_stream, FillNotice, OrderProgress};
+use crate::replay::{first_divergences, Divergence, ReplayInput, TradeNormalizer};
+use crate::session::{SessionPhase, SessionSchedule};
+use crate::strategy::{OrderIntent, RegisteredStrategy, Strategy};
+use dashmap::DashMap;
+use futures::Stream;
+use tokio::sync::mpsc;
+use event_processor::{EventProcessor, Event, OrderEvent, Probe, TradeEvent};
+use latency_profiler::profiler::{MeasurementPoint, ScopedMeasurement};
+use latency_profiler::{LatencyProfiler, RdtscTimestamp, GLOBAL_RDTSC_PROFILER};
+use risk_manager::{Position, RiskManager};
+use std::panic::AssertUnwindSafe;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use parking_lot::RwLock;
 use anyhow::Result;
-use tracing::info;
+use tracing::{error, info, warn};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
@@ -14,7 +31,61 @@ pub struct EngineConfig {
     pub max_symbols: usize,
     pub enable_risk_checks: bool,
     pub enable_event_emission: bool,
+    pub enable_execution_reports: bool,
+    /// Dispatch all events from one `submit_order` as a single `Event::Batch`
+    pub coalesce_events: bool,
     pub max_orders_per_symbol: usize,
+    /// Per-side price level cap applied to every book the engine creates
+    #[serde(default)]
+    pub level_cap: Option<LevelCap>,
+    /// Book-level order quantity bounds by symbol; symbols without an entry are unbounded
+    #[serde(default)]
+    pub order_size_limits: HashMap<String, OrderSizeLimits>,
+    /// Lot models by symbol; symbols without an entry trade fractional quantities
+    #[serde(default)]
+    pub lot_models: HashMap<String, LotModel>,
+    /// Self-trade prevention by symbol; symbols without an entry let an account trade with itself
+    #[serde(default)]
+    pub self_trade_prevention: HashMap<String, SelfTradePrevention>,
+    /// Window over which `submit_order_stream` folds fills into one progress update; 0 disables coalescing
+    #[serde(default = "default_fill_coalesce_window_us")]
+    pub fill_coalesce_window_us: u64,
+    /// Trading hours by symbol; symbols without a schedule trade around the clock
+    #[serde(default)]
+    pub session_schedules: HashMap<String, SessionSchedule>,
+    /// What happens to orders while the risk manager is unhealthy or its check panics
+    #[serde(default)]
+    pub risk_failure_policy: RiskFailurePolicy,
+    /// Publish one trade event per price an aggressor swept rather than one per fill.
+    /// Execution reports and risk updates still see every fill.
+    #[serde(default)]
+    pub consolidate_trade_prints: bool,
+    /// Hard cap on the notional of any single order, checked before risk. Market orders
+    /// are valued at the depth they would sweep.
+    #[serde(default)]
+    pub max_order_notional: Option<f64>,
+    /// Idle time after which `evict_inactive_books` moves a symbol's book to the cold store;
+    /// 0 keeps every book hot
+    #[serde(default)]
+    pub cold_after_ms: u64,
+    /// Time every `submit_order` into `MeasurementPoint::OrderAckLatency` on the engine's
+    /// latency profiler
+    #[serde(default)]
+    pub measure_ack_latency: bool,
+}
+
+/// Order handling when risk checks are enabled but cannot run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskFailurePolicy {
+    /// Reject every new order until the risk manager is healthy again
+    #[default]
+    FailClosed,
+    /// Accept orders without risk checks
+    FailOpen,
+}
+
+fn default_fill_coalesce_window_us() -> u64 {
+    200
 }
 
 impl Default for EngineConfig {
@@ -23,7 +94,20 @@ impl Default for EngineConfig {
             max_symbols: 1000,
             enable_risk_checks: true,
             enable_event_emission: true,
+            enable_execution_reports: false,
+            coalesce_events: false,
             max_orders_per_symbol: 1_000_000,
+            level_cap: None,
+            order_size_limits: HashMap::new(),
+            lot_models: HashMap::new(),
+            self_trade_prevention: HashMap::new(),
+            fill_coalesce_window_us: default_fill_coalesce_window_us(),
+            session_schedules: HashMap::new(),
+            risk_failure_policy: RiskFailurePolicy::default(),
+            consolidate_trade_prints: false,
+            max_order_notional: None,
+            cold_after_ms: 0,
+            measure_ack_latency: false,
         }
     }
 }
@@ -44,6 +128,8 @@ pub enum OrderResponse {
         order_id: OrderId,
         trades: Vec<Trade>,
         remaining_quantity: Quantity,
+        /// Quantity of a market order discarded for want of liquidity rather than rested
+        shortfall: Option<Quantity>,
         timestamp: chrono::DateTime<Utc>,
     },
     FullyFilled {
@@ -53,6 +139,16 @@ pub enum OrderResponse {
     },
 }
 
+impl OrderResponse {
+    /// Fills the order made on submission; empty unless it traded
+    pub fn trades(&self) -> &[Trade] {
+        match self {
+            OrderResponse::PartiallyFilled { trades, .. } | OrderResponse::FullyFilled { trades, .. } => trades,
+            OrderResponse::Accepted { .. } | OrderResponse::Rejected { .. } => &[],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CancelResponse {
     Cancelled {
@@ -65,11 +161,87 @@ pub enum CancelResponse {
     },
 }
 
+/// How a paused symbol treats new orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PauseMode {
+    /// Reject every new order for the symbol
+    HaltAll,
+    /// Accept orders but hold them unmatched until the symbol is resumed
+    QueueOnly,
+}
+
+#[derive(Debug)]
+struct SymbolPause {
+    mode: PauseMode,
+    deferred: Vec<Order>,
+}
+
+#[derive(Debug)]
+struct SymbolSession {
+    schedule: SessionSchedule,
+    /// Orders accepted during pre-open, released in arrival order once the session opens
+    queued: Vec<Order>,
+    /// Positions were flattened in the current flatten window
+    flattened: bool,
+}
+
+/// Keeps matching for a symbol added with `add_symbol_on_node` on that NUMA node.
+/// The book itself is allocated by the global allocator.
+pub trait BookPlacer: Send + Sync {
+    /// Pin the calling thread, a symbol's matching loop, to `numa_node`
+    fn pin_matching_thread(&self, symbol: &str, numa_node: usize) -> Result<()>;
+}
+
+/// The sending end of a `submit_order_stream` stream
+#[derive(Debug)]
+struct OrderWatcher {
+    symbol: String,
+    sender: mpsc::UnboundedSender<FillNotice>,
+    /// Set once the submission returns; until then the order may not be in its book yet
+    entered: bool,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    orders_submitted: AtomicU64,
+    orders_accepted: AtomicU64,
+    orders_rejected: AtomicU64,
+    orders_cancelled: AtomicU64,
+    trades_executed: AtomicU64,
+    events_dispatched: AtomicU64,
+}
+
+/// Point-in-time copy of the engine's always-on rate counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineCounters {
+    pub orders_submitted: u64,
+    pub orders_accepted: u64,
+    pub orders_rejected: u64,
+    pub orders_cancelled: u64,
+    pub trades_executed: u64,
+    pub events_dispatched: u64,
+}
+
 pub struct TradingEngine {
     config: EngineConfig,
+    counters: Counters,
     order_books: Arc<RwLock<HashMap<String, Arc<OrderBook>>>>,
+    cold_store: RwLock<Arc<dyn ColdStore>>,
+    cold_symbols: RwLock<HashSet<String>>,
+    book_placements: Arc<RwLock<HashMap<String, usize>>>,
+    book_placer: RwLock<Option<Arc<dyn BookPlacer>>>,
+    paused: RwLock<HashMap<String, SymbolPause>>,
+    sessions: RwLock<HashMap<String, SymbolSession>>,
+    matching_loops: RwLock<HashMap<String, MatchingLoop>>,
+    order_watchers: DashMap<OrderId, OrderWatcher>,
+    strategies: RwLock<Vec<Arc<RegisteredStrategy>>>,
+    order_ids: OrderIdGenerator,
+    #[cfg(feature = "chaos")]
+    latency_injector: LatencyInjector,
     risk_manager: Arc<RiskManager>,
     event_processor: Arc<EventProcessor>,
+    latency_profiler: Arc<LatencyProfiler>,
+    clock: Clock,
     running: Arc<RwLock<bool>>,
 }
 
@@ -81,14 +253,34 @@ impl TradingEngine {
     
     #[inline]
     pub fn with_config(config: EngineConfig) -> Self {
+        Self::with_clock(config, Clock::Global)
+    }
+    
+    /// Engine whose books, risk manager and events all read time from `clock`
+    pub fn with_clock(config: EngineConfig, clock: Clock) -> Self {
         let event_processor = Arc::new(EventProcessor::new());
-        let risk_manager = Arc::new(RiskManager::new());
+        let risk_manager = Arc::new(RiskManager::new().with_clock(clock.clone()));
         
         Self {
             config,
+            counters: Counters::default(),
             order_books: Arc::new(RwLock::new(HashMap::new())),
+            cold_store: RwLock::new(Arc::new(InMemoryColdStore::new())),
+            cold_symbols: RwLock::new(HashSet::new()),
+            book_placements: Arc::new(RwLock::new(HashMap::new())),
+            book_placer: RwLock::new(None),
+            paused: RwLock::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
+            matching_loops: RwLock::new(HashMap::new()),
+            order_watchers: DashMap::new(),
+            strategies: RwLock::new(Vec::new()),
+            order_ids: OrderIdGenerator::new(),
+            #[cfg(feature = "chaos")]
+            latency_injector: LatencyInjector::default(),
             risk_manager,
             event_processor,
+            latency_profiler: Arc::new(LatencyProfiler::new()),
+            clock,
             running: Arc::new(RwLock::new(false)),
         }
     }
@@ -120,29 +312,287 @@ impl TradingEngine {
         *self.running.read()
     }
     
+    /// Fails only when the engine cannot recover on its own: it is running but its event
+    /// processor has stopped. An engine that has not been started yet is still live.
+    pub fn liveness(&self) -> Probe {
+        Probe::check(!self.is_running() || self.event_processor.is_running(), || {
+            "event processor stopped while the engine is running".to_string()
+        })
+    }
+    
+    /// Whether orders should be routed here: started, with at least one symbol, a healthy
+    /// risk manager and risk limits loaded
+    pub fn readiness(&self) -> Probe {
+        if !self.is_running() {
+            return Probe::Fail("engine not started".to_string());
+        }
+        if self.order_books.read().is_empty() {
+            return Probe::Fail("no symbols configured".to_string());
+        }
+        if !self.risk_manager.is_healthy() {
+            return Probe::Fail("risk manager unhealthy".to_string());
+        }
+        Probe::check(!self.risk_manager.limits_snapshot().is_empty(), || "risk limits not loaded".to_string())
+    }
+    
     #[inline]
     pub fn add_symbol(&self, symbol: String) -> Result<()> {
         let mut books = self.order_books.write();
         
-        if books.len() >= self.config.max_symbols {
+        let cold_symbols = self.cold_symbols.read();
+        
+        if books.len() + cold_symbols.len() >= self.config.max_symbols {
             return Err(anyhow::anyhow!("Maximum symbols limit reached"));
         }
         
-        if !books.contains_key(&symbol) {
-            let order_book = Arc::new(OrderBook::new(symbol.clone()));
+        // A cold symbol already has a book; it comes back with its orders on first use
+        if !books.contains_key(&symbol) && !cold_symbols.contains(&symbol) {
+            let order_book = Arc::new(self.build_book(&symbol));
             books.insert(symbol.clone(), order_book);
+            if let Some(schedule) = self.config.session_schedules.get(&symbol) {
+                self.sessions.write().insert(symbol.clone(), SymbolSession { schedule: *schedule, queued: Vec::new(), flattened: false });
+            }
             info!("Added new symbol: {}", symbol);
         }
         
         Ok(())
     }
     
+    /// Empty book for `symbol` configured from the engine config
+    fn build_book(&self, symbol: &str) -> OrderBook {
+        let order_book = match self.config.level_cap {
+            Some(level_cap) => OrderBook::with_level_cap(symbol.to_string(), level_cap),
+            None => OrderBook::new(symbol.to_string()),
+        }
+        .with_clock(self.clock.clone());
+        let size_limits = self.config.order_size_limits.get(symbol).copied().unwrap_or_default();
+        let lot_model = self.config.lot_models.get(symbol).copied().unwrap_or_default();
+        let mut order_book = order_book.with_size_limits(size_limits).with_lot_model(lot_model);
+        if let Some(self_trade_prevention) = self.config.self_trade_prevention.get(symbol) {
+            order_book = order_book.with_self_trade_prevention(*self_trade_prevention);
+        }
+        order_book
+    }
+    
+    /// Replace where evicted books are kept. Books already evicted stay in the previous
+    /// store, so install this before evicting anything.
+    #[inline]
+    pub fn set_cold_store(&self, store: Arc<dyn ColdStore>) {
+        *self.cold_store.write() = store;
+    }
+    
+    /// Serialize a symbol's resting orders to the cold store and drop its book from memory.
+    /// The next `submit_order` or `cancel_order` for the symbol rebuilds it with every order
+    /// in its queue position; until then `get_order_book` and market data see no book.
+    /// Symbols with a matching loop, a pause, pre-open orders or an interrupted taker stay hot.
+    pub fn evict_book(&self, symbol: &str) -> Result<()> {
+        // Held until the book is gone so no loop, pause or pre-open order can slip in between
+        // the checks and the eviction. Taken before the books lock, as resuming and opening do.
+        let loops = self.matching_loops.read();
+        let paused = self.paused.read();
+        let sessions = self.sessions.read();
+        let mut books = self.order_books.write();
+        
+        if loops.contains_key(symbol) {
+            return Err(anyhow::anyhow!("Cannot evict {}: matching loop running", symbol));
+        }
+        if paused.contains_key(symbol) {
+            return Err(anyhow::anyhow!("Cannot evict {}: symbol paused", symbol));
+        }
+        if sessions.get(symbol).is_some_and(|session| !session.queued.is_empty()) {
+            return Err(anyhow::anyhow!("Cannot evict {}: pre-open orders queued", symbol));
+        }
+        let Some(order_book) = books.get(symbol).cloned() else {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        };
+        if !order_book.interrupted_orders().is_empty() {
+            return Err(anyhow::anyhow!("Cannot evict {}: taker interrupted mid-match", symbol));
+        }
+        
+        let orders = order_book.resting_orders();
+        let resting = orders.len();
+        self.cold_store.read().put(ColdBook {
+            symbol: symbol.to_string(),
+            orders,
+            evicted_at: self.clock.now(),
+        })?;
+        books.remove(symbol);
+        self.cold_symbols.write().insert(symbol.to_string());
+        info!("Evicted {} to cold store with {} resting orders", symbol, resting);
+        
+        Ok(())
+    }
+    
+    /// Evict every book idle for longer than `cold_after_ms`, skipping those `evict_book`
+    /// would keep hot. Returns the symbols evicted.
+    pub fn evict_inactive_books(&self) -> Vec<String> {
+        if self.config.cold_after_ms == 0 {
+            return Vec::new();
+        }
+        let cutoff = self.clock.now() - chrono::Duration::milliseconds(self.config.cold_after_ms as i64);
+        let idle: Vec<String> = self.order_books
+            .read()
+            .iter()
+            .filter(|(_, book)| book.stats().last_update <= cutoff)
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        
+        idle.into_iter()
+            .filter(|symbol| match self.evict_book(symbol) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Keeping {} hot: {}", symbol, e);
+                    false
+                }
+            })
+            .collect()
+    }
+    
+    /// Whether the symbol's book is in the cold store rather than in memory
+    #[inline]
+    pub fn is_cold(&self, symbol: &str) -> bool {
+        self.cold_symbols.read().contains(symbol)
+    }
+    
+    /// The symbol's live book, rebuilt from the cold store if it was evicted
+    fn hot_book(&self, symbol: &str) -> Result<Option<Arc<OrderBook>>> {
+        if let Some(order_book) = self.order_books.read().get(symbol) {
+            return Ok(Some(order_book.clone()));
+        }
+        if !self.is_cold(symbol) {
+            return Ok(None);
+        }
+        
+        let mut books = self.order_books.write();
+        // Another order may have rehydrated the book while we waited for the lock
+        if let Some(order_book) = books.get(symbol) {
+            return Ok(Some(order_book.clone()));
+        }
+        let cold_store = self.cold_store.read();
+        let Some(cold_book) = cold_store.get(symbol)? else {
+            return Ok(None);
+        };
+        
+        // Left in the store until fully restored, so a failed restore keeps the book cold
+        let order_book = self.build_book(symbol);
+        let resting = cold_book.orders.len();
+        for order in cold_book.orders {
+            order_book.restore_resting(order)?;
+        }
+        cold_store.remove(symbol)?;
+        let order_book = Arc::new(order_book);
+        books.insert(symbol.to_string(), order_book.clone());
+        self.cold_symbols.write().remove(symbol);
+        info!("Rehydrated {} from cold store with {} resting orders", symbol, resting);
+        
+        Ok(Some(order_book))
+    }
+    
+    /// Install the placer that pins matching loops of symbols added with `add_symbol_on_node`
+    #[inline]
+    pub fn set_book_placer(&self, placer: Arc<dyn BookPlacer>) {
+        *self.book_placer.write() = Some(placer);
+    }
+    
+    /// Add a symbol assigned to `numa_node`, so matching work for it can be routed to a
+    /// worker on that node and its matching loop pinned there
+    pub fn add_symbol_on_node(&self, symbol: String, numa_node: usize) -> Result<()> {
+        self.add_symbol(symbol.clone())?;
+        
+        self.book_placements.write().insert(symbol.clone(), numa_node);
+        info!("Placed symbol {} on NUMA node {}", symbol, numa_node);
+        
+        Ok(())
+    }
+    
+    /// Move matching for `symbol` onto a dedicated thread that drains a queue fed by
+    /// `enqueue_order` and `submit_order`, pinned to the symbol's NUMA node when it has a
+    /// placement.
+    pub fn start_matching_loop(self: &Arc<Self>, symbol: &str) -> Result<()> {
+        let mut loops = self.matching_loops.write();
+        // Checked under the loops lock, which `evict_book` holds while evicting
+        if self.hot_book(symbol)?.is_none() {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        }
+        
+        if !loops.contains_key(symbol) {
+            loops.insert(symbol.to_string(), MatchingLoop::spawn(self, symbol)?);
+            info!("Started matching loop for {}", symbol);
+        }
+        
+        Ok(())
+    }
+    
+    /// Stop a symbol's matching loop after it has matched every order already queued
+    pub fn stop_matching_loop(&self, symbol: &str) -> bool {
+        let matching_loop = self.matching_loops.write().remove(symbol);
+        match matching_loop {
+            Some(matching_loop) => {
+                matching_loop.stop();
+                true
+            }
+            None => false,
+        }
+    }
+    
+    #[inline]
+    pub fn has_matching_loop(&self, symbol: &str) -> bool {
+        self.matching_loops.read().contains_key(symbol)
+    }
+    
+    /// Queue an order for its symbol's matching loop. Orders are matched one at a time in
+    /// queue order; the returned future resolves with the same response `submit_order`
+    /// would give. Symbols without a loop are matched inline before this returns.
+    pub fn enqueue_order(&self, order: Order) -> Result<PendingOrder> {
+        if let Some(matching_loop) = self.matching_loops.read().get(&order.symbol) {
+            return matching_loop.enqueue(order);
+        }
+        
+        let order_id = order.id;
+        Ok(PendingOrder::ready(order_id, self.submit_order(order)))
+    }
+    
+    pub(crate) fn pin_matching_thread(&self, symbol: &str, numa_node: usize) -> Result<()> {
+        match self.book_placer.read().as_ref() {
+            Some(placer) => placer.pin_matching_thread(symbol, numa_node),
+            None => Ok(()),
+        }
+    }
+    
+    /// NUMA node a symbol's book was assigned to, if it was added with a node.
+    #[inline]
+    pub fn symbol_node(&self, symbol: &str) -> Option<usize> {
+        self.book_placements.read().get(symbol).copied()
+    }
+    
+    #[inline]
+    pub fn symbols_on_node(&self, numa_node: usize) -> Vec<String> {
+        self.book_placements
+            .read()
+            .iter()
+            .filter(|(_, node)| **node == numa_node)
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+    
     #[inline]
     pub fn remove_symbol(&self, symbol: &str) -> Result<()> {
         let mut books = self.order_books.write();
+        let was_cold = self.cold_symbols.write().remove(symbol);
+        if was_cold {
+            self.cold_store.read().remove(symbol)?;
+        }
         
-        if books.remove(symbol).is_some() {
+        if books.remove(symbol).is_some() || was_cold {
+            // The loop thread reads the books map while draining, so stop it without the lock.
+            // Pauses and sessions are locked before the books elsewhere, so release it first.
+            drop(books);
+            self.book_placements.write().remove(symbol);
+            self.paused.write().remove(symbol);
+            self.sessions.write().remove(symbol);
             info!("Removed symbol: {}", symbol);
+            self.stop_matching_loop(symbol);
+            self.close_finished_watchers(Some(symbol));
             Ok(())
         } else {
             Err(anyhow::anyhow!("Symbol not found: {}", symbol))
@@ -151,74 +601,306 @@ impl TradingEngine {
     
     #[inline]
     pub fn get_symbols(&self) -> Vec<String> {
-        self.order_books.read().keys().cloned().collect()
+        let mut symbols: Vec<String> = self.order_books.read().keys().cloned().collect();
+        symbols.extend(self.cold_symbols.read().iter().cloned());
+        symbols
+    }
+    
+    /// Continue issuing order ids above `high_water`, the highest id recovered from the journal.
+    /// Never moves the generator backwards.
+    #[inline]
+    pub fn recover_order_ids(&self, high_water: u64) {
+        self.order_ids.observe(OrderId::from_raw(high_water));
+    }
+    
+    /// Next id from this engine's generator rather than the process-wide `OrderId::new`
+    #[inline]
+    pub fn next_order_id(&self) -> OrderId {
+        self.order_ids.next_id()
     }
     
+    /// Highest order id this engine has issued or accepted
     #[inline]
+    pub fn order_id_high_water(&self) -> u64 {
+        self.order_ids.high_water()
+    }
+    
+    /// Build an order carrying an id from this engine's generator
+    pub fn new_order(
+        &self,
+        symbol: String,
+        side: Side,
+        order_type: OrderType,
+        price: Price,
+        quantity: Quantity,
+        client_id: uuid::Uuid,
+    ) -> Order {
+        Order {
+            id: self.next_order_id(),
+            timestamp: self.clock.now(),
+            ..Order::new(symbol, side, order_type, price, quantity, client_id)
+        }
+    }
+    
+    /// Match an order and return its response. Symbols with a matching loop match it on the
+    /// loop's thread, queued behind orders already enqueued there, and the caller blocks
+    /// until it has run; others match it on the calling thread.
     pub fn submit_order(&self, order: Order) -> Result<OrderResponse> {
+        let loops = self.matching_loops.read();
+        // The loop thread itself submits inline, never onto its own queue
+        let Some(matching_loop) = loops.get(&order.symbol).filter(|matching_loop| !matching_loop.is_loop_thread()) else {
+            drop(loops);
+            return self.submit_order_inline(order);
+        };
+        let response = matching_loop.submit(order);
+        drop(loops);
+        
+        response?
+            .recv()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("Matching loop stopped before processing the order")))
+    }
+    
+    #[inline]
+    pub(crate) fn submit_order_inline(&self, order: Order) -> Result<OrderResponse> {
+        // Ends when dropped, after the response is built and every event has been sent
+        let _ack = self.config.measure_ack_latency
+            .then(|| ScopedMeasurement::new(&self.latency_profiler, MeasurementPoint::OrderAckLatency));
         let symbol = order.symbol.clone();
         let order_id = order.id;
+        // Ids from replayed or externally built orders must never be issued again
+        self.order_ids.observe(order_id);
+        self.counters.orders_submitted.fetch_add(1, Ordering::Relaxed);
+        // Only orders carrying a budget pay for reading the clock
+        let started = order.latency_budget.map(|_| GLOBAL_RDTSC_PROFILER.timer().now());
+        
+        let pause_mode = self.pause_mode(&symbol);
+        if pause_mode == Some(PauseMode::HaltAll) {
+            return Ok(self.reject(order_id, format!("Matching halted for {}", symbol)));
+        }
+        
+        let phase = self.session_phase(&symbol);
+        if phase == SessionPhase::Closed {
+            return Ok(self.reject(order_id, format!("Session closed for {}", symbol)));
+        }
+        
+        if let Some(cap) = self.config.max_order_notional {
+            let notional = self.order_notional(&order);
+            if notional > cap {
+                return Ok(self.reject(
+                    order_id,
+                    format!("Order notional {:.2} exceeds per-order cap {:.2}", notional, cap),
+                ));
+            }
+        }
         
         if self.config.enable_risk_checks {
-            if let Err(e) = self.risk_manager.validate_order(&order) {
+            let reference_price = self.reference_price(&order);
+            let verdict = if self.risk_manager.is_healthy() {
+                std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    self.risk_manager.validate_order_with_reference_price(&order, reference_price)
+                }))
+                .map_err(|_| {
+                    // A check that panicked once cannot be trusted for the next order either
+                    self.risk_manager.set_healthy(false);
+                    "risk validation panicked"
+                })
+            } else {
+                Err("risk manager is unhealthy")
+            };
+            
+            let verdict = match verdict {
+                Ok(verdict) => verdict,
+                Err(cause) => match self.config.risk_failure_policy {
+                    RiskFailurePolicy::FailClosed => {
+                        warn!("Rejecting order {} fail-closed: {}", order_id, cause);
+                        return Ok(self.reject(order_id, format!("Risk checks unavailable: {}", cause)));
+                    }
+                    RiskFailurePolicy::FailOpen => {
+                        warn!("Accepting order {} without risk checks fail-open: {}", order_id, cause);
+                        Ok(())
+                    }
+                },
+            };
+            
+            if let Err(e) = verdict {
                 let response = OrderResponse::Rejected {
                     order_id,
                     reason: e.to_string(),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 };
                 
                 if self.config.enable_event_emission {
-                    let _ = self.event_processor.send_event(Event::Order(OrderEvent::OrderRejected {
+                    self.emit(Event::Order(OrderEvent::OrderRejected {
                         order_id,
                         reason: e.to_string(),
-                        timestamp: Utc::now(),
+                        timestamp: self.clock.now(),
                     }));
                 }
                 
+                self.counters.orders_rejected.fetch_add(1, Ordering::Relaxed);
                 return Ok(response);
             }
         }
         
-        let order_books = self.order_books.read();
-        let order_book = match order_books.get(&symbol) {
-            Some(book) => book.clone(),
+        if let Some(reason) = Self::deadline_exceeded(&order, started, "after risk checks") {
+            return Ok(self.reject(order_id, reason.to_string()));
+        }
+        
+        let order_book = match self.hot_book(&symbol)? {
+            Some(book) => book,
             None => {
                 let response = OrderResponse::Rejected {
                     order_id,
                     reason: format!("Symbol not supported: {}", symbol),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 };
+                self.counters.orders_rejected.fetch_add(1, Ordering::Relaxed);
                 return Ok(response);
             }
         };
-        drop(order_books);
         
-        let match_result = order_book.add_order(order.clone());
+        if let Err(e) = order_book.check_order(&order) {
+            return Ok(self.reject(order_id, e.to_string()));
+        }
+        
+        if pause_mode == Some(PauseMode::QueueOnly) {
+            let mut paused = self.paused.write();
+            // Re-check under the write lock in case the symbol was resumed meanwhile
+            if let Some(pause) = paused.get_mut(&symbol) {
+                if pause.mode == PauseMode::HaltAll {
+                    drop(paused);
+                    return Ok(self.reject(order_id, format!("Matching halted for {}", symbol)));
+                }
+                if pause.deferred.iter().any(|deferred| deferred.id == order_id) {
+                    drop(paused);
+                    return Ok(self.reject(order_id, OrderBookError::OrderAlreadyExists { order_id }.to_string()));
+                }
+                
+                pause.deferred.push(order);
+                self.counters.orders_accepted.fetch_add(1, Ordering::Relaxed);
+                return Ok(OrderResponse::Accepted {
+                    order_id,
+                    symbol,
+                    timestamp: self.clock.now(),
+                });
+            }
+        }
+        
+        match phase {
+            SessionPhase::PreOpen => {
+                let mut sessions = self.sessions.write();
+                if let Some(session) = sessions.get_mut(&symbol) {
+                    if session.queued.iter().any(|queued| queued.id == order_id) {
+                        drop(sessions);
+                        return Ok(self.reject(order_id, OrderBookError::OrderAlreadyExists { order_id }.to_string()));
+                    }
+                    
+                    session.queued.push(order);
+                    self.counters.orders_accepted.fetch_add(1, Ordering::Relaxed);
+                    return Ok(OrderResponse::Accepted {
+                        order_id,
+                        symbol,
+                        timestamp: self.clock.now(),
+                    });
+                }
+            }
+            // Orders queued during pre-open go ahead of anything arriving after the open
+            SessionPhase::Open => {
+                self.open_session(&symbol)?;
+            }
+            SessionPhase::Closed => {}
+        }
+        
+        let response = self.execute_order(&order_book, order, started);
+        // Rejections inside matching were already counted by `reject`
+        if !matches!(response, OrderResponse::Rejected { .. }) {
+            self.counters.orders_accepted.fetch_add(1, Ordering::Relaxed);
+        }
+        // Makers cancelled by the level cap, dust or self-trade prevention get no fill notice
+        self.close_finished_watchers(Some(&symbol));
+        Ok(response)
+    }
+    
+    /// Match and rest an order that has already passed risk and book checks. `started` is when
+    /// a budgeted order was submitted; orders released from a queue have no deadline left to meet.
+    fn execute_order(&self, order_book: &OrderBook, order: Order, started: Option<RdtscTimestamp>) -> OrderResponse {
+        let symbol = order.symbol.clone();
+        let order_id = order.id;
+        
+        #[cfg(feature = "chaos")]
+        self.latency_injector.delay(&symbol);
+        
+        if let Some(reason) = Self::deadline_exceeded(&order, started, "before matching") {
+            warn!("Order {} on {} missed its deadline: {}", order_id, symbol, reason);
+            return self.reject(order_id, reason.to_string());
+        }
+        
+        let mut events = Vec::new();
+        let match_result = if self.config.enable_event_emission && self.config.enable_execution_reports {
+            let (match_result, reports) = order_book.add_order_with_reports(order.clone());
+            for report in reports {
+                events.push(Event::Order(OrderEvent::Execution(report)));
+            }
+            match_result
+        } else {
+            match order_book.try_add_order(order.clone()) {
+                Ok(match_result) => match_result,
+                Err(e) => return self.reject(order_id, e.to_string()),
+            }
+        };
+        
+        if let MatchResult::PartialMatch { trades, .. } | MatchResult::FullMatch { trades, .. } = &match_result {
+            self.counters.trades_executed.fetch_add(trades.len() as u64, Ordering::Relaxed);
+            if !self.order_watchers.is_empty() {
+                self.notify_fills(order_book, trades);
+            }
+            for registered in self.strategies.read().iter() {
+                for trade in trades {
+                    registered.queue_fill(trade);
+                }
+            }
+        }
+        
+        // A market order's unfilled remainder is discarded, never rested
+        let shortfall = match_result.check_liquidity(&order).err();
+        if let Some(e) = &shortfall {
+            warn!("Market order {} on {}: {}", order_id, symbol, e);
+            if matches!(match_result, MatchResult::NoMatch) {
+                self.emit_all(events);
+                return self.reject(order_id, e.to_string());
+            }
+        }
         
         let response = match match_result {
+            MatchResult::Rejected { reason, .. } => return self.reject(order_id, reason),
             MatchResult::NoMatch => {
                 if self.config.enable_event_emission {
-                    let _ = self.event_processor.send_event(Event::Order(OrderEvent::AddOrder(order)));
+                    events.push(Event::Order(OrderEvent::AddOrder(order)));
                 }
                 
                 OrderResponse::Accepted {
                     order_id,
                     symbol,
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 }
             },
-            MatchResult::PartialMatch { trades, remaining_quantity } => {
+            MatchResult::PartialMatch { trades, remaining_quantity, .. } => {
                 if self.config.enable_event_emission {
-                    for trade in &trades {
-                        let _ = self.event_processor.send_event(Event::Trade(TradeEvent::TradeExecuted(trade.clone())));
-                    }
+                    self.push_trade_prints(&mut events, &trades);
                     
-                    let _ = self.event_processor.send_event(Event::Order(OrderEvent::OrderFilled {
+                    events.push(Event::Order(OrderEvent::OrderFilled {
                         order_id,
                         fill_quantity: order.quantity - remaining_quantity,
                         fill_price: trades.first().map(|t| t.price).unwrap_or(order.price),
-                        timestamp: Utc::now(),
+                        timestamp: self.clock.now(),
                     }));
+                    if let Some(e) = &shortfall {
+                        events.push(Event::Order(OrderEvent::OrderRejected {
+                            order_id,
+                            reason: e.to_string(),
+                            timestamp: self.clock.now(),
+                        }));
+                    }
                 }
                 
                 if self.config.enable_risk_checks {
@@ -231,20 +913,19 @@ impl TradingEngine {
                     order_id,
                     trades,
                     remaining_quantity,
-                    timestamp: Utc::now(),
+                    shortfall: shortfall.map(|_| remaining_quantity),
+                    timestamp: self.clock.now(),
                 }
             },
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 if self.config.enable_event_emission {
-                    for trade in &trades {
-                        let _ = self.event_processor.send_event(Event::Trade(TradeEvent::TradeExecuted(trade.clone())));
-                    }
+                    self.push_trade_prints(&mut events, &trades);
                     
-                    let _ = self.event_processor.send_event(Event::Order(OrderEvent::OrderFilled {
+                    events.push(Event::Order(OrderEvent::OrderFilled {
                         order_id,
                         fill_quantity: order.quantity,
                         fill_price: trades.first().map(|t| t.price).unwrap_or(order.price),
-                        timestamp: Utc::now(),
+                        timestamp: self.clock.now(),
                     }));
                 }
                 
@@ -257,68 +938,625 @@ impl TradingEngine {
                 OrderResponse::FullyFilled {
                     order_id,
                     trades,
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 }
             },
         };
         
-        Ok(response)
+        self.emit_all(events);
+        
+        response
     }
     
-    #[inline]
-    pub fn cancel_order(&self, symbol: &str, order_id: OrderId) -> Result<CancelResponse> {
-        let order_books = self.order_books.read();
-        let order_book = match order_books.get(symbol) {
-            Some(book) => book.clone(),
-            None => {
-                return Ok(CancelResponse::NotFound {
-                    order_id,
-                    timestamp: Utc::now(),
-                });
+    fn push_trade_prints(&self, events: &mut Vec<Event>, trades: &[Trade]) {
+        if self.config.consolidate_trade_prints {
+            events.extend(Trade::consolidate(trades).into_iter().map(|print| Event::Trade(TradeEvent::TradeExecuted(print))));
+        } else {
+            events.extend(trades.iter().map(|trade| Event::Trade(TradeEvent::TradeExecuted(trade.clone()))));
+        }
+    }
+    
+    /// Submit an order and follow it through its fills. Fills landing within the configured
+    /// coalescing window are folded into one `OrderProgress`; the stream ends once the order is
+    /// filled, cancelled or rejected.
+    pub fn submit_order_stream(&self, order: Order) -> Result<impl Stream<Item = OrderProgress>> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let order_id = order.id;
+        let stream = progress_stream(
+            order_id,
+            order.quantity,
+            receiver,
+            Duration::from_micros(self.config.fill_coalesce_window_us),
+        );
+        
+        // Watch before submitting so fills taken on entry are reported too
+        let symbol = order.symbol.clone();
+        self.order_watchers.insert(order_id, OrderWatcher { symbol: symbol.clone(), sender, entered: false });
+        let response = self.submit_order(order);
+        
+        if matches!(response, Ok(OrderResponse::Accepted { .. } | OrderResponse::PartiallyFilled { .. } | OrderResponse::FullyFilled { .. })) {
+            if let Some(mut watcher) = self.order_watchers.get_mut(&order_id) {
+                watcher.entered = true;
             }
-        };
-        drop(order_books);
+            // An IOC or market remainder is gone as soon as the submission returns
+            self.close_finished_watchers(Some(&symbol));
+        } else {
+            self.close_watcher(order_id, OrderStatus::Rejected);
+        }
         
-        match order_book.cancel_order(order_id) {
-            Some(cancelled_order) => {
-                if self.config.enable_event_emission {
-                    let _ = self.event_processor.send_event(Event::Order(OrderEvent::CancelOrder {
-                        order_id,
-                        symbol: symbol.to_string(),
-                        client_id: cancelled_order.client_id,
-                        timestamp: Utc::now(),
-                    }));
+        response.map(|_| stream)
+    }
+    
+    /// End a watched order's stream with `status`
+    fn close_watcher(&self, order_id: OrderId, status: OrderStatus) {
+        if let Some((_, watcher)) = self.order_watchers.remove(&order_id) {
+            let _ = watcher.sender.send(FillNotice::Closed(status));
+        }
+    }
+    
+    /// End the streams of orders on `symbol`, or on every symbol, that are no longer live,
+    /// and drop watchers whose stream was dropped. Returns how many watchers were removed.
+    /// Takes the book, pause and session locks, so callers must not hold any of them.
+    fn close_finished_watchers(&self, symbol: Option<&str>) -> usize {
+        if self.order_watchers.is_empty() {
+            return 0;
+        }
+        let before = self.order_watchers.len();
+        self.order_watchers.retain(|order_id, watcher| {
+            if watcher.sender.is_closed() {
+                return false;
+            }
+            if !watcher.entered || symbol.is_some_and(|symbol| symbol != watcher.symbol) {
+                return true;
+            }
+            match self.finished_status(&watcher.symbol, *order_id) {
+                Some(status) => {
+                    let _ = watcher.sender.send(FillNotice::Closed(status));
+                    false
                 }
+                None => true,
+            }
+        });
+        before.saturating_sub(self.order_watchers.len())
+    }
+    
+    /// How a watched order ended, or `None` while it rests, is parked mid-match, is deferred
+    /// by a pause, is queued for the open or sits in an evicted book. An order that vanished
+    /// without a status, as when its level was evicted or its symbol removed, was cancelled.
+    fn finished_status(&self, symbol: &str, order_id: OrderId) -> Option<OrderStatus> {
+        if self.is_cold(symbol) {
+            return None;
+        }
+        let order_book = self.order_books.read().get(symbol).cloned();
+        if let Some(order) = order_book.as_ref().and_then(|book| book.get_order(order_id)) {
+            return order.status.is_terminal().then_some(order.status);
+        }
+        let held = order_book.is_some_and(|book| book.interrupted_orders().iter().any(|order| order.id == order_id))
+            || self.deferred_orders(symbol).iter().any(|order| order.id == order_id)
+            || self.pre_open_orders(symbol).iter().any(|order| order.id == order_id);
+        (!held).then_some(OrderStatus::Cancelled)
+    }
+    
+    /// Close the streams of orders that ended without a fill or cancel notice and drop those
+    /// nobody reads any more. Call it periodically; returns how many watchers were removed.
+    pub fn prune_order_watchers(&self) -> usize {
+        self.close_finished_watchers(None)
+    }
+    
+    fn notify_fills(&self, order_book: &OrderBook, trades: &[Trade]) {
+        for trade in trades {
+            for order_id in [trade.buyer_order_id, trade.seller_order_id] {
+                let Some(watcher) = self.order_watchers.get(&order_id) else {
+                    continue;
+                };
+                let delivered = watcher.sender
+                    .send(FillNotice::Fill { quantity: trade.quantity, price: trade.price })
+                    .is_ok();
+                drop(watcher);
                 
-                Ok(CancelResponse::Cancelled {
-                    order_id,
-                    timestamp: Utc::now(),
-                })
-            },
-            None => {
-                Ok(CancelResponse::NotFound {
-                    order_id,
-                    timestamp: Utc::now(),
-                })
+                // Dropping the sender of a fully filled order ends its stream
+                if !delivered || order_book.get_order(order_id).is_none_or(|order| order.is_fully_filled()) {
+                    self.order_watchers.remove(&order_id);
+                }
             }
         }
     }
     
-    #[inline]
-    pub fn get_order(&self, symbol: &str, order_id: OrderId) -> Option<Order> {
-        let order_books = self.order_books.read();
-        order_books.get(symbol)?.get_order(order_id)
+    /// Price times quantity, valuing a market order at the levels it would sweep
+    fn order_notional(&self, order: &Order) -> f64 {
+        match order.order_type {
+            // A cold book is brought back so the depth is the one the order will meet
+            OrderType::Market => self
+                .hot_book(&order.symbol)
+                .ok()
+                .flatten()
+                .map_or(0.0, |book| book.sweep_notional(order.side, order.quantity)),
+            _ => order.price.to_f64() * order.quantity.to_f64(),
+        }
     }
     
-    #[inline]
-    pub fn get_order_book(&self, symbol: &str) -> Option<Arc<OrderBook>> {
-        self.order_books.read().get(symbol).cloned()
+    /// Average price a market order would fill at sweeping the book, for margining it. `None`
+    /// for priced orders and against an empty side.
+    fn reference_price(&self, order: &Order) -> Option<Price> {
+        if order.order_type != OrderType::Market {
+            return None;
+        }
+        let notional = self.order_notional(order);
+        (notional > 0.0).then(|| Price::new(notional / order.quantity.to_f64()))
     }
     
+    /// `DeadlineExceeded` once an order submitted at `started` has spent its latency budget
     #[inline]
-    pub fn get_market_data(&self, symbol: &str) -> Option<order_book::MarketData> {
-        let order_books = self.order_books.read();
-        let order_book = order_books.get(symbol)?;
+    fn deadline_exceeded(order: &Order, started: Option<RdtscTimestamp>, stage: &str) -> Option<RejectReason> {
+        let (budget, started) = order.latency_budget.zip(started)?;
+        let timer = GLOBAL_RDTSC_PROFILER.timer();
+        let elapsed = timer.duration(started, timer.now());
+        (elapsed > budget).then(|| RejectReason::DeadlineExceeded {
+            stage: stage.to_string(),
+            elapsed,
+            budget,
+        })
+    }
+    
+    fn reject(&self, order_id: OrderId, reason: String) -> OrderResponse {
+        if self.config.enable_event_emission {
+            self.emit(Event::Order(OrderEvent::OrderRejected {
+                order_id,
+                reason: reason.clone(),
+                timestamp: self.clock.now(),
+            }));
+        }
+        
+        self.counters.orders_rejected.fetch_add(1, Ordering::Relaxed);
+        OrderResponse::Rejected {
+            order_id,
+            reason,
+            timestamp: self.clock.now(),
+        }
+    }
+    
+    /// Delay every order matched for `symbol` by a sample from `distribution`, simulating a
+    /// degraded matching engine
+    #[cfg(feature = "chaos")]
+    pub fn inject_matching_latency(&self, symbol: &str, distribution: LatencyDistribution) {
+        self.latency_injector.set(symbol, distribution);
+    }
+    
+    /// Stop delaying `symbol`; returns whether injection was enabled
+    #[cfg(feature = "chaos")]
+    pub fn clear_matching_latency(&self, symbol: &str) -> bool {
+        self.latency_injector.clear(symbol)
+    }
+    
+    /// Pause matching for a symbol without removing its book. Switching a `QueueOnly`
+    /// pause to `HaltAll` keeps the orders already deferred.
+    pub fn pause_symbol(&self, symbol: &str, mode: PauseMode) -> Result<()> {
+        if !self.order_books.read().contains_key(symbol) {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        }
+        
+        self.paused
+            .write()
+            .entry(symbol.to_string())
+            .and_modify(|pause| pause.mode = mode)
+            .or_insert_with(|| SymbolPause { mode, deferred: Vec::new() });
+        info!("Paused symbol {} ({:?})", symbol, mode);
+        
+        Ok(())
+    }
+    
+    /// Resume matching for a symbol, running deferred orders against the book in arrival order
+    pub fn resume_symbol(&self, symbol: &str) -> Result<Vec<OrderResponse>> {
+        // Held while replaying so orders submitted after the resume cannot jump the queue
+        let mut paused = self.paused.write();
+        let Some(pause) = paused.remove(symbol) else {
+            return Ok(Vec::new());
+        };
+        
+        let Some(order_book) = self.get_order_book(symbol) else {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        };
+        
+        info!("Resuming symbol {} with {} deferred orders", symbol, pause.deferred.len());
+        let responses = pause.deferred
+            .into_iter()
+            .map(|order| match order_book.check_order(&order) {
+                Ok(()) => self.execute_order(&order_book, order, None),
+                Err(e) => self.reject(order.id, e.to_string()),
+            })
+            .collect();
+        drop(paused);
+        self.close_finished_watchers(Some(symbol));
+        
+        Ok(responses)
+    }
+    
+    #[inline]
+    pub fn pause_mode(&self, symbol: &str) -> Option<PauseMode> {
+        self.paused.read().get(symbol).map(|pause| pause.mode)
+    }
+    
+    /// Orders accepted while a symbol was paused in `QueueOnly` mode and not yet matched
+    pub fn deferred_orders(&self, symbol: &str) -> Vec<Order> {
+        self.paused
+            .read()
+            .get(symbol)
+            .map(|pause| pause.deferred.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Register trading hours for a symbol, replacing any previous schedule
+    pub fn set_session_schedule(&self, symbol: &str, schedule: SessionSchedule) -> Result<()> {
+        if !self.order_books.read().contains_key(symbol) {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        }
+        
+        self.sessions
+            .write()
+            .entry(symbol.to_string())
+            .and_modify(|session| session.schedule = schedule)
+            .or_insert_with(|| SymbolSession { schedule, queued: Vec::new(), flattened: false });
+        info!("Session schedule for {}: {:?}", symbol, schedule);
+        
+        Ok(())
+    }
+    
+    /// Current phase of a symbol's session; unscheduled symbols are always open
+    pub fn session_phase(&self, symbol: &str) -> SessionPhase {
+        self.sessions
+            .read()
+            .get(symbol)
+            .map_or(SessionPhase::Open, |session| session.schedule.phase_at(self.clock.now()))
+    }
+    
+    /// Orders accepted during the symbol's pre-open phase and not yet released
+    pub fn pre_open_orders(&self, symbol: &str) -> Vec<Order> {
+        self.sessions
+            .read()
+            .get(symbol)
+            .map(|session| session.queued.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Release orders queued during pre-open against the book in arrival order. Does
+    /// nothing unless the session is open; `submit_order` calls this on the first order
+    /// after the open.
+    pub fn open_session(&self, symbol: &str) -> Result<Vec<OrderResponse>> {
+        // Every order in the open phase comes through here, so only symbols with queued
+        // orders take the write lock
+        if self.sessions.read().get(symbol).is_none_or(|session| session.queued.is_empty()) {
+            return Ok(Vec::new());
+        }
+        
+        // Held while releasing so orders submitted after the open cannot jump the queue.
+        // Checked again below since another thread may have released the queue meanwhile.
+        let mut sessions = self.sessions.write();
+        let Some(session) = sessions.get_mut(symbol) else {
+            return Ok(Vec::new());
+        };
+        if session.queued.is_empty() || session.schedule.phase_at(self.clock.now()) != SessionPhase::Open {
+            return Ok(Vec::new());
+        }
+        
+        let Some(order_book) = self.get_order_book(symbol) else {
+            return Err(anyhow::anyhow!("Symbol not found: {}", symbol));
+        };
+        
+        let queued = std::mem::take(&mut session.queued);
+        info!("Opening session for {} with {} queued orders", symbol, queued.len());
+        let responses = queued
+            .into_iter()
+            .map(|order| match order_book.check_order(&order) {
+                Ok(()) => self.execute_order(&order_book, order, None),
+                Err(e) => self.reject(order.id, e.to_string()),
+            })
+            .collect();
+        drop(sessions);
+        self.close_finished_watchers(Some(symbol));
+        
+        Ok(responses)
+    }
+    
+    /// Close every non-flat position in `symbol` with reduce-only market orders routed through
+    /// `submit_order`, then block orders that would open new exposure until the next session.
+    /// A position whose order fails is logged and left for the next pass.
+    pub fn flatten_symbol(&self, symbol: &str) -> Vec<(Order, OrderResponse)> {
+        self.risk_manager.block_opening(symbol);
+        
+        let mut flattening = Vec::new();
+        for position in self.risk_manager.open_positions(symbol) {
+            info!("Flattening {} position {} of client {}", symbol, position.quantity, position.client_id);
+            match self.close_position(&position) {
+                Ok(closed) => flattening.push(closed),
+                Err(e) => error!("Failed to flatten {} position of client {}: {}", symbol, position.client_id, e),
+            }
+        }
+        flattening
+    }
+    
+    /// Close every position held longer than its symbol's maximum holding time with a
+    /// reduce-only market order. Call it periodically; symbols without a limit are left alone.
+    /// A position whose exit fails is logged and retried on the next pass.
+    pub fn exit_expired_positions(&self) -> Vec<(Order, OrderResponse)> {
+        let mut exits = Vec::new();
+        for position in self.risk_manager.positions_over_holding_time() {
+            warn!(
+                "Exiting {} position {} of client {}: held past its maximum holding time",
+                position.symbol, position.quantity, position.client_id
+            );
+            match self.close_position(&position) {
+                Ok(exit) => exits.push(exit),
+                Err(e) => error!("Failed to exit {} position of client {}: {}", position.symbol, position.client_id, e),
+            }
+        }
+        exits
+    }
+    
+    fn close_position(&self, position: &Position) -> Result<(Order, OrderResponse)> {
+        let side = if position.is_long() { Side::Sell } else { Side::Buy };
+        let order = self.new_order(
+            position.symbol.clone(),
+            side,
+            OrderType::Market,
+            Price::ZERO,
+            Quantity::new(position.quantity.abs()),
+            position.client_id,
+        ).with_reduce_only();
+        
+        let response = self.submit_order(order.clone())?;
+        Ok((order, response))
+    }
+    
+    /// Drive `strategy` from now on, placing its orders for `client_id`
+    pub fn register_strategy(&self, client_id: uuid::Uuid, strategy: Box<dyn Strategy>) {
+        info!("Registered strategy {} for client {}", strategy.name(), client_id);
+        self.strategies.write().push(Arc::new(RegisteredStrategy::new(client_id, strategy)));
+    }
+    
+    /// Hand `data` to every registered strategy and submit the orders they ask for
+    pub fn feed_market_data(&self, data: &MarketData) -> Result<Vec<(Order, OrderResponse)>> {
+        self.drive_strategies(|strategy| strategy.on_market_data(data))
+    }
+    
+    /// Fire every registered strategy's timer at the current clock time and submit the
+    /// orders they ask for. Call it periodically.
+    pub fn run_strategy_timers(&self) -> Result<Vec<(Order, OrderResponse)>> {
+        let now = self.clock.now();
+        self.drive_strategies(|strategy| strategy.on_timer(now))
+    }
+    
+    /// Run `callback` on each strategy after delivering its fills since the last drive. Fills
+    /// from the orders submitted here are delivered on the next drive, never re-entrantly.
+    fn drive_strategies(
+        &self,
+        callback: impl Fn(&mut dyn Strategy) -> Vec<OrderIntent>,
+    ) -> Result<Vec<(Order, OrderResponse)>> {
+        let strategies = self.strategies.read().clone();
+        let mut placed = Vec::new();
+        for registered in strategies {
+            for intent in registered.drive(&callback) {
+                let order = self.new_order(
+                    intent.symbol,
+                    intent.side,
+                    intent.order_type,
+                    intent.price,
+                    intent.quantity,
+                    registered.client_id,
+                );
+                let response = self.submit_order(order.clone())?;
+                placed.push((order, response));
+            }
+        }
+        Ok(placed)
+    }
+    
+    /// End-of-day pass over scheduled symbols: flattens each one when its flatten window starts
+    /// and lifts the opening block once the window has passed. Call it periodically; schedules
+    /// without a flatten time are left alone.
+    pub fn run_end_of_day(&self) -> Vec<(Order, OrderResponse)> {
+        let now = self.clock.now();
+        let mut due = Vec::new();
+        for (symbol, session) in self.sessions.write().iter_mut() {
+            let in_window = session.schedule.in_flatten_window(now);
+            if in_window && !session.flattened {
+                due.push(symbol.clone());
+            } else if !in_window && session.flattened {
+                self.risk_manager.allow_opening(symbol);
+            }
+            session.flattened = in_window;
+        }
+        
+        let mut flattening = Vec::new();
+        for symbol in due {
+            flattening.extend(self.flatten_symbol(&symbol));
+        }
+        flattening
+    }
+    
+    /// Run `input_log` through this engine and check the trades match `expected_output_log`,
+    /// the trades recorded when the log was captured. Trade ids and timestamps are ignored and
+    /// orders compared by their position in the input log. Reports the first divergence in each
+    /// symbol. Use a fresh engine with the recording's config; missing symbols are added.
+    pub fn replay_verify(&self, input_log: &[ReplayInput], expected_output_log: &[Trade]) -> std::result::Result<(), Vec<Divergence>> {
+        let normalizer = TradeNormalizer::new(input_log);
+        let mut produced = Vec::new();
+        
+        for (position, input) in input_log.iter().enumerate() {
+            let failed = |error: anyhow::Error| vec![Divergence::Failed { position, error: error.to_string() }];
+            if self.get_order_book(input.symbol()).is_none() {
+                self.add_symbol(input.symbol().to_string()).map_err(failed)?;
+            }
+            
+            match input {
+                ReplayInput::Submit(order) => {
+                    let response = self.submit_order(order.clone()).map_err(failed)?;
+                    produced.extend(response.trades().iter().map(|trade| normalizer.normalize(trade)));
+                }
+                ReplayInput::Cancel { symbol, order_id } => {
+                    self.cancel_order(symbol, *order_id).map_err(failed)?;
+                }
+            }
+        }
+        
+        let expected = expected_output_log.iter().map(|trade| normalizer.normalize(trade)).collect();
+        let divergences = first_divergences(expected, produced);
+        if divergences.is_empty() {
+            Ok(())
+        } else {
+            Err(divergences)
+        }
+    }
+    
+    #[inline]
+    pub fn cancel_order(&self, symbol: &str, order_id: OrderId) -> Result<CancelResponse> {
+        let order_book = match self.hot_book(symbol)? {
+            Some(book) => book,
+            None => {
+                return Ok(CancelResponse::NotFound {
+                    order_id,
+                    timestamp: self.clock.now(),
+                });
+            }
+        };
+        
+        let deferred = self.paused.write().get_mut(symbol).and_then(|pause| {
+            let index = pause.deferred.iter().position(|order| order.id == order_id)?;
+            Some(pause.deferred.remove(index))
+        });
+        let queued = || self.sessions.write().get_mut(symbol).and_then(|session| {
+            let index = session.queued.iter().position(|order| order.id == order_id)?;
+            Some(session.queued.remove(index))
+        });
+        
+        match deferred.or_else(queued).or_else(|| order_book.cancel_order(order_id)) {
+            Some(cancelled_order) => {
+                self.close_watcher(order_id, OrderStatus::Cancelled);
+                
+                self.counters.orders_cancelled.fetch_add(1, Ordering::Relaxed);
+                
+                if self.config.enable_event_emission {
+                    self.emit(Event::Order(OrderEvent::CancelOrder {
+                        order_id,
+                        symbol: symbol.to_string(),
+                        client_id: cancelled_order.client_id,
+                        timestamp: self.clock.now(),
+                    }));
+                }
+                
+                Ok(CancelResponse::Cancelled {
+                    order_id,
+                    timestamp: self.clock.now(),
+                })
+            },
+            None => {
+                Ok(CancelResponse::NotFound {
+                    order_id,
+                    timestamp: self.clock.now(),
+                })
+            }
+        }
+    }
+    
+    /// Cancel every live order on every symbol: resting, paused-deferred and pre-open queued.
+    /// Returns how many were cancelled.
+    pub fn cancel_all(&self) -> usize {
+        let books: Vec<(String, Arc<OrderBook>)> = self.order_books
+            .read()
+            .iter()
+            .map(|(symbol, book)| (symbol.clone(), book.clone()))
+            .collect();
+        
+        let mut cancelled = 0;
+        for (symbol, book) in books {
+            let snapshot = book.l3_snapshot();
+            let order_ids: Vec<OrderId> = snapshot.bids.iter()
+                .chain(&snapshot.asks)
+                .map(|order| order.order_id)
+                .chain(self.deferred_orders(&symbol).iter().map(|order| order.id))
+                .chain(self.pre_open_orders(&symbol).iter().map(|order| order.id))
+                .collect();
+            
+            for order_id in order_ids {
+                if matches!(self.cancel_order(&symbol, order_id), Ok(CancelResponse::Cancelled { .. })) {
+                    cancelled += 1;
+                }
+            }
+        }
+        
+        if cancelled > 0 {
+            warn!("Cancelled all {} live orders", cancelled);
+        }
+        cancelled
+    }
+    
+    /// Resting orders on every symbol older than `max_age`, oldest first, as
+    /// (symbol, order id, age). Orders this old are likely forgotten or stuck.
+    pub fn stale_orders(&self, max_age: Duration) -> Vec<(String, OrderId, Duration)> {
+        let mut stale: Vec<(String, OrderId, Duration)> = self.order_books
+            .read()
+            .iter()
+            .flat_map(|(symbol, book)| {
+                book.orders_older_than(max_age)
+                    .into_iter()
+                    .map(move |(order_id, age)| (symbol.clone(), order_id, age))
+            })
+            .collect();
+        stale.sort_by_key(|s| std::cmp::Reverse(s.2));
+        stale
+    }
+    
+    /// Every order resting for `client_id`, as (symbol, order) grouped by symbol. Each book is
+    /// read once through its client index; this is the read-side counterpart of `cancel_all`.
+    pub fn open_orders(&self, client_id: uuid::Uuid) -> Vec<(String, Order)> {
+        let mut open: Vec<(String, Order)> = self.order_books
+            .read()
+            .iter()
+            .flat_map(|(symbol, book)| {
+                book.client_orders(client_id)
+                    .into_iter()
+                    .map(move |order| (symbol.clone(), order))
+            })
+            .collect();
+        open.sort_by(|a, b| a.0.cmp(&b.0));
+        open
+    }
+    
+    #[inline]
+    pub fn get_order(&self, symbol: &str, order_id: OrderId) -> Option<Order> {
+        let order_books = self.order_books.read();
+        order_books.get(symbol)?.get_order(order_id)
+    }
+    
+    #[inline]
+    pub fn get_order_book(&self, symbol: &str) -> Option<Arc<OrderBook>> {
+        self.order_books.read().get(symbol).cloned()
+    }
+    
+    /// Archive every hot book's filled and cancelled orders to `sink`. Call it periodically so
+    /// books stay compact and their retired buffers never fill. A book whose batch fails is
+    /// logged and keeps it for the next pass. Returns the number of orders archived.
+    pub fn archive_terminal(&self, sink: &mut impl ArchiveSink) -> usize {
+        let books: Vec<_> = self.order_books.read().values().cloned().collect();
+        books.iter()
+            .map(|book| book.archive_terminal(sink).unwrap_or_else(|e| {
+                error!("Failed to archive terminal orders for {}: {}", book.symbol(), e);
+                0
+            }))
+            .sum()
+    }
+    
+    pub fn total_memory_footprint(&self) -> MemoryFootprint {
+        self.order_books
+            .read()
+            .values()
+            .map(|book| book.memory_footprint())
+            .sum()
+    }
+    
+    #[inline]
+    pub fn get_market_data(&self, symbol: &str) -> Option<order_book::MarketData> {
+        let order_books = self.order_books.read();
+        let order_book = order_books.get(symbol)?;
         
         Some(order_book::MarketData {
             symbol: symbol.to_string(),
@@ -329,10 +1567,38 @@ impl TradingEngine {
             last_trade_price: None,
             last_trade_quantity: None,
             volume: order_book.total_volume(Side::Buy) + order_book.total_volume(Side::Sell),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         })
     }
     
+    #[inline]
+    fn emit(&self, event: Event) {
+        if self.event_processor.send_event(event).is_ok() {
+            self.counters.events_dispatched.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    fn emit_all(&self, events: Vec<Event>) {
+        if self.config.coalesce_events && events.len() > 1 {
+            self.emit(Event::Batch(events));
+        } else {
+            for event in events {
+                self.emit(event);
+            }
+        }
+    }
+    
+    pub fn counters(&self) -> EngineCounters {
+        EngineCounters {
+            orders_submitted: self.counters.orders_submitted.load(Ordering::Relaxed),
+            orders_accepted: self.counters.orders_accepted.load(Ordering::Relaxed),
+            orders_rejected: self.counters.orders_rejected.load(Ordering::Relaxed),
+            orders_cancelled: self.counters.orders_cancelled.load(Ordering::Relaxed),
+            trades_executed: self.counters.trades_executed.load(Ordering::Relaxed),
+            events_dispatched: self.counters.events_dispatched.load(Ordering::Relaxed),
+        }
+    }
+    
     #[inline]
     pub fn event_processor(&self) -> &Arc<EventProcessor> {
         &self.event_processor
@@ -342,6 +1608,16 @@ impl TradingEngine {
     pub fn risk_manager(&self) -> &Arc<RiskManager> {
         &self.risk_manager
     }
+    
+    #[inline]
+    pub fn latency_profiler(&self) -> &Arc<LatencyProfiler> {
+        &self.latency_profiler
+    }
+    
+    #[inline]
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
 }
 
 impl Default for TradingEngine {
@@ -353,7 +1629,7 @@ impl Default for TradingEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use order_book::{OrderType, Price};
+    use order_book::{clock, OrderType, Price};
     use uuid::Uuid;
     
     fn create_test_order(symbol: &str, side: Side, price: f64, quantity: f64) -> Order {
@@ -482,6 +1758,90 @@ mod tests {
         assert!(matches!(cancel_response2, CancelResponse::NotFound { .. }));
     }
     
+    #[tokio::test]
+    async fn test_cancel_all_clears_every_symbol() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("ETHUSD", Side::Buy, 3000.0, 2.0)).unwrap();
+        
+        assert_eq!(engine.cancel_all(), 3);
+        assert_eq!(engine.counters().orders_cancelled, 3);
+        for symbol in ["BTCUSD", "ETHUSD"] {
+            let book = engine.get_order_book(symbol).unwrap();
+            assert_eq!((book.best_bid(), book.best_ask()), (None, None));
+        }
+        assert_eq!(engine.cancel_all(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_open_orders_lists_client_orders_across_symbols() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        
+        let client_id = Uuid::new_v4();
+        let order = |symbol: &str, side, price, quantity| Order {
+            client_id,
+            ..create_test_order(symbol, side, price, quantity)
+        };
+        let btc_bid = order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let btc_ask = order("BTCUSD", Side::Sell, 50100.0, 2.0);
+        let eth_bid = order("ETHUSD", Side::Buy, 3000.0, 2.0);
+        for order in [&btc_bid, &btc_ask, &eth_bid] {
+            engine.submit_order(order.clone()).unwrap();
+        }
+        
+        // Another client's order rests alongside and partly fills the ETH bid
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("ETHUSD", Side::Sell, 3000.0, 0.5)).unwrap();
+        
+        let open: Vec<(String, OrderId, Quantity)> = engine.open_orders(client_id)
+            .into_iter()
+            .map(|(symbol, order)| (symbol, order.id, order.remaining_quantity()))
+            .collect();
+        assert_eq!(open, vec![
+            ("BTCUSD".to_string(), btc_bid.id, Quantity::new(1.0)),
+            ("BTCUSD".to_string(), btc_ask.id, Quantity::new(2.0)),
+            ("ETHUSD".to_string(), eth_bid.id, Quantity::new(1.5)),
+        ]);
+        
+        // Cancelled orders leave the snapshot
+        engine.cancel_order("BTCUSD", btc_bid.id).unwrap();
+        assert_eq!(engine.open_orders(client_id).len(), 2);
+        assert!(engine.open_orders(Uuid::new_v4()).is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_stale_orders_lists_only_orders_beyond_max_age() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        
+        let submit_aged = |symbol: &str, price: f64, age: Duration| {
+            let mut order = create_test_order(symbol, Side::Buy, price, 1.0);
+            order.timestamp = clock::now() - chrono::Duration::from_std(age).unwrap();
+            engine.submit_order(order.clone()).unwrap();
+            order.id
+        };
+        let zombie = submit_aged("BTCUSD", 49000.0, Duration::from_secs(3600));
+        let stuck = submit_aged("ETHUSD", 3000.0, Duration::from_secs(600));
+        submit_aged("BTCUSD", 49500.0, Duration::from_secs(30));
+        submit_aged("ETHUSD", 2990.0, Duration::ZERO);
+        
+        let stale = engine.stale_orders(Duration::from_secs(300));
+        let listed: Vec<(&str, OrderId)> = stale.iter().map(|(symbol, order_id, _)| (symbol.as_str(), *order_id)).collect();
+        assert_eq!(listed, vec![("BTCUSD", zombie), ("ETHUSD", stuck)]);
+        assert!(stale[0].2 >= Duration::from_secs(3600));
+        
+        let book = engine.get_order_book("BTCUSD").unwrap();
+        assert!(book.oldest_order_age().unwrap() >= Duration::from_secs(3600));
+        assert!(engine.stale_orders(Duration::from_secs(7200)).is_empty());
+    }
+    
     #[tokio::test]
     async fn test_market_data_retrieval() {
         let engine = TradingEngine::new();
@@ -501,37 +1861,1046 @@ mod tests {
     }
     
     #[tokio::test]
-    async fn test_order_retrieval() {
+    async fn test_total_memory_footprint() {
         let engine = TradingEngine::new();
         engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
         
-        let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
-        let order_id = order.id;
-        let original_timestamp = order.timestamp;
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49950.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50050.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("ETHUSD", Side::Buy, 2999.0, 1.0)).unwrap();
         
-        engine.submit_order(order).unwrap();
+        let btc = engine.get_order_book("BTCUSD").unwrap().memory_footprint();
+        let eth = engine.get_order_book("ETHUSD").unwrap().memory_footprint();
+        let total = engine.total_memory_footprint();
         
-        let retrieved_order = engine.get_order("BTCUSD", order_id).unwrap();
-        assert_eq!(retrieved_order.id, order_id);
-        assert_eq!(retrieved_order.symbol, "BTCUSD");
-        assert_eq!(retrieved_order.price, Price::new(50000.0));
-        assert_eq!(retrieved_order.timestamp, original_timestamp);
+        assert_eq!(total.order_count, 3);
+        assert_eq!(total.level_count(), 3);
+        assert_eq!(total.total_bytes(), btc.total_bytes() + eth.total_bytes());
+    }
+    
+    #[tokio::test]
+    async fn test_engine_counters() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
         
-        assert!(engine.get_order("UNKNOWN", order_id).is_none());
-        assert!(engine.get_order("BTCUSD", OrderId::from_raw(99999)).is_none());
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 2.0)).unwrap();
+        
+        let resting = create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0);
+        let resting_id = resting.id;
+        engine.submit_order(resting).unwrap();
+        engine.cancel_order("BTCUSD", resting_id).unwrap();
+        engine.cancel_order("BTCUSD", resting_id).unwrap();
+        
+        engine.submit_order(create_test_order("UNKNOWN", Side::Buy, 100.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("UNKNOWN", Side::Sell, 100.0, 1.0)).unwrap();
+        
+        // Passes every check before matching, then finds nothing to match against
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        let market = Order::new("ETHUSD".to_string(), Side::Buy, OrderType::Market, Price::ZERO, Quantity::new(1.0), Uuid::new_v4());
+        assert!(matches!(engine.submit_order(market).unwrap(), OrderResponse::Rejected { .. }));
+        
+        let counters = engine.counters();
+        assert_eq!(counters.orders_submitted, 7);
+        assert_eq!(counters.orders_accepted, 4);
+        assert_eq!(counters.orders_rejected, 3);
+        assert_eq!(counters.orders_accepted + counters.orders_rejected, counters.orders_submitted);
+        assert_eq!(counters.orders_cancelled, 1);
+        assert_eq!(counters.trades_executed, 2);
+        // 3 resting adds, 2 trades + 1 fill for the aggressor, 1 cancel, 1 rejection
+        assert_eq!(counters.events_dispatched, 8);
     }
     
     #[tokio::test]
-    async fn test_engine_with_risk_checks_disabled() {
-        let mut config = EngineConfig::default();
-        config.enable_risk_checks = false;
+    async fn test_level_cap_rejects_order() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            level_cap: Some(LevelCap::new(2, order_book::LevelCapPolicy::Reject)),
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49800.0, 1.0)).unwrap();
+        let response = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49700.0, 1.0)).unwrap();
         
+        assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("cap")));
+        assert_eq!(engine.get_order_book("BTCUSD").unwrap().depth(10).bids.len(), 2);
+        assert_eq!(engine.counters().orders_rejected, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_order_size_limits_reject_dust_and_oversized_orders() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            order_size_limits: HashMap::from([(
+                "BTCUSD".to_string(),
+                OrderSizeLimits::new(Quantity::new(0.01), Quantity::new(5.0)),
+            )]),
+            ..EngineConfig::default()
+        };
         let engine = TradingEngine::with_config(config);
         engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
         
-        let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
-        let response = engine.submit_order(order).unwrap();
+        let dust = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 0.001)).unwrap();
+        assert!(matches!(dust, OrderResponse::Rejected { ref reason, .. } if reason.contains("below the minimum")));
         
-        assert!(matches!(response, OrderResponse::Accepted { .. }));
+        let oversized = engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 6.0)).unwrap();
+        assert!(matches!(oversized, OrderResponse::Rejected { ref reason, .. } if reason.contains("exceeds the maximum")));
+        
+        let within = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        assert!(matches!(within, OrderResponse::Accepted { .. }));
+        let unbounded = engine.submit_order(create_test_order("ETHUSD", Side::Buy, 3000.0, 0.001)).unwrap();
+        assert!(matches!(unbounded, OrderResponse::Accepted { .. }));
+        
+        assert_eq!(engine.counters().orders_rejected, 2);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_integer_lot_symbol_rejects_fractional_orders() {
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            lot_models: HashMap::from([("AAPL".to_string(), LotModel::Integer(1))]),
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("AAPL".to_string()).unwrap();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let fractional = engine.submit_order(create_test_order("AAPL", Side::Buy, 190.0, 0.5)).unwrap();
+        assert!(matches!(fractional, OrderResponse::Rejected { ref reason, .. } if reason.contains("whole multiple")));
+        
+        engine.submit_order(create_test_order("AAPL", Side::Sell, 190.0, 2.0)).unwrap();
+        let OrderResponse::FullyFilled { trades, .. } = engine.submit_order(create_test_order("AAPL", Side::Buy, 190.0, 2.0)).unwrap() else {
+            panic!("Expected whole-share buy to fill");
+        };
+        assert_eq!(trades[0].quantity, Quantity::new(2.0));
+        
+        let crypto = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 0.5)).unwrap();
+        assert!(matches!(crypto, OrderResponse::Accepted { .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_recovered_order_ids_continue_above_high_water() {
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.recover_order_ids(1_000_000);
+        engine.recover_order_ids(10);
+        
+        let order = engine.new_order(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            Price::new(50000.0),
+            Quantity::new(1.0),
+            Uuid::new_v4(),
+        );
+        assert_eq!(order.id.to_raw(), 1_000_001);
+        engine.submit_order(order).unwrap();
+        
+        // A replayed order above the mark pushes later ids past it
+        let mut replayed = create_test_order("BTCUSD", Side::Buy, 49900.0, 1.0);
+        replayed.id = OrderId::from_raw(2_000_000);
+        engine.submit_order(replayed).unwrap();
+        assert_eq!(engine.order_id_high_water(), 2_000_000);
+        assert_eq!(engine.next_order_id().to_raw(), 2_000_001);
+        
+        let other = TradingEngine::new();
+        assert_eq!(other.next_order_id().to_raw(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_coalesced_match_events() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            coalesce_events: true,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        for i in 0..50 {
+            engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0 + i as f64, 0.1)).unwrap();
+        }
+        let receiver = engine.event_processor().channels().order_receiver();
+        assert_eq!(receiver.try_iter().count(), 50);
+        
+        let before = engine.counters().events_dispatched;
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50100.0, 5.0)).unwrap();
+        assert_eq!(engine.counters().events_dispatched, before + 1);
+        
+        let events: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(events.len(), 1);
+        assert!(engine.event_processor().channels().trade_receiver().is_empty());
+        
+        let members = match &events[0] {
+            Event::Batch(members) => members.clone(),
+            other => panic!("Expected batch event, got {:?}", other),
+        };
+        let trades = members.iter()
+            .filter(|e| matches!(e, Event::Trade(TradeEvent::TradeExecuted(_))))
+            .count();
+        assert_eq!(trades, 50);
+        assert!(matches!(members.last(), Some(Event::Order(OrderEvent::OrderFilled { .. }))));
+        assert_eq!(events[0].clone().into_flat().len(), 51);
+    }
+    
+    #[tokio::test]
+    async fn test_order_retrieval() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let order_id = order.id;
+        let original_timestamp = order.timestamp;
+        
+        engine.submit_order(order).unwrap();
+        
+        let retrieved_order = engine.get_order("BTCUSD", order_id).unwrap();
+        assert_eq!(retrieved_order.id, order_id);
+        assert_eq!(retrieved_order.symbol, "BTCUSD");
+        assert_eq!(retrieved_order.price, Price::new(50000.0));
+        assert_eq!(retrieved_order.timestamp, original_timestamp);
+        
+        assert!(engine.get_order("UNKNOWN", order_id).is_none());
+        assert!(engine.get_order("BTCUSD", OrderId::from_raw(99999)).is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_engine_with_risk_checks_disabled() {
+        let mut config = EngineConfig::default();
+        config.enable_risk_checks = false;
+        
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let order = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let response = engine.submit_order(order).unwrap();
+        
+        assert!(matches!(response, OrderResponse::Accepted { .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_execution_reports_emitted() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            enable_execution_reports: true,
+            ..EngineConfig::default()
+        };
+        
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let resting1 = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        let resting2 = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        let (resting1_id, resting2_id) = (resting1.id, resting2.id);
+        engine.submit_order(resting1).unwrap();
+        engine.submit_order(resting2).unwrap();
+        
+        let aggressor = create_test_order("BTCUSD", Side::Buy, 50000.0, 2.0);
+        let aggressor_id = aggressor.id;
+        engine.submit_order(aggressor).unwrap();
+        
+        let reports: Vec<_> = engine.event_processor().channels().order_receiver().try_iter()
+            .filter_map(|event| match event {
+                Event::Order(OrderEvent::Execution(report)) => Some(report),
+                _ => None,
+            })
+            .collect();
+        
+        let taker: Vec<_> = reports.iter().filter(|r| r.order_id == aggressor_id).collect();
+        assert_eq!(taker.len(), 2);
+        assert!(taker.iter().all(|r| !r.is_maker()));
+        assert_eq!(taker[0].cumulative_quantity, Quantity::new(1.0));
+        assert_eq!(taker[1].cumulative_quantity, Quantity::new(2.0));
+        
+        let makers: Vec<_> = reports.iter().filter(|r| r.is_maker()).map(|r| r.order_id).collect();
+        assert_eq!(makers, vec![resting1_id, resting2_id]);
+    }
+    
+    #[tokio::test]
+    async fn test_queue_only_pause_defers_matching_until_resume() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0)).unwrap();
+        engine.pause_symbol("BTCUSD", PauseMode::QueueOnly).unwrap();
+        assert_eq!(engine.pause_mode("BTCUSD"), Some(PauseMode::QueueOnly));
+        
+        let buy = create_test_order("BTCUSD", Side::Buy, 50000.0, 0.4);
+        let response = engine.submit_order(buy.clone()).unwrap();
+        assert!(matches!(response, OrderResponse::Accepted { .. }));
+        let sell = create_test_order("BTCUSD", Side::Sell, 49900.0, 0.5);
+        engine.submit_order(sell).unwrap();
+        let crossing_buy = create_test_order("BTCUSD", Side::Buy, 50100.0, 2.0);
+        engine.submit_order(crossing_buy.clone()).unwrap();
+        
+        assert_eq!(engine.counters().trades_executed, 0);
+        assert_eq!(engine.deferred_orders("BTCUSD").len(), 3);
+        assert_eq!(engine.get_order_book("BTCUSD").unwrap().best_ask(), Some(Price::new(50000.0)));
+        assert_eq!(engine.get_order_book("BTCUSD").unwrap().best_bid(), None);
+        
+        let responses = engine.resume_symbol("BTCUSD").unwrap();
+        assert_eq!(engine.pause_mode("BTCUSD"), None);
+        assert_eq!(responses.len(), 3);
+        assert!(matches!(&responses[0], OrderResponse::FullyFilled { order_id, .. } if *order_id == buy.id));
+        assert!(matches!(&responses[1], OrderResponse::Accepted { .. }));
+        assert!(matches!(
+            &responses[2],
+            OrderResponse::PartiallyFilled { order_id, trades, remaining_quantity, shortfall: None, .. }
+                if *order_id == crossing_buy.id && trades.len() == 2 && *remaining_quantity == Quantity::new(0.9)
+        ));
+        assert_eq!(engine.counters().trades_executed, 3);
+        
+        let book = engine.get_order_book("BTCUSD").unwrap();
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), Some(Price::new(50100.0)));
+    }
+    
+    #[tokio::test]
+    async fn test_halt_all_pause_rejects_orders() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.pause_symbol("BTCUSD", PauseMode::HaltAll).unwrap();
+        assert!(engine.pause_symbol("ETHUSD", PauseMode::HaltAll).is_err());
+        
+        let response = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("halted")));
+        assert_eq!(engine.counters().orders_rejected, 1);
+        assert!(engine.resume_symbol("BTCUSD").unwrap().is_empty());
+        
+        let response = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        assert!(matches!(response, OrderResponse::Accepted { .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_order_stream_coalesces_fills() {
+        use futures::StreamExt;
+        
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            fill_coalesce_window_us: 20_000,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let buy = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let buy_id = buy.id;
+        let mut progress = Box::pin(engine.submit_order_stream(buy).unwrap());
+        
+        for _ in 0..3 {
+            engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 0.25)).unwrap();
+        }
+        let first = progress.next().await.unwrap();
+        assert_eq!(first.order_id, buy_id);
+        assert_eq!(first.fills, 3);
+        assert_eq!(first.status, OrderStatus::PartiallyFilled);
+        assert_eq!(first.filled_quantity, Quantity::new(0.75));
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 0.125)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 0.125)).unwrap();
+        let last = progress.next().await.unwrap();
+        assert_eq!(last.fills, 2);
+        assert_eq!(last.status, OrderStatus::Filled);
+        assert_eq!(last.remaining_quantity, Quantity::ZERO);
+        assert_eq!(last.last_fill_price, Some(Price::new(50000.0)));
+        
+        assert!(progress.next().await.is_none());
+        assert!(engine.order_watchers.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_order_stream_ends_on_cancel() {
+        use futures::StreamExt;
+        
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let buy = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        let buy_id = buy.id;
+        let progress = engine.submit_order_stream(buy).unwrap();
+        engine.cancel_order("BTCUSD", buy_id).unwrap();
+        
+        let updates: Vec<_> = progress.collect().await;
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, OrderStatus::Cancelled);
+        assert_eq!(updates[0].fills, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_order_stream_ends_when_level_cap_evicts_it() {
+        use futures::StreamExt;
+        
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            level_cap: Some(LevelCap::new(1, order_book::LevelCapPolicy::EvictWorst)),
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let progress = engine.submit_order_stream(create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0)).unwrap();
+        // A better bid pushes the watched order's level out of the book
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        
+        let updates: Vec<_> = progress.collect().await;
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, OrderStatus::Cancelled);
+        assert!(engine.order_watchers.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_dropped_order_streams_are_pruned() {
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let kept = engine.submit_order_stream(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        drop(engine.submit_order_stream(create_test_order("BTCUSD", Side::Buy, 49000.0, 1.0)).unwrap());
+        
+        assert_eq!(engine.prune_order_watchers(), 1);
+        assert_eq!(engine.order_watchers.len(), 1);
+        drop(kept);
+    }
+    
+    /// Schedule placing the current time `hours_into_open` after the open of a two-hour session
+    fn session_around_now(hours_into_open: i64) -> SessionSchedule {
+        let now = clock::now().time();
+        let open = now - chrono::Duration::hours(hours_into_open);
+        SessionSchedule::new(open, open + chrono::Duration::hours(2))
+    }
+    
+    #[tokio::test]
+    async fn test_session_hours_reject_outside_and_accept_within() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            // Open for the next hour, so the order below is within hours
+            session_schedules: HashMap::from([("BTCUSD".to_string(), session_around_now(1))]),
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        assert_eq!(engine.session_phase("BTCUSD"), SessionPhase::Open);
+        
+        let response = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0)).unwrap();
+        assert!(matches!(response, OrderResponse::Accepted { .. }));
+        
+        // Opens in three hours
+        engine.set_session_schedule("ETHUSD", session_around_now(-3)).unwrap();
+        assert_eq!(engine.session_phase("ETHUSD"), SessionPhase::Closed);
+        let response = engine.submit_order(create_test_order("ETHUSD", Side::Buy, 3000.0, 1.0)).unwrap();
+        assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("Session closed")));
+        assert_eq!(engine.get_order_book("ETHUSD").unwrap().best_bid(), None);
+        assert!(engine.set_session_schedule("SOLUSD", session_around_now(1)).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_pre_open_queues_instead_of_matching() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let now = clock::now().time();
+        let open = now + chrono::Duration::hours(1);
+        let schedule = SessionSchedule::new(open, open + chrono::Duration::hours(2))
+            .with_pre_open(now - chrono::Duration::hours(1));
+        engine.set_session_schedule("BTCUSD", schedule).unwrap();
+        assert_eq!(engine.session_phase("BTCUSD"), SessionPhase::PreOpen);
+        
+        let sell = create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0);
+        let buy = create_test_order("BTCUSD", Side::Buy, 50100.0, 1.0);
+        assert!(matches!(engine.submit_order(sell.clone()).unwrap(), OrderResponse::Accepted { .. }));
+        assert!(matches!(engine.submit_order(buy).unwrap(), OrderResponse::Accepted { .. }));
+        
+        assert_eq!(engine.counters().trades_executed, 0);
+        assert_eq!(engine.pre_open_orders("BTCUSD").len(), 2);
+        let book = engine.get_order_book("BTCUSD").unwrap();
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.best_ask(), None);
+        
+        // Queued orders are cancellable, and stay queued until the session opens
+        assert!(matches!(engine.cancel_order("BTCUSD", sell.id).unwrap(), CancelResponse::Cancelled { .. }));
+        assert_eq!(engine.pre_open_orders("BTCUSD").len(), 1);
+        assert!(engine.open_session("BTCUSD").unwrap().is_empty());
+        
+        engine.set_session_schedule("BTCUSD", session_around_now(1)).unwrap();
+        let responses = engine.open_session("BTCUSD").unwrap();
+        assert_eq!(responses.len(), 1);
+        assert!(engine.pre_open_orders("BTCUSD").is_empty());
+        assert_eq!(book.best_bid(), Some(Price::new(50100.0)));
+    }
+    
+    #[tokio::test]
+    async fn test_end_of_day_flattens_positions_and_blocks_opening_orders() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let client_id = Uuid::new_v4();
+        let fill = Trade::new(
+            "BTCUSD",
+            OrderId::new(),
+            OrderId::new(),
+            Price::new(50000.0),
+            Quantity::new(1.5),
+            client_id,
+            Uuid::new_v4(),
+            Side::Buy,
+        );
+        engine.risk_manager().process_trade(&fill).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49900.0, 2.0)).unwrap();
+        
+        // Nothing happens before the flatten time
+        let now = clock::now().time();
+        engine.set_session_schedule("BTCUSD", session_around_now(1).with_flatten_at(now + chrono::Duration::minutes(30))).unwrap();
+        assert!(engine.run_end_of_day().is_empty());
+        assert!(!engine.risk_manager().is_opening_blocked("BTCUSD"));
+        
+        engine.set_session_schedule("BTCUSD", session_around_now(1).with_flatten_at(now - chrono::Duration::minutes(1))).unwrap();
+        // Every non-flat position is flattened, the counterparty's short included
+        let flattening = engine.run_end_of_day();
+        assert_eq!(flattening.len(), 2);
+        let (order, response) = flattening.iter().find(|(order, _)| order.client_id == client_id).unwrap();
+        assert_eq!(order.side, Side::Sell);
+        assert_eq!(order.order_type, OrderType::Market);
+        assert_eq!(order.quantity, Quantity::new(1.5));
+        assert!(order.reduce_only);
+        assert!(matches!(response, OrderResponse::FullyFilled { .. }));
+        assert!(engine.risk_manager().get_position("BTCUSD", client_id).unwrap().is_flat());
+        
+        // Flattened once per window, and only position-reducing orders pass until the next session
+        assert!(engine.run_end_of_day().is_empty());
+        let mut opening = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0);
+        opening.client_id = client_id;
+        let response = engine.submit_order(opening).unwrap();
+        assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("blocked")));
+    }
+    
+    #[tokio::test]
+    async fn test_market_orders_without_enough_liquidity_are_flagged() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        let market_buy = |quantity: f64| {
+            Order::new(
+                "BTCUSD".to_string(),
+                Side::Buy,
+                OrderType::Market,
+                Price::ZERO,
+                Quantity::new(quantity),
+                Uuid::new_v4(),
+            )
+        };
+        
+        let response = engine.submit_order(market_buy(1.0)).unwrap();
+        assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("Insufficient liquidity")));
+        assert_eq!(engine.counters().orders_rejected, 1);
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 0.5)).unwrap();
+        let response = engine.submit_order(market_buy(2.0)).unwrap();
+        match response {
+            OrderResponse::PartiallyFilled { trades, remaining_quantity, shortfall, .. } => {
+                assert_eq!(trades.len(), 1);
+                assert_eq!(remaining_quantity, Quantity::new(1.5));
+                assert_eq!(shortfall, Some(Quantity::new(1.5)));
+            }
+            other => panic!("Expected partial fill, got {:?}", other),
+        }
+        // The remainder is gone rather than resting as a bid
+        assert_eq!(engine.get_order_book("BTCUSD").unwrap().best_bid(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_matching_loop_serializes_orders_from_many_threads() {
+        type Fill = (OrderId, OrderId, Price, Quantity);
+        fn outcome(response: &OrderResponse) -> (&'static str, Vec<Fill>) {
+            let fills = |trades: &[Trade]| {
+                trades.iter().map(|t| (t.buyer_order_id, t.seller_order_id, t.price, t.quantity)).collect()
+            };
+            match response {
+                OrderResponse::Accepted { .. } => ("accepted", Vec::new()),
+                OrderResponse::Rejected { .. } => ("rejected", Vec::new()),
+                OrderResponse::PartiallyFilled { trades, .. } => ("partial", fills(trades)),
+                OrderResponse::FullyFilled { trades, .. } => ("filled", fills(trades)),
+            }
+        }
+        
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let engine = Arc::new(TradingEngine::with_config(config.clone()));
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.start_matching_loop("BTCUSD").unwrap();
+        assert!(engine.has_matching_loop("BTCUSD"));
+        
+        // Overlapping prices from every thread so matching depends on arrival order
+        let submitters: Vec<_> = (0..4)
+            .map(|thread| {
+                let engine = Arc::clone(&engine);
+                std::thread::spawn(move || {
+                    (0..50)
+                        .map(|i| {
+                            let side = if (thread + i) % 2 == 0 { Side::Buy } else { Side::Sell };
+                            let price = 50000.0 + ((thread * 7 + i) % 5) as f64;
+                            let order = create_test_order("BTCUSD", side, price, 1.0 + (i % 3) as f64);
+                            let pending = engine.enqueue_order(order.clone()).unwrap();
+                            (order, pending)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        
+        let mut results = Vec::new();
+        for submitter in submitters {
+            for (order, pending) in submitter.join().unwrap() {
+                assert_eq!(pending.order_id(), order.id);
+                let sequence = pending.sequence().unwrap();
+                results.push((sequence, order, pending.await.unwrap()));
+            }
+        }
+        results.sort_by_key(|(sequence, _, _)| *sequence);
+        assert!(results.iter().enumerate().all(|(i, (sequence, _, _))| *sequence == i as u64));
+        assert!(results.iter().any(|(_, _, response)| !outcome(response).1.is_empty()));
+        
+        // Replaying the queue order inline must reproduce every response and the final book
+        let replay = TradingEngine::with_config(config);
+        replay.add_symbol("BTCUSD".to_string()).unwrap();
+        for (_, order, response) in &results {
+            let expected = replay.submit_order(order.clone()).unwrap();
+            assert_eq!(outcome(response), outcome(&expected));
+        }
+        
+        let book = engine.get_order_book("BTCUSD").unwrap();
+        let replay_book = replay.get_order_book("BTCUSD").unwrap();
+        let (depth, replay_depth) = (book.depth(10), replay_book.depth(10));
+        assert_eq!((depth.bids, depth.asks), (replay_depth.bids, replay_depth.asks));
+        assert_eq!(engine.counters().trades_executed, replay.counters().trades_executed);
+        
+        assert!(engine.stop_matching_loop("BTCUSD"));
+        let inline = engine.enqueue_order(create_test_order("BTCUSD", Side::Buy, 1.0, 1.0)).unwrap();
+        assert_eq!(inline.sequence(), None);
+        assert!(matches!(inline.await.unwrap(), OrderResponse::Accepted { .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_submit_order_queues_behind_enqueued_orders_on_a_matching_loop() {
+        let engine = Arc::new(TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        }));
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.start_matching_loop("BTCUSD").unwrap();
+        
+        let asks: Vec<_> = (0..200)
+            .map(|_| engine.enqueue_order(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0)).unwrap())
+            .collect();
+        // Matched after every ask above, not on this thread ahead of them
+        let response = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50000.0, 200.0)).unwrap();
+        assert!(matches!(response, OrderResponse::FullyFilled { ref trades, .. } if trades.len() == 200));
+        
+        for pending in asks {
+            assert!(matches!(pending.await.unwrap(), OrderResponse::Accepted { .. }));
+        }
+        assert!(engine.stop_matching_loop("BTCUSD"));
+    }
+    
+    #[test]
+    fn test_unhealthy_risk_manager_rejects_fail_closed_and_accepts_fail_open() {
+        for policy in [RiskFailurePolicy::FailClosed, RiskFailurePolicy::FailOpen] {
+            let engine = TradingEngine::with_config(EngineConfig {
+                risk_failure_policy: policy,
+                ..EngineConfig::default()
+            });
+            engine.add_symbol("BTCUSD".to_string()).unwrap();
+            engine.risk_manager().set_healthy(false);
+            
+            let order = engine.new_order("BTCUSD".to_string(), Side::Buy, OrderType::Limit, Price::new(50000.0), Quantity::new(1.0), Uuid::new_v4());
+            let response = engine.submit_order(order).unwrap();
+            match policy {
+                RiskFailurePolicy::FailClosed => {
+                    assert!(matches!(response, OrderResponse::Rejected { ref reason, .. } if reason.contains("unhealthy")));
+                }
+                RiskFailurePolicy::FailOpen => assert!(matches!(response, OrderResponse::Accepted { .. })),
+            }
+            
+            // Recovery restores normal checking under either policy
+            engine.risk_manager().set_healthy(true);
+            let order = engine.new_order("BTCUSD".to_string(), Side::Buy, OrderType::Limit, Price::new(50000.0), Quantity::new(1.0), Uuid::new_v4());
+            assert!(matches!(engine.submit_order(order).unwrap(), OrderResponse::Accepted { .. }));
+        }
+    }
+    
+    #[cfg(feature = "chaos")]
+    #[test]
+    fn test_injected_matching_latency_delays_but_does_not_change_results() {
+        use crate::chaos::LatencyDistribution;
+        use std::time::Instant;
+        
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+        let delay = Duration::from_millis(20);
+        engine.inject_matching_latency("BTCUSD", LatencyDistribution::Fixed(delay));
+        
+        let client = Uuid::new_v4();
+        let sell = engine.new_order("BTCUSD".to_string(), Side::Sell, OrderType::Limit, Price::new(50000.0), Quantity::new(1.0), client);
+        let started = Instant::now();
+        engine.submit_order(sell).unwrap();
+        let buy = engine.new_order("BTCUSD".to_string(), Side::Buy, OrderType::Limit, Price::new(50000.0), Quantity::new(1.0), Uuid::new_v4());
+        let response = engine.submit_order(buy).unwrap();
+        assert!(started.elapsed() >= delay * 2);
+        
+        match response {
+            OrderResponse::FullyFilled { trades, .. } => {
+                assert_eq!(trades.len(), 1);
+                assert_eq!(trades[0].price, Price::new(50000.0));
+                assert_eq!(trades[0].quantity, Quantity::new(1.0));
+            }
+            other => panic!("Expected a fill, got {:?}", other),
+        }
+        
+        let eth = engine.new_order("ETHUSD".to_string(), Side::Buy, OrderType::Limit, Price::new(3000.0), Quantity::new(1.0), client);
+        let started = Instant::now();
+        engine.submit_order(eth).unwrap();
+        assert!(started.elapsed() < delay);
+        
+        assert!(engine.clear_matching_latency("BTCUSD"));
+        assert!(!engine.clear_matching_latency("BTCUSD"));
+    }
+    
+    #[cfg(feature = "chaos")]
+    #[test]
+    fn test_latency_budget_rejects_orders_delayed_past_their_deadline() {
+        use crate::chaos::LatencyDistribution;
+        
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.inject_matching_latency("BTCUSD", LatencyDistribution::Fixed(Duration::from_millis(20)));
+        
+        let late = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0).with_latency_budget(Duration::from_millis(1));
+        let late_id = late.id;
+        match engine.submit_order(late).unwrap() {
+            OrderResponse::Rejected { reason, .. } => assert!(reason.starts_with("Deadline exceeded before matching"), "{}", reason),
+            other => panic!("Expected a deadline rejection, got {:?}", other),
+        }
+        assert!(engine.get_order("BTCUSD", late_id).is_none());
+        
+        let timely = create_test_order("BTCUSD", Side::Buy, 50000.0, 1.0).with_latency_budget(Duration::from_secs(5));
+        let timely_id = timely.id;
+        assert!(matches!(engine.submit_order(timely).unwrap(), OrderResponse::Accepted { .. }));
+        assert!(engine.get_order("BTCUSD", timely_id).is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_engine_is_live_but_not_ready_until_setup_completes() {
+        let engine = TradingEngine::new();
+        assert!(engine.liveness().is_pass());
+        assert_eq!(engine.readiness(), Probe::Fail("engine not started".to_string()));
+        
+        engine.start().await.unwrap();
+        assert!(engine.liveness().is_pass());
+        assert_eq!(engine.readiness(), Probe::Fail("no symbols configured".to_string()));
+        
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        assert_eq!(engine.readiness(), Probe::Fail("risk limits not loaded".to_string()));
+        
+        engine.risk_manager().add_symbol_limits("BTCUSD".to_string(), risk_manager::RiskLimits::new("BTCUSD".to_string()));
+        assert_eq!(engine.readiness(), Probe::Pass);
+        
+        engine.risk_manager().set_healthy(false);
+        assert!(!engine.readiness().is_pass());
+        assert!(engine.liveness().is_pass());
+        engine.risk_manager().set_healthy(true);
+        
+        engine.stop().await.unwrap();
+        assert!(engine.liveness().is_pass());
+        assert!(!engine.readiness().is_pass());
+    }
+    
+    #[tokio::test]
+    async fn test_consolidated_tape_prints_same_price_sweep_once() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            enable_execution_reports: true,
+            consolidate_trade_prints: true,
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        for quantity in [0.5, 1.0, 1.5] {
+            engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50000.0, quantity)).unwrap();
+        }
+        let orders = engine.event_processor().channels().order_receiver();
+        let tape = engine.event_processor().channels().trade_receiver();
+        orders.try_iter().count();
+        
+        let taker = create_test_order("BTCUSD", Side::Buy, 50000.0, 3.0);
+        let response = engine.submit_order(taker.clone()).unwrap();
+        assert!(matches!(&response, OrderResponse::FullyFilled { trades, .. } if trades.len() == 3));
+        
+        let prints: Vec<_> = tape.try_iter().collect();
+        assert_eq!(prints.len(), 1);
+        match &prints[0] {
+            Event::Trade(TradeEvent::TradeExecuted(print)) => {
+                assert_eq!(print.quantity, Quantity::new(3.0));
+                assert_eq!(print.price, Price::new(50000.0));
+                assert_eq!(print.buyer_order_id, taker.id);
+            }
+            other => panic!("Expected a trade print, got {:?}", other),
+        }
+        
+        let taker_reports = orders
+            .try_iter()
+            .filter(|event| matches!(event, Event::Order(OrderEvent::Execution(report)) if report.order_id == taker.id))
+            .count();
+        assert_eq!(taker_reports, 3);
+    }
+    
+    #[test]
+    fn test_max_order_notional_rejects_orders_over_the_cap() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            max_order_notional: Some(1_000_000.0),
+            ..EngineConfig::default()
+        };
+        let engine = TradingEngine::with_config(config);
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        let under = engine.submit_order(create_test_order("BTCUSD", Side::Buy, 49_999.0, 20.0)).unwrap();
+        assert!(matches!(under, OrderResponse::Accepted { .. }));
+        
+        let over = create_test_order("BTCUSD", Side::Buy, 50_001.0, 20.0);
+        match engine.submit_order(over.clone()).unwrap() {
+            OrderResponse::Rejected { order_id, reason, .. } => {
+                assert_eq!(order_id, over.id);
+                assert!(reason.contains("exceeds per-order cap"), "unexpected reason: {}", reason);
+            }
+            other => panic!("Expected rejection, got {:?}", other),
+        }
+        
+        // A market order is valued at every level it would sweep: 16 at the 60,000 touch would
+        // pass at 960,000, but the last 6 come from the 70,000 level
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 60_000.0, 10.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 70_000.0, 10.0)).unwrap();
+        let market = Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            Price::ZERO,
+            Quantity::new(16.0),
+            Uuid::new_v4(),
+        );
+        assert!(matches!(engine.submit_order(market).unwrap(), OrderResponse::Rejected { .. }));
+        
+        let book = engine.get_order_book("BTCUSD").unwrap();
+        assert_eq!(book.best_bid(), Some(Price::new(49_999.0)));
+        assert_eq!(book.best_ask(), Some(Price::new(60_000.0)));
+    }
+    
+    #[test]
+    fn test_market_order_beyond_buying_power_is_rejected() {
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 5_000.0, 10.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 6_000.0, 10.0)).unwrap();
+        
+        // 10% initial margin on 10,000 of equity buys up to 100,000 of notional
+        let client_id = Uuid::new_v4();
+        engine.risk_manager().set_account_equity(client_id, 10_000.0);
+        engine.risk_manager().set_initial_margin("BTCUSD", 0.1);
+        let market_buy = |quantity: f64| {
+            Order::new("BTCUSD".to_string(), Side::Buy, OrderType::Market, Price::ZERO, Quantity::new(quantity), client_id)
+        };
+        
+        // 20 sweeps both levels for 110,000
+        match engine.submit_order(market_buy(20.0)).unwrap() {
+            OrderResponse::Rejected { reason, .. } => assert!(reason.contains("Insufficient margin"), "unexpected reason: {}", reason),
+            other => panic!("Expected rejection, got {:?}", other),
+        }
+        assert_eq!(engine.get_order_book("BTCUSD").unwrap().best_ask(), Some(Price::new(5_000.0)));
+        
+        // 15 costs 80,000
+        assert!(matches!(engine.submit_order(market_buy(15.0)).unwrap(), OrderResponse::FullyFilled { .. }));
+    }
+    
+    fn record_session(engine: &TradingEngine, inputs: &[ReplayInput]) -> Vec<Trade> {
+        let mut trades = Vec::new();
+        for input in inputs {
+            match input {
+                ReplayInput::Submit(order) => trades.extend_from_slice(engine.submit_order(order.clone()).unwrap().trades()),
+                ReplayInput::Cancel { symbol, order_id } => {
+                    engine.cancel_order(symbol, *order_id).unwrap();
+                }
+            }
+        }
+        trades
+    }
+    
+    #[test]
+    fn test_replay_verify_reproduces_recorded_session() {
+        let config = EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        };
+        let resting = create_test_order("BTCUSD", Side::Sell, 50100.0, 2.0);
+        let inputs = vec![
+            ReplayInput::Submit(create_test_order("BTCUSD", Side::Sell, 50000.0, 1.0)),
+            ReplayInput::Submit(resting.clone()),
+            ReplayInput::Submit(create_test_order("ETHUSD", Side::Buy, 3000.0, 5.0)),
+            ReplayInput::Submit(create_test_order("BTCUSD", Side::Buy, 50100.0, 1.5)),
+            ReplayInput::Submit(create_test_order("ETHUSD", Side::Sell, 2990.0, 2.0)),
+            ReplayInput::Cancel { symbol: "BTCUSD".to_string(), order_id: resting.id },
+            ReplayInput::Submit(create_test_order("BTCUSD", Side::Sell, 49900.0, 1.0)),
+            ReplayInput::Submit(create_test_order("BTCUSD", Side::Buy, 50200.0, 1.0)),
+        ];
+        
+        let recorder = TradingEngine::with_config(config.clone());
+        recorder.add_symbol("BTCUSD".to_string()).unwrap();
+        recorder.add_symbol("ETHUSD".to_string()).unwrap();
+        let recorded = record_session(&recorder, &inputs);
+        assert_eq!(recorded.len(), 4);
+        
+        let replay = TradingEngine::with_config(config.clone());
+        assert_eq!(replay.replay_verify(&inputs, &recorded), Ok(()));
+        
+        // A recording the matching core no longer reproduces is caught at the first bad trade
+        let mut altered = recorded.clone();
+        altered[1].quantity = Quantity::new(0.25);
+        let divergences = TradingEngine::with_config(config).replay_verify(&inputs, &altered).unwrap_err();
+        assert_eq!(divergences.len(), 1);
+        match &divergences[0] {
+            Divergence::Mismatch { symbol, index, expected, actual } => {
+                assert_eq!(symbol, "BTCUSD");
+                assert_eq!(*index, 1);
+                assert_eq!(expected.quantity, Quantity::new(0.25));
+                assert_eq!(actual.quantity, Quantity::new(0.5));
+            }
+            other => panic!("Expected a mismatch, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_momentum_strategy_buy_intent_is_executed() {
+        /// Buys once the trade price has risen `rises` times in a row, until it is filled
+        struct Momentum {
+            rises: usize,
+            streak: usize,
+            last_price: Option<Price>,
+            filled: Quantity,
+        }
+        
+        impl Strategy for Momentum {
+            fn name(&self) -> &str {
+                "momentum"
+            }
+            
+            fn on_market_data(&mut self, data: &MarketData) -> Vec<OrderIntent> {
+                let Some(price) = data.last_trade_price else {
+                    return Vec::new();
+                };
+                self.streak = match self.last_price {
+                    Some(last) if price > last => self.streak + 1,
+                    _ => 0,
+                };
+                self.last_price = Some(price);
+                if self.streak >= self.rises && self.filled == Quantity::ZERO {
+                    vec![OrderIntent::market(data.symbol.clone(), Side::Buy, Quantity::new(1.0))]
+                } else {
+                    Vec::new()
+                }
+            }
+            
+            fn on_fill(&mut self, trade: &Trade, _side: Side) -> Vec<OrderIntent> {
+                self.filled += trade.quantity;
+                Vec::new()
+            }
+        }
+        
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50100.0, 5.0)).unwrap();
+        
+        let client_id = Uuid::new_v4();
+        engine.register_strategy(client_id, Box::new(Momentum { rises: 3, streak: 0, last_price: None, filled: Quantity::ZERO }));
+        let tick = |price: f64| {
+            let mut data = MarketData::new("BTCUSD".to_string());
+            data.last_trade_price = Some(Price::new(price));
+            engine.feed_market_data(&data).unwrap()
+        };
+        
+        // Nothing until the third consecutive rise
+        for price in [50000.0, 50010.0, 50020.0] {
+            assert!(tick(price).is_empty());
+        }
+        let placed = tick(50030.0);
+        assert_eq!(placed.len(), 1);
+        let (order, response) = &placed[0];
+        assert_eq!(order.client_id, client_id);
+        assert_eq!(order.side, Side::Buy);
+        assert_eq!(order.order_type, OrderType::Market);
+        assert!(matches!(response, OrderResponse::FullyFilled { .. }));
+        assert_eq!(engine.risk_manager().get_position("BTCUSD", client_id).unwrap().quantity, 1.0);
+        
+        // The fill reaches the strategy on the next drive, so it does not buy again
+        for price in [50040.0, 50050.0, 50060.0, 50070.0] {
+            assert!(tick(price).is_empty());
+        }
+    }
+    
+    #[test]
+    fn test_ack_latency_measured_per_submitted_order() {
+        let engine = TradingEngine::with_config(EngineConfig {
+            measure_ack_latency: true,
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        
+        engine.submit_order(create_test_order("BTCUSD", Side::Sell, 50_000.0, 1.0)).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50_000.0, 1.0)).unwrap();
+        // Rejections are acknowledged too
+        engine.submit_order(create_test_order("ETHUSD", Side::Buy, 3_000.0, 1.0)).unwrap();
+        
+        let metrics = engine.latency_profiler().get_metrics(MeasurementPoint::OrderAckLatency).unwrap();
+        assert_eq!(metrics.count(), 3);
+        assert!(metrics.min() > Duration::ZERO);
+        assert!(metrics.max() < Duration::from_secs(1));
+        
+        // Off by default
+        let engine = TradingEngine::new();
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        engine.submit_order(create_test_order("BTCUSD", Side::Buy, 50_000.0, 1.0)).unwrap();
+        assert!(engine.latency_profiler().get_metrics(MeasurementPoint::OrderAckLatency).is_none());
+    }
+}
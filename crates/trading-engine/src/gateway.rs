@@ -0,0 +1,190 @@
+use crate::engine::{OrderResponse, TradingEngine};
+use anyhow::Result;
+use dashmap::DashMap;
+use order_book::Order;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::warn;
+use uuid::Uuid;
+
+/// First sequence number a new session is expected to send
+pub const INITIAL_SEQUENCE: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+pub enum RejectReason {
+    /// The message was a duplicate (`received < expected`) or skipped ahead (`received > expected`)
+    #[error("Sequence gap: expected {expected}, received {received}")]
+    SequenceGap { expected: u64, received: u64 },
+    /// The order used up its latency budget before it could be matched
+    #[error("Deadline exceeded {stage}: {elapsed:?} elapsed against a {budget:?} budget")]
+    DeadlineExceeded { stage: String, elapsed: Duration, budget: Duration },
+}
+
+/// Gateway reply to one sequenced message; `server_sequence` orders every reply the gateway sends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GatewayAck {
+    Accepted {
+        server_sequence: u64,
+        client_sequence: u64,
+        response: OrderResponse,
+    },
+    Rejected {
+        server_sequence: u64,
+        client_sequence: u64,
+        reason: RejectReason,
+    },
+}
+
+impl GatewayAck {
+    #[inline]
+    pub fn server_sequence(&self) -> u64 {
+        match self {
+            Self::Accepted { server_sequence, .. } | Self::Rejected { server_sequence, .. } => *server_sequence,
+        }
+    }
+
+    #[inline]
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted { .. })
+    }
+}
+
+/// Order-entry front door that requires each client session (keyed by `Order::client_id`)
+/// to number its messages consecutively, so replayed or dropped messages never reach the engine
+pub struct SessionGateway {
+    engine: Arc<TradingEngine>,
+    expected: DashMap<Uuid, u64>,
+    server_sequence: AtomicU64,
+}
+
+impl SessionGateway {
+    pub fn new(engine: Arc<TradingEngine>) -> Self {
+        Self {
+            engine,
+            expected: DashMap::new(),
+            server_sequence: AtomicU64::new(0),
+        }
+    }
+
+    /// Forward `order` to the engine if `sequence` is the next one for its session. An order
+    /// the engine itself rejects still consumes the sequence number.
+    pub fn submit(&self, sequence: u64, order: Order) -> Result<GatewayAck> {
+        // The entry stays locked until the engine responds, keeping each session strictly ordered
+        let mut expected = self.expected.entry(order.client_id).or_insert(INITIAL_SEQUENCE);
+
+        if sequence != *expected {
+            warn!("Session {} sent sequence {}, expected {}", order.client_id, sequence, *expected);
+            return Ok(GatewayAck::Rejected {
+                server_sequence: self.next_server_sequence(),
+                client_sequence: sequence,
+                reason: RejectReason::SequenceGap {
+                    expected: *expected,
+                    received: sequence,
+                },
+            });
+        }
+
+        let response = self.engine.submit_order(order)?;
+        *expected += 1;
+
+        Ok(GatewayAck::Accepted {
+            server_sequence: self.next_server_sequence(),
+            client_sequence: sequence,
+            response,
+        })
+    }
+
+    /// Next sequence number the session must send
+    #[inline]
+    pub fn expected_sequence(&self, client_id: Uuid) -> u64 {
+        self.expected.get(&client_id).map_or(INITIAL_SEQUENCE, |expected| *expected)
+    }
+
+    /// Forget a session so it starts again from `INITIAL_SEQUENCE`
+    pub fn reset_session(&self, client_id: Uuid) {
+        self.expected.remove(&client_id);
+    }
+
+    #[inline]
+    pub fn engine(&self) -> &Arc<TradingEngine> {
+        &self.engine
+    }
+
+    #[inline]
+    fn next_server_sequence(&self) -> u64 {
+        self.server_sequence.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::EngineConfig;
+    use order_book::{OrderType, Price, Quantity, Side};
+
+    fn order(client_id: Uuid, price: f64) -> Order {
+        Order::new(
+            "BTCUSD".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            Price::new(price),
+            Quantity::new(1.0),
+            client_id,
+        )
+    }
+
+    fn gateway() -> SessionGateway {
+        let engine = TradingEngine::with_config(EngineConfig {
+            enable_risk_checks: false,
+            ..EngineConfig::default()
+        });
+        engine.add_symbol("BTCUSD".to_string()).unwrap();
+        SessionGateway::new(Arc::new(engine))
+    }
+
+    #[test]
+    fn test_in_order_duplicate_and_gapped_sequences() {
+        let gateway = gateway();
+        let client = Uuid::new_v4();
+
+        let first = gateway.submit(1, order(client, 49900.0)).unwrap();
+        let second = gateway.submit(2, order(client, 49800.0)).unwrap();
+        assert!(matches!(first, GatewayAck::Accepted { client_sequence: 1, response: OrderResponse::Accepted { .. }, .. }));
+        assert!(second.is_accepted());
+        assert!(second.server_sequence() > first.server_sequence());
+
+        let duplicate = gateway.submit(2, order(client, 49700.0)).unwrap();
+        assert!(matches!(
+            duplicate,
+            GatewayAck::Rejected { reason: RejectReason::SequenceGap { expected: 3, received: 2 }, .. }
+        ));
+
+        let gapped = gateway.submit(5, order(client, 49600.0)).unwrap();
+        assert!(matches!(
+            gapped,
+            GatewayAck::Rejected { reason: RejectReason::SequenceGap { expected: 3, received: 5 }, .. }
+        ));
+
+        assert_eq!(gateway.expected_sequence(client), 3);
+        assert_eq!(gateway.engine().counters().orders_submitted, 2);
+        assert!(gateway.submit(3, order(client, 49700.0)).unwrap().is_accepted());
+    }
+
+    #[test]
+    fn test_sessions_are_sequenced_independently() {
+        let gateway = gateway();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(gateway.submit(1, order(alice, 49900.0)).unwrap().is_accepted());
+        assert!(gateway.submit(1, order(bob, 49800.0)).unwrap().is_accepted());
+        assert!(!gateway.submit(1, order(alice, 49700.0)).unwrap().is_accepted());
+
+        gateway.reset_session(alice);
+        assert_eq!(gateway.expected_sequence(alice), INITIAL_SEQUENCE);
+        assert!(gateway.submit(1, order(alice, 49700.0)).unwrap().is_accepted());
+        assert_eq!(gateway.expected_sequence(bob), 2);
+    }
+}
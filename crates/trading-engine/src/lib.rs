@@ -1,11 +1,33 @@
 pub mod engine;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod state;
 pub mod config;
+pub mod cold;
 pub mod portfolio;
+pub mod gateway;
+pub mod market_maker;
+pub mod matching_loop;
+pub mod progress;
+pub mod replay;
+pub mod router;
+pub mod session;
+pub mod strategy;
 
-pub use engine::TradingEngine;
+pub use engine::{PauseMode, RiskFailurePolicy, TradingEngine};
+#[cfg(feature = "chaos")]
+pub use chaos::LatencyDistribution;
 pub use state::*;
 pub use config::EngineConfig;
+pub use cold::{ColdBook, ColdStore, InMemoryColdStore};
 pub use portfolio::Portfolio;
+pub use gateway::{GatewayAck, RejectReason, SessionGateway};
+pub use market_maker::{MarketMaker, MarketMakerConfig, QuotePair};
+pub use matching_loop::PendingOrder;
+pub use progress::OrderProgress;
+pub use replay::{Divergence, NormalizedTrade, OrderRef, ReplayInput};
+pub use router::{RoutingResult, SmartOrderRouter};
+pub use session::{SessionPhase, SessionSchedule};
+pub use strategy::{OrderIntent, Strategy};
 
 pub type Result<T> = anyhow::Result<T>;
\ No newline at end of file
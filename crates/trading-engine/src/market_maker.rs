@@ -0,0 +1,176 @@
+use order_book::{MarketData, Order, OrderBook, OrderType, Price, Quantity, Side};
+use risk_manager::RiskManager;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketMakerConfig {
+    /// Distance between the bid and ask quotes, in price units
+    pub target_spread: f64,
+    pub quote_size: Quantity,
+    /// Price units the quotes move against the position per unit held: a long position
+    /// lowers both quotes, a short one raises them
+    pub inventory_skew: f64,
+}
+
+impl Default for MarketMakerConfig {
+    fn default() -> Self {
+        Self {
+            target_spread: 1.0,
+            quote_size: Quantity::new(1.0),
+            inventory_skew: 0.0,
+        }
+    }
+}
+
+/// Two-sided quote centered on the inventory-adjusted fair value
+#[derive(Debug, Clone)]
+pub struct QuotePair {
+    pub bid: Order,
+    pub ask: Order,
+    /// Microprice of the book before the inventory skew
+    pub microprice: Price,
+    pub inventory: f64,
+}
+
+/// Quotes around the top-of-book microprice, so the pair leans toward the side with less
+/// resting size, and skews both quotes to work the client's position back toward flat.
+/// Quotes are not post-only: a large enough skew can price one side through the book.
+pub struct MarketMaker {
+    config: MarketMakerConfig,
+    risk_manager: Arc<RiskManager>,
+    client_id: Uuid,
+}
+
+impl MarketMaker {
+    pub fn new(config: MarketMakerConfig, risk_manager: Arc<RiskManager>, client_id: Uuid) -> Self {
+        Self {
+            config,
+            risk_manager,
+            client_id,
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &MarketMakerConfig {
+        &self.config
+    }
+
+    /// Size-weighted mid of the book's best bid and ask; `None` unless both sides are quoted
+    pub fn microprice(&self, book: &OrderBook) -> Option<Price> {
+        let top = book.depth(1);
+        let (bid, bid_size) = *top.bids.first()?;
+        let (ask, ask_size) = *top.asks.first()?;
+
+        let mut market_data = MarketData::new(book.symbol().to_string());
+        market_data.best_bid = Some(bid);
+        market_data.best_ask = Some(ask);
+        market_data.bid_size = bid_size;
+        market_data.ask_size = ask_size;
+        market_data.weighted_mid()
+    }
+
+    /// The client's signed position in `symbol` as tracked by the risk manager
+    pub fn inventory(&self, symbol: &str) -> f64 {
+        self.risk_manager
+            .get_position(symbol, self.client_id)
+            .map_or(0.0, |position| position.quantity)
+    }
+
+    /// Buy and sell limit orders `target_spread` apart around the skewed microprice
+    pub fn quote(&self, book: &OrderBook) -> Option<QuotePair> {
+        let microprice = self.microprice(book)?;
+        let inventory = self.inventory(book.symbol());
+        let fair_value = microprice.to_f64() - inventory * self.config.inventory_skew;
+        let half_spread = self.config.target_spread / 2.0;
+
+        let order = |side: Side, price: f64| {
+            Order::new(
+                book.symbol().to_string(),
+                side,
+                OrderType::Limit,
+                Price::new(price),
+                self.config.quote_size,
+                self.client_id,
+            )
+        };
+
+        Some(QuotePair {
+            bid: order(Side::Buy, fair_value - half_spread),
+            ask: order(Side::Sell, fair_value + half_spread),
+            microprice,
+            inventory,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use order_book::Trade;
+
+    fn book() -> OrderBook {
+        let book = OrderBook::new("BTCUSD".to_string());
+        let order = |side, price, quantity| {
+            Order::new("BTCUSD".to_string(), side, OrderType::Limit, Price::new(price), Quantity::new(quantity), Uuid::new_v4())
+        };
+        // Three lots bid against one offered: the microprice sits above the 100 mid
+        book.add_order(order(Side::Buy, 99.0, 3.0));
+        book.add_order(order(Side::Sell, 101.0, 1.0));
+        book
+    }
+
+    fn config() -> MarketMakerConfig {
+        MarketMakerConfig {
+            target_spread: 1.0,
+            quote_size: Quantity::new(2.0),
+            inventory_skew: 0.25,
+        }
+    }
+
+    #[test]
+    fn test_flat_quotes_straddle_microprice_at_target_spread() {
+        let maker = MarketMaker::new(config(), Arc::new(RiskManager::new()), Uuid::new_v4());
+        let book = book();
+
+        let quotes = maker.quote(&book).unwrap();
+        assert_eq!(quotes.microprice, Price::new(100.5));
+        assert_eq!(quotes.inventory, 0.0);
+        assert_eq!((quotes.bid.side, quotes.bid.price), (Side::Buy, Price::new(100.0)));
+        assert_eq!((quotes.ask.side, quotes.ask.price), (Side::Sell, Price::new(101.0)));
+        assert_eq!(quotes.ask.price - quotes.bid.price, Price::new(1.0));
+        assert_eq!(quotes.bid.quantity, Quantity::new(2.0));
+
+        assert!(maker.quote(&OrderBook::new("BTCUSD".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_long_inventory_skews_quotes_down() {
+        let risk_manager = Arc::new(RiskManager::new());
+        let client_id = Uuid::new_v4();
+        let maker = MarketMaker::new(config(), Arc::clone(&risk_manager), client_id);
+        let book = book();
+        let flat = maker.quote(&book).unwrap();
+
+        let fill = Trade::new(
+            "BTCUSD",
+            order_book::OrderId::new(),
+            order_book::OrderId::new(),
+            Price::new(100.0),
+            Quantity::new(2.0),
+            client_id,
+            Uuid::new_v4(),
+            Side::Buy,
+        );
+        risk_manager.process_trade(&fill).unwrap();
+
+        let long = maker.quote(&book).unwrap();
+        assert_eq!(long.inventory, 2.0);
+        // Two lots at 0.25 each: both quotes half a point lower, the offer now inside the book
+        assert_eq!(long.bid.price, flat.bid.price - Price::new(0.5));
+        assert_eq!(long.ask.price, flat.ask.price - Price::new(0.5));
+        assert!(long.ask.price < book.best_ask().unwrap());
+        assert_eq!(long.ask.price - long.bid.price, Price::new(1.0));
+    }
+}
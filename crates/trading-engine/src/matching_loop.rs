@@ -0,0 +1,169 @@
+use crate::engine::{OrderResponse, TradingEngine};
+use anyhow::Result;
+use order_book::{Order, OrderId};
+use parking_lot::Mutex;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll};
+use std::thread::{JoinHandle, ThreadId};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+struct QueuedOrder {
+    order: Order,
+    reply: Reply,
+}
+
+/// Where the loop sends a queued order's response
+enum Reply {
+    /// `enqueue_order`, awaited as a `PendingOrder`
+    Pending(oneshot::Sender<Result<OrderResponse>>),
+    /// `submit_order`, whose caller blocks until the order is matched
+    Blocking(mpsc::SyncSender<Result<OrderResponse>>),
+}
+
+impl Reply {
+    fn send(self, response: Result<OrderResponse>) {
+        match self {
+            Reply::Pending(sender) => {
+                let _ = sender.send(response);
+            }
+            Reply::Blocking(sender) => {
+                let _ = sender.send(response);
+            }
+        }
+    }
+}
+
+/// Response to an order handed to a symbol's matching thread, resolved once the thread has run it
+pub struct PendingOrder {
+    order_id: OrderId,
+    sequence: Option<u64>,
+    response: oneshot::Receiver<Result<OrderResponse>>,
+}
+
+impl PendingOrder {
+    pub(crate) fn ready(order_id: OrderId, response: Result<OrderResponse>) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let _ = sender.send(response);
+        Self {
+            order_id,
+            sequence: None,
+            response: receiver,
+        }
+    }
+
+    #[inline]
+    pub fn order_id(&self) -> OrderId {
+        self.order_id
+    }
+
+    /// Position in the symbol's matching queue; the thread matches orders in this order.
+    /// `None` when the order was matched inline because the symbol has no matching loop.
+    #[inline]
+    pub fn sequence(&self) -> Option<u64> {
+        self.sequence
+    }
+}
+
+impl Future for PendingOrder {
+    type Output = Result<OrderResponse>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.response).poll(cx).map(|response| {
+            response.unwrap_or_else(|_| Err(anyhow::anyhow!("Matching loop stopped before processing the order")))
+        })
+    }
+}
+
+/// Single consumer that matches one symbol's orders serially on a dedicated thread
+pub(crate) struct MatchingLoop {
+    // Sequence numbers are taken under the same lock as the send, so they follow queue order
+    queue: Mutex<(u64, mpsc::Sender<QueuedOrder>)>,
+    handle: Option<JoinHandle<()>>,
+    thread: ThreadId,
+}
+
+impl MatchingLoop {
+    pub(crate) fn spawn(engine: &Arc<TradingEngine>, symbol: &str) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel::<QueuedOrder>();
+        let weak: Weak<TradingEngine> = Arc::downgrade(engine);
+        let numa_node = engine.symbol_node(symbol);
+        let thread_symbol = symbol.to_string();
+
+        let handle = std::thread::Builder::new()
+            .name(format!("match-{}", symbol))
+            .spawn(move || {
+                if let (Some(engine), Some(numa_node)) = (weak.upgrade(), numa_node) {
+                    if let Err(e) = engine.pin_matching_thread(&thread_symbol, numa_node) {
+                        warn!("Matching thread for {} not pinned to node {}: {}", thread_symbol, numa_node, e);
+                    }
+                }
+
+                // Ends once the loop is stopped (sender dropped) or the engine is gone
+                while let Ok(queued) = receiver.recv() {
+                    let Some(engine) = weak.upgrade() else {
+                        break;
+                    };
+                    queued.reply.send(engine.submit_order_inline(queued.order));
+                }
+                info!("Matching loop for {} stopped", thread_symbol);
+            })?;
+
+        Ok(Self {
+            queue: Mutex::new((0, sender)),
+            thread: handle.thread().id(),
+            handle: Some(handle),
+        })
+    }
+
+    /// Whether the caller is the loop's own thread, which must match inline rather than
+    /// wait on its own queue
+    #[inline]
+    pub(crate) fn is_loop_thread(&self) -> bool {
+        self.thread == std::thread::current().id()
+    }
+
+    pub(crate) fn enqueue(&self, order: Order) -> Result<PendingOrder> {
+        let order_id = order.id;
+        let (reply, response) = oneshot::channel();
+        let sequence = self.push(order, Reply::Pending(reply))?;
+
+        Ok(PendingOrder {
+            order_id,
+            sequence: Some(sequence),
+            response,
+        })
+    }
+
+    /// Queue `order` behind everything already enqueued; the receiver yields its response
+    /// once the thread has matched it
+    pub(crate) fn submit(&self, order: Order) -> Result<mpsc::Receiver<Result<OrderResponse>>> {
+        let (reply, response) = mpsc::sync_channel(1);
+        self.push(order, Reply::Blocking(reply))?;
+        Ok(response)
+    }
+
+    fn push(&self, order: Order, reply: Reply) -> Result<u64> {
+        let mut queue = self.queue.lock();
+        queue.1
+            .send(QueuedOrder { order, reply })
+            .map_err(|_| anyhow::anyhow!("Matching loop is not running"))?;
+        let sequence = queue.0;
+        queue.0 += 1;
+        Ok(sequence)
+    }
+
+    /// Close the queue and wait for the thread to drain it
+    pub(crate) fn stop(mut self) {
+        let handle = self.handle.take();
+        drop(self);
+        if let Some(handle) = handle {
+            if handle.thread().id() != std::thread::current().id() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
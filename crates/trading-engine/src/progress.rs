@@ -0,0 +1,105 @@
+use futures::Stream;
+use order_book::{OrderId, OrderStatus, Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// What the engine reports to a watched order's stream
+#[derive(Debug, Clone)]
+pub(crate) enum FillNotice {
+    Fill { quantity: Quantity, price: Price },
+    /// No more fills will follow; the order was filled, cancelled or rejected
+    Closed(OrderStatus),
+}
+
+/// Cumulative state of a streamed order after one or more fills
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderProgress {
+    pub order_id: OrderId,
+    pub status: OrderStatus,
+    pub filled_quantity: Quantity,
+    pub remaining_quantity: Quantity,
+    /// Fills folded into this update by the coalescing window
+    pub fills: u32,
+    pub last_fill_price: Option<Price>,
+}
+
+impl OrderProgress {
+    #[inline]
+    pub fn is_final(&self) -> bool {
+        matches!(self.status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected)
+    }
+}
+
+struct ProgressState {
+    receiver: mpsc::UnboundedReceiver<FillNotice>,
+    progress: OrderProgress,
+    window: Duration,
+    finished: bool,
+}
+
+impl ProgressState {
+    /// Fold a notice into the progress; returns whether the order is done
+    fn apply(&mut self, notice: FillNotice) -> bool {
+        match notice {
+            FillNotice::Fill { quantity, price } => {
+                self.progress.filled_quantity += quantity;
+                self.progress.remaining_quantity -= quantity.min(self.progress.remaining_quantity);
+                self.progress.fills += 1;
+                self.progress.last_fill_price = Some(price);
+                self.progress.status = if self.progress.remaining_quantity == Quantity::ZERO {
+                    OrderStatus::Filled
+                } else {
+                    OrderStatus::PartiallyFilled
+                };
+            }
+            FillNotice::Closed(status) => self.progress.status = status,
+        }
+        self.progress.is_final()
+    }
+}
+
+/// Stream one update per coalescing window: the first notice opens a window of `window`
+/// and every notice arriving before it closes is folded into the same update
+pub(crate) fn progress_stream(
+    order_id: OrderId,
+    quantity: Quantity,
+    receiver: mpsc::UnboundedReceiver<FillNotice>,
+    window: Duration,
+) -> impl Stream<Item = OrderProgress> {
+    let state = ProgressState {
+        receiver,
+        progress: OrderProgress {
+            order_id,
+            status: OrderStatus::Pending,
+            filled_quantity: Quantity::ZERO,
+            remaining_quantity: quantity,
+            fills: 0,
+            last_fill_price: None,
+        },
+        window,
+        finished: false,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        if state.finished {
+            return None;
+        }
+
+        state.progress.fills = 0;
+        let notice = state.receiver.recv().await?;
+        state.finished = state.apply(notice);
+
+        let deadline = Instant::now() + state.window;
+        while !state.finished {
+            match tokio::time::timeout_at(deadline, state.receiver.recv()).await {
+                Ok(Some(notice)) => state.finished = state.apply(notice),
+                // Sender dropped: emit what we have, the next poll ends the stream
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        Some((state.progress.clone(), state))
+    })
+}
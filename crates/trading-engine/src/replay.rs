@@ -0,0 +1,138 @@
+use order_book::{Order, OrderId, Price, Quantity, Side, Trade};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// One recorded call into the engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayInput {
+    Submit(Order),
+    Cancel { symbol: String, order_id: OrderId },
+}
+
+impl ReplayInput {
+    pub fn symbol(&self) -> &str {
+        match self {
+            ReplayInput::Submit(order) => &order.symbol,
+            ReplayInput::Cancel { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// An order named by the position of its submission in the input log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderRef {
+    Input(usize),
+    /// An order the input log never submitted
+    External(OrderId),
+}
+
+/// A trade without the trade id and timestamp, which differ on every run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedTrade {
+    pub symbol: String,
+    pub buyer: OrderRef,
+    pub seller: OrderRef,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub buyer_client_id: Uuid,
+    pub seller_client_id: Uuid,
+    pub aggressor_side: Side,
+}
+
+/// Where a replay first stopped reproducing the recording in one symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Divergence {
+    /// The `index`th trade in the symbol differs from the recorded one
+    Mismatch {
+        symbol: String,
+        index: usize,
+        expected: NormalizedTrade,
+        actual: NormalizedTrade,
+    },
+    /// The replay stopped trading before the recording did
+    Missing {
+        symbol: String,
+        index: usize,
+        expected: NormalizedTrade,
+    },
+    /// The replay kept trading after the recording stopped
+    Unexpected {
+        symbol: String,
+        index: usize,
+        actual: NormalizedTrade,
+    },
+    /// The engine returned an error for the input at `position`
+    Failed { position: usize, error: String },
+}
+
+/// Maps run-specific order ids back to input positions
+pub(crate) struct TradeNormalizer {
+    positions: HashMap<OrderId, usize>,
+}
+
+impl TradeNormalizer {
+    pub(crate) fn new(input_log: &[ReplayInput]) -> Self {
+        let mut positions = HashMap::new();
+        for (position, input) in input_log.iter().enumerate() {
+            if let ReplayInput::Submit(order) = input {
+                positions.entry(order.id).or_insert(position);
+            }
+        }
+        Self { positions }
+    }
+
+    pub(crate) fn normalize(&self, trade: &Trade) -> NormalizedTrade {
+        NormalizedTrade {
+            symbol: trade.symbol.clone(),
+            buyer: self.order_ref(trade.buyer_order_id),
+            seller: self.order_ref(trade.seller_order_id),
+            price: trade.price,
+            quantity: trade.quantity,
+            buyer_client_id: trade.buyer_client_id,
+            seller_client_id: trade.seller_client_id,
+            aggressor_side: trade.aggressor_side,
+        }
+    }
+
+    fn order_ref(&self, order_id: OrderId) -> OrderRef {
+        match self.positions.get(&order_id) {
+            Some(&position) => OrderRef::Input(position),
+            None => OrderRef::External(order_id),
+        }
+    }
+}
+
+/// Compare each symbol's trade sequence separately and keep only its first divergence;
+/// everything after it in that symbol is fallout
+pub(crate) fn first_divergences(expected: Vec<NormalizedTrade>, actual: Vec<NormalizedTrade>) -> Vec<Divergence> {
+    let mut by_symbol: BTreeMap<String, (Vec<NormalizedTrade>, Vec<NormalizedTrade>)> = BTreeMap::new();
+    for trade in expected {
+        by_symbol.entry(trade.symbol.clone()).or_default().0.push(trade);
+    }
+    for trade in actual {
+        by_symbol.entry(trade.symbol.clone()).or_default().1.push(trade);
+    }
+
+    by_symbol
+        .into_iter()
+        .filter_map(|(symbol, (expected, actual))| {
+            let mut expected = expected.into_iter();
+            let mut actual = actual.into_iter();
+            let mut index = 0;
+            loop {
+                let divergence = match (expected.next(), actual.next()) {
+                    (None, None) => return None,
+                    (Some(expected), Some(actual)) if expected == actual => {
+                        index += 1;
+                        continue;
+                    }
+                    (Some(expected), Some(actual)) => Divergence::Mismatch { symbol, index, expected, actual },
+                    (Some(expected), None) => Divergence::Missing { symbol, index, expected },
+                    (None, Some(actual)) => Divergence::Unexpected { symbol, index, actual },
+                };
+                return Some(divergence);
+            }
+        })
+        .collect()
+}
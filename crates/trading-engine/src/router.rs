@@ -0,0 +1,213 @@
+use order_book::{MatchResult, Order, OrderBook, OrderId, OrderType, Price, Quantity, Side, Trade};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::debug;
+
+/// A book the router can send child orders to
+#[derive(Clone)]
+pub struct Venue {
+    pub name: String,
+    pub book: Arc<OrderBook>,
+}
+
+/// Quantity planned for one venue, with the worst price the child order may trade at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChildAllocation {
+    pub venue: String,
+    pub quantity: Quantity,
+    pub limit_price: Price,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildFill {
+    pub venue: String,
+    pub order_id: OrderId,
+    pub requested_quantity: Quantity,
+    pub filled_quantity: Quantity,
+    pub trades: Vec<Trade>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingResult {
+    pub parent_id: OrderId,
+    pub children: Vec<ChildFill>,
+    pub filled_quantity: Quantity,
+    /// Parent quantity no venue could fill; the router never rests it
+    pub remaining_quantity: Quantity,
+    pub average_price: Option<Price>,
+}
+
+/// Splits a parent order across venue books carrying the same instrument. Allocation is
+/// best-price-first over every venue's estimated fill, ties going to the earlier venue.
+#[derive(Default)]
+pub struct SmartOrderRouter {
+    venues: Vec<Venue>,
+}
+
+impl SmartOrderRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_venue(&mut self, name: impl Into<String>, book: Arc<OrderBook>) {
+        self.venues.push(Venue { name: name.into(), book });
+    }
+
+    #[inline]
+    pub fn venues(&self) -> &[Venue] {
+        &self.venues
+    }
+
+    /// Per-venue split of `parent` from the venues' current books, in routing order
+    pub fn plan(&self, parent: &Order) -> Vec<ChildAllocation> {
+        let limit_price = (parent.order_type != OrderType::Market).then_some(parent.price);
+
+        // Every reachable level on every venue, tagged with the venue index
+        let mut levels: Vec<(Price, Quantity, usize)> = self.venues
+            .iter()
+            .enumerate()
+            .flat_map(|(index, venue)| {
+                venue.book
+                    .estimate_fill(parent.side, parent.remaining_quantity(), limit_price)
+                    .levels
+                    .into_iter()
+                    .map(move |(price, quantity)| (price, quantity, index))
+            })
+            .collect();
+        levels.sort_by(|a, b| match parent.side {
+            Side::Buy => a.0.cmp(&b.0).then(a.2.cmp(&b.2)),
+            Side::Sell => b.0.cmp(&a.0).then(a.2.cmp(&b.2)),
+        });
+
+        let mut allocations: Vec<ChildAllocation> = Vec::new();
+        let mut remaining = parent.remaining_quantity();
+        for (price, available, index) in levels {
+            if remaining == Quantity::ZERO {
+                break;
+            }
+            let quantity = remaining.min(available);
+            remaining -= quantity;
+
+            let venue = &self.venues[index].name;
+            match allocations.iter_mut().find(|allocation| &allocation.venue == venue) {
+                Some(allocation) => {
+                    allocation.quantity += quantity;
+                    allocation.limit_price = price;
+                }
+                None => allocations.push(ChildAllocation {
+                    venue: venue.clone(),
+                    quantity,
+                    limit_price: price,
+                }),
+            }
+        }
+
+        allocations
+    }
+
+    /// Plan and send child orders. Child orders are immediate-or-cancel: whatever a venue
+    /// cannot fill on arrival is cancelled rather than left resting.
+    pub fn route(&self, parent: &Order) -> RoutingResult {
+        let mut children = Vec::new();
+        let mut notional = 0.0;
+        let mut filled_quantity = Quantity::ZERO;
+
+        for allocation in self.plan(parent) {
+            let Some(venue) = self.venues.iter().find(|venue| venue.name == allocation.venue) else {
+                continue;
+            };
+
+            let child = Order::new(
+                venue.book.symbol().to_string(),
+                parent.side,
+                OrderType::Limit,
+                allocation.limit_price,
+                allocation.quantity,
+                parent.client_id,
+            );
+            let child_id = child.id;
+
+            let trades = match venue.book.add_order(child) {
+                MatchResult::NoMatch | MatchResult::Rejected { .. } => Vec::new(),
+                MatchResult::PartialMatch { trades, .. } | MatchResult::FullMatch { trades, .. } => trades,
+            };
+            venue.book.cancel_order(child_id);
+
+            let child_filled = trades.iter().fold(Quantity::ZERO, |total, trade| total + trade.quantity);
+            notional += trades.iter().map(|trade| trade.price.to_f64() * trade.quantity.to_f64()).sum::<f64>();
+            filled_quantity += child_filled;
+            debug!("Routed {} of parent {} to {}, filled {}", allocation.quantity, parent.id, venue.name, child_filled);
+
+            children.push(ChildFill {
+                venue: venue.name.clone(),
+                order_id: child_id,
+                requested_quantity: allocation.quantity,
+                filled_quantity: child_filled,
+                trades,
+            });
+        }
+
+        let parent_quantity = parent.remaining_quantity();
+        RoutingResult {
+            parent_id: parent.id,
+            children,
+            filled_quantity,
+            remaining_quantity: parent_quantity - filled_quantity.min(parent_quantity),
+            average_price: (filled_quantity > Quantity::ZERO)
+                .then(|| Price::new(notional / filled_quantity.to_f64())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn order(side: Side, price: f64, quantity: f64) -> Order {
+        Order::new(
+            "BTCUSD".to_string(),
+            side,
+            OrderType::Limit,
+            Price::new(price),
+            Quantity::new(quantity),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_router_fills_cheaper_venue_first() {
+        let native = Arc::new(OrderBook::new("BTCUSD".to_string()));
+        native.add_order(order(Side::Sell, 50010.0, 2.0));
+        native.add_order(order(Side::Sell, 50030.0, 5.0));
+
+        let mirror = Arc::new(OrderBook::new("BTCUSD".to_string()));
+        mirror.add_order(order(Side::Sell, 50000.0, 1.0));
+        mirror.add_order(order(Side::Sell, 50020.0, 1.0));
+
+        let mut router = SmartOrderRouter::new();
+        router.add_venue("native", native.clone());
+        router.add_venue("okx-mirror", mirror.clone());
+
+        let parent = order(Side::Buy, 50025.0, 5.0);
+        let plan = router.plan(&parent);
+        assert_eq!(plan, vec![
+            ChildAllocation { venue: "okx-mirror".to_string(), quantity: Quantity::new(2.0), limit_price: Price::new(50020.0) },
+            ChildAllocation { venue: "native".to_string(), quantity: Quantity::new(2.0), limit_price: Price::new(50010.0) },
+        ]);
+
+        let result = router.route(&parent);
+        assert_eq!(result.children[0].venue, "okx-mirror");
+        assert_eq!(result.children[0].trades[0].price, Price::new(50000.0));
+        assert_eq!(result.filled_quantity, Quantity::new(4.0));
+        // Nothing cheaper than 50025 is left, and the unfilled unit was not rested anywhere
+        assert_eq!(result.remaining_quantity, Quantity::new(1.0));
+        assert_eq!(mirror.best_ask(), None);
+        assert_eq!(native.best_ask(), Some(Price::new(50030.0)));
+        assert_eq!(native.best_bid(), None);
+        assert_eq!(mirror.best_bid(), None);
+
+        let average = result.average_price.unwrap().to_f64();
+        assert!((average - 50010.0).abs() < 0.05);
+    }
+}
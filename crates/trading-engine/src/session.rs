@@ -0,0 +1,74 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a symbol is in its trading day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionPhase {
+    /// Orders are rejected
+    Closed,
+    /// Orders are accepted and queued for the opening, never matched
+    PreOpen,
+    /// Continuous matching
+    Open,
+}
+
+/// Daily UTC trading window for a symbol. A window whose close is earlier than its open
+/// runs across midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSchedule {
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+    /// Start of the pre-open auction window that runs until `open`
+    #[serde(default)]
+    pub pre_open: Option<NaiveTime>,
+    /// Time before `close` at which open positions are flattened and new opening orders blocked
+    #[serde(default)]
+    pub flatten_at: Option<NaiveTime>,
+}
+
+impl SessionSchedule {
+    pub fn new(open: NaiveTime, close: NaiveTime) -> Self {
+        Self {
+            open,
+            close,
+            pre_open: None,
+            flatten_at: None,
+        }
+    }
+
+    pub fn with_pre_open(mut self, pre_open: NaiveTime) -> Self {
+        self.pre_open = Some(pre_open);
+        self
+    }
+
+    pub fn with_flatten_at(mut self, flatten_at: NaiveTime) -> Self {
+        self.flatten_at = Some(flatten_at);
+        self
+    }
+
+    /// Whether `time` falls between the flatten time and the close
+    pub fn in_flatten_window(&self, time: DateTime<Utc>) -> bool {
+        self.flatten_at.is_some_and(|flatten_at| within(time.time(), flatten_at, self.close))
+    }
+
+    pub fn phase_at(&self, time: DateTime<Utc>) -> SessionPhase {
+        let time = time.time();
+        if within(time, self.open, self.close) {
+            SessionPhase::Open
+        } else if self.pre_open.is_some_and(|pre_open| within(time, pre_open, self.open)) {
+            SessionPhase::PreOpen
+        } else {
+            SessionPhase::Closed
+        }
+    }
+}
+
+/// `start <= time < end`, wrapping past midnight when `end < start`
+#[inline]
+fn within(time: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    }
+}
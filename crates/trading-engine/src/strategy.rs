@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use order_book::{MarketData, OrderType, Price, Quantity, Side, Trade};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An order a strategy wants placed; the engine fills in the id and the strategy's client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderIntent {
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+impl OrderIntent {
+    pub fn limit(symbol: impl Into<String>, side: Side, price: Price, quantity: Quantity) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            price,
+            quantity,
+        }
+    }
+
+    pub fn market(symbol: impl Into<String>, side: Side, quantity: Quantity) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Market,
+            price: Price::ZERO,
+            quantity,
+        }
+    }
+}
+
+/// Trading logic the engine drives. Each callback returns the orders the strategy wants
+/// placed, which the engine routes through `submit_order` under the strategy's client id;
+/// callbacks a strategy does not need can be left at their defaults.
+pub trait Strategy: Send {
+    fn name(&self) -> &str;
+
+    fn on_market_data(&mut self, _data: &MarketData) -> Vec<OrderIntent> {
+        Vec::new()
+    }
+
+    /// One of the strategy's orders traded on `side`
+    fn on_fill(&mut self, _trade: &Trade, _side: Side) -> Vec<OrderIntent> {
+        Vec::new()
+    }
+
+    fn on_timer(&mut self, _now: DateTime<Utc>) -> Vec<OrderIntent> {
+        Vec::new()
+    }
+}
+
+/// A strategy and the client its orders are placed for
+pub(crate) struct RegisteredStrategy {
+    pub(crate) client_id: Uuid,
+    pub(crate) strategy: Mutex<Box<dyn Strategy>>,
+    /// Fills since the strategy was last driven, delivered before its next callback
+    pub(crate) pending_fills: Mutex<Vec<(Trade, Side)>>,
+}
+
+impl RegisteredStrategy {
+    pub(crate) fn new(client_id: Uuid, strategy: Box<dyn Strategy>) -> Self {
+        Self {
+            client_id,
+            strategy: Mutex::new(strategy),
+            pending_fills: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue the sides of `trade` that belong to this strategy's client
+    pub(crate) fn queue_fill(&self, trade: &Trade) {
+        if trade.buyer_client_id == self.client_id {
+            self.pending_fills.lock().push((trade.clone(), Side::Buy));
+        }
+        if trade.seller_client_id == self.client_id {
+            self.pending_fills.lock().push((trade.clone(), Side::Sell));
+        }
+    }
+
+    /// Deliver queued fills, then run `callback`, collecting every intent returned
+    pub(crate) fn drive(&self, callback: impl FnOnce(&mut dyn Strategy) -> Vec<OrderIntent>) -> Vec<OrderIntent> {
+        let fills = std::mem::take(&mut *self.pending_fills.lock());
+        let mut strategy = self.strategy.lock();
+        let mut intents = Vec::new();
+        for (trade, side) in &fills {
+            intents.extend(strategy.on_fill(trade, *side));
+        }
+        intents.extend(callback(strategy.as_mut()));
+        intents
+    }
+}
@@ -0,0 +1,58 @@
+//! Persisting filled and cancelled orders that leave the books
+
+use order_book::{ArchiveSink, Order};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Appends archived orders to a file as one JSON object per line, for end-of-day
+/// reconciliation. A batch that fails part way is offered again in full, so readers
+/// should keep the last line per order id.
+pub struct JsonLinesArchive {
+    writer: BufWriter<File>,
+}
+
+impl JsonLinesArchive {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl ArchiveSink for JsonLinesArchive {
+    fn archive(&mut self, orders: &[Order]) -> io::Result<()> {
+        for order in orders {
+            serde_json::to_writer(&mut self.writer, order)?;
+            self.writer.write_all(b"\n")?;
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use order_book::{OrderType, Price, Quantity, Side};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_archived_orders_are_appended_one_per_line() {
+        let path = std::env::temp_dir().join(format!("archive-{}.jsonl", Uuid::new_v4()));
+        let order = |price| Order::new("BTCUSD".to_string(), Side::Buy, OrderType::Limit, Price::new(price), Quantity::new(1.0), Uuid::new_v4());
+        let orders = [order(100.0), order(101.0)];
+
+        JsonLinesArchive::open(&path).unwrap().archive(&orders[..1]).unwrap();
+        JsonLinesArchive::open(&path).unwrap().archive(&orders[1..]).unwrap();
+
+        let archived: Vec<Order> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(archived.iter().map(|order| order.id).collect::<Vec<_>>(), vec![orders[0].id, orders[1].id]);
+    }
+}
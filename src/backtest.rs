@@ -0,0 +1,392 @@
+//! Deterministic replay of recorded market data through the trading engine
+
+use crate::simulation::SimulationRng;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use latency_profiler::{LatencyMetrics, LatencyProfiler};
+use latency_profiler::profiler::MeasurementPoint;
+use market_data::Tick;
+use order_book::{Clock, MarketData, OrderId, OrderType, Price, Quantity, Side, Trade};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use trading_engine::engine::{EngineConfig, OrderResponse};
+use trading_engine::{OrderIntent, Strategy, TradingEngine};
+use uuid::Uuid;
+
+/// An order from the historical record, replayed as other participants' liquidity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalOrder {
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// One line of a replay file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayEvent {
+    /// Trade print shown to the strategy
+    Tick(Tick),
+    /// Order submitted to the engine
+    Order(HistoricalOrder),
+}
+
+impl ReplayEvent {
+    #[inline]
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            ReplayEvent::Tick(tick) => tick.timestamp,
+            ReplayEvent::Order(order) => order.timestamp,
+        }
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> &str {
+        match self {
+            ReplayEvent::Tick(tick) => &tick.symbol,
+            ReplayEvent::Order(order) => &order.symbol,
+        }
+    }
+}
+
+/// Read a replay file of one JSON `ReplayEvent` per line. Blank lines are skipped and
+/// events must be in time order.
+pub fn read_events(path: impl AsRef<Path>) -> Result<Vec<ReplayEvent>> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open replay file {}", path.display()))?;
+
+    let mut events: Vec<ReplayEvent> = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: ReplayEvent = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid replay event", path.display(), index + 1))?;
+        if let Some(previous) = events.last() {
+            if event.timestamp() < previous.timestamp() {
+                anyhow::bail!("{}:{}: event is earlier than the one before it", path.display(), index + 1);
+            }
+        }
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// Write `events` in the format `read_events` expects
+pub fn write_events(path: impl AsRef<Path>, events: &[ReplayEvent]) -> Result<()> {
+    let mut contents = String::new();
+    for event in events {
+        contents.push_str(&serde_json::to_string(event)?);
+        contents.push('\n');
+    }
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Strategy state the backtester keeps up to date for the report
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct StrategyPosition {
+    /// Signed quantity, long positive
+    quantity: f64,
+    cash: f64,
+}
+
+/// How fast a replay runs against the wall clock. Speed only changes pacing: every event and
+/// timer happens at the same simulated time, and so with the same outcome, at any speed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ReplaySpeed {
+    /// No waiting; the clock jumps straight to each event
+    #[default]
+    Unbounded,
+    /// One simulated second per wall second
+    Realtime,
+    /// This many simulated seconds per wall second
+    Factor(f64),
+}
+
+impl ReplaySpeed {
+    /// Wall time that `simulated` takes to replay; `None` when unpaced
+    pub fn wall_time(&self, simulated: chrono::Duration) -> Option<Duration> {
+        let simulated = simulated.to_std().unwrap_or_default();
+        match *self {
+            ReplaySpeed::Unbounded => None,
+            ReplaySpeed::Realtime => Some(simulated),
+            ReplaySpeed::Factor(factor) if factor > 0.0 => Some(simulated.div_f64(factor)),
+            ReplaySpeed::Factor(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    /// Seeds the run's client ids. Strategies that decide at random should seed their own
+    /// `SimulationRng` so they replay identically too.
+    pub seed: u64,
+    pub engine: EngineConfig,
+    #[serde(default)]
+    pub speed: ReplaySpeed,
+    /// Simulated time between strategy `on_timer` calls, counted from the first event;
+    /// 0 disables them
+    #[serde(default)]
+    pub timer_interval_ms: u64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            engine: EngineConfig {
+                enable_risk_checks: false,
+                enable_event_emission: false,
+                ..EngineConfig::default()
+            },
+            speed: ReplaySpeed::Unbounded,
+            timer_interval_ms: 0,
+        }
+    }
+}
+
+/// Outcome of a backtest. Everything except `order_latency` is a function of the input
+/// events, strategy and seed alone.
+#[derive(Debug, Clone)]
+pub struct BacktestReport {
+    pub events_replayed: usize,
+    pub strategy_orders: u64,
+    /// Fills involving a strategy order
+    pub strategy_trades: u64,
+    pub submitted_quantity: f64,
+    pub filled_quantity: f64,
+    pub position: f64,
+    pub cash: f64,
+    /// Cash plus the position marked at the last tick price
+    pub pnl: f64,
+    /// Wall-clock time the engine spent in `submit_order` for strategy orders
+    pub order_latency: LatencyMetrics,
+}
+
+impl BacktestReport {
+    /// Share of submitted strategy quantity that traded
+    pub fn fill_ratio(&self) -> f64 {
+        if self.submitted_quantity > 0.0 {
+            self.filled_quantity / self.submitted_quantity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Feeds a time-ordered event stream into a fresh `TradingEngine` on its own simulated clock
+pub struct Backtester {
+    config: BacktestConfig,
+}
+
+impl Backtester {
+    pub fn new(config: BacktestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn run_file(&self, path: impl AsRef<Path>, strategy: &mut impl Strategy) -> Result<BacktestReport> {
+        self.run(&read_events(path)?, strategy)
+    }
+
+    /// Replay `events`, stepping the run's clock to each event's timestamp. The clock belongs
+    /// to this run's engine alone, so runs on different threads do not interfere.
+    ///
+    /// `strategy` is driven as the live engine drives it: ticks arrive through `on_market_data`
+    /// with the book's touch and the print as its last trade, timers through `on_timer` and
+    /// every fill of one of its orders through `on_fill` as soon as the order that traded it
+    /// returns.
+    pub fn run(&self, events: &[ReplayEvent], strategy: &mut impl Strategy) -> Result<BacktestReport> {
+        let clock = Clock::simulated(events.first().map_or_else(Utc::now, ReplayEvent::timestamp));
+        let engine = TradingEngine::with_clock(self.config.engine.clone(), clock.clone());
+        let profiler = LatencyProfiler::new();
+        let mut rng = SimulationRng::seeded(self.config.seed);
+        let market_client = rng.client_id();
+        let strategy_client = rng.client_id();
+
+        let mut run = Run::default();
+        let pacer = Pacer::new(self.config.speed, events.first().map(ReplayEvent::timestamp));
+        let timer_interval = chrono::Duration::milliseconds(self.config.timer_interval_ms as i64);
+        let mut next_timer = events
+            .first()
+            .filter(|_| self.config.timer_interval_ms > 0)
+            .map(|first| first.timestamp() + timer_interval);
+
+        for event in events {
+            // Timers due up to the event fire first, each at its own simulated time
+            while let Some(at) = next_timer.filter(|at| *at <= event.timestamp()) {
+                pacer.wait_until(at);
+                clock.set(at);
+                let intents = strategy.on_timer(at);
+                run.submit(&engine, &profiler, strategy_client, strategy, intents)?;
+                next_timer = Some(at + timer_interval);
+            }
+
+            pacer.wait_until(event.timestamp());
+            clock.set(event.timestamp());
+            if engine.get_order_book(event.symbol()).is_none() {
+                engine.add_symbol(event.symbol().to_string())?;
+            }
+
+            match event {
+                ReplayEvent::Order(order) => {
+                    let order = engine.new_order(
+                        order.symbol.clone(),
+                        order.side,
+                        order.order_type,
+                        order.price,
+                        order.quantity,
+                        market_client,
+                    );
+                    let response = engine.submit_order(order)?;
+                    let intents = run.record_fills(&response, strategy);
+                    run.submit(&engine, &profiler, strategy_client, strategy, intents)?;
+                }
+                ReplayEvent::Tick(tick) => {
+                    run.last_price = tick.price.to_f64();
+                    let intents = strategy.on_market_data(&tick_data(&engine, tick));
+                    run.submit(&engine, &profiler, strategy_client, strategy, intents)?;
+                }
+            }
+        }
+
+        Ok(BacktestReport {
+            events_replayed: events.len(),
+            strategy_orders: run.strategy_orders,
+            strategy_trades: run.strategy_trades,
+            submitted_quantity: run.submitted_quantity,
+            filled_quantity: run.filled_quantity,
+            position: run.position.quantity,
+            cash: run.position.cash,
+            pnl: run.position.cash + run.position.quantity * run.last_price,
+            order_latency: profiler.get_metrics(MeasurementPoint::OrderMatched).unwrap_or_default(),
+        })
+    }
+}
+
+impl Default for Backtester {
+    fn default() -> Self {
+        Self::new(BacktestConfig::default())
+    }
+}
+
+#[derive(Default)]
+struct Run {
+    strategy_order_ids: HashSet<OrderId>,
+    strategy_orders: u64,
+    strategy_trades: u64,
+    submitted_quantity: f64,
+    filled_quantity: f64,
+    position: StrategyPosition,
+    last_price: f64,
+}
+
+impl Run {
+    /// Submit `intents` and any the strategy places in response to their fills
+    fn submit(
+        &mut self,
+        engine: &TradingEngine,
+        profiler: &LatencyProfiler,
+        strategy_client: Uuid,
+        strategy: &mut impl Strategy,
+        intents: Vec<OrderIntent>,
+    ) -> Result<()> {
+        let mut intents = VecDeque::from(intents);
+        while let Some(intent) = intents.pop_front() {
+            let order = engine.new_order(
+                intent.symbol,
+                intent.side,
+                intent.order_type,
+                intent.price,
+                intent.quantity,
+                strategy_client,
+            );
+            self.strategy_order_ids.insert(order.id);
+            self.strategy_orders += 1;
+            self.submitted_quantity += order.quantity.to_f64();
+
+            let started = Instant::now();
+            let response = engine.submit_order(order)?;
+            profiler.record_latency(MeasurementPoint::OrderMatched, started.elapsed());
+            intents.extend(self.record_fills(&response, strategy));
+        }
+        Ok(())
+    }
+
+    /// Book every fill in `response` where a strategy order was the buyer or the seller,
+    /// whether it took liquidity or rested and was hit later, and hand each to the strategy.
+    /// Returns the orders it places in response.
+    fn record_fills(&mut self, response: &OrderResponse, strategy: &mut impl Strategy) -> Vec<OrderIntent> {
+        let trades: &[Trade] = match response {
+            OrderResponse::PartiallyFilled { trades, .. } | OrderResponse::FullyFilled { trades, .. } => trades,
+            OrderResponse::Accepted { .. } | OrderResponse::Rejected { .. } => return Vec::new(),
+        };
+
+        let mut intents = Vec::new();
+        for trade in trades {
+            let quantity = trade.quantity.to_f64();
+            let notional = trade.notional_value();
+            if self.strategy_order_ids.contains(&trade.buyer_order_id) {
+                self.position.quantity += quantity;
+                self.position.cash -= notional;
+                self.strategy_trades += 1;
+                self.filled_quantity += quantity;
+                intents.extend(strategy.on_fill(trade, Side::Buy));
+            }
+            if self.strategy_order_ids.contains(&trade.seller_order_id) {
+                self.position.quantity -= quantity;
+                self.position.cash += notional;
+                self.strategy_trades += 1;
+                self.filled_quantity += quantity;
+                intents.extend(strategy.on_fill(trade, Side::Sell));
+            }
+        }
+        intents
+    }
+}
+
+/// What a strategy sees for a trade print: the book's current touch with the print as the
+/// last trade
+fn tick_data(engine: &TradingEngine, tick: &Tick) -> MarketData {
+    let mut data = engine
+        .get_market_data(&tick.symbol)
+        .unwrap_or_else(|| MarketData::new(tick.symbol.clone()));
+    data.last_trade_price = Some(tick.price);
+    data.last_trade_quantity = Some(tick.quantity);
+    data.timestamp = tick.timestamp;
+    data
+}
+
+/// Holds each simulated instant back until its wall-clock due time. Due times are measured
+/// from the start of the run, so oversleeping on one step is made up on the next.
+struct Pacer {
+    speed: ReplaySpeed,
+    started: Instant,
+    origin: Option<DateTime<Utc>>,
+}
+
+impl Pacer {
+    fn new(speed: ReplaySpeed, origin: Option<DateTime<Utc>>) -> Self {
+        Self {
+            speed,
+            started: Instant::now(),
+            origin,
+        }
+    }
+
+    fn wait_until(&self, at: DateTime<Utc>) {
+        let Some(due) = self.origin.and_then(|origin| self.speed.wall_time(at - origin)) else {
+            return;
+        };
+        if let Some(remaining) = due.checked_sub(self.started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+}
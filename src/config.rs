@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingConfig {
@@ -9,6 +10,9 @@ pub struct TradingConfig {
     pub max_orders_per_second: u64,
     pub risk_limits: RiskLimits,
     pub latency_thresholds: LatencyThresholds,
+    /// Per-symbol risk manager limits, reloadable at runtime
+    #[serde(default)]
+    pub symbol_risk_limits: HashMap<String, SymbolRiskLimits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +22,28 @@ pub struct RiskLimits {
     pub daily_loss_limit: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolRiskLimits {
+    pub position_limit: f64,
+    pub daily_pnl_limit: f64,
+    pub order_size_limit: f64,
+    pub price_deviation_limit: f64,
+    pub notional_limit: f64,
+}
+
+impl SymbolRiskLimits {
+    pub fn to_risk_limits(&self, symbol: &str) -> risk_manager::RiskLimits {
+        risk_manager::RiskLimits::with_custom_limits(
+            symbol.to_string(),
+            self.position_limit,
+            self.daily_pnl_limit,
+            self.order_size_limit,
+            self.price_deviation_limit,
+            self.notional_limit,
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LatencyThresholds {
     pub order_processing_ns: u64,
@@ -40,6 +66,7 @@ impl Default for TradingConfig {
                 market_data_ns: 500,
                 trade_execution_ns: 2_000,
             },
+            symbol_risk_limits: HashMap::new(),
         }
     }
 }
@@ -51,6 +78,14 @@ impl TradingConfig {
         Ok(config)
     }
 
+    /// `symbol_risk_limits` in the form `RiskManager::reload_limits` takes
+    pub fn risk_limits_by_symbol(&self) -> HashMap<String, risk_manager::RiskLimits> {
+        self.symbol_risk_limits
+            .iter()
+            .map(|(symbol, limits)| (symbol.clone(), limits.to_risk_limits(symbol)))
+            .collect()
+    }
+
     pub fn save_to_file(&self, path: &str) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
         std::fs::write(path, content)?;
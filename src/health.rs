@@ -0,0 +1,116 @@
+//! System-wide health aggregation
+
+use chrono::{DateTime, Utc};
+use event_processor::{HealthStatus, Probe};
+use metrics::gauge;
+use serde::{Deserialize, Serialize};
+
+/// Event queue depth above which the event processor is reported as degraded
+pub const QUEUE_DEPTH_WARNING: usize = 5_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    pub detail: String,
+    /// Failing means the component should be restarted
+    pub liveness: Probe,
+    /// Failing means traffic should be held back, without restarting anything
+    pub readiness: Probe,
+}
+
+impl ComponentHealth {
+    /// A component without probes of its own is taken as live and ready
+    pub fn new(name: &str, status: HealthStatus, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail,
+            liveness: Probe::Pass,
+            readiness: Probe::Pass,
+        }
+    }
+
+    pub fn with_probes(mut self, liveness: Probe, readiness: Probe) -> Self {
+        self.liveness = liveness;
+        self.readiness = readiness;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    pub overall_status: HealthStatus,
+    pub engine_running: bool,
+    pub event_queue_depth: usize,
+    pub profiler_enabled: bool,
+    pub numa_net_allocated_bytes: usize,
+    pub components: Vec<ComponentHealth>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl SystemHealth {
+    /// Overall status is the worst status reported by any component
+    pub fn overall(components: &[ComponentHealth]) -> HealthStatus {
+        components
+            .iter()
+            .map(|c| c.status)
+            .max_by_key(|status| *status as u8)
+            .unwrap_or(HealthStatus::Healthy)
+    }
+
+    pub fn component(&self, name: &str) -> Option<&ComponentHealth> {
+        self.components.iter().find(|c| c.name == name)
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.overall_status == HealthStatus::Healthy
+    }
+
+    /// Live while every component is; an orchestrator restarts the process otherwise
+    pub fn is_live(&self) -> bool {
+        self.components.iter().all(|c| c.liveness.is_pass())
+    }
+
+    /// Ready once every component is; an orchestrator gates traffic on this
+    pub fn is_ready(&self) -> bool {
+        self.components.iter().all(|c| c.readiness.is_pass())
+    }
+
+    /// Publish the health view as gauges for the Prometheus exporter
+    pub fn record_metrics(&self) {
+        gauge!("system_health_status").set(self.overall_status as u8 as f64);
+        gauge!("engine_running").set(if self.engine_running { 1.0 } else { 0.0 });
+        gauge!("event_queue_depth").set(self.event_queue_depth as f64);
+        gauge!("profiler_enabled").set(if self.profiler_enabled { 1.0 } else { 0.0 });
+        gauge!("numa_net_allocated_bytes").set(self.numa_net_allocated_bytes as f64);
+        gauge!("system_live").set(if self.is_live() { 1.0 } else { 0.0 });
+        gauge!("system_ready").set(if self.is_ready() { 1.0 } else { 0.0 });
+
+        for component in &self.components {
+            gauge!("component_health_status", "component" => component.name.clone())
+                .set(component.status as u8 as f64);
+            gauge!("component_live", "component" => component.name.clone())
+                .set(if component.liveness.is_pass() { 1.0 } else { 0.0 });
+            gauge!("component_ready", "component" => component.name.clone())
+                .set(if component.readiness.is_pass() { 1.0 } else { 0.0 });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overall_is_worst_component() {
+        let components = vec![
+            ComponentHealth::new("a", HealthStatus::Healthy, String::new()),
+            ComponentHealth::new("b", HealthStatus::Warning, String::new()),
+            ComponentHealth::new("c", HealthStatus::Healthy, String::new()),
+        ];
+
+        assert_eq!(SystemHealth::overall(&components), HealthStatus::Warning);
+        assert_eq!(SystemHealth::overall(&[]), HealthStatus::Healthy);
+    }
+}
@@ -12,6 +12,10 @@ pub mod metrics;
 pub mod types;
 pub mod utils;
 pub mod numa;
+pub mod health;
+pub mod simulation;
+pub mod backtest;
+pub mod archive;
 
 pub use order_book;
 pub use event_processor;
@@ -4,25 +4,66 @@ use tracing::debug;
 use tokio::signal;
 use tokio::time::{interval, Duration};
 use std::sync::Arc;
-use uuid::Uuid;
 
 use trading_engine::TradingEngine;
-use order_book::{Order, OrderType, Side, Price, Quantity};
+use order_book::{ClockSource, Order, OrderType, Side, Price, Quantity};
 use event_processor::{Event, OrderEvent, TradeEvent, SystemEvent, HealthStatus};
 use risk_manager::RiskLimits;
+use market_data::{FailoverConfig, FailoverFeed, MarketDataFeed, OrderBookSnapshot};
 use latency_profiler::LatencyProfiler;
+use hft::archive::JsonLinesArchive;
+use hft::config::TradingConfig;
+use hft::health::{ComponentHealth, SystemHealth, QUEUE_DEPTH_WARNING};
+use hft::numa::{NumaAllocator, NumaTopology};
+use hft::simulation::SimulationRng;
 
 #[cfg(feature = "integrations")]
-use integrations::{IntegrationConfig, okx::{OkxIntegration, websocket::OkxWebSocketEvent}};
+use integrations::{IntegrationConfig, IntegrationCoordinator, okx::{OkxIntegration, websocket::OkxWebSocketEvent}};
 
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+/// Why the system is going down, which decides the cleanup and the process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShutdownReason {
+    /// Deliberate stop by an operator; resting orders are left for the restart
+    Operator,
+    /// A risk breaker tripped; nothing may stay live in the market
+    BreakerTrip,
+    /// Market data was lost for good, so resting quotes are priced blind
+    FeedLoss,
+}
+
+impl ShutdownReason {
+    /// Exit code for the supervisor: zero is a clean stop, anything else asks for attention
+    fn exit_code(self) -> i32 {
+        match self {
+            ShutdownReason::Operator => 0,
+            ShutdownReason::BreakerTrip => 2,
+            ShutdownReason::FeedLoss => 3,
+        }
+    }
+    
+    fn cancels_orders(self) -> bool {
+        !matches!(self, ShutdownReason::Operator)
+    }
+}
+
 struct HftSystem {
     trading_engine: Arc<TradingEngine>,
     profiler: Arc<LatencyProfiler>,
+    numa_allocator: Arc<NumaAllocator>,
+    /// OKX books as the primary, the engine's own books as the backup
+    market_feed: Arc<FailoverFeed>,
+    /// Realized loss for the day by any one client that trips the breaker
+    daily_loss_limit: f64,
+    rng: parking_lot::Mutex<SimulationRng>,
+    shutdown_reason: parking_lot::Mutex<Option<ShutdownReason>>,
     #[cfg(feature = "integrations")]
     okx_integration: Option<Arc<OkxIntegration>>,
+    /// Trades signals through OKX and tracks their orders from the `orders` channel
+    #[cfg(feature = "integrations")]
+    coordinator: Option<Arc<IntegrationCoordinator>>,
 }
 
 impl HftSystem {
@@ -32,27 +73,57 @@ impl HftSystem {
         let trading_engine = Arc::new(TradingEngine::new());
         let profiler = Arc::new(LatencyProfiler::new());
         
+        let topology = NumaTopology::detect().unwrap_or_else(|e| {
+            warn!("Failed to detect NUMA topology: {}, assuming a single node", e);
+            NumaTopology::single_node()
+        });
+        let numa_allocator = Arc::new(NumaAllocator::new(Arc::new(topology)));
+        
+        let rng = SimulationRng::from_env();
+        info!("Simulation seed: {} (set HFT_SEED to replay)", rng.seed());
+        
+        // Disconnected until the OKX WebSocket reports itself connected
+        let market_feed = Arc::new(FailoverFeed::new(
+            Arc::new(MarketDataFeed::new()),
+            Arc::new(MarketDataFeed::new()),
+            FailoverConfig::default(),
+        ).with_primary_connected(false));
+        
+        let daily_loss_limit = match std::env::var("HFT_CONFIG") {
+            Ok(path) => TradingConfig::load_from_file(&path)?.risk_limits.daily_loss_limit,
+            Err(_) => TradingConfig::default().risk_limits.daily_loss_limit,
+        };
+        
         #[cfg(feature = "integrations")]
-        let okx_integration = {
+        let (okx_integration, coordinator) = {
             match IntegrationConfig::from_env() {
                 Ok(config) => {
                     info!("Loading OKX integration with environment configuration");
-                    match OkxIntegration::new(config.okx).await {
+                    match OkxIntegration::new(config.okx.clone()).await {
                         Ok(integration) => {
                             info!("OKX integration initialized successfully");
-                            Some(Arc::new(integration))
+                            let okx = Arc::new(integration);
+                            let coordinator = match IntegrationCoordinator::with_exchange(Arc::new(config), okx.clone()).await {
+                                Ok(coordinator) => Some(Arc::new(coordinator)),
+                                Err(e) => {
+                                    warn!("Failed to initialize integration coordinator: {}", e);
+                                    warn!("Continuing with OKX market data only");
+                                    None
+                                }
+                            };
+                            (Some(okx), coordinator)
                         }
                         Err(e) => {
                             warn!("Failed to initialize OKX integration: {}", e);
                             warn!("Continuing without OKX integration");
-                            None
+                            (None, None)
                         }
                     }
                 }
                 Err(e) => {
                     warn!("Failed to load integration config: {}", e);
                     warn!("Continuing without OKX integration");
-                    None
+                    (None, None)
                 }
             }
         };
@@ -60,8 +131,15 @@ impl HftSystem {
         Ok(Self {
             trading_engine,
             profiler,
+            numa_allocator,
+            market_feed,
+            daily_loss_limit,
+            rng: parking_lot::Mutex::new(rng),
+            shutdown_reason: parking_lot::Mutex::new(None),
             #[cfg(feature = "integrations")]
             okx_integration,
+            #[cfg(feature = "integrations")]
+            coordinator,
         })
     }
     
@@ -77,7 +155,11 @@ impl HftSystem {
         #[cfg(feature = "integrations")]
         if let Some(okx) = &self.okx_integration {
             info!("Starting OKX integration...");
-            okx.start().await?;
+            // The coordinator starts OKX as its exchange
+            match &self.coordinator {
+                Some(coordinator) => coordinator.start().await?,
+                None => okx.start().await?,
+            }
             self.setup_okx_market_data().await?;
             info!("OKX integration started successfully");
         }
@@ -92,7 +174,10 @@ impl HftSystem {
         #[cfg(feature = "integrations")]
         if let Some(okx) = &self.okx_integration {
             info!("Stopping OKX integration...");
-            okx.stop().await?;
+            match &self.coordinator {
+                Some(coordinator) => coordinator.stop().await?,
+                None => okx.stop().await?,
+            }
         }
         
         self.trading_engine.stop().await?;
@@ -101,6 +186,47 @@ impl HftSystem {
         Ok(())
     }
     
+    /// Record `reason`, run its cleanup and stop. Only the first reason is kept, so a
+    /// breaker trip is not masked by the operator stop that follows it.
+    async fn shutdown_with(&self, reason: ShutdownReason) -> anyhow::Result<()> {
+        let reason = *self.shutdown_reason.lock().get_or_insert(reason);
+        warn!("Shutting down: {:?} (exit code {})", reason, reason.exit_code());
+        
+        if reason.cancels_orders() {
+            let cancelled = self.trading_engine.cancel_all();
+            info!("Cancelled {} live orders before shutdown", cancelled);
+        }
+        
+        self.stop().await
+    }
+    
+    /// Why the system must stop on its own, if it must: a client's realized loss for the day
+    /// has reached the breaker limit, or the OKX feed dropped and has not come back within a minute
+    fn shutdown_trigger(&self) -> Option<ShutdownReason> {
+        const FEED_LOSS_AFTER_SECS: i64 = 60;
+        
+        let daily_pnl = self.trading_engine.risk_manager().worst_daily_pnl();
+        if daily_pnl <= -self.daily_loss_limit {
+            error!("Daily loss {:.2} reached the breaker limit {:.2}", -daily_pnl, self.daily_loss_limit);
+            return Some(ShutdownReason::BreakerTrip);
+        }
+        
+        if let Some(down_since) = self.market_feed.primary_down_since() {
+            let down_for = order_book::clock::now() - down_since;
+            if down_for >= chrono::Duration::seconds(FEED_LOSS_AFTER_SECS) {
+                error!("Primary market data feed down for {}s", down_for.num_seconds());
+                return Some(ShutdownReason::FeedLoss);
+            }
+        }
+        
+        None
+    }
+    
+    /// Exit code for the recorded shutdown reason; zero until one is recorded
+    fn exit_code(&self) -> i32 {
+        self.shutdown_reason.lock().map_or(0, ShutdownReason::exit_code)
+    }
+    
     async fn setup_symbols(&self) -> anyhow::Result<()> {
         let symbols = vec!["BTCUSD", "ETHUSD", "SOLUSD", "ADAUSD"];
         
@@ -115,6 +241,11 @@ impl HftSystem {
     async fn setup_risk_limits(&self) -> anyhow::Result<()> {
         let risk_manager = self.trading_engine.risk_manager();
         
+        if let Ok(path) = std::env::var("HFT_CONFIG") {
+            self.reload_risk_limits(&path)?;
+            return Ok(());
+        }
+        
         let btc_limits = RiskLimits::with_custom_limits(
             "BTCUSD".to_string(),
             10.0,      // position limit
@@ -140,6 +271,35 @@ impl HftSystem {
         Ok(())
     }
     
+    /// Swap in the per-symbol risk limits from a `TradingConfig` file without restarting
+    fn reload_risk_limits(&self, path: &str) -> anyhow::Result<()> {
+        let config = TradingConfig::load_from_file(path)?;
+        self.trading_engine.risk_manager().reload_limits(config.risk_limits_by_symbol());
+        info!("Risk limits loaded from {} for {} symbols", path, config.symbol_risk_limits.len());
+        Ok(())
+    }
+    
+    /// Reload risk limits from `HFT_CONFIG` on every SIGHUP
+    #[cfg(unix)]
+    async fn risk_limit_reload_loop(&self) {
+        let Ok(path) = std::env::var("HFT_CONFIG") else {
+            return;
+        };
+        let mut hangups = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                warn!("Risk limit hot-reload unavailable: {}", e);
+                return;
+            }
+        };
+        
+        while hangups.recv().await.is_some() {
+            if let Err(e) = self.reload_risk_limits(&path) {
+                error!("Failed to reload risk limits from {}: {}; keeping current limits", path, e);
+            }
+        }
+    }
+    
     async fn setup_event_handlers(&self) -> anyhow::Result<()> {
         let event_processor = self.trading_engine.event_processor();
         let profiler = Arc::clone(&self.profiler);
@@ -212,40 +372,60 @@ impl HftSystem {
         Ok(())
     }
     
+    /// Build the demo's crossing pair and follow-up order from the seeded RNG
+    fn demo_orders(rng: &mut SimulationRng) -> Vec<Order> {
+        let maker_client = rng.client_id();
+        let taker_client = rng.client_id();
+        let maker_side = if rng.chance(0.5) { Side::Sell } else { Side::Buy };
+        let quantity = Quantity::new((rng.range(0.05, 0.2) * 100.0).round() / 100.0);
+        
+        vec![
+            Order::new(
+                "BTCUSD".to_string(),
+                maker_side,
+                OrderType::Limit,
+                Price::new(45000.0),
+                quantity,
+                maker_client,
+            ),
+            Order::new(
+                "BTCUSD".to_string(),
+                maker_side.opposite(),
+                OrderType::Limit,
+                Price::new(45000.0),
+                quantity,
+                taker_client,
+            ),
+            Order::new(
+                "DEMO".to_string(),
+                Side::Buy,
+                OrderType::Limit,
+                Price::new(1000.0),
+                Quantity::new(1.0),
+                taker_client,
+            ),
+        ]
+    }
+    
     async fn run_demo_trading(&self) -> anyhow::Result<()> {
         info!("Starting demo trading simulation...");
         
-        let client1 = Uuid::new_v4();
-        let client2 = Uuid::new_v4();
-        
-        let sell_order = Order::new(
-            "BTCUSD".to_string(),
-            Side::Sell,
-            OrderType::Limit,
-            Price::new(45000.0),
-            Quantity::new(0.1),
-            client1,
-        );
-        
-        let buy_order = Order::new(
-            "BTCUSD".to_string(),
-            Side::Buy,
-            OrderType::Limit,
-            Price::new(45000.0),
-            Quantity::new(0.1),
-            client2,
-        );
+        let mut orders = Self::demo_orders(&mut self.rng.lock()).into_iter();
+        let (maker_order, taker_order, demo_order) = match (orders.next(), orders.next(), orders.next()) {
+            (Some(maker), Some(taker), Some(demo)) => (maker, taker, demo),
+            _ => anyhow::bail!("Demo order plan is incomplete"),
+        };
         
         let id = self.profiler.start_measurement(latency_profiler::profiler::MeasurementPoint::OrderReceived);
         
-        let response1 = self.trading_engine.submit_order(sell_order)?;
-        info!("Sell order response: {:?}", response1);
+        let response1 = self.trading_engine.submit_order(maker_order)?;
+        info!("Maker order response: {:?}", response1);
         
-        let response2 = self.trading_engine.submit_order(buy_order)?;
+        let response2 = self.trading_engine.submit_order(taker_order)?;
         
         // End the latency measurement
         self.profiler.end_measurement(id);
-        info!("Buy order response: {:?}", response2);
+        info!("Taker order response: {:?}", response2);
         
         // Force a health check event for demonstration
         let health_event = Event::System(SystemEvent::SystemHealthCheck {
@@ -260,14 +440,6 @@ impl HftSystem {
         }
         
         // Force a manual order event for demonstration
-        let demo_order = Order::new(
-            "DEMO".to_string(),
-            Side::Buy,
-            OrderType::Limit,
-            Price::new(1000.0),
-            Quantity::new(1.0),
-            client2,
-        );
         let order_event = Event::Order(OrderEvent::AddOrder(demo_order));
         if let Err(e) = self.trading_engine.event_processor().send_event(order_event) {
             warn!("Failed to send demo order event: {}", e);
@@ -295,8 +467,9 @@ impl HftSystem {
         if let Some(okx) = &self.okx_integration {
             info!("Setting up OKX market data subscriptions...");
             
-            // Subscribe to market data for the symbols we're trading
-            let symbols = vec!["BTC-USDT", "ETH-USDT", "SOL-USDT", "ADA-USDT"];
+            // Subscribe to market data for every instrument in the symbol table
+            let mut symbols: Vec<_> = okx.symbols().venue_symbols().collect();
+            symbols.sort_unstable();
             
             for symbol in symbols {
                 // Subscribe to ticker data
@@ -337,6 +510,8 @@ impl HftSystem {
             let okx_clone = Arc::clone(okx);
             let trading_engine = Arc::clone(&self.trading_engine);
             let profiler = Arc::clone(&self.profiler);
+            let market_feed = Arc::clone(&self.market_feed);
+            let coordinator = self.coordinator.clone();
             
             // Start processing WebSocket events
             tokio::spawn(async move {
@@ -351,11 +526,16 @@ impl HftSystem {
                         match event {
                             OkxWebSocketEvent::MarketData(data) => {
                                 // Process market data and update our order book
-                                Self::process_okx_market_data(&trading_engine, &data).await;
+                                Self::process_okx_market_data(&trading_engine, okx_clone.symbols(), &market_feed, &data).await;
                             }
                             OkxWebSocketEvent::OrderUpdate(data) => {
-                                // Process order updates
-                                info!("Received order update: {:?}", data);
+                                // Fills complete tracked signal orders and record their outcomes
+                                debug!("Received order update: {:?}", data);
+                                if let Some(coordinator) = &coordinator {
+                                    for order in coordinator.handle_order_update(&data).await {
+                                        info!("Signal {} order {} filled", order.signal_id, order.order_id);
+                                    }
+                                }
                             }
                             OkxWebSocketEvent::PositionUpdate(data) => {
                                 // Process position updates
@@ -367,9 +547,11 @@ impl HftSystem {
                             }
                             OkxWebSocketEvent::Connected => {
                                 info!("OKX WebSocket connected");
+                                market_feed.set_primary_connected(true);
                             }
                             OkxWebSocketEvent::Disconnected => {
-                                warn!("OKX WebSocket disconnected");
+                                warn!("OKX WebSocket disconnected, market data falls back to the local books");
+                                market_feed.set_primary_connected(false);
                             }
                             OkxWebSocketEvent::Error(error) => {
                                 error!("OKX WebSocket error: {}", error);
@@ -384,28 +566,38 @@ impl HftSystem {
     }
     
     #[cfg(feature = "integrations")]
-    async fn process_okx_market_data(_trading_engine: &Arc<TradingEngine>, data: &serde_json::Value) {
+    async fn process_okx_market_data(
+        trading_engine: &Arc<TradingEngine>,
+        symbols: &integrations::okx::SymbolMapper,
+        market_feed: &FailoverFeed,
+        data: &serde_json::Value,
+    ) {
         // Process different types of market data
         if let Some(data_array) = data.as_array() {
             for item in data_array {
                 if let Some(inst_id) = item.get("instId").and_then(|v| v.as_str()) {
                     // Convert OKX symbol format to our internal format
-                    let symbol = inst_id.replace("-", "");
+                    let symbol = match symbols.to_internal(inst_id) {
+                        Ok(symbol) => symbol,
+                        Err(e) => {
+                            warn!("Dropping OKX market data: {}", e);
+                            continue;
+                        }
+                    };
                     
                     // Process ticker data
-                    if let Some(last_price) = item.get("last").and_then(|v| v.as_str()) {
-                        if let Ok(price) = last_price.parse::<f64>() {
-                            // Update market data in our system
-                            debug!("Updated {} price to {}", symbol, price);
-                            
-                            // You could emit events to your trading engine here
-                            // For example, trigger re-evaluation of trading strategies
-                        }
+                    let last_price = item.get("last").and_then(|v| v.as_str()).and_then(|v| v.parse::<f64>().ok());
+                    if let Some(price) = last_price {
+                        debug!("Updated {} price to {}", symbol, price);
                     }
                     
-                    // Process order book data
-                    if let (Some(_bids), Some(_asks)) = (item.get("bids"), item.get("asks")) {
-                        // Process bid/ask data and update order book
+                    // Process order book data; books5 pushes are full five-level snapshots
+                    if let (Some(bids), Some(asks)) = (item.get("bids"), item.get("asks")) {
+                        let sequence = item.get("seqId").and_then(|v| v.as_u64()).unwrap_or_default();
+                        let mut snapshot = OrderBookSnapshot::new(symbol.to_string(), sequence);
+                        snapshot.bids = Self::okx_levels(bids);
+                        snapshot.asks = Self::okx_levels(asks);
+                        market_feed.primary().publish_snapshot(snapshot);
                         debug!("Received order book update for {}", symbol);
                     }
                     
@@ -413,11 +605,59 @@ impl HftSystem {
                     if let Some(trade_id) = item.get("tradeId") {
                         debug!("Received trade data for {}: {:?}", symbol, trade_id);
                     }
+                    
+                    // Re-evaluate registered strategies against the feed's current view
+                    let mut market_data = order_book::MarketData::new(symbol.to_string());
+                    if let Some(snapshot) = market_feed.get_snapshot(&market_data.symbol) {
+                        if let Some(&(price, size)) = snapshot.bids.first() {
+                            market_data.best_bid = Some(price);
+                            market_data.bid_size = size;
+                        }
+                        if let Some(&(price, size)) = snapshot.asks.first() {
+                            market_data.best_ask = Some(price);
+                            market_data.ask_size = size;
+                        }
+                    }
+                    market_data.last_trade_price = last_price.map(Price::new);
+                    if let Err(e) = trading_engine.feed_market_data(&market_data) {
+                        warn!("Failed to place strategy orders for {}: {}", symbol, e);
+                    }
                 }
             }
         }
     }
     
+    /// Parse OKX `[price, size, ..]` string levels, skipping malformed ones
+    #[cfg(feature = "integrations")]
+    fn okx_levels(levels: &serde_json::Value) -> Vec<(Price, Quantity)> {
+        levels
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|level| {
+                let price = level.get(0)?.as_str()?.parse::<f64>().ok()?;
+                let size = level.get(1)?.as_str()?.parse::<f64>().ok()?;
+                Some((Price::new(price), Quantity::new(size)))
+            })
+            .collect()
+    }
+    
+    /// Publish the engine's books to the backup feed, which serves reads while OKX is stale
+    fn publish_local_books(&self) {
+        const BACKUP_DEPTH: usize = 5;
+        
+        for symbol in self.trading_engine.get_symbols() {
+            let Some(book) = self.trading_engine.get_order_book(&symbol) else {
+                continue;
+            };
+            let depth = book.depth(BACKUP_DEPTH);
+            let mut snapshot = OrderBookSnapshot::new(symbol, book.stats().sequence_number);
+            snapshot.bids = depth.bids;
+            snapshot.asks = depth.asks;
+            self.market_feed.backup().publish_snapshot(snapshot);
+        }
+    }
+    
     #[cfg(feature = "integrations")]
     #[allow(dead_code)]
     async fn execute_okx_trade(&self, symbol: &str, side: &str, size: &str, price: Option<&str>) -> anyhow::Result<()> {
@@ -429,7 +669,7 @@ impl HftSystem {
             use rust_decimal::Decimal;
             
             let signal = TradingSignal {
-                id: uuid::Uuid::new_v4(),
+                id: self.rng.lock().client_id(),
                 symbol: symbol.to_string(),
                 signal_type: match side {
                     "buy" => SignalType::Buy,
@@ -491,6 +731,77 @@ impl HftSystem {
         }
     }
     
+    async fn system_health(&self) -> SystemHealth {
+        let mut components = Vec::new();
+        
+        let engine_running = self.trading_engine.is_running();
+        components.push(
+            ComponentHealth::new(
+                "trading_engine",
+                if engine_running { HealthStatus::Healthy } else { HealthStatus::Down },
+                format!("running={}", engine_running),
+            )
+            .with_probes(self.trading_engine.liveness(), self.trading_engine.readiness()),
+        );
+        
+        let event_processor = self.trading_engine.event_processor();
+        let event_queue_depth = event_processor.queue_depth();
+        components.push(ComponentHealth::new(
+            "event_processor",
+            if !event_processor.is_running() {
+                HealthStatus::Down
+            } else if event_queue_depth > QUEUE_DEPTH_WARNING {
+                HealthStatus::Warning
+            } else {
+                HealthStatus::Healthy
+            },
+            format!("queue_depth={}", event_queue_depth),
+        ));
+        
+        let profiler_enabled = self.profiler.is_enabled();
+        let profiler_stats = self.profiler.get_performance_stats();
+        components.push(ComponentHealth::new(
+            "latency_profiler",
+            if profiler_enabled { HealthStatus::Healthy } else { HealthStatus::Warning },
+            format!("enabled={}, active_measurements={}", profiler_enabled, profiler_stats.active_measurements),
+        ));
+        
+        let numa_stats = self.numa_allocator.stats();
+        components.push(ComponentHealth::new(
+            "numa_pools",
+            HealthStatus::Healthy,
+            format!("nodes={}, net_allocated_bytes={}", numa_stats.node_stats.len(), numa_stats.net_allocated_bytes),
+        ));
+        
+        #[cfg(feature = "integrations")]
+        if let Some(okx) = &self.okx_integration {
+            use integrations::types::HealthStatus as IntegrationStatus;
+            use integrations::ExchangeAdapter;
+            
+            let (status, detail) = match okx.health_check().await {
+                Ok(IntegrationStatus::Healthy) => (HealthStatus::Healthy, "healthy".to_string()),
+                Ok(IntegrationStatus::Degraded) => (HealthStatus::Warning, "degraded".to_string()),
+                Ok(IntegrationStatus::Unhealthy) => (HealthStatus::Critical, "unhealthy".to_string()),
+                Ok(IntegrationStatus::Unknown) => (HealthStatus::Warning, "unknown".to_string()),
+                Err(e) => (HealthStatus::Critical, e.to_string()),
+            };
+            components.push(
+                ComponentHealth::new("okx_integration", status, detail)
+                    .with_probes(okx.liveness(), okx.readiness().await),
+            );
+        }
+        
+        SystemHealth {
+            overall_status: SystemHealth::overall(&components),
+            engine_running,
+            event_queue_depth,
+            profiler_enabled,
+            numa_net_allocated_bytes: numa_stats.net_allocated_bytes,
+            components,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+    
     async fn health_check_loop(&self) {
         let mut interval = interval(Duration::from_secs(30));
         let event_processor = self.trading_engine.event_processor();
@@ -498,14 +809,13 @@ impl HftSystem {
         loop {
             interval.tick().await;
             
+            let health = self.system_health().await;
+            health.record_metrics();
+            
             let health_event = Event::System(SystemEvent::SystemHealthCheck {
-                component: "trading_engine".to_string(),
-                status: if self.trading_engine.is_running() {
-                    HealthStatus::Healthy
-                } else {
-                    HealthStatus::Down
-                },
-                timestamp: chrono::Utc::now(),
+                component: "hft_system".to_string(),
+                status: health.overall_status,
+                timestamp: health.timestamp,
             });
             
             if let Err(e) = event_processor.send_event(health_event) {
@@ -513,6 +823,64 @@ impl HftSystem {
             }
         }
     }
+    
+    /// Drive the engine's periodic passes: refreshing the backup market data feed, strategy
+    /// timers, end-of-day flattening, maximum holding time exits, evicting idle books to the
+    /// cold store, pruning order stream watchers and, every minute, archiving terminal orders
+    /// to `HFT_ARCHIVE_PATH`. Returns once the breaker trips or the feed is lost.
+    async fn housekeeping_loop(&self) -> ShutdownReason {
+        const ARCHIVE_EVERY_TICKS: u64 = 60;
+        
+        let archive_path = std::env::var("HFT_ARCHIVE_PATH").unwrap_or_else(|_| "terminal_orders.jsonl".to_string());
+        let mut archive = match JsonLinesArchive::open(&archive_path) {
+            Ok(archive) => Some(archive),
+            Err(e) => {
+                warn!("Failed to open order archive {}: {}, terminal orders will not be archived", archive_path, e);
+                None
+            }
+        };
+        let mut interval = interval(Duration::from_secs(1));
+        let mut ticks: u64 = 0;
+        
+        loop {
+            interval.tick().await;
+            ticks += 1;
+            
+            if let Some(reason) = self.shutdown_trigger() {
+                return reason;
+            }
+            
+            self.publish_local_books();
+            
+            if let Err(e) = self.trading_engine.run_strategy_timers() {
+                warn!("Failed to place strategy timer orders: {}", e);
+            }
+            
+            let flattened = self.trading_engine.run_end_of_day();
+            if !flattened.is_empty() {
+                info!("End of day: placed {} flattening orders", flattened.len());
+            }
+            
+            let exits = self.trading_engine.exit_expired_positions();
+            if !exits.is_empty() {
+                info!("Placed {} maximum holding time exits", exits.len());
+            }
+            
+            let evicted = self.trading_engine.evict_inactive_books();
+            if !evicted.is_empty() {
+                info!("Evicted idle books to the cold store: {:?}", evicted);
+            }
+            
+            self.trading_engine.prune_order_watchers();
+            
+            if let Some(archive) = archive.as_mut().filter(|_| ticks.is_multiple_of(ARCHIVE_EVERY_TICKS)) {
+                let archived = self.trading_engine.archive_terminal(archive);
+                if archived > 0 {
+                    info!("Archived {} terminal orders to {}", archived, archive_path);
+                }
+            }
+        }
+    }
 }
 
 #[tokio::main]
@@ -522,6 +890,14 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     info!("Starting HFT Trading System v{}", env!("CARGO_PKG_VERSION"));
+
+    let clock_source = ClockSource::from_env();
+    order_book::clock::install(clock_source);
+    info!("Clock source: {:?}", clock_source);
+    
+    if let Err(e) = metrics_exporter_prometheus::PrometheusBuilder::new().install() {
+        warn!("Failed to install Prometheus exporter: {}", e);
+    }
     
     let system = HftSystem::new().await?;
     
@@ -534,19 +910,155 @@ async fn main() -> anyhow::Result<()> {
         health_system.health_check_loop().await;
     });
     
+    let housekeeping_system = Arc::clone(&system_arc);
+    let housekeeping = tokio::spawn(async move {
+        housekeeping_system.housekeeping_loop().await
+    });
+    
+    #[cfg(unix)]
+    {
+        let reload_system = Arc::clone(&system_arc);
+        tokio::spawn(async move {
+            reload_system.risk_limit_reload_loop().await;
+        });
+    }
+    
     system_arc.run_demo_trading().await?;
     
     system_arc.print_performance_stats().await;
     
     info!("System running. Press Ctrl+C to stop...");
     
-    signal::ctrl_c().await?;
+    let reason = tokio::select! {
+        result = signal::ctrl_c() => {
+            result?;
+            ShutdownReason::Operator
+        }
+        reason = housekeeping => reason?,
+    };
     
-    system_arc.stop().await?;
+    system_arc.shutdown_with(reason).await?;
     
     system_arc.print_performance_stats().await;
     
     info!("HFT Trading System shutdown complete");
+    
+    let exit_code = system_arc.exit_code();
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_system_health_tracks_engine_state() {
+        let system = HftSystem::new().await.unwrap();
+        
+        system.trading_engine.start().await.unwrap();
+        let health = system.system_health().await;
+        assert!(health.engine_running);
+        assert_eq!(health.component("trading_engine").unwrap().status, HealthStatus::Healthy);
+        assert_eq!(health.component("event_processor").unwrap().status, HealthStatus::Healthy);
+        assert!(health.component("numa_pools").is_some());
+        
+        system.trading_engine.stop().await.unwrap();
+        let health = system.system_health().await;
+        assert!(!health.engine_running);
+        assert_eq!(health.component("trading_engine").unwrap().status, HealthStatus::Down);
+        assert_eq!(health.overall_status, HealthStatus::Down);
+    }
+    
+    #[tokio::test]
+    async fn test_system_is_live_but_not_ready_until_setup_completes() {
+        let system = HftSystem::new().await.unwrap();
+        
+        let health = system.system_health().await;
+        assert!(health.is_live());
+        assert!(!health.is_ready());
+        assert!(!health.component("trading_engine").unwrap().readiness.is_pass());
+        
+        system.trading_engine.start().await.unwrap();
+        system.setup_symbols().await.unwrap();
+        let health = system.system_health().await;
+        assert!(health.is_live());
+        assert_eq!(
+            health.component("trading_engine").unwrap().readiness,
+            event_processor::Probe::Fail("risk limits not loaded".to_string())
+        );
+        
+        system.setup_risk_limits().await.unwrap();
+        let health = system.system_health().await;
+        assert!(health.is_live());
+        assert!(health.component("trading_engine").unwrap().readiness.is_pass());
+    }
+    
+    #[tokio::test]
+    async fn test_only_breaker_trip_shutdown_cancels_orders() {
+        let resting_after = |reason| async move {
+            let system = HftSystem::new().await.unwrap();
+            system.trading_engine.start().await.unwrap();
+            system.trading_engine.add_symbol("BTCUSD".to_string()).unwrap();
+            system.trading_engine.submit_order(Order::new(
+                "BTCUSD".to_string(),
+                Side::Buy,
+                OrderType::Limit,
+                Price::new(50000.0),
+                Quantity::new(1.0),
+                uuid::Uuid::new_v4(),
+            )).unwrap();
+            
+            system.shutdown_with(reason).await.unwrap();
+            let book = system.trading_engine.get_order_book("BTCUSD").unwrap();
+            (book.best_bid().is_some(), system.exit_code())
+        };
+        
+        assert_eq!(resting_after(ShutdownReason::Operator).await, (true, 0));
+        assert_eq!(resting_after(ShutdownReason::BreakerTrip).await, (false, 2));
+    }
+    
+    #[tokio::test]
+    async fn test_daily_loss_past_the_limit_trips_the_breaker() {
+        let mut system = HftSystem::new().await.unwrap();
+        system.daily_loss_limit = 5.0;
+        system.trading_engine.start().await.unwrap();
+        system.trading_engine.add_symbol("BTCUSD".to_string()).unwrap();
+        assert_eq!(system.shutdown_trigger(), None);
+        
+        // The client buys at 100 and sells back at 90
+        let (client, counterparty) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let order = |side, price, client_id| Order::new("BTCUSD".to_string(), side, OrderType::Limit, Price::new(price), Quantity::new(1.0), client_id);
+        for (side, price, client_id) in [
+            (Side::Sell, 100.0, counterparty),
+            (Side::Buy, 100.0, client),
+            (Side::Buy, 90.0, counterparty),
+            (Side::Sell, 90.0, client),
+        ] {
+            system.trading_engine.submit_order(order(side, price, client_id)).unwrap();
+        }
+        
+        assert_eq!(system.trading_engine.risk_manager().get_daily_pnl(client), -10.0);
+        assert_eq!(system.shutdown_trigger(), Some(ShutdownReason::BreakerTrip));
+    }
+    
+    #[test]
+    fn test_seeded_demo_orders_are_reproducible() {
+        let plan = |seed| {
+            HftSystem::demo_orders(&mut SimulationRng::seeded(seed))
+                .into_iter()
+                .map(|o| (o.client_id, o.symbol, o.side, o.price, o.quantity))
+                .collect::<Vec<_>>()
+        };
+        
+        let first = plan(7);
+        assert_eq!(first.len(), 3);
+        assert_eq!(first, plan(7));
+        assert_eq!(first[0].3, first[1].3);
+        assert_eq!(first[0].2, first[1].2.opposite());
+        assert_ne!(first, plan(8));
+    }
+}
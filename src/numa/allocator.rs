@@ -71,6 +71,11 @@ impl NumaAllocator {
         }
     }
     
+    /// Get the topology the allocator places memory on
+    pub fn topology(&self) -> &Arc<NumaTopology> {
+        &self.topology
+    }
+    
     /// Allocate memory on the current thread's NUMA node
     pub fn allocate(&self, layout: Layout) -> Result<NumaAllocation, NumaAllocError> {
         let numa_node = get_thread_numa_node().unwrap_or(self.default_node);
@@ -333,6 +338,9 @@ impl std::ops::DerefMut for NumaAllocation {
 unsafe impl Send for NumaAllocation {}
 unsafe impl Sync for NumaAllocation {}
 
+// Pooled blocks are owned exclusively by their node pool's queue
+unsafe impl Send for PooledBlock {}
+
 /// Statistics for NUMA allocator
 #[derive(Debug, Clone)]
 pub struct NumaAllocatorStats {
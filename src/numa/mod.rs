@@ -1,7 +1,9 @@
 pub mod topology;
 pub mod threading;
 pub mod allocator;
+pub mod placement;
 
 pub use topology::{NumaTopology, NumaNode, CpuInfo};
-pub use threading::{NumaAwareThreadPool, NumaWorker, WorkerConfig};
-pub use allocator::{NumaAllocator, NumaAllocation};
\ No newline at end of file
+pub use threading::{NumaAwareThreadPool, NumaWorker, WorkerConfig, IsolationPolicy, ThreadPriority, ThreadSchedule, SchedPolicy, WaitStrategy};
+pub use allocator::{NumaAllocator, NumaAllocation};
+pub use placement::{NumaBookPlacer, NumaOrderRouter};
\ No newline at end of file
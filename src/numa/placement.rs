@@ -0,0 +1,91 @@
+use super::threading::{NumaAwareThreadPool, WorkPriority};
+use super::topology::{CpuAffinity, NumaTopology};
+use order_book::Order;
+use std::sync::Arc;
+use trading_engine::engine::BookPlacer;
+use trading_engine::TradingEngine;
+
+/// Pins each placed symbol's matching loop to the CPUs of its NUMA node
+pub struct NumaBookPlacer {
+    topology: Arc<NumaTopology>,
+}
+
+impl NumaBookPlacer {
+    pub fn new(topology: Arc<NumaTopology>) -> Self {
+        Self { topology }
+    }
+}
+
+impl BookPlacer for NumaBookPlacer {
+    fn pin_matching_thread(&self, _symbol: &str, numa_node: usize) -> anyhow::Result<()> {
+        CpuAffinity::new(Arc::clone(&self.topology))
+            .pin_to_node(numa_node)
+            .map_err(|e| anyhow::anyhow!("Failed to pin matching thread to node {}: {}", numa_node, e))
+    }
+}
+
+/// Routes orders to a worker on the NUMA node their symbol's book is assigned to
+pub struct NumaOrderRouter {
+    engine: Arc<TradingEngine>,
+    pool: NumaAwareThreadPool<Order>,
+}
+
+impl NumaOrderRouter {
+    /// Create a router whose workers submit orders to `engine`
+    pub fn new(
+        engine: Arc<TradingEngine>,
+        topology: Arc<NumaTopology>,
+        num_workers: usize,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let worker_engine = Arc::clone(&engine);
+        let pool = NumaAwareThreadPool::new(topology, num_workers, move |worker_id, order: Order| {
+            if let Err(e) = worker_engine.submit_order(order) {
+                tracing::error!("Worker {} failed to submit order: {}", worker_id, e);
+            }
+        })?;
+
+        Ok(Self { engine, pool })
+    }
+
+    /// Submit an order to a worker on its book's node, or round-robin if the book has no placement
+    pub fn route(&self, order: Order, priority: WorkPriority) -> Result<(), Box<dyn std::error::Error>> {
+        match self.engine.symbol_node(&order.symbol) {
+            Some(numa_node) => self.pool.submit_to_node(order, priority, numa_node),
+            None => self.pool.submit(order, priority),
+        }
+    }
+
+    /// Get the number of workers
+    pub fn worker_count(&self) -> usize {
+        self.pool.worker_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_book_placed_on_node() {
+        let topology = Arc::new(NumaTopology::detect().unwrap());
+        let engine = Arc::new(TradingEngine::new());
+        engine.set_book_placer(Arc::new(NumaBookPlacer::new(topology.clone())));
+
+        let node = topology.num_nodes() - 1;
+        engine.add_symbol_on_node("BTCUSD".to_string(), node).unwrap();
+        engine.add_symbol("ETHUSD".to_string()).unwrap();
+
+        assert_eq!(engine.symbol_node("BTCUSD"), Some(node));
+        assert_eq!(engine.symbol_node("ETHUSD"), None);
+        assert_eq!(engine.symbols_on_node(node), vec!["BTCUSD".to_string()]);
+        assert!(engine.get_order_book("BTCUSD").is_some());
+
+        // The placed symbol's matching loop runs pinned to its node
+        engine.start_matching_loop("BTCUSD").unwrap();
+        assert!(engine.stop_matching_loop("BTCUSD"));
+
+        engine.remove_symbol("BTCUSD").unwrap();
+        assert!(engine.symbols_on_node(node).is_empty());
+    }
+}
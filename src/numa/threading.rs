@@ -14,8 +14,44 @@ pub struct WorkerConfig {
     pub cpu_affinity: Option<usize>,
     pub stack_size: Option<usize>,
     pub priority: ThreadPriority,
+    pub wait_strategy: WaitStrategy,
+    pub isolation: IsolationPolicy,
 }
 
+/// What happens when a worker is pinned to a CPU the OS scheduler still uses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IsolationPolicy {
+    /// Pin without checking
+    Ignore,
+    /// Log a warning and start the worker anyway
+    #[default]
+    Warn,
+    /// Refuse to start the worker
+    Require,
+}
+
+/// How an idle worker waits for its next work item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitStrategy {
+    /// Poll the queue continuously: lowest wakeup latency, one core kept fully busy
+    BusySpin,
+    /// Poll for `spin` after the queue runs dry, then block on the channel
+    SpinThenPark { spin: Duration },
+    /// Block on the channel as soon as the queue is empty
+    Block,
+}
+
+impl Default for WaitStrategy {
+    fn default() -> Self {
+        WaitStrategy::SpinThenPark {
+            spin: Duration::from_micros(50),
+        }
+    }
+}
+
+/// Longest a parked worker blocks before rechecking the shutdown flag
+const PARK_TIMEOUT: Duration = Duration::from_millis(1);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ThreadPriority {
     Low,
@@ -24,6 +60,59 @@ pub enum ThreadPriority {
     RealTime,
 }
 
+/// Kernel scheduling policy applied to a worker thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedPolicy {
+    /// Time-shared `SCHED_OTHER`, ordered by nice value
+    Other,
+    /// Real-time `SCHED_RR`
+    RoundRobin,
+    /// Real-time `SCHED_FIFO`
+    Fifo,
+}
+
+/// Policy, real-time priority and nice value of a thread. `priority` is only meaningful for
+/// the real-time policies and `nice` only for `SchedPolicy::Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadSchedule {
+    pub policy: SchedPolicy,
+    pub priority: i32,
+    pub nice: i32,
+}
+
+impl ThreadSchedule {
+    #[inline]
+    pub const fn time_shared(nice: i32) -> Self {
+        Self { policy: SchedPolicy::Other, priority: 0, nice }
+    }
+
+    #[inline]
+    pub const fn real_time(policy: SchedPolicy, priority: i32) -> Self {
+        Self { policy, priority, nice: 0 }
+    }
+}
+
+impl ThreadPriority {
+    /// Scheduling requested for this level
+    pub const fn schedule(self) -> ThreadSchedule {
+        match self {
+            ThreadPriority::Low => ThreadSchedule::time_shared(10),
+            ThreadPriority::Normal => ThreadSchedule::time_shared(0),
+            ThreadPriority::High => ThreadSchedule::real_time(SchedPolicy::RoundRobin, 10),
+            ThreadPriority::RealTime => ThreadSchedule::real_time(SchedPolicy::Fifo, 80),
+        }
+    }
+
+    /// Time-shared scheduling used when a real-time policy is refused (no `CAP_SYS_NICE`)
+    pub const fn fallback_schedule(self) -> ThreadSchedule {
+        match self {
+            ThreadPriority::High => ThreadSchedule::time_shared(-5),
+            ThreadPriority::RealTime => ThreadSchedule::time_shared(-10),
+            level => level.schedule(),
+        }
+    }
+}
+
 impl Default for WorkerConfig {
     fn default() -> Self {
         Self {
@@ -32,6 +121,8 @@ impl Default for WorkerConfig {
             cpu_affinity: None,
             stack_size: Some(8 * 1024 * 1024), // 8MB stack
             priority: ThreadPriority::Normal,
+            wait_strategy: WaitStrategy::default(),
+            isolation: IsolationPolicy::default(),
         }
     }
 }
@@ -50,8 +141,8 @@ pub struct NumaWorker<T> {
     #[allow(dead_code)]
     id: usize,
     numa_node: usize,
-    #[allow(dead_code)]
     cpu_id: Option<usize>,
+    cpu_isolated: Option<bool>,
     handle: Option<JoinHandle<()>>,
     work_sender: Sender<WorkItem<T>>,
     #[allow(dead_code)]
@@ -85,6 +176,33 @@ where
         num_workers: usize,
         worker_fn: F,
     ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: Fn(usize, T) + Send + Sync + Clone + 'static,
+    {
+        Self::with_wait_strategy(topology, num_workers, WaitStrategy::default(), worker_fn)
+    }
+    
+    /// Create a thread pool whose idle workers wait according to `wait_strategy`
+    pub fn with_wait_strategy<F>(
+        topology: Arc<NumaTopology>,
+        num_workers: usize,
+        wait_strategy: WaitStrategy,
+        worker_fn: F,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: Fn(usize, T) + Send + Sync + Clone + 'static,
+    {
+        Self::with_worker_options(topology, num_workers, wait_strategy, IsolationPolicy::default(), worker_fn)
+    }
+    
+    /// Create a thread pool that also checks each pinned CPU is isolated according to `isolation`
+    pub fn with_worker_options<F>(
+        topology: Arc<NumaTopology>,
+        num_workers: usize,
+        wait_strategy: WaitStrategy,
+        isolation: IsolationPolicy,
+        worker_fn: F,
+    ) -> Result<Self, Box<dyn std::error::Error>>
     where
         F: Fn(usize, T) + Send + Sync + Clone + 'static,
     {
@@ -104,6 +222,8 @@ where
                 numa_node: Some(numa_node),
                 cpu_affinity: cpu_id,
                 priority: ThreadPriority::High,
+                wait_strategy,
+                isolation,
                 ..Default::default()
             };
             
@@ -195,6 +315,11 @@ where
         &self.topology
     }
     
+    /// The pool's workers, in submission order
+    pub fn workers(&self) -> &[NumaWorker<T>] {
+        &self.workers
+    }
+    
     /// Shutdown the thread pool gracefully
     pub fn shutdown(mut self, _timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
         self.shutdown.store(true, Ordering::Relaxed);
@@ -232,6 +357,7 @@ where
     where
         F: Fn(usize, T) + Send + 'static,
     {
+        let cpu_isolated = Self::check_isolation(id, cpu_id, config.isolation, &topology)?;
         let (work_sender, work_receiver) = unbounded();
         
         let mut thread_builder = thread::Builder::new()
@@ -262,6 +388,7 @@ where
             id,
             numa_node,
             cpu_id,
+            cpu_isolated,
             handle: Some(handle),
             work_sender,
             work_receiver,
@@ -269,6 +396,41 @@ where
         })
     }
     
+    /// CPU this worker is pinned to, if any
+    pub fn cpu_id(&self) -> Option<usize> {
+        self.cpu_id
+    }
+    
+    /// Whether the pinned CPU is isolated from the OS scheduler; `None` for unpinned workers
+    pub fn is_cpu_isolated(&self) -> Option<bool> {
+        self.cpu_isolated
+    }
+    
+    fn check_isolation(
+        worker_id: usize,
+        cpu_id: Option<usize>,
+        policy: IsolationPolicy,
+        topology: &NumaTopology,
+    ) -> Result<Option<bool>, Box<dyn std::error::Error>> {
+        let Some(cpu) = cpu_id else {
+            return Ok(None);
+        };
+        let isolated = topology.is_cpu_isolated(cpu);
+        if !isolated {
+            match policy {
+                IsolationPolicy::Ignore => {}
+                IsolationPolicy::Warn => {
+                    eprintln!("Warning: Worker {} is pinned to CPU {}, which is not isolated; expect scheduler jitter",
+                             worker_id, cpu);
+                }
+                IsolationPolicy::Require => {
+                    return Err(format!("Worker {} pinned to CPU {}, which is not isolated", worker_id, cpu).into());
+                }
+            }
+        }
+        Ok(Some(isolated))
+    }
+    
     fn worker_loop<F>(
         worker_id: usize,
         numa_node: usize,
@@ -290,22 +452,24 @@ where
         set_thread_numa_node(numa_node);
         
         let mut work_queue: VecDeque<WorkItem<T>> = VecDeque::new();
-        let mut last_yield = Instant::now();
         
         while !shutdown.load(Ordering::Relaxed) {
-            // Try to receive work items
-            match receiver.try_recv() {
+            // Only wait when there is nothing queued locally
+            let received = if work_queue.is_empty() {
+                Self::wait_for_work(&receiver, config.wait_strategy)
+            } else {
+                receiver.try_recv().map_err(|error| match error {
+                    crossbeam_channel::TryRecvError::Empty => crossbeam_channel::RecvTimeoutError::Timeout,
+                    crossbeam_channel::TryRecvError::Disconnected => crossbeam_channel::RecvTimeoutError::Disconnected,
+                })
+            };
+            
+            match received {
                 Ok(work_item) => {
                     work_queue.push_back(work_item);
                 }
-                Err(crossbeam_channel::TryRecvError::Empty) => {
-                    // No work available, check if we should yield
-                    if last_yield.elapsed() > Duration::from_micros(100) {
-                        thread::yield_now();
-                        last_yield = Instant::now();
-                    }
-                }
-                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
                     // Channel disconnected, shutdown
                     break;
                 }
@@ -328,6 +492,34 @@ where
         }
     }
     
+    /// Wait for the next work item; `Timeout` means the caller should recheck shutdown
+    fn wait_for_work(
+        receiver: &Receiver<WorkItem<T>>,
+        strategy: WaitStrategy,
+    ) -> Result<WorkItem<T>, crossbeam_channel::RecvTimeoutError> {
+        use crossbeam_channel::{RecvTimeoutError, TryRecvError};
+        
+        let spin = match strategy {
+            WaitStrategy::BusySpin => None,
+            WaitStrategy::SpinThenPark { spin } => Some(spin),
+            WaitStrategy::Block => return receiver.recv_timeout(PARK_TIMEOUT),
+        };
+        
+        let started = Instant::now();
+        loop {
+            match receiver.try_recv() {
+                Ok(work_item) => return Ok(work_item),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => match spin {
+                    Some(spin) if started.elapsed() >= spin => return receiver.recv_timeout(PARK_TIMEOUT),
+                    // Busy-spinning still returns periodically so shutdown is noticed
+                    None if started.elapsed() >= PARK_TIMEOUT => return Err(RecvTimeoutError::Timeout),
+                    _ => std::hint::spin_loop(),
+                },
+            }
+        }
+    }
+    
     fn setup_worker_thread(
         worker_id: usize,
         _numa_node: usize,
@@ -361,42 +553,85 @@ where
         Ok(())
     }
     
-    fn set_thread_priority(_priority: ThreadPriority) -> Result<(), Box<dyn std::error::Error>> {
-        #[cfg(target_os = "linux")]
-        {
-            let (policy, priority_value) = match _priority {
-                ThreadPriority::Low => (libc::SCHED_OTHER, 0),
-                ThreadPriority::Normal => (libc::SCHED_OTHER, 0),
-                ThreadPriority::High => (libc::SCHED_OTHER, 0),
-                ThreadPriority::RealTime => (libc::SCHED_FIFO, 1),
-            };
-            
-            let param = libc::sched_param {
-                sched_priority: priority_value,
-            };
-            
-            unsafe {
-                let result = libc::pthread_setschedparam(
-                    libc::pthread_self(),
-                    policy,
-                    &param,
-                );
-                
-                if result != 0 {
-                    eprintln!("Warning: Failed to set thread priority: {}", 
-                             std::io::Error::last_os_error());
-                }
+    /// Apply `priority` to the calling thread and return the scheduling it ended up with.
+    /// Without `CAP_SYS_NICE` the real-time levels fall back to a negative nice value, and
+    /// failing that to the default time-shared schedule.
+    #[cfg(target_os = "linux")]
+    fn set_thread_priority(priority: ThreadPriority) -> Result<ThreadSchedule, Box<dyn std::error::Error>> {
+        let requested = priority.schedule();
+        let error = match Self::apply_schedule(requested) {
+            Ok(()) => return Ok(requested),
+            Err(error) => error,
+        };
+
+        let fallback = priority.fallback_schedule();
+        if fallback != requested {
+            eprintln!("Warning: Failed to set {:?} thread priority ({}), falling back to nice {}",
+                     priority, error, fallback.nice);
+            if Self::apply_schedule(fallback).is_ok() {
+                return Ok(fallback);
             }
         }
-        
-        #[cfg(not(target_os = "linux"))]
-        {
-            // Thread priority setting not implemented for this platform
-            eprintln!("Thread priority setting not supported on this platform");
+
+        eprintln!("Warning: Failed to set {:?} thread priority, keeping the default schedule", priority);
+        Ok(Self::current_schedule())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn set_thread_priority(_priority: ThreadPriority) -> Result<ThreadSchedule, Box<dyn std::error::Error>> {
+        // Thread priority setting not implemented for this platform
+        eprintln!("Thread priority setting not supported on this platform");
+        Ok(ThreadSchedule::time_shared(0))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn apply_schedule(schedule: ThreadSchedule) -> std::io::Result<()> {
+        let policy = match schedule.policy {
+            SchedPolicy::Other => libc::SCHED_OTHER,
+            SchedPolicy::RoundRobin => libc::SCHED_RR,
+            SchedPolicy::Fifo => libc::SCHED_FIFO,
+        };
+        let param = libc::sched_param {
+            sched_priority: schedule.priority,
+        };
+
+        unsafe {
+            // pthread functions return the error code rather than setting errno
+            let result = libc::pthread_setschedparam(libc::pthread_self(), policy, &param);
+            if result != 0 {
+                return Err(std::io::Error::from_raw_os_error(result));
+            }
+
+            // Nice values are per thread on Linux, addressed by thread id
+            if schedule.policy == SchedPolicy::Other {
+                let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+                if libc::setpriority(libc::PRIO_PROCESS, tid, schedule.nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
         }
-        
+
         Ok(())
     }
+
+    /// Scheduling the kernel reports for the calling thread
+    #[cfg(target_os = "linux")]
+    fn current_schedule() -> ThreadSchedule {
+        let mut policy = 0;
+        let mut param = libc::sched_param { sched_priority: 0 };
+
+        unsafe {
+            libc::pthread_getschedparam(libc::pthread_self(), &mut policy, &mut param);
+            let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+            let nice = libc::getpriority(libc::PRIO_PROCESS, tid);
+
+            match policy {
+                libc::SCHED_FIFO => ThreadSchedule::real_time(SchedPolicy::Fifo, param.sched_priority),
+                libc::SCHED_RR => ThreadSchedule::real_time(SchedPolicy::RoundRobin, param.sched_priority),
+                _ => ThreadSchedule::time_shared(nice),
+            }
+        }
+    }
 }
 
 /// Specialized worker pool for HFT order processing
@@ -642,4 +877,120 @@ mod tests {
         assert_eq!(config.cpu_affinity, Some(1));
         assert_eq!(config.priority, ThreadPriority::High);
     }
-}
\ No newline at end of file
+    
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_thread_priority_levels_differ() {
+        // Each level runs on a fresh thread: raising a nice value cannot be undone unprivileged
+        let applied: Vec<(ThreadPriority, ThreadSchedule, ThreadSchedule)> = [
+            ThreadPriority::Low,
+            ThreadPriority::Normal,
+            ThreadPriority::High,
+            ThreadPriority::RealTime,
+        ]
+        .into_iter()
+        .map(|priority| {
+            thread::spawn(move || {
+                let applied = NumaWorker::<()>::set_thread_priority(priority).unwrap();
+                (priority, applied, NumaWorker::<()>::current_schedule())
+            })
+            .join()
+            .unwrap()
+        })
+        .collect();
+
+        for (priority, applied, effective) in &applied {
+            assert_eq!(applied, effective, "{:?} reported a schedule the kernel does not show", priority);
+            assert!(
+                *applied == priority.schedule() || applied.policy == SchedPolicy::Other,
+                "{:?} applied unexpected {:?}", priority, applied
+            );
+        }
+
+        // Lowering priority never needs privileges, so these two always differ
+        assert_eq!(applied[0].1, ThreadSchedule::time_shared(10));
+        assert_eq!(applied[1].1, ThreadSchedule::time_shared(0));
+
+        // With CAP_SYS_NICE every level is distinct
+        if applied[3].1 == ThreadPriority::RealTime.schedule() {
+            assert_eq!(applied[2].1, ThreadSchedule::real_time(SchedPolicy::RoundRobin, 10));
+            assert_eq!(applied[3].1, ThreadSchedule::real_time(SchedPolicy::Fifo, 80));
+        }
+    }
+    
+    fn median_wakeup_latency(strategy: WaitStrategy) -> Duration {
+        let topology = Arc::new(NumaTopology::detect().unwrap());
+        let (latency_tx, latency_rx) = unbounded();
+        
+        let pool = NumaAwareThreadPool::with_wait_strategy(topology, 1, strategy, move |_worker_id, sent: Instant| {
+            let _ = latency_tx.send(sent.elapsed());
+        }).unwrap();
+        
+        let mut latencies: Vec<Duration> = (0..41)
+            .map(|_| {
+                // Let the worker go idle (and park, where the strategy allows) before each item
+                thread::sleep(Duration::from_millis(3));
+                pool.submit(Instant::now(), WorkPriority::Normal).unwrap();
+                latency_rx.recv_timeout(Duration::from_secs(1)).unwrap()
+            })
+            .collect();
+        
+        pool.shutdown(Duration::from_secs(1)).unwrap();
+        latencies.sort();
+        latencies[latencies.len() / 2]
+    }
+    
+    #[test]
+    fn test_busy_spin_wakes_faster_than_block() {
+        // A spinning worker only wins when it does not share the submitter's core
+        if thread::available_parallelism().map_or(1, |cores| cores.get()) < 2 {
+            eprintln!("Skipping wakeup latency comparison on a single core");
+            return;
+        }
+        
+        let busy_spin = median_wakeup_latency(WaitStrategy::BusySpin);
+        let block = median_wakeup_latency(WaitStrategy::Block);
+        
+        assert!(
+            busy_spin * 2 < block,
+            "busy-spin median wakeup {:?} not well below blocking median {:?}", busy_spin, block
+        );
+    }
+    
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_worker_reports_cpu_isolation() {
+        let isolated = Arc::new(NumaTopology::single_node().with_isolated_cpus("0,2-3\n").unwrap());
+        assert_eq!(isolated.isolated_cpus(), &[0, 2, 3]);
+        let pool = NumaAwareThreadPool::with_worker_options(
+            isolated,
+            1,
+            WaitStrategy::Block,
+            IsolationPolicy::Require,
+            |_worker_id, _data: u32| {},
+        ).unwrap();
+        assert_eq!(pool.workers()[0].cpu_id(), Some(0));
+        assert_eq!(pool.workers()[0].is_cpu_isolated(), Some(true));
+        pool.shutdown(Duration::from_secs(1)).unwrap();
+        
+        let shared = Arc::new(NumaTopology::single_node().with_isolated_cpus("").unwrap());
+        let pool = NumaAwareThreadPool::with_worker_options(
+            shared.clone(),
+            1,
+            WaitStrategy::Block,
+            IsolationPolicy::Warn,
+            |_worker_id, _data: u32| {},
+        ).unwrap();
+        assert_eq!(pool.workers()[0].is_cpu_isolated(), Some(false));
+        pool.shutdown(Duration::from_secs(1)).unwrap();
+        
+        let refused = NumaAwareThreadPool::with_worker_options(
+            shared,
+            1,
+            WaitStrategy::Block,
+            IsolationPolicy::Require,
+            |_worker_id, _data: u32| {},
+        );
+        assert!(refused.is_err());
+    }
+}
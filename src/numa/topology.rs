@@ -10,6 +10,8 @@ pub struct NumaTopology {
     cpu_to_node: HashMap<usize, usize>,
     total_cpus: usize,
     online_cpus: Vec<usize>,
+    /// CPUs removed from general scheduling (`isolcpus`), where pinned workers see no OS noise
+    isolated_cpus: Vec<usize>,
 }
 
 /// Information about a single NUMA node
@@ -76,9 +78,16 @@ impl NumaTopology {
             cpu_to_node,
             total_cpus: num_cpus,
             online_cpus: cpus,
+            isolated_cpus: Self::get_isolated_cpus().unwrap_or_default(),
         }
     }
     
+    /// Replace the isolated CPU set with one given in kernel cpulist format, e.g. `"2-5,8"`
+    pub fn with_isolated_cpus(mut self, cpulist: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        self.isolated_cpus = Self::parse_cpu_list(cpulist.trim())?;
+        Ok(self)
+    }
+    
     /// Get the number of NUMA nodes
     pub fn num_nodes(&self) -> usize {
         self.nodes.len()
@@ -109,6 +118,16 @@ impl NumaTopology {
         self.total_cpus
     }
     
+    /// CPUs isolated from the general scheduler, sorted
+    pub fn isolated_cpus(&self) -> &[usize] {
+        &self.isolated_cpus
+    }
+    
+    /// Whether the scheduler keeps other tasks off `cpu_id`
+    pub fn is_cpu_isolated(&self, cpu_id: usize) -> bool {
+        self.isolated_cpus.binary_search(&cpu_id).is_ok()
+    }
+    
     /// Find the best NUMA node for allocation based on CPU affinity
     pub fn best_node_for_cpus(&self, cpus: &[usize]) -> Option<usize> {
         let mut node_scores: HashMap<usize, usize> = HashMap::new();
@@ -255,12 +274,14 @@ impl NumaTopology {
         // Get online CPUs
         let online_cpus = Self::get_online_cpus()?;
         let total_cpus = online_cpus.len();
+        let isolated_cpus = Self::get_isolated_cpus()?;
         
         Ok(Self {
             nodes,
             cpu_to_node,
             total_cpus,
             online_cpus,
+            isolated_cpus,
         })
     }
     
@@ -367,6 +388,21 @@ impl NumaTopology {
         }
     }
     
+    fn get_isolated_cpus() -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        #[cfg(target_os = "linux")]
+        {
+            // Empty when nothing is isolated; absent on kernels without isolcpus support
+            match fs::read_to_string("/sys/devices/system/cpu/isolated") {
+                Ok(isolated_str) => Self::parse_cpu_list(isolated_str.trim()),
+                Err(_) => Ok(Vec::new()),
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(Vec::new())
+        }
+    }
+    
     fn get_total_memory_mb() -> u64 {
         #[cfg(target_os = "linux")]
         {
@@ -0,0 +1,74 @@
+//! Seedable randomness for the demo and simulated flows
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
+
+/// Single source of client ids and random decisions, so a fixed seed reproduces a run
+#[derive(Debug, Clone)]
+pub struct SimulationRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl SimulationRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Pick a fresh seed; log `seed()` to replay the run later
+    pub fn from_entropy() -> Self {
+        Self::seeded(rand::thread_rng().gen())
+    }
+
+    /// Use `HFT_SEED` if set, otherwise a fresh seed
+    pub fn from_env() -> Self {
+        std::env::var("HFT_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .map(Self::seeded)
+            .unwrap_or_else(Self::from_entropy)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn client_id(&mut self) -> Uuid {
+        uuid::Builder::from_random_bytes(self.rng.gen()).into_uuid()
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.rng.gen_bool(probability.clamp(0.0, 1.0))
+    }
+
+    pub fn range(&mut self, low: f64, high: f64) -> f64 {
+        self.rng.gen_range(low..high)
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SimulationRng::seeded(42);
+        let mut b = SimulationRng::seeded(42);
+
+        for _ in 0..16 {
+            assert_eq!(a.client_id(), b.client_id());
+            assert_eq!(a.range(0.0, 1.0), b.range(0.0, 1.0));
+        }
+
+        let mut c = SimulationRng::seeded(43);
+        assert_ne!(SimulationRng::seeded(42).client_id(), c.client_id());
+    }
+}
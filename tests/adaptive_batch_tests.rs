@@ -0,0 +1,96 @@
+//! Adaptive batch sizing under a bursty arrival pattern
+
+use chrono::Utc;
+use event_processor::{AdaptiveBatching, BatchConfig, BatchProcessor, Event, SystemEvent};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+fn event() -> Event {
+    Event::System(SystemEvent::MarketOpen {
+        symbol: "BTCUSD".to_string(),
+        timestamp: Utc::now(),
+    })
+}
+
+#[test]
+fn test_batches_grow_during_burst_and_shrink_during_lull() {
+    let latency_target = Duration::from_millis(1);
+    let processor = BatchProcessor::new(BatchConfig {
+        max_batch_size: 500,
+        max_batch_delay: Duration::from_secs(60),
+        adaptive: Some(AdaptiveBatching {
+            latency_target,
+            window: 32,
+            gap_percentile: 90.0,
+        }),
+        ..BatchConfig::default()
+    });
+
+    // 2000 events 10us apart, then 50 events 5ms apart, then another burst
+    let start = Instant::now();
+    let mut arrivals = Vec::new();
+    let mut at = start;
+    for (count, gap) in [(2000, 10), (50, 5000), (2000, 10)] {
+        for _ in 0..count {
+            at += Duration::from_micros(gap);
+            arrivals.push(at);
+        }
+    }
+    let lull = arrivals[2000]..=arrivals[2049];
+
+    // Arrival time of each pending event; a flush drains the oldest `batch.len()` of them
+    let mut pending: VecDeque<Instant> = VecDeque::new();
+    let mut batches: Vec<(Instant, usize)> = Vec::new();
+    let mut record = |flushed_at: Instant, len: usize, pending: &mut VecDeque<Instant>| {
+        let oldest = pending[0];
+        assert!(
+            flushed_at - oldest <= latency_target,
+            "batch of {} flushed {:?} after its first event",
+            len,
+            flushed_at - oldest
+        );
+        pending.drain(..len);
+        batches.push((flushed_at, len));
+    };
+
+    for arrival in arrivals {
+        // A flush timer wakes exactly at the deadline when one falls before the next event
+        if let Some(deadline) = processor.flush_deadline().filter(|deadline| *deadline <= arrival) {
+            let batch = processor.flush_due(deadline).unwrap();
+            record(deadline, batch.len(), &mut pending);
+        }
+        pending.push_back(arrival);
+        if let Some(batch) = processor.add_event_at(event(), arrival) {
+            record(arrival, batch.len(), &mut pending);
+        }
+    }
+
+    let mean = |range: &dyn Fn(Instant) -> bool| {
+        let sizes: Vec<usize> = batches.iter().filter(|(at, _)| range(*at)).map(|(_, len)| *len).collect();
+        sizes.iter().sum::<usize>() as f64 / sizes.len() as f64
+    };
+    let first_burst = mean(&|at| at < *lull.start());
+    let during_lull = mean(&|at| at > *lull.start() + Duration::from_millis(50) && at <= *lull.end());
+    let second_burst = mean(&|at| at > *lull.end() + Duration::from_millis(5));
+
+    assert!(first_burst > 50.0, "burst batches averaged {}", first_burst);
+    assert!(second_burst > 50.0, "second burst batches averaged {}", second_burst);
+    assert_eq!(during_lull, 1.0);
+    assert!(processor.effective_batch_size() > 50);
+    assert!(batches.iter().all(|(_, len)| *len <= 500));
+}
+
+#[test]
+fn test_fixed_batching_is_unchanged_without_adaptive_config() {
+    let processor = BatchProcessor::new(BatchConfig {
+        max_batch_size: 3,
+        max_batch_delay: Duration::from_secs(60),
+        ..BatchConfig::default()
+    });
+
+    assert!(processor.add_event(event()).is_none());
+    assert!(processor.add_event(event()).is_none());
+    assert_eq!(processor.add_event(event()).unwrap().len(), 3);
+    assert_eq!(processor.effective_batch_size(), 3);
+    assert_eq!(processor.flush_deadline(), None);
+}
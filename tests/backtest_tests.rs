@@ -0,0 +1,191 @@
+//! Deterministic backtest replay over a small synthetic dataset
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hft::backtest::{read_events, write_events, BacktestConfig, Backtester, HistoricalOrder, ReplayEvent, ReplaySpeed};
+use market_data::Tick;
+use order_book::types::{MarketData, OrderType, Price, Quantity, Side, Trade};
+use trading_engine::{OrderIntent, Strategy};
+
+/// Buys one lot through the offer when flat, then offers it out at the last print
+#[derive(Default)]
+struct FlipStrategy {
+    position: f64,
+}
+
+impl Strategy for FlipStrategy {
+    fn name(&self) -> &str {
+        "flip"
+    }
+
+    fn on_market_data(&mut self, data: &MarketData) -> Vec<OrderIntent> {
+        let Some(last) = data.last_trade_price else {
+            return Vec::new();
+        };
+        let (side, price) = if self.position == 0.0 {
+            (Side::Buy, last + Price::new(0.5))
+        } else {
+            (Side::Sell, last)
+        };
+        vec![OrderIntent::limit(data.symbol.clone(), side, price, Quantity::new(1.0))]
+    }
+
+    fn on_fill(&mut self, trade: &Trade, side: Side) -> Vec<OrderIntent> {
+        match side {
+            Side::Buy => self.position += trade.quantity.to_f64(),
+            Side::Sell => self.position -= trade.quantity.to_f64(),
+        }
+        Vec::new()
+    }
+}
+
+fn at(start: DateTime<Utc>, seconds: i64) -> DateTime<Utc> {
+    start + Duration::seconds(seconds)
+}
+
+fn order(timestamp: DateTime<Utc>, side: Side, price: f64, quantity: f64) -> ReplayEvent {
+    ReplayEvent::Order(HistoricalOrder {
+        symbol: "BTCUSD".to_string(),
+        side,
+        order_type: OrderType::Limit,
+        price: Price::new(price),
+        quantity: Quantity::new(quantity),
+        timestamp,
+    })
+}
+
+fn tick(timestamp: DateTime<Utc>, price: f64) -> ReplayEvent {
+    ReplayEvent::Tick(Tick {
+        symbol: "BTCUSD".to_string(),
+        price: Price::new(price),
+        quantity: Quantity::new(1.0),
+        side: Side::Buy,
+        timestamp,
+    })
+}
+
+fn synthetic_events() -> Vec<ReplayEvent> {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 0).unwrap();
+    vec![
+        order(at(start, 0), Side::Sell, 101.0, 2.0),
+        order(at(start, 1), Side::Buy, 99.0, 2.0),
+        // Strategy lifts one lot at 101
+        tick(at(start, 2), 100.5),
+        // Market takes the other lot at 101; no strategy fill
+        order(at(start, 3), Side::Buy, 102.0, 1.0),
+        // Strategy offers at 103 and rests
+        tick(at(start, 4), 103.0),
+        // Market lifts the strategy's offer
+        order(at(start, 5), Side::Buy, 103.0, 1.0),
+        // Flat again: buys at 99.5 would not cross the 99 bid, so this one rests unfilled
+        tick(at(start, 6), 99.0),
+    ]
+}
+
+#[test]
+fn test_backtest_replays_synthetic_data_deterministically() {
+    let path = std::env::temp_dir().join(format!("hft-backtest-{}.jsonl", std::process::id()));
+    write_events(&path, &synthetic_events()).unwrap();
+    assert_eq!(read_events(&path).unwrap(), synthetic_events());
+
+    let backtester = Backtester::new(BacktestConfig {
+        seed: 7,
+        ..BacktestConfig::default()
+    });
+    let report = backtester.run_file(&path, &mut FlipStrategy::default()).unwrap();
+    let rerun = backtester.run_file(&path, &mut FlipStrategy::default()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(report.events_replayed, 7);
+    assert_eq!(report.strategy_orders, 3);
+    assert_eq!(report.strategy_trades, 2);
+    assert_eq!(report.position, 0.0);
+    // Bought at 101, sold at 103
+    assert_eq!(report.cash, 2.0);
+    assert_eq!(report.pnl, 2.0);
+    assert_eq!(report.fill_ratio(), 2.0 / 3.0);
+    assert_eq!(report.order_latency.count(), 3);
+
+    assert_eq!(
+        (rerun.strategy_orders, rerun.strategy_trades, rerun.position, rerun.cash, rerun.pnl),
+        (report.strategy_orders, report.strategy_trades, report.position, report.cash, report.pnl)
+    );
+}
+
+#[test]
+fn test_backtest_rejects_out_of_order_events() {
+    let mut events = synthetic_events();
+    events.swap(0, 1);
+    let path = std::env::temp_dir().join(format!("hft-backtest-unordered-{}.jsonl", std::process::id()));
+    write_events(&path, &events).unwrap();
+
+    let result = read_events(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+/// Rests one bid on the first tick and notes when the timer first sees its TTL run out
+struct ExpiringQuote {
+    ttl: Duration,
+    placed_at: Option<DateTime<Utc>>,
+    expired_at: Option<DateTime<Utc>>,
+}
+
+impl Strategy for ExpiringQuote {
+    fn name(&self) -> &str {
+        "expiring-quote"
+    }
+
+    fn on_market_data(&mut self, data: &MarketData) -> Vec<OrderIntent> {
+        if self.placed_at.is_some() {
+            return Vec::new();
+        }
+        self.placed_at = Some(data.timestamp);
+        vec![OrderIntent::limit(data.symbol.clone(), Side::Buy, Price::new(90.0), Quantity::new(1.0))]
+    }
+
+    fn on_timer(&mut self, now: DateTime<Utc>) -> Vec<OrderIntent> {
+        if self.expired_at.is_none() && self.placed_at.is_some_and(|placed_at| now >= placed_at + self.ttl) {
+            self.expired_at = Some(now);
+        }
+        Vec::new()
+    }
+}
+
+#[test]
+fn test_accelerated_replay_is_fast_and_fires_timers_at_simulated_times() {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 0).unwrap();
+    let events = vec![
+        order(at(start, 0), Side::Sell, 101.0, 1.0),
+        tick(at(start, 1), 100.0),
+        tick(at(start, 60), 100.0),
+    ];
+    let run = |speed: ReplaySpeed| {
+        let backtester = Backtester::new(BacktestConfig {
+            speed,
+            timer_interval_ms: 1_000,
+            ..BacktestConfig::default()
+        });
+        let mut strategy = ExpiringQuote {
+            ttl: Duration::seconds(5),
+            placed_at: None,
+            expired_at: None,
+        };
+        let started = std::time::Instant::now();
+        backtester.run(&events, &mut strategy).unwrap();
+        (strategy, started.elapsed())
+    };
+
+    // A minute of data at 10,000x is paced to about 6ms of wall time
+    let (paced, elapsed) = run(ReplaySpeed::Factor(10_000.0));
+    assert!(elapsed >= std::time::Duration::from_millis(6));
+    assert!(elapsed < std::time::Duration::from_secs(1));
+    assert_eq!(paced.expired_at, Some(at(start, 6)));
+
+    // Unpaced, the timer still fires at the same simulated time
+    let (unpaced, _) = run(ReplaySpeed::Unbounded);
+    assert_eq!(unpaced.expired_at, paced.expired_at);
+
+    assert_eq!(ReplaySpeed::Realtime.wall_time(Duration::seconds(2)), Some(std::time::Duration::from_secs(2)));
+    assert_eq!(ReplaySpeed::Factor(4.0).wall_time(Duration::seconds(2)), Some(std::time::Duration::from_millis(500)));
+    assert_eq!(ReplaySpeed::Unbounded.wall_time(Duration::seconds(2)), None);
+}
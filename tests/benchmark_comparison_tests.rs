@@ -184,7 +184,7 @@ fn test_order_matching_latency_benchmark() {
         let latency = start.elapsed();
         
         // Only record latencies for matched orders
-        if matches!(result, MatchResult::FullMatch { trades: _ } | MatchResult::PartialMatch { trades: _, remaining_quantity: _ }) {
+        if matches!(result, MatchResult::FullMatch { .. } | MatchResult::PartialMatch { .. }) {
             matching_latencies.push(latency.as_nanos() as u64);
         }
     }
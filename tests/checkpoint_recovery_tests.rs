@@ -0,0 +1,96 @@
+//! Recovering a book from its latest checkpoint plus the delta segment logged after it
+
+use market_data::{BookDeltaServer, CheckpointConfig, CheckpointManager, DeltaServerConfig};
+use order_book::types::{Order, OrderType, Price, Quantity, Side};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+fn order(side: Side, price: f64, quantity: f64) -> Order {
+    Order::new(
+        "BTCUSD".to_string(),
+        side,
+        OrderType::Limit,
+        Price::new(price),
+        Quantity::new(quantity),
+        Uuid::new_v4(),
+    )
+}
+
+fn checkpoint_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("hft_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    dir
+}
+
+fn server() -> BookDeltaServer {
+    let mut server = BookDeltaServer::new(DeltaServerConfig::default());
+    server.add_symbol("BTCUSD".to_string());
+    server
+}
+
+fn assert_recovered(server: &BookDeltaServer, dir: &Path) {
+    let recovered = CheckpointManager::recover(dir, "BTCUSD").unwrap();
+    let live = server.depth("BTCUSD", usize::MAX).unwrap();
+    assert_eq!(recovered.snapshot.bids, live.bids);
+    assert_eq!(recovered.snapshot.asks, live.asks);
+    assert_eq!(recovered.sequence, server.sequence("BTCUSD").unwrap());
+}
+
+#[test]
+fn test_recovery_replays_deltas_after_the_checkpoint() {
+    let dir = checkpoint_dir("checkpoint_replay");
+    let server = server();
+    server.add_order(order(Side::Buy, 49990.0, 1.0)).unwrap();
+    server.add_order(order(Side::Sell, 50010.0, 2.0)).unwrap();
+
+    let config = CheckpointConfig {
+        max_deltas: 1_000,
+        ..CheckpointConfig::default()
+    };
+    let mut checkpoints = CheckpointManager::start(&server, "BTCUSD", &dir, config).unwrap();
+    assert_eq!(checkpoints.sequence(), 2);
+
+    let resting = order(Side::Buy, 49980.0, 0.5);
+    let resting_id = resting.id;
+    server.add_order(resting).unwrap();
+    server.add_order(order(Side::Buy, 50010.0, 0.75)).unwrap();
+    server.add_order(order(Side::Sell, 50020.0, 1.0)).unwrap();
+    server.cancel_order("BTCUSD", resting_id).unwrap();
+    assert_eq!(checkpoints.poll(&server).unwrap(), 4);
+    assert_eq!(checkpoints.sequence(), server.sequence("BTCUSD").unwrap());
+
+    assert_recovered(&server, &dir);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_checkpoints_roll_over_and_prune_old_segments() {
+    let dir = checkpoint_dir("checkpoint_rollover");
+    let server = server();
+    let config = CheckpointConfig {
+        max_deltas: 3,
+        retain: 2,
+        ..CheckpointConfig::default()
+    };
+    let mut checkpoints = CheckpointManager::start(&server, "BTCUSD", &dir, config).unwrap();
+
+    for round in 0..4 {
+        for i in 0..3 {
+            server.add_order(order(Side::Buy, 49000.0 + (round * 10 + i) as f64, 1.0)).unwrap();
+        }
+        checkpoints.poll(&server).unwrap();
+    }
+    // A checkpoint is due after every third delta, so each round ends on a fresh one
+    assert_eq!(checkpoints.sequence(), 12);
+    server.add_order(order(Side::Sell, 49005.0, 2.5)).unwrap();
+    checkpoints.poll(&server).unwrap();
+
+    let snapshots = std::fs::read_dir(&dir)
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().path().extension().is_some_and(|ext| ext == "snapshot"))
+        .count();
+    assert_eq!(snapshots, 2);
+
+    assert_recovered(&server, &dir);
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,71 @@
+//! Cold books: idle symbols are evicted to the cold store and rebuilt on their next order
+
+use chrono::Utc;
+use order_book::{Clock, Order, OrderStatus, OrderType, Price, Quantity, Side};
+use std::time::Duration;
+use trading_engine::engine::EngineConfig;
+use trading_engine::TradingEngine;
+use uuid::Uuid;
+
+fn limit(symbol: &str, side: Side, price: f64, quantity: f64) -> Order {
+    Order::new(
+        symbol.to_string(),
+        side,
+        OrderType::Limit,
+        Price::new(price),
+        Quantity::new(quantity),
+        Uuid::new_v4(),
+    )
+}
+
+#[test]
+fn test_evicted_book_is_rehydrated_with_resting_orders_on_next_order() {
+    let clock = Clock::simulated(Utc::now());
+    let engine = TradingEngine::with_clock(
+        EngineConfig {
+            cold_after_ms: 60_000,
+            ..EngineConfig::default()
+        },
+        clock.clone(),
+    );
+    engine.add_symbol("BTCUSD".to_string()).unwrap();
+    engine.add_symbol("ETHUSD".to_string()).unwrap();
+
+    let first_ask = limit("BTCUSD", Side::Sell, 101.0, 1.0);
+    let second_ask = limit("BTCUSD", Side::Sell, 101.0, 2.0);
+    let bid = limit("BTCUSD", Side::Buy, 99.0, 3.0);
+    let (first_ask_id, second_ask_id, bid_id) = (first_ask.id, second_ask.id, bid.id);
+    for order in [first_ask, second_ask, bid] {
+        engine.submit_order(order).unwrap();
+    }
+
+    // ETHUSD keeps trading while BTCUSD goes quiet
+    clock.advance(Duration::from_secs(30));
+    engine.submit_order(limit("ETHUSD", Side::Buy, 10.0, 1.0)).unwrap();
+    clock.advance(Duration::from_secs(40));
+
+    assert_eq!(engine.evict_inactive_books(), vec!["BTCUSD".to_string()]);
+    assert!(engine.is_cold("BTCUSD"));
+    assert!(!engine.is_cold("ETHUSD"));
+    assert!(engine.get_order_book("BTCUSD").is_none());
+    assert!(engine.get_symbols().contains(&"BTCUSD".to_string()));
+    // Re-adding a cold symbol must not replace its book with an empty one
+    engine.add_symbol("BTCUSD".to_string()).unwrap();
+    assert!(engine.is_cold("BTCUSD"));
+
+    // The next order sees the book exactly as it was left, queue positions included
+    let response = engine.submit_order(limit("BTCUSD", Side::Buy, 101.0, 2.0)).unwrap();
+    let makers: Vec<_> = response.trades().iter().map(|trade| trade.seller_order_id).collect();
+    assert_eq!(makers, vec![first_ask_id, second_ask_id]);
+
+    assert!(!engine.is_cold("BTCUSD"));
+    let book = engine.get_order_book("BTCUSD").unwrap();
+    assert_eq!(book.get_order(first_ask_id).unwrap().status, OrderStatus::Filled);
+    assert_eq!(book.get_order(second_ask_id).unwrap().remaining_quantity(), Quantity::new(1.0));
+    assert_eq!(book.get_order(bid_id).unwrap().remaining_quantity(), Quantity::new(3.0));
+    assert_eq!(book.best_bid(), Some(Price::new(99.0)));
+    assert_eq!(book.best_ask(), Some(Price::new(101.0)));
+
+    // A freshly traded book is hot again
+    assert!(engine.evict_inactive_books().is_empty());
+}
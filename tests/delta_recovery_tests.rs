@@ -0,0 +1,98 @@
+//! Gap recovery for consumers mirroring a book from the market-data delta stream
+
+use market_data::{BookDeltaServer, BookMirror, DeltaError, DeltaServerConfig, SnapshotRequest};
+use order_book::types::{Order, OrderType, Price, Quantity, Side};
+use uuid::Uuid;
+
+fn order(side: Side, price: f64, quantity: f64) -> Order {
+    Order::new(
+        "BTCUSD".to_string(),
+        side,
+        OrderType::Limit,
+        Price::new(price),
+        Quantity::new(quantity),
+        Uuid::new_v4(),
+    )
+}
+
+fn server(subscriber_capacity: usize) -> BookDeltaServer {
+    let mut server = BookDeltaServer::new(DeltaServerConfig { subscriber_capacity });
+    server.add_symbol("BTCUSD".to_string());
+    server
+}
+
+fn assert_converged(server: &BookDeltaServer, mirror: &BookMirror) {
+    let book = server.depth("BTCUSD", usize::MAX).unwrap();
+    assert_eq!(mirror.bids(), book.bids);
+    assert_eq!(mirror.asks(), book.asks);
+    assert_eq!(mirror.sequence(), server.sequence("BTCUSD").unwrap());
+}
+
+#[test]
+fn test_snapshot_boundary_has_no_missed_or_duplicated_deltas() {
+    let server = server(1024);
+    server.add_order(order(Side::Buy, 49990.0, 1.0)).unwrap();
+    server.add_order(order(Side::Sell, 50010.0, 2.0)).unwrap();
+
+    let (response, subscription) = server.snapshot(&SnapshotRequest { symbol: "BTCUSD".to_string() }).unwrap();
+    assert_eq!(response.sequence, 2);
+    assert_eq!(response.snapshot.bids, vec![(Price::new(49990.0), Quantity::new(1.0))]);
+
+    server.add_order(order(Side::Buy, 50010.0, 0.5)).unwrap();
+    let first = subscription.try_recv().unwrap();
+    assert_eq!(first.sequence, response.sequence + 1);
+    assert_eq!(first.update.quantity, Quantity::new(1.5));
+    assert!(subscription.try_recv().is_err());
+}
+
+#[test]
+fn test_mirror_recovers_from_gap_and_converges() {
+    // A tiny buffer lets the server drop deltas for a consumer that stops polling
+    let server = server(4);
+    let mut mirror = BookMirror::subscribe(&server, "BTCUSD").unwrap();
+
+    let resting = order(Side::Sell, 50020.0, 1.0);
+    let resting_id = resting.id;
+    server.add_order(order(Side::Buy, 49990.0, 1.0)).unwrap();
+    server.add_order(resting).unwrap();
+    assert_eq!(mirror.poll(&server).unwrap(), 2);
+    assert_converged(&server, &mirror);
+
+    for i in 0..10 {
+        server.add_order(order(Side::Buy, 49900.0 + i as f64, 1.0)).unwrap();
+        server.add_order(order(Side::Sell, 50100.0 + i as f64, 2.0)).unwrap();
+    }
+    server.add_order(order(Side::Buy, 50100.0, 1.5)).unwrap();
+    server.cancel_order("BTCUSD", resting_id).unwrap();
+
+    // The buffered deltas are contiguous; the gap only shows once a later delta gets through
+    assert_eq!(mirror.poll(&server).unwrap(), 4);
+    assert_eq!(mirror.recoveries(), 0);
+    assert!(mirror.sequence() < server.sequence("BTCUSD").unwrap());
+
+    server.add_order(order(Side::Buy, 49800.0, 3.0)).unwrap();
+    mirror.poll(&server).unwrap();
+    assert_eq!(mirror.recoveries(), 1);
+    assert_converged(&server, &mirror);
+
+    // Deltas after the recovery snapshot apply in sequence without another resync
+    server.add_order(order(Side::Sell, 49990.0, 0.25)).unwrap();
+    assert_eq!(mirror.poll(&server).unwrap(), 1);
+    assert_eq!(mirror.recoveries(), 1);
+    assert_converged(&server, &mirror);
+}
+
+#[test]
+fn test_mirror_rejects_out_of_sequence_delta() {
+    let server = server(16);
+    let (_, subscription) = server.snapshot(&SnapshotRequest { symbol: "BTCUSD".to_string() }).unwrap();
+    let mut mirror = BookMirror::subscribe(&server, "BTCUSD").unwrap();
+
+    server.add_order(order(Side::Buy, 49990.0, 1.0)).unwrap();
+    server.add_order(order(Side::Buy, 49980.0, 1.0)).unwrap();
+    let _first = subscription.try_recv().unwrap();
+    let second = subscription.try_recv().unwrap();
+
+    assert_eq!(mirror.apply(&second), Err(DeltaError::SequenceGap { expected: 1, received: 2 }));
+    assert!(server.snapshot(&SnapshotRequest { symbol: "ETHUSD".to_string() }).is_err());
+}
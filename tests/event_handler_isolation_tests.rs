@@ -0,0 +1,123 @@
+//! Panicking event handlers are contained without disturbing their neighbours
+
+use chrono::Utc;
+use event_processor::{Event, EventProcessor, ProcessorConfig, SystemEvent};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn event() -> Event {
+    Event::System(SystemEvent::MarketOpen {
+        symbol: "BTCUSD".to_string(),
+        timestamp: Utc::now(),
+    })
+}
+
+/// Processor with a handler that always panics registered ahead of one that counts deliveries
+fn processor_with_faulty_handler(remove_panicking_handlers: bool) -> (EventProcessor, Arc<AtomicUsize>) {
+    let processor = EventProcessor::with_config(ProcessorConfig {
+        worker_threads: 1,
+        remove_panicking_handlers,
+        ..ProcessorConfig::default()
+    });
+    let delivered = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&delivered);
+    processor.add_event_handler(Arc::new(|_: &Event| -> anyhow::Result<()> { panic!("handler bug") }));
+    processor.add_event_handler(Arc::new(move |_: &Event| -> anyhow::Result<()> {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }));
+    (processor, delivered)
+}
+
+async fn wait_for(delivered: &AtomicUsize, expected: usize) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while delivered.load(Ordering::Relaxed) < expected && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_panicking_handler_is_counted_and_others_keep_receiving_events() {
+    let (processor, delivered) = processor_with_faulty_handler(false);
+    processor.start().await.unwrap();
+    
+    for _ in 0..5 {
+        processor.send_event(event()).unwrap();
+    }
+    wait_for(&delivered, 5).await;
+    
+    assert_eq!(delivered.load(Ordering::Relaxed), 5);
+    assert_eq!(processor.handler_panics(), 5);
+    assert_eq!(processor.handler_count(), 2);
+    processor.stop().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_panicking_handler_can_be_removed_after_its_first_panic() {
+    let (processor, delivered) = processor_with_faulty_handler(true);
+    processor.start().await.unwrap();
+    
+    for _ in 0..5 {
+        processor.send_event(event()).unwrap();
+    }
+    wait_for(&delivered, 5).await;
+    
+    assert_eq!(delivered.load(Ordering::Relaxed), 5);
+    assert_eq!(processor.handler_panics(), 1);
+    assert_eq!(processor.handler_count(), 1);
+    processor.stop().await.unwrap();
+}
+
+/// Processor with a handler that sleeps past the budget ahead of one that counts deliveries
+fn processor_with_slow_handler(max_slow_handler_calls: u32) -> (EventProcessor, Arc<AtomicUsize>) {
+    let processor = EventProcessor::with_config(ProcessorConfig {
+        worker_threads: 1,
+        handler_time_budget: Some(Duration::from_millis(5)),
+        max_slow_handler_calls,
+        ..ProcessorConfig::default()
+    });
+    let delivered = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&delivered);
+    processor.add_event_handler(Arc::new(|_: &Event| -> anyhow::Result<()> {
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(())
+    }));
+    processor.add_event_handler(Arc::new(move |_: &Event| -> anyhow::Result<()> {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }));
+    (processor, delivered)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_slow_handler_is_flagged_by_the_watchdog() {
+    let (processor, delivered) = processor_with_slow_handler(0);
+    processor.start().await.unwrap();
+    
+    for _ in 0..4 {
+        processor.send_event(event()).unwrap();
+    }
+    wait_for(&delivered, 4).await;
+    
+    assert_eq!(delivered.load(Ordering::Relaxed), 4);
+    assert_eq!(processor.handler_slow_calls(), 4);
+    assert_eq!(processor.handler_count(), 2);
+    processor.stop().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_slow_handler_is_removed_after_repeated_offenses() {
+    let (processor, delivered) = processor_with_slow_handler(3);
+    processor.start().await.unwrap();
+    
+    for _ in 0..6 {
+        processor.send_event(event()).unwrap();
+    }
+    wait_for(&delivered, 6).await;
+    
+    assert_eq!(delivered.load(Ordering::Relaxed), 6);
+    assert_eq!(processor.handler_slow_calls(), 3);
+    assert_eq!(processor.handler_count(), 1);
+    processor.stop().await.unwrap();
+}
@@ -0,0 +1,97 @@
+//! Feed anomaly screening: spikes, crossed quotes and stale data are flagged or suppressed
+
+use chrono::Duration;
+use market_data::{
+    AnomalyAction, AnomalyConfig, AnomalyKind, FeedAnomalyDetector, MarketDataFeed, MarketEvent, OrderBookSnapshot,
+    Screening, Tick,
+};
+use order_book::clock;
+use order_book::types::{Price, Quantity, Side};
+
+fn tick(price: f64) -> Tick {
+    Tick::new("BTCUSD".to_string(), Price::new(price), Quantity::new(1.0), Side::Buy)
+}
+
+fn snapshot(bid: f64, ask: f64) -> OrderBookSnapshot {
+    let mut snapshot = OrderBookSnapshot::new("BTCUSD".to_string(), 1);
+    snapshot.bids.push((Price::new(bid), Quantity::new(1.0)));
+    snapshot.asks.push((Price::new(ask), Quantity::new(1.0)));
+    snapshot
+}
+
+#[test]
+fn test_normal_stream_then_each_anomaly_is_flagged_and_suppressed() {
+    let (sender, anomalies) = crossbeam_channel::unbounded();
+    let mut detector = FeedAnomalyDetector::new(AnomalyConfig::default()).with_anomaly_sender(sender);
+
+    for price in [50000.0, 50100.0, 49950.0, 50200.0] {
+        assert_eq!(detector.screen_tick(&tick(price)), Screening::Pass);
+    }
+    assert_eq!(detector.screen_snapshot(&snapshot(50190.0, 50210.0)), Screening::Pass);
+
+    // 50% jump from the last accepted price
+    let spike = detector.screen_tick(&tick(75300.0));
+    assert!(!spike.forward());
+    assert!(matches!(
+        spike.anomaly().unwrap().kind,
+        AnomalyKind::PriceSpike { previous, .. } if previous == Price::new(50200.0)
+    ));
+    // The spike did not move the baseline, so a normal print still passes
+    assert_eq!(detector.screen_tick(&tick(50250.0)), Screening::Pass);
+
+    let crossed = detector.screen_snapshot(&snapshot(50300.0, 50250.0));
+    assert!(matches!(crossed, Screening::Suppressed(ref anomaly) if matches!(anomaly.kind, AnomalyKind::CrossedQuote { .. })));
+
+    let zero = detector.screen_tick(&tick(0.0));
+    assert!(matches!(zero, Screening::Suppressed(ref anomaly) if anomaly.kind == AnomalyKind::InvalidQuote));
+
+    let mut stale = tick(50260.0);
+    stale.timestamp = clock::now() - Duration::seconds(30);
+    assert!(matches!(
+        detector.screen_tick(&stale),
+        Screening::Suppressed(ref anomaly) if matches!(anomaly.kind, AnomalyKind::Stale { .. })
+    ));
+
+    assert_eq!(detector.anomalies(), 4);
+    assert_eq!(detector.suppressed(), 4);
+    assert_eq!(anomalies.try_iter().count(), 4);
+}
+
+#[test]
+fn test_flag_policy_lets_anomalous_data_through() {
+    let config = AnomalyConfig {
+        on_stale: AnomalyAction::Flag,
+        ..AnomalyConfig::default()
+    };
+    let mut detector = FeedAnomalyDetector::new(config);
+    assert_eq!(detector.screen_tick(&tick(50000.0)), Screening::Pass);
+
+    // Earlier than the tick already accepted
+    let mut out_of_order = tick(50010.0);
+    out_of_order.timestamp -= Duration::milliseconds(500);
+    let screening = detector.screen_tick(&out_of_order);
+    assert!(matches!(screening, Screening::Flagged(ref anomaly) if anomaly.action == AnomalyAction::Flag));
+    assert!(screening.forward());
+    assert_eq!(detector.suppressed(), 0);
+}
+
+#[test]
+fn test_feed_drops_suppressed_ticks_before_streams() {
+    let mut feed = MarketDataFeed::new();
+    feed.add_symbol("BTCUSD".to_string());
+    feed.set_anomaly_detector(FeedAnomalyDetector::default());
+
+    feed.publish_tick(tick(50000.0));
+    feed.publish_tick(tick(90000.0));
+    feed.publish_tick(tick(50050.0));
+
+    let stream = feed.get_stream("BTCUSD").unwrap();
+    let prices: Vec<Price> = std::iter::from_fn(|| stream.try_recv().ok())
+        .filter_map(|event| match event {
+            MarketEvent::Tick(tick) => Some(tick.price),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(prices, vec![Price::new(50000.0), Price::new(50050.0)]);
+    assert_eq!(feed.get_summary("BTCUSD").unwrap().high, Price::new(50050.0));
+}
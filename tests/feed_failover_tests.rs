@@ -0,0 +1,71 @@
+//! Feed failover: reads follow the primary while it is fresh and fall back to the backup otherwise
+
+use chrono::Duration;
+use market_data::{FailoverConfig, FailoverFeed, FeedSource, MarketDataFeed, OrderBookSnapshot};
+use order_book::clock;
+use order_book::types::{Price, Quantity};
+use std::sync::Arc;
+
+fn snapshot(bid: f64, age_ms: i64) -> OrderBookSnapshot {
+    let mut snapshot = OrderBookSnapshot::new("BTCUSD".to_string(), 1);
+    snapshot.bids.push((Price::new(bid), Quantity::new(1.0)));
+    snapshot.asks.push((Price::new(bid + 1.0), Quantity::new(1.0)));
+    snapshot.timestamp = clock::now() - Duration::milliseconds(age_ms);
+    snapshot
+}
+
+fn best_bid(feed: &FailoverFeed) -> Option<Price> {
+    feed.get_snapshot("BTCUSD").and_then(|snapshot| snapshot.best_bid())
+}
+
+#[test]
+fn test_reads_fail_over_to_backup_and_back() {
+    let primary = Arc::new(MarketDataFeed::new());
+    let backup = Arc::new(MarketDataFeed::new());
+    let feed = FailoverFeed::new(primary.clone(), backup.clone(), FailoverConfig { max_staleness_ms: 1_000 });
+
+    primary.publish_snapshot(snapshot(50000.0, 0));
+    backup.publish_snapshot(snapshot(49990.0, 0));
+    assert_eq!(feed.active_source("BTCUSD"), FeedSource::Primary);
+    assert_eq!(best_bid(&feed), Some(Price::new(50000.0)));
+
+    // The primary's newest data ages past the limit
+    primary.publish_snapshot(snapshot(50010.0, 5_000));
+    assert!(!feed.is_primary_healthy("BTCUSD"));
+    assert_eq!(best_bid(&feed), Some(Price::new(49990.0)));
+    assert_eq!(feed.active_source("BTCUSD"), FeedSource::Backup);
+    assert_eq!(feed.failovers(), 1);
+
+    primary.publish_snapshot(snapshot(50020.0, 0));
+    assert_eq!(best_bid(&feed), Some(Price::new(50020.0)));
+    assert_eq!(feed.active_source("BTCUSD"), FeedSource::Primary);
+
+    // A dropped connection fails over even while the last data is fresh
+    feed.set_primary_connected(false);
+    assert_eq!(best_bid(&feed), Some(Price::new(49990.0)));
+    feed.set_primary_connected(true);
+    assert_eq!(best_bid(&feed), Some(Price::new(50020.0)));
+    assert_eq!(feed.failovers(), 2);
+}
+
+#[test]
+fn test_primary_down_since_tracks_drops_after_a_connection() {
+    let feed = FailoverFeed::new(Arc::new(MarketDataFeed::new()), Arc::new(MarketDataFeed::new()), FailoverConfig::default())
+        .with_primary_connected(false);
+    // Never connected yet, so nothing was lost
+    assert_eq!(feed.primary_down_since(), None);
+    feed.set_primary_connected(false);
+    assert_eq!(feed.primary_down_since(), None);
+
+    feed.set_primary_connected(true);
+    let before = clock::now();
+    feed.set_primary_connected(false);
+    let down_since = feed.primary_down_since().unwrap();
+    assert!(down_since >= before);
+    // Repeated reports keep the time of the first drop
+    feed.set_primary_connected(false);
+    assert_eq!(feed.primary_down_since(), Some(down_since));
+
+    feed.set_primary_connected(true);
+    assert_eq!(feed.primary_down_since(), None);
+}
@@ -66,15 +66,21 @@ proptest! {
         // Result should be one of the valid variants
         match result {
             MatchResult::NoMatch => {
-                // Order should be in the book
-                prop_assert!(order_book.get_order(order.id).is_some());
+                // Limit-style orders rest; market orders never do
+                prop_assert_eq!(
+                    order_book.get_order(order.id).is_some(),
+                    order.order_type != OrderType::Market
+                );
             },
-            MatchResult::PartialMatch { trades, remaining_quantity } => {
+            MatchResult::PartialMatch { trades, remaining_quantity, .. } => {
                 prop_assert!(!trades.is_empty());
                 prop_assert!(remaining_quantity > Quantity::ZERO);
             },
-            MatchResult::FullMatch { trades } => {
+            MatchResult::FullMatch { trades, .. } => {
                 prop_assert!(!trades.is_empty());
+            },
+            MatchResult::Rejected { .. } => {
+                prop_assert!(order_book.get_order(order.id).is_none());
             }
         }
         
@@ -109,13 +115,14 @@ proptest! {
                     }
                     added_orders.insert(order.id);
                 },
-                MatchResult::FullMatch { trades } => {
+                MatchResult::FullMatch { trades, .. } => {
                     // Verify trades are valid
                     for trade in trades {
                         prop_assert!(trade.quantity > Quantity::ZERO);
                         prop_assert!(trade.price > Price::ZERO);
                     }
-                }
+                },
+                MatchResult::Rejected { .. } => {}
             }
         }
         
@@ -50,7 +50,7 @@ async fn test_end_to_end_order_processing() {
 
     // Verify matching occurred
     match result2 {
-        MatchResult::FullMatch { trades } => {
+        MatchResult::FullMatch { trades, .. } => {
             assert_eq!(trades.len(), 1);
             assert_eq!(trades[0].price, Price::new(50000.0));
             assert_eq!(trades[0].quantity, Quantity::new(1.0));
@@ -0,0 +1,85 @@
+//! Maximum holding time: positions held too long are closed with reduce-only market orders
+
+use chrono::Utc;
+use order_book::{Clock, Order, OrderId, OrderType, Price, Quantity, Side, Trade};
+use std::time::Duration;
+use trading_engine::engine::{EngineConfig, OrderResponse};
+use trading_engine::TradingEngine;
+use uuid::Uuid;
+
+fn fill(engine: &TradingEngine, client_id: Uuid, side: Side, quantity: f64) {
+    let (buyer, seller) = match side {
+        Side::Buy => (client_id, Uuid::new_v4()),
+        Side::Sell => (Uuid::new_v4(), client_id),
+    };
+    let trade = Trade::new(
+        "BTCUSD",
+        OrderId::new(),
+        OrderId::new(),
+        Price::new(50000.0),
+        Quantity::new(quantity),
+        buyer,
+        seller,
+        side,
+    )
+    .with_timestamp(engine.clock().now());
+    engine.risk_manager().process_trade(&trade).unwrap();
+}
+
+/// Exits placed for `client_id`. Every fill also opens a position for its random counterparty
+/// in the same symbol, and those are exited alongside the client's.
+fn client_exits(engine: &TradingEngine, client_id: Uuid) -> Vec<(Order, OrderResponse)> {
+    engine
+        .exit_expired_positions()
+        .into_iter()
+        .filter(|(order, _)| order.client_id == client_id)
+        .collect()
+}
+
+#[test]
+fn test_position_held_past_max_holding_time_is_flattened() {
+    let engine = TradingEngine::with_clock(EngineConfig::default(), Clock::simulated(Utc::now()));
+    engine.add_symbol("BTCUSD".to_string()).unwrap();
+    engine.risk_manager().set_max_holding_time("BTCUSD", Duration::from_secs(60));
+
+    // Liquidity for the exit to trade against
+    let bid = Order::new(
+        "BTCUSD".to_string(),
+        Side::Buy,
+        OrderType::Limit,
+        Price::new(49990.0),
+        Quantity::new(5.0),
+        Uuid::new_v4(),
+    );
+    engine.submit_order(bid).unwrap();
+
+    let client_id = Uuid::new_v4();
+    fill(&engine, client_id, Side::Buy, 2.0);
+    engine.clock().advance(Duration::from_secs(40));
+    assert!(client_exits(&engine, client_id).is_empty());
+
+    // Trimming and rebuilding the position keeps the clock from when it opened
+    fill(&engine, client_id, Side::Sell, 1.0);
+    fill(&engine, client_id, Side::Buy, 2.0);
+    engine.clock().advance(Duration::from_secs(30));
+
+    let exits = client_exits(&engine, client_id);
+    assert_eq!(exits.len(), 1);
+    let (order, response) = &exits[0];
+    assert_eq!(order.client_id, client_id);
+    assert_eq!(order.side, Side::Sell);
+    assert_eq!(order.order_type, OrderType::Market);
+    assert_eq!(order.quantity, Quantity::new(3.0));
+    assert!(order.reduce_only);
+    assert!(matches!(response, OrderResponse::FullyFilled { .. }));
+
+    let position = engine.risk_manager().get_position("BTCUSD", client_id).unwrap();
+    assert!(position.is_flat());
+    assert!(position.opened_at.is_none());
+    assert!(client_exits(&engine, client_id).is_empty());
+
+    // A fresh position starts a new clock
+    fill(&engine, client_id, Side::Sell, 1.0);
+    engine.clock().advance(Duration::from_secs(30));
+    assert!(client_exits(&engine, client_id).is_empty());
+}
@@ -46,11 +46,12 @@ fn test_concurrent_order_book_operations() {
                 
                 match order_book.add_order(order) {
                     MatchResult::NoMatch => orders_added += 1,
-                    MatchResult::PartialMatch { trades: _, remaining_quantity: _ } => {
+                    MatchResult::PartialMatch { .. } => {
                         orders_added += 1;
                         orders_matched += 1;
                     },
-                    MatchResult::FullMatch { trades: _ } => orders_matched += 1,
+                    MatchResult::FullMatch { .. } => orders_matched += 1,
+                    MatchResult::Rejected { .. } => {},
                 }
             }
             